@@ -25,7 +25,7 @@ const pluginCanvasBackgroundColor = {
 
 for (let i=0; i < hists.length; i++) {
     var h = hists[i];
-    var ctx = document.getElementById('chart-hist-' + h.count);
+    var ctx = document.getElementById('chart-hist-' + h.id);
     var myChart = new Chart(ctx, {
         type: 'bar',
         data: {
@@ -181,33 +181,269 @@ if (typeof groups != "undefined") {
             });
             buildPlotDownload(myChart, g, fname);
         }
+        buildChartTableDownload(myChart, g, fname);
         buildLogToggle(myChart, g);
     }
 }
 
+if (typeof histograms != "undefined") {
+    for (let i=0; i < histograms.length; i++) {
+        var d = histograms[i];
+        var ctx = document.getElementById('chart-histogram-' + d.count);
+        var myChart = new Chart(ctx, {
+            type: 'bar',
+            data: {
+                labels: d.index,
+                datasets: [{
+                    label: fname,
+                    data: d.values,
+                    borderWidth: 1,
+                    backgroundColor: PCOLORS[0],
+                    borderColor: '#FFFFFF'
+                }]
+            },
+            options: {
+                scales: {
+                    y: {
+                        title: {
+                            display: true,
+                            text: d.y_label,
+                        },
+                        beginAtZero: true,
+                        grid: {
+                            color: '#FFFFFF',
+                        }
+                    },
+                    x: {
+                        title: {
+                            display: true,
+                            text: d.x_label,
+                        },
+                        grid: {
+                            color: '#FFFFFF',
+                        },
+                        ticks: {
+                            maxRotation: 90,
+                            minRotation: 65
+                        }
+                    },
+                },
+                plugins: {
+                    customCanvasBackgroundColor: {
+                        color: '#E5E4EE',
+                    }
+                }
+            },
+            plugins: [pluginCanvasBackgroundColor],
+        });
+        buildPlotDownload(myChart, d, fname);
+        buildChartTableDownload(myChart, d, fname);
+        buildLogToggle(myChart, d);
+    }
+}
+
+if (typeof coverage_classes != "undefined") {
+    for (let i=0; i < coverage_classes.length; i++) {
+        var c = coverage_classes[i];
+        var ctx = document.getElementById('chart-coverageclasses-' + c.count);
+        var myChart = new Chart(ctx, {
+            type: 'bar',
+            data: {
+                labels: c.index,
+                datasets: c.classes.map(function(cls, j) {
+                    return {
+                        label: cls,
+                        data: c.values[j],
+                        borderWidth: 1,
+                        backgroundColor: PCOLORS[j % PCOLORS.length],
+                        borderColor: '#FFFFFF'
+                    };
+                }),
+            },
+            options: {
+                scales: {
+                    y: {
+                        title: {
+                            display: true,
+                            text: '#' + c.count + 's',
+                        },
+                        beginAtZero: true,
+                        grid: {
+                            color: '#FFFFFF',
+                        }
+                    },
+                    x: {
+                        title: {
+                            display: true,
+                            text: 'groups',
+                        },
+                        grid: {
+                            color: '#FFFFFF',
+                        },
+                        ticks: {
+                            maxRotation: 90,
+                            minRotation: 65
+                        }
+                    },
+                },
+                plugins: {
+                    customCanvasBackgroundColor: {
+                        color: '#E5E4EE',
+                    }
+                }
+            },
+            plugins: [pluginCanvasBackgroundColor],
+        });
+        buildPlotDownload(myChart, c, fname);
+        buildChartTableDownload(myChart, c, fname);
+        buildLogToggle(myChart, c);
+    }
+}
+
+if (typeof profiles != "undefined") {
+    for (let i=0; i < profiles.length; i++) {
+        var p = profiles[i];
+        var ctx = document.getElementById('chart-profile-' + p.count);
+        var myChart = new Chart(ctx, {
+            type: 'line',
+            data: {
+                labels: p.index,
+                datasets: p.series.map(function(s, j) {
+                    return {
+                        label: s,
+                        data: p.values[j],
+                        borderWidth: 1,
+                        pointRadius: 0,
+                        backgroundColor: PCOLORS[j % PCOLORS.length],
+                        borderColor: PCOLORS[j % PCOLORS.length]
+                    };
+                }),
+            },
+            options: {
+                scales: {
+                    y: {
+                        title: {
+                            display: true,
+                            text: p.y_label,
+                        },
+                        beginAtZero: true,
+                        grid: {
+                            color: '#FFFFFF',
+                        }
+                    },
+                    x: {
+                        title: {
+                            display: true,
+                            text: p.x_label,
+                        },
+                        grid: {
+                            color: '#FFFFFF',
+                        },
+                        ticks: {
+                            maxRotation: 90,
+                            minRotation: 65
+                        }
+                    },
+                },
+                plugins: {
+                    customCanvasBackgroundColor: {
+                        color: '#E5E4EE',
+                    }
+                }
+            },
+            plugins: [pluginCanvasBackgroundColor],
+        });
+        buildPlotDownload(myChart, p, fname);
+        buildChartTableDownload(myChart, p, fname);
+        buildLogToggle(myChart, p);
+    }
+}
+
 if (typeof info != "undefined" && info != "") {
     buildInfoTableDownload(info, "graph", fname);
     buildInfoTableDownload(info, "node", fname);
     buildInfoTableDownload(info, "path", fname);
     buildInfoTableDownload(info, "group", fname);
+    buildInfoTableDownload(info, "samples", fname);
+}
+
+// shaded confidence bands (if any) between the lowest and highest percentile of sampled growths
+function growthBandDatasets(g) {
+    if (typeof growth_bands == "undefined") {
+        return [];
+    }
+    const band = growth_bands.find(b => b.count == g.id);
+    if (!band) {
+        return [];
+    }
+    return Array.from(g.getThresholds().entries()).reverse().flatMap(function([i, [c, q]]) {
+        const [low, high] = band.getBandFor(c, q);
+        return [{
+            type: 'line',
+            label: g.getLabelFor(c, q) + ', ' + band.p_low + '-' + band.p_high + ' percentile',
+            data: high,
+            fill: '+1',
+            backgroundColor: PCOLORS[i % PCOLORS.length] + '55',
+            borderWidth: 0,
+            pointRadius: 0
+        }, {
+            type: 'line',
+            label: '',
+            data: low,
+            fill: false,
+            borderWidth: 0,
+            pointRadius: 0
+        }];
+    });
+}
+
+// number of countables added by each taxon (if requested) on a secondary axis, together with the
+// saturation threshold
+function growthIncrementDatasets(g) {
+    if (typeof growth_saturation == "undefined") {
+        return [];
+    }
+    return Array.from(g.getThresholds().entries()).reverse().map(function([i, [c, q]]) {
+        const growth = g.getGrowthFor(c, q);
+        return {
+            label: g.getLabelFor(c, q) + ', increment',
+            data: growth.map((x, k) => k == 0 ? x : x - growth[k-1]),
+            yAxisID: 'y2',
+            borderDash: [4, 4],
+            borderWidth: 1,
+            pointRadius: 0,
+            backgroundColor: PCOLORS[i % PCOLORS.length],
+            borderColor: PCOLORS[i % PCOLORS.length]
+        };
+    }).concat([{
+        label: 'saturation threshold',
+        data: g.index.map(() => growth_saturation),
+        yAxisID: 'y2',
+        borderDash: [1, 3],
+        borderWidth: 1,
+        pointRadius: 0,
+        backgroundColor: '#000000',
+        borderColor: '#000000'
+    }]);
 }
 
 for (let i=0; i < growths.length; i++) {
     var g = growths[i];
-    var ctx = document.getElementById('chart-growth-' + g.count);
+    var ctx = document.getElementById('chart-growth-' + g.id);
     var myChart = new Chart(ctx, {
-        type: 'bar',
+        type: 'line',
         data: {
             labels: g.index,
             datasets: Array.from(g.getThresholds().entries()).reverse().map(function([i, [c, q]]) {
                 return {
-                    label: 'coverage \u2265 ' + c + ', quorum \u2265 ' + (q*100).toFixed(0) + '%',
+                    label: g.getLabelFor(c, q),
                     data: g.getGrowthFor(c, q),
-                    borderWidth: 1,
+                    borderWidth: 2,
+                    pointRadius: 0,
                     backgroundColor: PCOLORS[i % PCOLORS.length],
-                    borderColor: '#FFFFFF'
+                    borderColor: PCOLORS[i % PCOLORS.length]
                 };
-            }),
+            }).concat(growthBandDatasets(g)).concat(growthIncrementDatasets(g)),
         },
         options: {
             scales: {
@@ -222,6 +458,18 @@ for (let i=0; i < growths.length; i++) {
                     },
                     stacked: false,
                 },
+                y2: {
+                    display: typeof growth_saturation != "undefined",
+                    position: 'right',
+                    title: {
+                        display: true,
+                        text: 'new ' + g.count + 's per taxon',
+                    },
+                    beginAtZero: true,
+                    grid: {
+                        drawOnChartArea: false,
+                    },
+                },
                 x: {
                     title: {
                         display: true,
@@ -234,12 +482,24 @@ for (let i=0; i < growths.length; i++) {
                         maxRotation: 90,
                         minRotation: 65
                     },
-                    stacked: true,
                 },
             },
+            // report all curves at the hovered number of taxa
+            interaction: {
+                mode: 'index',
+                intersect: false,
+            },
             plugins: {
                 customCanvasBackgroundColor: {
                     color: '#E5E4EE',
+                },
+                legend: {
+                    labels: {
+                        filter: item => item.text !== '',
+                    }
+                },
+                tooltip: {
+                    filter: item => item.dataset.label !== '',
                 }
             }
         },
@@ -247,12 +507,16 @@ for (let i=0; i < growths.length; i++) {
     });
     buildPlotDownload(myChart, g, fname);
     buildGrowthTableDownload(myChart, g, fname);
+    buildGrowthRecompute(myChart, g);
+    buildLogToggle(myChart, g);
 }
 
 var tabs = document.querySelectorAll('button[data-bs-toggle="tab"]')
 tabs.forEach(function(tab) {
     tab.addEventListener('show.bs.tab', function (event) {
         document.querySelector(event.target.dataset.bsTarget).classList.remove('d-none');
-        document.querySelector(event.relatedTarget.dataset.bsTarget).classList.add('d-none');
+        if (event.relatedTarget) {
+            document.querySelector(event.relatedTarget.dataset.bsTarget).classList.add('d-none');
+        }
     });
 });
@@ -1 +1 @@
-(()=>{"use strict";const t=Array.from(document.querySelectorAll('[data-bs-toggle="tooltip"]'));t.forEach(t=>{new bootstrap.Tooltip(t)})})();const plots=hists.concat(growths);const pluginCanvasBackgroundColor={id:"customCanvasBackgroundColor",beforeDraw:(t,o,a)=>{const{ctx:e,chartArea:{top:r,bottom:l,left:n,right:i,width:s,height:d},scales:{x:g,y:u}}=t;e.save();e.globalCompositeOperation="destination-over";e.fillStyle=a.color||"#99ffff";e.fillRect(n,r,s,d);e.restore()}};for(let t=0;t<hists.length;t++){var h=hists[t];var ctx=document.getElementById("chart-hist-"+h.count);var myChart=new Chart(ctx,{type:"bar",data:{labels:h.index,datasets:[{label:fname,data:h.coverage,borderWidth:1,backgroundColor:PCOLORS[0],borderColor:"#FFFFFF"}]},options:{scales:{y:{title:{display:true,text:"#"+h.count+"s"},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:"taxa"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,h,fname);buildHistTableDownload(myChart,h,fname);buildLogToggle(myChart,h)}if(typeof groups!="undefined"){for(let t=0;t<groups.length;t++){var g=groups[t];var ctx=document.getElementById("chart-group-"+g.count);var myChart;if(g.is_hist){myChart=new Chart(ctx,{type:"bar",data:{labels:g.index,datasets:[{label:fname,data:g.length,borderWidth:1,backgroundColor:PCOLORS[0],borderColor:"#FFFFFF"}]},options:{scales:{y:{title:{display:true,text:"#groups"},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:"#"+g.count+"s"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,g,fname)}else{myChart=new Chart(ctx,{type:"bar",data:{labels:g.index,datasets:[{label:fname,data:g.length,borderWidth:1,backgroundColor:PCOLORS[0],borderColor:"#FFFFFF"}]},options:{scales:{y:{title:{display:true,text:"#"+g.count+"s"},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:"groups"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,g,fname)}buildLogToggle(myChart,g)}}if(typeof info!="undefined"&&info!=""){buildInfoTableDownload(info,"graph",fname);buildInfoTableDownload(info,"node",fname);buildInfoTableDownload(info,"path",fname);buildInfoTableDownload(info,"group",fname)}for(let t=0;t<growths.length;t++){var g=growths[t];var ctx=document.getElementById("chart-growth-"+g.count);var myChart=new Chart(ctx,{type:"bar",data:{labels:g.index,datasets:Array.from(g.getThresholds().entries()).reverse().map(function([t,[o,a]]){return{label:"coverage ≥ "+o+", quorum ≥ "+(a*100).toFixed(0)+"%",data:g.getGrowthFor(o,a),borderWidth:1,backgroundColor:PCOLORS[t%PCOLORS.length],borderColor:"#FFFFFF"}})},options:{scales:{y:{title:{display:true,text:"#"+g.count+"s"},beginAtZero:true,grid:{color:"#FFFFFF"},stacked:false},x:{title:{display:true,text:"taxa"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65},stacked:true}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,g,fname);buildGrowthTableDownload(myChart,g,fname)}var tabs=document.querySelectorAll('button[data-bs-toggle="tab"]');tabs.forEach(function(t){t.addEventListener("show.bs.tab",function(t){document.querySelector(t.target.dataset.bsTarget).classList.remove("d-none");document.querySelector(t.relatedTarget.dataset.bsTarget).classList.add("d-none")})});
+(()=>{"use strict";const t=Array.from(document.querySelectorAll('[data-bs-toggle="tooltip"]'));t.forEach(t=>{new bootstrap.Tooltip(t)})})();const plots=hists.concat(growths);const pluginCanvasBackgroundColor={id:"customCanvasBackgroundColor",beforeDraw:(t,o,a)=>{const{ctx:e,chartArea:{top:r,bottom:l,left:n,right:i,width:s,height:d},scales:{x:g,y:u}}=t;e.save();e.globalCompositeOperation="destination-over";e.fillStyle=a.color||"#99ffff";e.fillRect(n,r,s,d);e.restore()}};for(let t=0;t<hists.length;t++){var h=hists[t];var ctx=document.getElementById("chart-hist-"+h.id);var myChart=new Chart(ctx,{type:"bar",data:{labels:h.index,datasets:[{label:fname,data:h.coverage,borderWidth:1,backgroundColor:PCOLORS[0],borderColor:"#FFFFFF"}]},options:{scales:{y:{title:{display:true,text:"#"+h.count+"s"},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:"taxa"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,h,fname);buildHistTableDownload(myChart,h,fname);buildLogToggle(myChart,h)}if(typeof groups!="undefined"){for(let t=0;t<groups.length;t++){var g=groups[t];var ctx=document.getElementById("chart-group-"+g.count);var myChart;if(g.is_hist){myChart=new Chart(ctx,{type:"bar",data:{labels:g.index,datasets:[{label:fname,data:g.length,borderWidth:1,backgroundColor:PCOLORS[0],borderColor:"#FFFFFF"}]},options:{scales:{y:{title:{display:true,text:"#groups"},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:"#"+g.count+"s"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,g,fname)}else{myChart=new Chart(ctx,{type:"bar",data:{labels:g.index,datasets:[{label:fname,data:g.length,borderWidth:1,backgroundColor:PCOLORS[0],borderColor:"#FFFFFF"}]},options:{scales:{y:{title:{display:true,text:"#"+g.count+"s"},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:"groups"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,g,fname)}buildChartTableDownload(myChart,g,fname);buildLogToggle(myChart,g)}}if(typeof histograms!="undefined"){for(let t=0;t<histograms.length;t++){var d=histograms[t];var ctx=document.getElementById("chart-histogram-"+d.count);var myChart=new Chart(ctx,{type:"bar",data:{labels:d.index,datasets:[{label:fname,data:d.values,borderWidth:1,backgroundColor:PCOLORS[0],borderColor:"#FFFFFF"}]},options:{scales:{y:{title:{display:true,text:d.y_label},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:d.x_label},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,d,fname);buildChartTableDownload(myChart,d,fname);buildLogToggle(myChart,d)}}if(typeof coverage_classes!="undefined"){for(let t=0;t<coverage_classes.length;t++){var c=coverage_classes[t];var ctx=document.getElementById("chart-coverageclasses-"+c.count);var myChart=new Chart(ctx,{type:"bar",data:{labels:c.index,datasets:c.classes.map(function(o,a){return{label:o,data:c.values[a],borderWidth:1,backgroundColor:PCOLORS[a%PCOLORS.length],borderColor:"#FFFFFF"}})},options:{scales:{y:{title:{display:true,text:"#"+c.count+"s"},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:"groups"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,c,fname);buildChartTableDownload(myChart,c,fname);buildLogToggle(myChart,c)}}if(typeof profiles!="undefined"){for(let t=0;t<profiles.length;t++){var p=profiles[t];var ctx=document.getElementById("chart-profile-"+p.count);var myChart=new Chart(ctx,{type:"line",data:{labels:p.index,datasets:p.series.map(function(o,a){return{label:o,data:p.values[a],borderWidth:1,pointRadius:0,backgroundColor:PCOLORS[a%PCOLORS.length],borderColor:PCOLORS[a%PCOLORS.length]}})},options:{scales:{y:{title:{display:true,text:p.y_label},beginAtZero:true,grid:{color:"#FFFFFF"}},x:{title:{display:true,text:p.x_label},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,p,fname);buildChartTableDownload(myChart,p,fname);buildLogToggle(myChart,p)}}if(typeof info!="undefined"&&info!=""){buildInfoTableDownload(info,"graph",fname);buildInfoTableDownload(info,"node",fname);buildInfoTableDownload(info,"path",fname);buildInfoTableDownload(info,"group",fname),buildInfoTableDownload(info,"samples",fname)}function growthBandDatasets(g){if(typeof growth_bands=="undefined"){return[]}const b=growth_bands.find(t=>t.count==g.id);if(!b){return[]}return Array.from(g.getThresholds().entries()).reverse().flatMap(function([t,[o,a]]){const[l,h]=b.getBandFor(o,a);return[{type:"line",label:g.getLabelFor(o,a)+", "+b.p_low+"-"+b.p_high+" percentile",data:h,fill:"+1",backgroundColor:PCOLORS[t%PCOLORS.length]+"55",borderWidth:0,pointRadius:0},{type:"line",label:"",data:l,fill:false,borderWidth:0,pointRadius:0}]})}function growthIncrementDatasets(g){if(typeof growth_saturation=="undefined"){return[]}return Array.from(g.getThresholds().entries()).reverse().map(function([t,[o,a]]){const r=g.getGrowthFor(o,a);return{label:g.getLabelFor(o,a)+", increment",data:r.map((x,k)=>k==0?x:x-r[k-1]),yAxisID:"y2",borderDash:[4,4],borderWidth:1,pointRadius:0,backgroundColor:PCOLORS[t%PCOLORS.length],borderColor:PCOLORS[t%PCOLORS.length]}}).concat([{label:"saturation threshold",data:g.index.map(()=>growth_saturation),yAxisID:"y2",borderDash:[1,3],borderWidth:1,pointRadius:0,backgroundColor:"#000000",borderColor:"#000000"}])}for(let t=0;t<growths.length;t++){var g=growths[t];var ctx=document.getElementById("chart-growth-"+g.id);var myChart=new Chart(ctx,{type:"line",data:{labels:g.index,datasets:Array.from(g.getThresholds().entries()).reverse().map(function([t,[o,a]]){return{label:g.getLabelFor(o,a),data:g.getGrowthFor(o,a),borderWidth:2,pointRadius:0,backgroundColor:PCOLORS[t%PCOLORS.length],borderColor:PCOLORS[t%PCOLORS.length]}}).concat(growthBandDatasets(g)).concat(growthIncrementDatasets(g))},options:{scales:{y:{title:{display:true,text:"#"+g.count+"s"},beginAtZero:true,grid:{color:"#FFFFFF"},stacked:false},y2:{display:typeof growth_saturation!="undefined",position:"right",title:{display:true,text:"new "+g.count+"s per taxon"},beginAtZero:true,grid:{drawOnChartArea:false}},x:{title:{display:true,text:"taxa"},grid:{color:"#FFFFFF"},ticks:{maxRotation:90,minRotation:65}}},interaction:{mode:"index",intersect:false},plugins:{customCanvasBackgroundColor:{color:"#E5E4EE"},legend:{labels:{filter:t=>t.text!==""}},tooltip:{filter:t=>t.dataset.label!==""}}},plugins:[pluginCanvasBackgroundColor]});buildPlotDownload(myChart,g,fname);buildGrowthTableDownload(myChart,g,fname);buildGrowthRecompute(myChart,g);buildLogToggle(myChart,g)}var tabs=document.querySelectorAll('button[data-bs-toggle="tab"]');tabs.forEach(function(t){t.addEventListener("show.bs.tab",function(t){document.querySelector(t.target.dataset.bsTarget).classList.remove("d-none");t.relatedTarget&&document.querySelector(t.relatedTarget.dataset.bsTarget).classList.add("d-none")})});
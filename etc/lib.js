@@ -5,8 +5,9 @@
 const PCOLORS = ['#f77189', '#bb9832', '#50b131', '#36ada4', '#3ba3ec', '#e866f4'];
 
 class Hist {
-    constructor(count_type, index, coverage) {
+    constructor(count_type, index, coverage, section) {
         this.count = count_type;
+        this.id = section === undefined ? count_type : count_type + '-' + section;
         this.index = index;
         this.coverage = coverage;
     }
@@ -22,16 +23,54 @@ class Group {
 }
 
 
+class Histogram {
+    constructor(count_type, index, values, x_label, y_label) {
+        this.count = count_type;
+        this.index = index;
+        this.values = values;
+        this.x_label = x_label;
+        this.y_label = y_label;
+    }
+}
+
+
+class CoverageClasses {
+    constructor(count_type, index, classes, values) {
+        this.count = count_type;
+        this.index = index;
+        this.classes = classes;
+        this.values = values;
+    }
+}
+
+
+class Profile {
+    constructor(count_type, index, series, values, x_label, y_label) {
+        this.count = count_type;
+        this.index = index;
+        this.series = series;
+        this.values = values;
+        this.x_label = x_label;
+        this.y_label = y_label === undefined ? '#' + count_type + 's' : y_label;
+    }
+}
+
+
 class Growth {
-    constructor(count_type, index, coverage_t, quorum_t, growths) {
+    constructor(count_type, index, coverage_t, quorum_t, growths, section, names) {
         this.count = count_type;
+        this.id = section === undefined ? count_type : count_type + '-' + section;
         this.index = index;
         this.growths = {};
+        this.names = {};
         var srt = [];
         for (let i = 0; i < coverage_t.length; i++) {
             let t = [coverage_t[i], quorum_t[i]];
             srt[i] = [quorum_t[i], coverage_t[i]];
             this.growths[t] = growths[i];
+            if (names !== undefined && names[i] !== null) {
+                this.names[t] = names[i];
+            }
         }
         srt.sort();
         this.coverage_t = srt.map(([q, c]) => c);
@@ -50,22 +89,56 @@ class Growth {
     getGrowthFor(c, q) {
         return this.growths[[c, q]];
     }
+
+    getNameFor(c, q) {
+        return this.names[[c, q]];
+    }
+
+    // legend label of the growth curve under the given thresholds
+    getLabelFor(c, q) {
+        const name = this.getNameFor(c, q);
+        return name !== undefined ? name : 'coverage \u2265 ' + c + ', quorum \u2265 ' + (q*100).toFixed(0) + '%';
+    }
+}
+
+
+class GrowthBand {
+    constructor(count_type, coverage_t, quorum_t, p_low, p_high, low, high) {
+        this.count = count_type;
+        this.p_low = p_low;
+        this.p_high = p_high;
+        this.bands = {};
+        for (let i = 0; i < coverage_t.length; i++) {
+            this.bands[[coverage_t[i], quorum_t[i]]] = [low[i], high[i]];
+        }
+    }
+
+    getBandFor(c, q) {
+        return this.bands[[c, q]];
+    }
+}
+
+
+// identifier of the HTML elements of an object; objects of report sections (e.g., per sequence)
+// carry their own, all others are identified by their count type
+function objId(obj) {
+    return obj.id === undefined ? obj.count : obj.id;
 }
 
 
 function buildPlotDownload(chart, obj, prefix) {
-    console.log('btn-download-plot-' + obj.constructor.name.toLowerCase() + '-' + obj.count);
-    document.getElementById('btn-download-plot-' + obj.constructor.name.toLowerCase() + '-' + obj.count).onclick = function() {
+    console.log('btn-download-plot-' + obj.constructor.name.toLowerCase() + '-' + objId(obj));
+    document.getElementById('btn-download-plot-' + obj.constructor.name.toLowerCase() + '-' + objId(obj)).onclick = function() {
         var a = document.createElement('a');
         a.href = chart.toBase64Image();
-        a.download = prefix + '_' + obj.constructor.name.toLowerCase() + '_' + obj.count + '.png';
+        a.download = prefix + '_' + obj.constructor.name.toLowerCase() + '_' + objId(obj) + '.png';
         a.click();
     };
 }
 
 
 function buildHistTableDownload(chart, obj, prefix) {
-    document.getElementById('btn-download-table-hist-' + obj.count).onclick = function() {
+    document.getElementById('btn-download-table-hist-' + objId(obj)).onclick = function() {
 
         var table = 'panacus\thist\ncount\t' + obj.count + '\n\t\n\t\n';
 
@@ -76,14 +149,14 @@ function buildHistTableDownload(chart, obj, prefix) {
         let blob = new Blob([table], {type: 'text/plain'});
         var a = document.createElement('a');
         a.href = URL.createObjectURL(blob);
-        a.download = prefix + '_hist_' + obj.count + '.tsv';
+        a.download = prefix + '_hist_' + objId(obj) + '.tsv';
         a.click();
     };
 }
 
 
 function buildGrowthTableDownload(chart, obj, prefix) {
-    document.getElementById('btn-download-table-growth-' + obj.count).onclick = function() {
+    document.getElementById('btn-download-table-growth-' + objId(obj)).onclick = function() {
 
         var table = '';
 
@@ -96,14 +169,19 @@ function buildGrowthTableDownload(chart, obj, prefix) {
         cs = '\ncoverage\t' + thresholds[0][0];
         qs = '\nquorum\t' + thresholds[0][1];
         zero = '\n0\tNaN'
+        var ns = '\nname\t' + (obj.getNameFor(thresholds[0][0], thresholds[0][1]) || '');
         for (var i=1; i < thresholds.length; i++) {
             growths += '\tgrowth';
             counts += '\t' + obj.count;
             cs += '\t' + thresholds[i][0];
             qs += '\t' + thresholds[i][1];
+            ns += '\t' + (obj.getNameFor(thresholds[i][0], thresholds[i][1]) || '');
             zero += '\tNaN';
         }
-        table += growths + counts + cs + qs + zero + '\n';
+        if (Object.keys(obj.names).length == 0) {
+            ns = '';
+        }
+        table += growths + counts + cs + qs + ns + zero + '\n';
 
         for (var i=0; i < obj.index.length; i++) {
             table += obj.index[i];
@@ -116,14 +194,41 @@ function buildGrowthTableDownload(chart, obj, prefix) {
         let blob = new Blob([table], {type: 'text/plain'});
         var a = document.createElement('a');
         a.href = URL.createObjectURL(blob);
-        a.download = prefix + '_growth_' + obj.count + '.tsv';
+        a.download = prefix + '_growth_' + objId(obj) + '.tsv';
         if (typeof obj.index[0] === 'string' || obj.index[0] instanceof String) {
-            a.download = prefix + '_orderedgrowth_' + obj.count + '.tsv';
+            a.download = prefix + '_orderedgrowth_' + objId(obj) + '.tsv';
         }
         a.click();
     };
 }
 
+// table of the data shown in a chart, with one row per label and one column per (named) dataset
+function buildChartTableDownload(chart, obj, prefix) {
+    const name = obj.constructor.name.toLowerCase();
+    document.getElementById('btn-download-table-' + name + '-' + objId(obj)).onclick = function() {
+        const datasets = chart.data.datasets.filter(d => d.label !== '');
+        var table = (obj.x_label === undefined ? 'index' : obj.x_label);
+        for (var j=0; j < datasets.length; j++) {
+            table += '\t' + datasets[j].label;
+        }
+        table += '\n';
+
+        for (var i=0; i < chart.data.labels.length; i++) {
+            table += chart.data.labels[i];
+            for (var j=0; j < datasets.length; j++) {
+                table += '\t' + datasets[j].data[i];
+            }
+            table += '\n';
+        }
+
+        let blob = new Blob([table], {type: 'text/plain'});
+        var a = document.createElement('a');
+        a.href = URL.createObjectURL(blob);
+        a.download = prefix + '_' + name + '_' + objId(obj) + '.tsv';
+        a.click();
+    };
+}
+
 function buildInfoTableDownload(table, infoType, prefix) {
     document.getElementById('btn-download-table-info-' + infoType).onclick = function() {
         let blob = new Blob([table], {type: 'text/plain'});
@@ -136,7 +241,7 @@ function buildInfoTableDownload(table, infoType, prefix) {
 
 
 function buildLogToggle(chart, obj) {
-    document.getElementById('btn-logscale-plot-' + obj.constructor.name.toLowerCase() + '-' + obj.count).addEventListener('change', function(event) {
+    document.getElementById('btn-logscale-plot-' + obj.constructor.name.toLowerCase() + '-' + objId(obj)).addEventListener('change', function(event) {
         if (event.currentTarget.checked) {
             chart.options.scales.y.type = 'logarithmic';
         } else {
@@ -145,3 +250,44 @@ function buildLogToggle(chart, obj) {
         chart.update();
     });
 }
+
+// recomputes growth from the histogram of the same count type (and section) for the coverage and
+// quorum thresholds entered by the user, using the growth calculation of panacus compiled to
+// WebAssembly
+var growthWasm;
+function buildGrowthRecompute(chart, g) {
+    const h = hists.find(h => h.id == g.id);
+    if (typeof growth_wasm == 'undefined' || h === undefined) {
+        return;
+    }
+    if (growthWasm === undefined) {
+        const bytes = Uint8Array.from(atob(growth_wasm), c => c.charCodeAt(0));
+        growthWasm = WebAssembly.instantiate(bytes).then(r => r.instance.exports);
+    }
+    document.getElementById('form-recompute-growth-' + g.id).classList.remove('d-none');
+    document.getElementById('btn-recompute-growth-' + g.id).addEventListener('click', function() {
+        const c = Math.max(1, parseInt(document.getElementById('input-coverage-growth-' + g.id).value) || 1);
+        const q = Math.min(1, Math.max(0, parseFloat(document.getElementById('input-quorum-growth-' + g.id).value) || 0));
+        growthWasm.then(function(wasm) {
+            const n = h.coverage.length;
+            const cov = wasm.alloc_f64(n);
+            const out = wasm.alloc_f64(n - 1);
+            new Float64Array(wasm.memory.buffer, cov, n).set(h.coverage);
+            wasm.growth(cov, n, c, q, out);
+            const data = Array.from(new Float64Array(wasm.memory.buffer, out, n - 1), Math.floor);
+            wasm.free_f64(cov, n);
+            wasm.free_f64(out, n - 1);
+            const color = PCOLORS[chart.data.datasets.length % PCOLORS.length];
+            chart.data.datasets.push({
+                label: g.getLabelFor(c, q),
+                data: data,
+                borderWidth: 2,
+                borderDash: [5, 5],
+                pointRadius: 0,
+                backgroundColor: color,
+                borderColor: color
+            });
+            chart.update();
+        });
+    });
+}
@@ -0,0 +1,7 @@
+#!/bin/bash
+# builds the WebAssembly module of the growth calculation that is embedded in HTML reports
+
+cd "$(dirname "$0")/../growth-wasm" || exit 1
+rustup target add wasm32-unknown-unknown
+cargo build --release --target wasm32-unknown-unknown
+cp target/wasm32-unknown-unknown/release/panacus_growth_wasm.wasm ../etc/growth.wasm
@@ -0,0 +1,62 @@
+// C ABI of the growth calculation for the HTML report. Coverage histograms and growth curves are
+// exchanged through buffers of f64 in the linear memory of the WebAssembly module, which the
+// caller allocates by alloc_f64 and releases by free_f64.
+
+#[path = "../../src/growth.rs"]
+mod growth;
+
+#[no_mangle]
+pub extern "C" fn alloc_f64(len: usize) -> *mut f64 {
+    Box::into_raw(vec![0.0; len].into_boxed_slice()) as *mut f64
+}
+
+/// # Safety
+///
+/// ptr and len must have been obtained from alloc_f64, and the buffer must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn free_f64(ptr: *mut f64, len: usize) {
+    drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)));
+}
+
+/// Writes the growth curve of the countables covered by at least c groups and by at least the
+/// given fraction of the groups drawn to out (of length len-1), where coverage (of length len) is
+/// the coverage histogram over len-1 groups.
+///
+/// # Safety
+///
+/// coverage and out must be buffers of lengths len and len-1 obtained from alloc_f64.
+#[no_mangle]
+pub unsafe extern "C" fn growth(
+    coverage: *const f64,
+    len: usize,
+    c: usize,
+    quorum: f64,
+    out: *mut f64,
+) {
+    let coverage: Vec<usize> = std::slice::from_raw_parts(coverage, len)
+        .iter()
+        .map(|x| *x as usize)
+        .collect();
+    let res = growth::growth(&coverage, c, quorum);
+    std::slice::from_raw_parts_mut(out, res.len()).copy_from_slice(&res);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_growth() {
+        let coverage = [0.0, 3.0, 2.0, 0.0, 4.0];
+        unsafe {
+            let cov = alloc_f64(coverage.len());
+            let out = alloc_f64(coverage.len() - 1);
+            std::slice::from_raw_parts_mut(cov, coverage.len()).copy_from_slice(&coverage);
+            growth(cov, coverage.len(), 1, 0.0, out);
+            let res = std::slice::from_raw_parts(out, coverage.len() - 1).to_vec();
+            free_f64(cov, coverage.len());
+            free_f64(out, coverage.len() - 1);
+            assert_eq!(res, growth::growth(&[0, 3, 2, 0, 4], 1, 0.0));
+        }
+    }
+}
@@ -3,68 +3,375 @@ use std::fs;
 use std::io::{BufReader, BufWriter, Write};
 use std::io::{Error, ErrorKind};
 use std::iter::FromIterator;
+use std::sync::Mutex;
+use std::time::SystemTime;
 //use std::sync::{Arc, Mutex};
 
 /* external crate*/
 use itertools::Itertools;
+use once_cell::sync::Lazy;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
 use strum::IntoEnumIterator;
 
 /* private use */
 use crate::cli::Params;
+use crate::cluster::{distances_from_similarities, Tree};
 use crate::graph::*;
+use crate::hist::{hypergeom_at_least, Hist, HistAuxilliary};
 use crate::io::*;
 use crate::util::*;
 
+// coordinates parsed from BED files, keyed by file name, modification time, and PanSN delimiter, so
+// that a file that is shared by several subset or exclude lists (e.g., of --subsets or list
+// expressions) is parsed only once as long as it does not change
+type CoordListCache = HashMap<(String, Option<SystemTime>, char), Vec<PathSegment>>;
+static COORD_LIST_CACHE: Lazy<Mutex<CoordListCache>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
 pub struct AbacusAuxilliary {
     pub groups: HashMap<PathSegment, String>,
     pub include_coords: Option<Vec<PathSegment>>,
     pub exclude_coords: Option<Vec<PathSegment>>,
     pub order: Option<Vec<PathSegment>>,
+    pub canonicalize_edges: bool,
+    // count bps of nodes that are only partially covered by subset or exclude coordinates exactly
+    pub exact_bp: bool,
+    // node-based subset and exclude lists, indexed by numerical node IDs
+    pub include_nodes: Option<Vec<bool>>,
+    pub exclude_nodes: Option<Vec<bool>>,
+}
+
+// options from which the AbacusAuxilliary is built; file names and lists are given as on the
+// command line, where empty strings mean "not given"
+#[derive(Debug, Clone, Default)]
+pub struct AbacusOptions {
+    pub gfa_file: String,
+    pub more_gfa_files: Vec<String>,
+    pub positive_list: String,
+    pub negative_list: String,
+    pub groupby: String,
+    pub groupby_column: String,
+    pub groupby_haplotype: bool,
+    pub groupby_sample: bool,
+    pub groupby_cluster: String,
+    pub rename: String,
+    pub order: String,
+    pub canonicalize_edges: bool,
+    pub exact_bp: bool,
+}
+
+// subset and exclude lists prefixed as follows list node IDs instead of paths or coordinates
+const NODE_LIST_PREFIX: &str = "nodes:";
+// ... or coordinates on a reference path that are projected onto the nodes they traverse
+const LIFTOVER_PREFIX: &str = "liftover:";
+
+// a subset or exclude list is given either by paths or coordinates, or by nodes
+type SubsetList = (Option<Vec<PathSegment>>, Option<Vec<bool>>);
+
+// abaci of one view on the graph, along with the numbers of nodes and bps of each path, if known
+pub type ViewAbaci = (Vec<AbacusByTotal>, Option<PathsLen>);
+
+// set operations by which several subset or exclude lists can be combined
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ListOp {
+    Union,
+    Intersection,
+    Difference,
+}
+
+impl ListOp {
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "+" => Some(ListOp::Union),
+            "&" => Some(ListOp::Intersection),
+            "-" => Some(ListOp::Difference),
+            _ => None,
+        }
+    }
+
+    fn apply(&self, a: bool, b: bool) -> bool {
+        match self {
+            ListOp::Union => a || b,
+            ListOp::Intersection => a && b,
+            ListOp::Difference => a && !b,
+        }
+    }
+}
+
+// splits an expression of the form "<file> [<op> <file> ...]", where operators are separated by
+// whitespace and evaluated from left to right; strings that do not contain any operator are
+// taken as a single file name, which may then also contain whitespace
+fn parse_list_expression(expr: &str) -> Result<Vec<(Option<ListOp>, &str)>, Error> {
+    let tokens: Vec<&str> = expr.split_whitespace().collect();
+    if !tokens.iter().any(|t| ListOp::from_token(t).is_some()) {
+        return Ok(vec![(None, expr)]);
+    }
+    let invalid = || {
+        let msg = format!(
+            "invalid list expression \"{}\": expected files separated by the operators + (union), & (intersection), or - (difference)",
+            expr
+        );
+        log::error!("{}", &msg);
+        Error::new(ErrorKind::InvalidInput, msg)
+    };
+    let mut res = Vec::new();
+    let mut op = None;
+    for (i, token) in tokens.iter().enumerate() {
+        match (i % 2, ListOp::from_token(token)) {
+            (0, None) => res.push((op, *token)),
+            (1, Some(o)) => op = Some(o),
+            _ => return Err(invalid()),
+        }
+    }
+    if tokens.len() % 2 == 0 {
+        return Err(invalid());
+    }
+    Ok(res)
+}
+
+// combines two lists of path segments interval by interval; paths without coordinates span the
+// entire path, and the order of paths is that of their first occurrence in either list
+fn combine_path_segments(a: &[PathSegment], b: &[PathSegment], op: ListOp) -> Vec<PathSegment> {
+    let map_a = AbacusAuxilliary::build_subpath_map(a);
+    let map_b = AbacusAuxilliary::build_subpath_map(b);
+    let mut paths: Vec<PathSegment> = Vec::new();
+    for p in a.iter().chain(b.iter()) {
+        let p = p.clear_coords();
+        if !paths.contains(&p) {
+            paths.push(p);
+        }
+    }
+    let mut res = Vec::new();
+    for p in paths {
+        let x = map_a.get(&p.id()).map(|v| &v[..]).unwrap_or(&[]);
+        let y = map_b.get(&p.id()).map(|v| &v[..]).unwrap_or(&[]);
+        for (start, end) in combine_intervals(x, y, op) {
+            let mut q = p.clone();
+            if (start, end) != (0, usize::MAX) {
+                q.start = Some(start);
+                q.end = Some(end);
+            }
+            res.push(q);
+        }
+    }
+    res
+}
+
+// set operation on two sorted lists of disjoint, half-open intervals
+fn combine_intervals(
+    a: &[(usize, usize)],
+    b: &[(usize, usize)],
+    op: ListOp,
+) -> Vec<(usize, usize)> {
+    match op {
+        ListOp::Union => {
+            let mut v: Vec<(usize, usize)> = a.iter().chain(b.iter()).cloned().collect();
+            v.sort();
+            let mut res: Vec<(usize, usize)> = Vec::new();
+            for (start, end) in v {
+                match res.last_mut() {
+                    Some(last) if last.1 >= start => last.1 = std::cmp::max(last.1, end),
+                    _ => res.push((start, end)),
+                }
+            }
+            res
+        }
+        ListOp::Intersection => {
+            let mut res = Vec::new();
+            let (mut i, mut j) = (0, 0);
+            while i < a.len() && j < b.len() {
+                let start = std::cmp::max(a[i].0, b[j].0);
+                let end = std::cmp::min(a[i].1, b[j].1);
+                if start < end {
+                    res.push((start, end));
+                }
+                if a[i].1 < b[j].1 {
+                    i += 1;
+                } else {
+                    j += 1;
+                }
+            }
+            res
+        }
+        ListOp::Difference => {
+            let mut res = Vec::new();
+            for &(mut start, end) in a {
+                for &(s, e) in b {
+                    if e <= start || s >= end {
+                        continue;
+                    }
+                    if s > start {
+                        res.push((start, s));
+                    }
+                    start = e;
+                }
+                if start < end {
+                    res.push((start, end));
+                }
+            }
+            res
+        }
+    }
 }
 
 impl AbacusAuxilliary {
     pub fn from_params(params: &Params, graph_aux: &GraphAuxilliary) -> Result<Self, Error> {
         match params {
             Params::Histgrowth {
+                gfa_file,
                 positive_list,
                 negative_list,
                 groupby,
+                groupby_column,
                 groupby_sample,
                 groupby_haplotype,
+                groupby_cluster,
+                rename,
                 ..
             }
             | Params::Hist {
+                gfa_file,
                 positive_list,
                 negative_list,
                 groupby,
+                groupby_column,
                 groupby_sample,
                 groupby_haplotype,
+                groupby_cluster,
+                rename,
                 ..
             }
             | Params::Info {
+                gfa_file,
                 positive_list,
                 negative_list,
                 groupby,
+                groupby_column,
                 groupby_sample,
                 groupby_haplotype,
+                groupby_cluster,
+                rename,
                 ..
             }
             | Params::OrderedHistgrowth {
+                gfa_file,
                 positive_list,
                 negative_list,
                 groupby,
+                groupby_column,
                 groupby_sample,
                 groupby_haplotype,
+                groupby_cluster,
+                rename,
                 ..
             }
             | Params::Table {
+                gfa_file,
+                positive_list,
+                negative_list,
+                groupby,
+                groupby_column,
+                groupby_sample,
+                groupby_haplotype,
+                groupby_cluster,
+                rename,
+                ..
+            }
+            | Params::Branching {
+                gfa_file,
+                positive_list,
+                negative_list,
+                groupby,
+                groupby_column,
+                groupby_sample,
+                groupby_haplotype,
+                groupby_cluster,
+                rename,
+                ..
+            }
+            | Params::Delta {
+                gfa_file,
+                positive_list,
+                negative_list,
+                groupby,
+                groupby_column,
+                groupby_sample,
+                groupby_haplotype,
+                groupby_cluster,
+                rename,
+                ..
+            }
+            | Params::Query {
+                gfa_file,
+                positive_list,
+                negative_list,
+                groupby,
+                groupby_column,
+                groupby_sample,
+                groupby_haplotype,
+                groupby_cluster,
+                rename,
+                ..
+            }
+            | Params::Pairwise {
+                gfa_file,
+                positive_list,
+                negative_list,
+                groupby,
+                groupby_column,
+                groupby_sample,
+                groupby_haplotype,
+                groupby_cluster,
+                rename,
+                ..
+            }
+            | Params::Private {
+                gfa_file,
+                positive_list,
+                negative_list,
+                groupby,
+                groupby_column,
+                groupby_sample,
+                groupby_haplotype,
+                groupby_cluster,
+                rename,
+                ..
+            }
+            | Params::Strand {
+                gfa_file,
+                positive_list,
+                negative_list,
+                groupby,
+                groupby_column,
+                groupby_sample,
+                groupby_haplotype,
+                groupby_cluster,
+                rename,
+                ..
+            }
+            | Params::Similarity {
+                gfa_file,
+                positive_list,
+                negative_list,
+                groupby,
+                groupby_column,
+                groupby_sample,
+                groupby_haplotype,
+                groupby_cluster,
+                rename,
+                ..
+            }
+            | Params::Windows {
+                gfa_file,
                 positive_list,
                 negative_list,
                 groupby,
+                groupby_column,
                 groupby_sample,
                 groupby_haplotype,
+                groupby_cluster,
+                rename,
                 ..
             }
             //| Params::Cdbg {
@@ -76,97 +383,53 @@ impl AbacusAuxilliary {
             //    ..
             //}
             => {
-                let groups = AbacusAuxilliary::load_groups(
-                    groupby,
-                    *groupby_haplotype,
-                    *groupby_sample,
-                    graph_aux,
-                )?;
-                let include_coords = AbacusAuxilliary::complement_with_group_assignments(
-                    AbacusAuxilliary::load_coord_list(positive_list)?,
-                    &groups,
-                )?;
-                let exclude_coords = AbacusAuxilliary::complement_with_group_assignments(
-                    AbacusAuxilliary::load_coord_list(negative_list)?,
-                    &groups,
-                )?;
-
-                let order = if let Params::OrderedHistgrowth { order, .. } = params {
-                    let maybe_order = AbacusAuxilliary::complement_with_group_assignments(
-                        AbacusAuxilliary::load_coord_list(order)?,
-                        &groups,
-                    )?;
-                    if let Some(o) = &maybe_order {
-                        // if order is given, check that it comprises all included coords
-                        let all_included_paths: Vec<PathSegment> = match &include_coords {
-                            None => {
-                                let exclude: HashSet<&PathSegment> = match &exclude_coords {
-                                    Some(e) => e.iter().collect(),
-                                    None => HashSet::new(),
-                                };
-                                graph_aux
-                                    .path_segments
-                                    .iter()
-                                    .filter_map(|x| {
-                                        if !exclude.contains(x) {
-                                            Some(x.clear_coords())
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                    .collect()
-                            }
-                            Some(include) => include.iter().map(|x| x.clear_coords()).collect(),
-                        };
-                        let order_set: HashSet<&PathSegment> = HashSet::from_iter(o.iter());
-
-                        for p in all_included_paths.iter() {
-                            if !order_set.contains(p) {
-                                let msg = format!(
-                                    "order list does not contain information about path {}",
-                                    p
-                                );
-                                log::error!("{}", &msg);
-                                // let's not be that harsh, shall we?
-                                // return Err(Error::new( ErrorKind::InvalidData, msg));
-                            }
-                        }
-
-                        // check that groups are not scrambled in include
-                        let mut visited: HashSet<&str> = HashSet::new();
-                        let mut cur: &str = groups.get(&o[0]).unwrap();
-                        for p in o.iter() {
-                            let g: &str = groups.get(p).unwrap();
-                            if cur != g && !visited.insert(g) {
-                                let msg = format!("order of paths contains fragmented groups: path {} belongs to group that is interspersed by one or more other groups", p);
-                                log::error!("{}", &msg);
-                                return Err(Error::new(ErrorKind::InvalidData, msg));
-                            }
-                            cur = g;
+                let options = AbacusOptions {
+                    gfa_file: gfa_file.clone(),
+                    more_gfa_files: match params {
+                        Params::Histgrowth { more_gfa_files, .. }
+                        | Params::Hist { more_gfa_files, .. }
+                        | Params::Info { more_gfa_files, .. }
+                        | Params::Table { more_gfa_files, .. } => more_gfa_files.clone(),
+                        _ => Vec::new(),
+                    },
+                    positive_list: positive_list.clone(),
+                    negative_list: negative_list.clone(),
+                    groupby: groupby.clone(),
+                    groupby_column: groupby_column.clone(),
+                    groupby_haplotype: *groupby_haplotype,
+                    groupby_sample: *groupby_sample,
+                    groupby_cluster: groupby_cluster.clone(),
+                    rename: rename.clone(),
+                    order: match params {
+                        Params::OrderedHistgrowth { order, .. } => order.clone(),
+                        _ => String::new(),
+                    },
+                    canonicalize_edges: matches!(
+                        params,
+                        Params::Histgrowth {
+                            canonicalize_edges: true,
+                            ..
+                        } | Params::Hist {
+                            canonicalize_edges: true,
+                            ..
+                        } | Params::OrderedHistgrowth {
+                            canonicalize_edges: true,
+                            ..
+                        } | Params::Table {
+                            canonicalize_edges: true,
+                            ..
+                        } | Params::Branching {
+                            canonicalize_edges: true,
+                            ..
                         }
-                    }
-                    maybe_order
-                } else {
-                    None
+                    ),
+                    exact_bp: matches!(
+                        params,
+                        Params::Histgrowth { exact_bp: true, .. }
+                            | Params::Hist { exact_bp: true, .. }
+                    ),
                 };
-
-                //let n_groups = HashSet::<&String>::from_iter(groups.values()).len();
-                //if n_groups > 65534 {
-                //    return Err(Error::new(
-                //        ErrorKind::Unsupported,
-                //        format!(
-                //            "data has {} path groups, but command is not supported for more than 65534",
-                //            n_groups
-                //        ),
-                //    ));
-                //}
-
-                Ok(AbacusAuxilliary {
-                    groups,
-                    include_coords,
-                    exclude_coords,
-                    order,
-                })
+                AbacusAuxilliary::from_options(&options, graph_aux)
             }
             _ => Err(Error::new(
                 ErrorKind::InvalidData,
@@ -175,6 +438,165 @@ impl AbacusAuxilliary {
         }
     }
 
+    // Resolves path groups and subset, exclude, and order lists as given in the options
+    pub fn from_options(
+        options: &AbacusOptions,
+        graph_aux: &GraphAuxilliary,
+    ) -> Result<Self, Error> {
+        let AbacusOptions {
+            gfa_file,
+            more_gfa_files,
+            positive_list,
+            negative_list,
+            groupby,
+            groupby_column,
+            groupby_haplotype,
+            groupby_sample,
+            groupby_cluster,
+            rename,
+            order,
+            ..
+        } = options;
+        let groups = AbacusAuxilliary::load_groups(
+            groupby,
+            groupby_column,
+            *groupby_haplotype,
+            *groupby_sample,
+            groupby_cluster,
+            graph_aux,
+        )?;
+        // renaming is applied before subset, exclude, and order lists are resolved, so
+        // that groups merged by renaming are included or excluded as a whole; these lists
+        // hence must refer to groups by their new identifiers
+        let old_groups: HashSet<String> = groups.values().cloned().collect();
+        let groups = AbacusAuxilliary::rename_groups(groups, rename)?;
+        let new_groups: HashSet<&String> = groups.values().collect();
+        let renamed: HashSet<&String> = old_groups
+            .iter()
+            .filter(|g| !new_groups.contains(g))
+            .collect();
+        for list in [positive_list, negative_list, order] {
+            AbacusAuxilliary::check_renamed_references(
+                list,
+                &renamed,
+                &groups,
+                graph_aux.pansn_delimiter,
+            )?;
+        }
+        let gfa_files = gfa_file_list(gfa_file, more_gfa_files);
+        let (include_coords, include_nodes) =
+            AbacusAuxilliary::load_list(positive_list, &gfa_files, graph_aux, &groups)?;
+        let (exclude_coords, exclude_nodes) =
+            AbacusAuxilliary::load_list(negative_list, &gfa_files, graph_aux, &groups)?;
+
+        let order = {
+            let maybe_order = AbacusAuxilliary::complement_with_group_assignments(
+                AbacusAuxilliary::load_coord_list(order, graph_aux.pansn_delimiter)?,
+                &groups,
+            )?;
+            if let Some(o) = &maybe_order {
+                // if order is given, check that it comprises all included coords
+                let all_included_paths: Vec<PathSegment> = match &include_coords {
+                    None => {
+                        let exclude: HashSet<&PathSegment> = match &exclude_coords {
+                            Some(e) => e.iter().collect(),
+                            None => HashSet::new(),
+                        };
+                        graph_aux
+                            .path_segments
+                            .iter()
+                            .filter_map(|x| {
+                                if !exclude.contains(x) {
+                                    Some(x.clear_coords())
+                                } else {
+                                    None
+                                }
+                            })
+                            .collect()
+                    }
+                    Some(include) => include.iter().map(|x| x.clear_coords()).collect(),
+                };
+                let order_set: HashSet<&PathSegment> = HashSet::from_iter(o.iter());
+
+                for p in all_included_paths.iter() {
+                    if !order_set.contains(p) {
+                        let msg =
+                            format!("order list does not contain information about path {}", p);
+                        log::error!("{}", &msg);
+                        // let's not be that harsh, shall we?
+                        // return Err(Error::new( ErrorKind::InvalidData, msg));
+                    }
+                }
+
+                // check that groups are not scrambled in include
+                let mut visited: HashSet<&str> = HashSet::new();
+                let mut cur: &str = groups.get(&o[0]).unwrap();
+                for p in o.iter() {
+                    let g: &str = groups.get(p).unwrap();
+                    if cur != g && !visited.insert(g) {
+                        let msg = format!("order of paths contains fragmented groups: path {} belongs to group that is interspersed by one or more other groups", p);
+                        log::error!("{}", &msg);
+                        return Err(Error::new(ErrorKind::InvalidData, msg));
+                    }
+                    cur = g;
+                }
+            }
+            maybe_order
+        };
+
+        //let n_groups = HashSet::<&String>::from_iter(groups.values()).len();
+        //if n_groups > 65534 {
+        //    return Err(Error::new(
+        //        ErrorKind::Unsupported,
+        //        format!(
+        //            "data has {} path groups, but command is not supported for more than 65534",
+        //            n_groups
+        //        ),
+        //    ));
+        //}
+
+        Ok(AbacusAuxilliary {
+            groups,
+            include_coords,
+            exclude_coords,
+            order,
+            canonicalize_edges: options.canonicalize_edges,
+            exact_bp: options.exact_bp,
+            include_nodes,
+            exclude_nodes,
+        })
+    }
+
+    // fails if the given subset, exclude, or order list refers to a group by an identifier that
+    // has been renamed
+    fn check_renamed_references(
+        list: &str,
+        renamed: &HashSet<&String>,
+        groups: &HashMap<PathSegment, String>,
+        pansn_delimiter: char,
+    ) -> Result<(), Error> {
+        if renamed.is_empty() || list.is_empty() {
+            return Ok(());
+        }
+        for (_, file_name) in parse_list_expression(list)? {
+            for p in Self::load_coord_list(file_name, pansn_delimiter)?.unwrap_or_default() {
+                if renamed.contains(&p.id())
+                    && p.coords().is_none()
+                    && !groups.contains_key(&p.clear_coords())
+                {
+                    let msg = format!(
+                        "{} refers to group {}, which is renamed by --rename; refer to it by its new identifier",
+                        file_name,
+                        p.id()
+                    );
+                    log::error!("{}", &msg);
+                    return Err(Error::new(ErrorKind::InvalidInput, msg));
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn complement_with_group_assignments(
         coords: Option<Vec<PathSegment>>,
         groups: &HashMap<PathSegment, String>,
@@ -226,25 +648,176 @@ impl AbacusAuxilliary {
         }
     }
 
-    fn load_coord_list(file_name: &str) -> Result<Option<Vec<PathSegment>>, Error> {
-        Ok(if file_name.is_empty() {
-            None
-        } else {
-            log::info!("loading coordinates from {}", file_name);
-            let mut data = BufReader::new(fs::File::open(file_name)?);
-            let use_block_info = true;
-            let coords = parse_bed_to_path_segments(&mut data, use_block_info);
-            log::debug!("loaded {} coordinates", coords.len());
-            Some(coords)
-        })
+    // loads a subset or exclude list, which is either a single file or an expression combining
+    // several files by set operations; lists of paths or coordinates can only be combined with
+    // each other, and so can node lists
+    fn load_list(
+        list: &str,
+        gfa_files: &[String],
+        graph_aux: &GraphAuxilliary,
+        groups: &HashMap<PathSegment, String>,
+    ) -> Result<SubsetList, Error> {
+        let mut res = (None, None);
+        for (op, file_name) in parse_list_expression(list)? {
+            let coords = Self::complement_with_group_assignments(
+                Self::load_coord_list(file_name, graph_aux.pansn_delimiter)?,
+                groups,
+            )?;
+            let nodes = Self::load_node_list(file_name, gfa_files, graph_aux)?;
+            res = match (op, res, coords, nodes) {
+                (None, _, coords, nodes) => (coords, nodes),
+                (Some(op), (Some(a), None), Some(b), None) => {
+                    (Some(combine_path_segments(&a, &b, op)), None)
+                }
+                (Some(op), (None, Some(a)), None, Some(b)) => (
+                    None,
+                    Some(
+                        a.iter()
+                            .zip(b.iter())
+                            .map(|(x, y)| op.apply(*x, *y))
+                            .collect(),
+                    ),
+                ),
+                _ => {
+                    let msg = format!(
+                        "unable to combine \"{}\" in \"{}\": node lists cannot be combined with lists of paths or coordinates",
+                        file_name, list
+                    );
+                    log::error!("{}", &msg);
+                    return Err(Error::new(ErrorKind::InvalidInput, msg));
+                }
+            };
+        }
+        Ok(res)
+    }
+
+    fn load_coord_list(
+        file_name: &str,
+        pansn_delimiter: char,
+    ) -> Result<Option<Vec<PathSegment>>, Error> {
+        Ok(
+            if file_name.is_empty()
+                || file_name.starts_with(NODE_LIST_PREFIX)
+                || file_name.starts_with(LIFTOVER_PREFIX)
+            {
+                None
+            } else {
+                let key = (
+                    file_name.to_string(),
+                    fs::metadata(file_name)?.modified().ok(),
+                    pansn_delimiter,
+                );
+                if let Some(coords) = COORD_LIST_CACHE.lock().unwrap().get(&key) {
+                    log::info!("reusing coordinates loaded from {}", file_name);
+                    return Ok(Some(coords.clone()));
+                }
+                log::info!("loading coordinates from {}", file_name);
+                let mut data = BufReader::new(fs::File::open(file_name)?);
+                let use_block_info = true;
+                let coords = parse_bed_to_path_segments(&mut data, use_block_info, pansn_delimiter);
+                let paths: HashSet<String> = coords.iter().map(|c| c.id()).collect();
+                let bps: usize = coords
+                    .iter()
+                    .filter_map(|c| c.coords())
+                    .map(|(start, end)| end.saturating_sub(start))
+                    .sum();
+                log::info!(
+                    "loaded {} coordinates on {} paths ({} bp in intervals) from {}",
+                    coords.len(),
+                    paths.len(),
+                    bps,
+                    file_name
+                );
+                COORD_LIST_CACHE.lock().unwrap().insert(key, coords.clone());
+                Some(coords)
+            },
+        )
+    }
+
+    // reads the nodes of a list given as "nodes:<file>", or those traversed by coordinates given
+    // as "liftover:<file>"; returns a table over numerical node IDs that tells whether a node is
+    // listed
+    fn load_node_list(
+        file_name: &str,
+        gfa_files: &[String],
+        graph_aux: &GraphAuxilliary,
+    ) -> Result<Option<Vec<bool>>, Error> {
+        if let Some(file_name) = file_name.strip_prefix(LIFTOVER_PREFIX) {
+            return Self::lift_coord_list(file_name, gfa_files, graph_aux).map(Some);
+        }
+        let file_name = match file_name.strip_prefix(NODE_LIST_PREFIX) {
+            None => return Ok(None),
+            Some(f) => f,
+        };
+        log::info!("loading node list from {}", file_name);
+        let mut data = BufReader::new(fs::File::open(file_name)?);
+        let mut listed = vec![false; graph_aux.node_count + 1];
+        let mut unknown = 0;
+        for node in parse_node_list(&mut data)? {
+            match graph_aux.node2id.get(node.as_bytes()) {
+                Some(id) => listed[id.0 as usize] = true,
+                None => unknown += 1,
+            }
+        }
+        if unknown > 0 {
+            log::warn!(
+                "ignoring {} nodes of {} that are not part of the graph",
+                unknown,
+                file_name
+            );
+        }
+        log::debug!("loaded {} nodes", listed.iter().filter(|x| **x).count());
+        Ok(Some(listed))
+    }
+
+    // projects coordinates given on one or more reference paths onto the nodes they traverse;
+    // through these, the coordinates extend to all other paths of the graph
+    fn lift_coord_list(
+        file_name: &str,
+        gfa_files: &[String],
+        graph_aux: &GraphAuxilliary,
+    ) -> Result<Vec<bool>, Error> {
+        log::info!("lifting over coordinates from {}", file_name);
+        let mut data = BufReader::new(fs::File::open(file_name)?);
+        let coords = parse_bed_to_path_segments(&mut data, true, graph_aux.pansn_delimiter);
+        let mut listed = vec![false; graph_aux.node_count + 1];
+        for (path_name, intervals) in Self::build_subpath_map(&coords) {
+            let mut data = bufreader_from_gfa_files(gfa_files)?;
+            let path = parse_path_nodes(&mut data, &path_name, graph_aux)?;
+            let mut offsets = vec![0];
+            for (id, _) in &path {
+                offsets.push(offsets.last().unwrap() + graph_aux.node_len(id) as usize);
+            }
+            for (start, end) in intervals {
+                // first node of the path that ends after the interval start
+                let mut i = offsets.partition_point(|&x| x <= start).saturating_sub(1);
+                while i < path.len() && offsets[i] < end {
+                    listed[path[i].0 .0 as usize] = true;
+                    i += 1;
+                }
+            }
+        }
+        log::debug!(
+            "lifted {} coordinates over to {} nodes",
+            coords.len(),
+            listed.iter().filter(|x| **x).count()
+        );
+        Ok(listed)
     }
 
     fn load_groups(
         file_name: &str,
+        column: &str,
         groupby_haplotype: bool,
         groupby_sample: bool,
+        cluster_file_name: &str,
         graph_aux: &GraphAuxilliary,
     ) -> Result<HashMap<PathSegment, String>, Error> {
+        if !column.is_empty() && file_name.is_empty() {
+            let msg = "selecting a column of the group table requires a group table (--groupby)";
+            log::error!("{}", msg);
+            return Err(Error::new(ErrorKind::InvalidInput, msg));
+        }
         if groupby_haplotype {
             Ok(graph_aux
                 .path_segments
@@ -253,8 +826,9 @@ impl AbacusAuxilliary {
                     (
                         x.clear_coords(),
                         format!(
-                            "{}#{}",
+                            "{}{}{}",
                             &x.sample,
+                            graph_aux.pansn_delimiter,
                             &x.haplotype.as_ref().unwrap_or(&String::new())
                         ),
                     )
@@ -267,11 +841,16 @@ impl AbacusAuxilliary {
                 .map(|x| (x.clear_coords(), x.sample.clone()))
                 .collect())
         } else if !file_name.is_empty() {
-            log::info!("loading groups from {}", file_name);
             let mut data = BufReader::new(fs::File::open(file_name)?);
-            let group_assignments = parse_groups(&mut data)?;
+            let group_assignments = if column.is_empty() {
+                log::info!("loading groups from {}", file_name);
+                parse_groups(&mut data, graph_aux.pansn_delimiter)?
+            } else {
+                log::info!("loading groups from column {} of {}", column, file_name);
+                parse_group_metadata(&mut data, column, graph_aux.pansn_delimiter)?
+            };
             let mut path_to_group = HashMap::default();
-            for (i, (path, group)) in group_assignments.into_iter().enumerate() {
+            for (path, group, i) in group_assignments {
                 let path_nocoords = path.clear_coords();
                 match path_to_group.get(&path_nocoords) {
                     Some(g) => {
@@ -297,6 +876,58 @@ impl AbacusAuxilliary {
                 path_to_group.entry(path).or_insert_with(|| x.id());
             });
             Ok(path_to_group)
+        } else if !cluster_file_name.is_empty() {
+            log::info!("loading clusters from {}", cluster_file_name);
+            let mut data = BufReader::new(fs::File::open(cluster_file_name)?);
+            let mut member_to_cluster: HashMap<String, String> = HashMap::default();
+            for (representative, member, i) in parse_clusters(&mut data)? {
+                if let Some(c) = member_to_cluster.get(&member) {
+                    if c != &representative {
+                        let msg = format!(
+                            "error in line {}: {} cannot be member of more than one cluster, but is member of at least two clusters: {}, {}",
+                            i, &member, &c, &representative
+                        );
+                        log::error!("{}", &msg);
+                        return Err(Error::new(ErrorKind::InvalidData, msg));
+                    }
+                }
+                member_to_cluster.insert(member, representative);
+            }
+            log::debug!("loaded {} cluster members", member_to_cluster.len());
+
+            // members are matched by path, haplotype, or sample identifier (in that order);
+            // unassigned path segments are treated as singleton clusters
+            let mut unassigned = 0;
+            let path_to_group: HashMap<PathSegment, String> = graph_aux
+                .path_segments
+                .iter()
+                .map(|x| {
+                    let path = x.clear_coords();
+                    let haplotype = format!(
+                        "{}{}{}",
+                        &x.sample,
+                        graph_aux.pansn_delimiter,
+                        &x.haplotype.as_ref().unwrap_or(&String::new())
+                    );
+                    let cluster = member_to_cluster
+                        .get(&path.id())
+                        .or_else(|| member_to_cluster.get(&haplotype))
+                        .or_else(|| member_to_cluster.get(&x.sample))
+                        .cloned()
+                        .unwrap_or_else(|| {
+                            unassigned += 1;
+                            path.id()
+                        });
+                    (path, cluster)
+                })
+                .collect();
+            if unassigned > 0 {
+                log::warn!(
+                    "{} paths are not member of any cluster and are treated as singleton clusters",
+                    unassigned
+                );
+            }
+            Ok(path_to_group)
         } else {
             log::info!("no explicit grouping instruction given, group paths by their IDs (sample ID+haplotype ID+seq ID)");
             Ok(graph_aux
@@ -307,6 +938,116 @@ impl AbacusAuxilliary {
         }
     }
 
+    fn rename_groups(
+        groups: HashMap<PathSegment, String>,
+        file_name: &str,
+    ) -> Result<HashMap<PathSegment, String>, Error> {
+        if file_name.is_empty() {
+            return Ok(groups);
+        }
+        log::info!("loading group renaming rules from {}", file_name);
+        let mut data = BufReader::new(fs::File::open(file_name)?);
+        let (names, patterns) = parse_rename_rules(&mut data)?;
+
+        let mut renamed: HashMap<String, String> = HashMap::default();
+        for g in groups.values() {
+            if renamed.contains_key(g) {
+                continue;
+            }
+            let new = match names.get(g) {
+                Some(n) => n.clone(),
+                None => patterns.iter().fold(g.clone(), |acc, (re, rep)| {
+                    re.replace_all(&acc, rep.as_str()).into_owned()
+                }),
+            };
+            renamed.insert(g.clone(), new);
+        }
+
+        let n_before = renamed.len();
+        let n_after = renamed.values().collect::<HashSet<&String>>().len();
+        log::info!(
+            "renamed {} of {} groups",
+            renamed.iter().filter(|(k, v)| k != v).count(),
+            n_before
+        );
+        if n_after < n_before {
+            log::warn!(
+                "renaming merged {} groups into {} groups",
+                n_before,
+                n_after
+            );
+        }
+
+        Ok(groups
+            .into_iter()
+            .map(|(p, g)| {
+                let n = renamed[&g].clone();
+                (p, n)
+            })
+            .collect())
+    }
+
+    // same grouping and exclusions, but restricted to a different subset of paths
+    pub fn with_subset(
+        &self,
+        positive_list: &str,
+        gfa_files: &[String],
+        graph_aux: &GraphAuxilliary,
+    ) -> Result<Self, Error> {
+        let (include_coords, include_nodes) =
+            Self::load_list(positive_list, gfa_files, graph_aux, &self.groups)?;
+        Ok(Self {
+            groups: self.groups.clone(),
+            include_coords,
+            exclude_coords: self.exclude_coords.clone(),
+            order: None,
+            canonicalize_edges: self.canonicalize_edges,
+            exact_bp: self.exact_bp,
+            include_nodes,
+            exclude_nodes: self.exclude_nodes.clone(),
+        })
+    }
+
+    // tells how the bps of nodes that are only partially covered by subset or exclude coordinates
+    // are counted, if there may be any
+    pub fn bp_counting_mode(&self, count: CountType) -> Option<String> {
+        if !matches!(count, CountType::Bp | CountType::All)
+            || (self.include_coords.is_none() && self.exclude_coords.is_none())
+        {
+            return None;
+        }
+        Some(if self.exact_bp {
+            "bps of partially covered nodes are counted exactly".to_string()
+        } else {
+            "bps of partially covered nodes are counted approximately (see --exact-bp)".to_string()
+        })
+    }
+
+    // restricts the paths under consideration to those on the given sequence (e.g., chromosome),
+    // i.e., to those whose PanSN name carries the given sequence identifier
+    pub fn with_sequence(&self, seqid: &str, graph_aux: &GraphAuxilliary) -> Self {
+        let on_sequence = |p: &&PathSegment| p.seqid.as_deref() == Some(seqid);
+        let include_coords = match &self.include_coords {
+            Some(include) => include.iter().filter(on_sequence).cloned().collect(),
+            None => graph_aux
+                .path_segments
+                .iter()
+                .filter(on_sequence)
+                .map(|p| p.clear_coords())
+                .collect(),
+        };
+        Self {
+            groups: self.groups.clone(),
+            include_coords: Some(include_coords),
+            exclude_coords: self.exclude_coords.clone(),
+            order: None,
+            canonicalize_edges: self.canonicalize_edges,
+            exact_bp: self.exact_bp,
+            include_nodes: self.include_nodes.clone(),
+            exclude_nodes: self.exclude_nodes.clone(),
+        }
+    }
+
     fn get_path_order<'a>(&'a self, path_segments: &[PathSegment]) -> Vec<(ItemIdSize, &'a str)> {
         // orders elements of path_segments by the order in abacus_aux.include; the returned vector
         // maps indices of path_segments to the group identifier
@@ -402,14 +1143,48 @@ impl AbacusAuxilliary {
             };
 
         // this table stores information about excluded nodes *if* the exclude setting is used
-        let exclude_table = self.exclude_coords.as_ref().map(|_| {
-            ActiveTable::new(
+        let mut exclude_table = if self.exclude_coords.is_some()
+            || self.include_nodes.is_some()
+            || self.exclude_nodes.is_some()
+        {
+            Some(ActiveTable::new(
                 graph_aux.number_of_items(count) + 1,
                 count == &CountType::Bp,
-            )
-        });
+            ))
+        } else {
+            None
+        };
 
-        // build "include" lookup table
+        // nodes that are not part of a node-based subset list or that are part of a node-based
+        // exclude list are excluded entirely
+        if let Some(table) = exclude_table.as_mut() {
+            let is_masked = |id: &ItemId| {
+                let i = id.0 as usize;
+                self.include_nodes.as_ref().map_or(false, |x| !x[i])
+                    || self.exclude_nodes.as_ref().map_or(false, |x| x[i])
+            };
+            match count {
+                CountType::Edge => {
+                    if let Some(edge2id) = &graph_aux.edge2id {
+                        for (Edge(u, _, v, _), id) in edge2id.iter() {
+                            if is_masked(u) || is_masked(v) {
+                                table.activate(id);
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    for i in 1..graph_aux.node_count + 1 {
+                        let id = ItemId(i as ItemIdSize);
+                        if is_masked(&id) {
+                            table.activate(&id);
+                        }
+                    }
+                }
+            }
+        }
+
+        // build "include" lookup table
         let include_map = match &self.include_coords {
             None => HashMap::default(),
             Some(coords) => Self::build_subpath_map(coords),
@@ -430,6 +1205,9 @@ pub struct AbacusByTotal {
     pub count: CountType,
     pub countable: Vec<CountSize>,
     pub uncovered_bps: Option<HashMap<ItemIdSize, usize>>,
+    // if bps are counted exactly, the numbers of bps of partially covered nodes by the number of
+    // groups that cover them, replacing their entry in uncovered_bps
+    pub exact_bps: Option<HashMap<ItemIdSize, Vec<usize>>>,
     pub groups: Vec<String>,
 }
 
@@ -439,26 +1217,87 @@ impl AbacusByTotal {
         abacus_aux: &AbacusAuxilliary,
         graph_aux: &GraphAuxilliary,
         count: CountType,
-    ) -> Self {
+    ) -> Result<Self, Error> {
         let (item_table, exclude_table, subset_covered_bps, _paths_len) =
-            parse_gfa_paths_walks(data, abacus_aux, graph_aux, &count);
-        Self::item_table_to_abacus(
+            parse_gfa_paths_walks(data, abacus_aux, graph_aux, &count)?;
+        Ok(Self::item_table_to_abacus(
             abacus_aux,
             graph_aux,
             count,
-            item_table,
+            &item_table,
             exclude_table,
             subset_covered_bps,
-        )
+        ))
+    }
+
+    // computes the coverage of countables with a single counter array while streaming over the
+    // paths of the GFA file, without constructing a table of the countables of all paths; this
+    // requires that paths of the same group appear consecutively in the GFA file and does not
+    // support subset or exclude lists
+    pub fn from_gfa_low_mem<R: std::io::Read>(
+        data: &mut BufReader<R>,
+        abacus_aux: &AbacusAuxilliary,
+        graph_aux: &GraphAuxilliary,
+        count: CountType,
+    ) -> Result<Self, Error> {
+        if abacus_aux.include_coords.is_some()
+            || abacus_aux.exclude_coords.is_some()
+            || abacus_aux.include_nodes.is_some()
+            || abacus_aux.exclude_nodes.is_some()
+        {
+            let msg = "low-memory mode does not support subset or exclude lists";
+            log::error!("{}", msg);
+            return Err(Error::new(ErrorKind::Unsupported, msg));
+        }
+
+        let mut stream = AbacusStream::new(graph_aux, count)?;
+        let mut res = Ok(());
+        parse_gfa_paths_walks_streaming(
+            data,
+            graph_aux,
+            &count,
+            abacus_aux.canonicalize_edges,
+            |path_seg, item_table| {
+                if res.is_err() {
+                    return;
+                }
+                let group_id = abacus_aux.groups.get(&path_seg.clear_coords()).unwrap();
+                let g = match stream.enter_group(group_id) {
+                    Some(g) => g,
+                    None => {
+                        let msg = format!(
+                            "paths of group {} do not appear consecutively in the GFA file, which is required in low-memory mode",
+                            group_id
+                        );
+                        log::error!("{}", &msg);
+                        res = Err(Error::new(ErrorKind::InvalidData, msg));
+                        return;
+                    }
+                };
+                for items in item_table.items.iter() {
+                    for &sid in items {
+                        if let Err(e) = stream.cover(sid as usize, g) {
+                            res = Err(e);
+                            return;
+                        }
+                    }
+                }
+            },
+        )?;
+        res?;
+
+        let mut abacus = stream.finalize();
+        abacus.uncovered_bps = Some(quantify_uncovered_bps(&None, &None, graph_aux));
+        Ok(abacus)
     }
 
     pub fn item_table_to_abacus(
         abacus_aux: &AbacusAuxilliary,
         graph_aux: &GraphAuxilliary,
         count: CountType,
-        item_table: ItemTable,
+        item_table: &ItemTable,
         exclude_table: Option<ActiveTable>,
-        subset_covered_bps: Option<IntervalContainer>,
+        mut subset_covered_bps: Option<IntervalContainer>,
     ) -> Self {
         log::info!("counting abacus entries..");
         // first element in countable is "zero" element. It is ignored in counting
@@ -469,6 +1308,7 @@ impl AbacusByTotal {
             vec![ItemIdSize::MAX; graph_aux.number_of_items(&count) + 1];
 
         let mut groups = Vec::new();
+        let mut path_groups = Vec::new();
         for (path_id, group_id) in abacus_aux.get_path_order(&graph_aux.path_segments) {
             if groups.is_empty() || groups.last().unwrap() != group_id {
                 groups.push(group_id.to_string());
@@ -476,11 +1316,12 @@ impl AbacusByTotal {
             AbacusByTotal::coverage(
                 &mut countable,
                 &mut last,
-                &item_table,
+                item_table,
                 &exclude_table,
                 path_id,
                 groups.len() as ItemIdSize - 1,
             );
+            path_groups.push((path_id as usize, groups.len() - 1));
         }
 
         log::info!(
@@ -489,6 +1330,25 @@ impl AbacusByTotal {
             countable.len() - 1
         );
 
+        let exact_bps = if abacus_aux.exact_bp && count == CountType::Bp {
+            let exact = quantify_exact_bps(
+                item_table,
+                &exclude_table,
+                &path_groups,
+                groups.len(),
+                graph_aux,
+            );
+            // nodes whose bps are counted exactly need no approximation
+            if let Some(subset_map) = subset_covered_bps.as_mut() {
+                for id in exact.keys() {
+                    subset_map.remove(&ItemId(*id));
+                }
+            }
+            Some(exact)
+        } else {
+            None
+        };
+
         Self {
             count,
             countable,
@@ -497,6 +1357,7 @@ impl AbacusByTotal {
                 &subset_covered_bps,
                 graph_aux,
             )),
+            exact_bps,
             groups,
         }
     }
@@ -651,7 +1512,11 @@ impl AbacusByTotal {
                 let sid = item_table.items[i][j] as usize;
                 unsafe {
                     if last[sid] != group_id
-                        && (exclude_table.is_none() || !exclude_table.as_ref().unwrap().items[sid])
+                        && (exclude_table.is_none()
+                            || !exclude_table
+                                .as_ref()
+                                .unwrap()
+                                .is_active(&ItemId(sid as ItemIdSize)))
                     {
                         (*countable_ptr.0)[sid] += 1;
                         (*last_ptr.0)[sid] = group_id;
@@ -662,28 +1527,285 @@ impl AbacusByTotal {
     }
 
     pub fn abaci_from_gfa(
-        gfa_file: &str,
+        gfa_files: &[String],
         count: CountType,
         graph_aux: &GraphAuxilliary,
         abacus_aux: &AbacusAuxilliary,
+        low_mem: bool,
     ) -> Result<Vec<Self>, Error> {
-        let mut abaci = Vec::new();
-        if let CountType::All = count {
-            for count_type in CountType::iter() {
-                if let CountType::All = count_type {
-                } else {
-                    let mut data = bufreader_from_compressed_gfa(gfa_file);
-                    let abacus =
-                        AbacusByTotal::from_gfa(&mut data, abacus_aux, graph_aux, count_type);
-                    abaci.push(abacus);
+        Ok(Self::abaci_and_paths_len_from_gfa(gfa_files, count, graph_aux, abacus_aux, low_mem)?.0)
+    }
+
+    // like abaci_from_gfa, but also reports the numbers of nodes and bps of each path if they
+    // are determined along the way, i.e., if nodes or bps are counted and low-memory mode is off
+    pub fn abaci_and_paths_len_from_gfa(
+        gfa_files: &[String],
+        count: CountType,
+        graph_aux: &GraphAuxilliary,
+        abacus_aux: &AbacusAuxilliary,
+        low_mem: bool,
+    ) -> Result<(Vec<Self>, Option<PathsLen>), Error> {
+        let mut views =
+            Self::abaci_of_views_from_gfa(gfa_files, count, graph_aux, &[abacus_aux], low_mem)?;
+        Ok(views.pop().unwrap())
+    }
+
+    // calculates the abaci (and, if determined along the way, the numbers of nodes and bps of
+    // each path) of several views on the graph, e.g., one per sequence or per subset of paths,
+    // in a single pass over the paths and walks of the GFA file
+    pub fn abaci_of_views_from_gfa(
+        gfa_files: &[String],
+        count: CountType,
+        graph_aux: &GraphAuxilliary,
+        views: &[&AbacusAuxilliary],
+        low_mem: bool,
+    ) -> Result<Vec<ViewAbaci>, Error> {
+        let count_types: Vec<CountType> = if let CountType::All = count {
+            CountType::iter()
+                .filter(|c| !matches!(c, CountType::All))
+                .collect()
+        } else {
+            vec![count]
+        };
+        if low_mem {
+            let mut res = Vec::new();
+            for abacus_aux in views {
+                let mut abaci = Vec::new();
+                for count_type in count_types.iter() {
+                    let mut data = bufreader_from_gfa_files(gfa_files)?;
+                    abaci.push(AbacusByTotal::from_gfa_low_mem(
+                        &mut data,
+                        abacus_aux,
+                        graph_aux,
+                        *count_type,
+                    )?);
                 }
+                res.push((abaci, None));
             }
-        } else {
-            let mut data = bufreader_from_compressed_gfa(gfa_file);
-            let abacus = AbacusByTotal::from_gfa(&mut data, abacus_aux, graph_aux, count);
-            abaci.push(abacus);
+            return Ok(res);
+        }
+
+        let mut parsed_views: Vec<(&AbacusAuxilliary, CountType)> = Vec::new();
+        let mut shared_views = Vec::new();
+        for abacus_aux in views {
+            // without subset and exclude lists, node and bp counts are based on the same table
+            let share_node_table = count_types.contains(&CountType::Node)
+                && count_types.contains(&CountType::Bp)
+                && abacus_aux.include_coords.is_none()
+                && abacus_aux.exclude_coords.is_none()
+                && abacus_aux.include_nodes.is_none()
+                && abacus_aux.exclude_nodes.is_none();
+            let parsed_types: Vec<CountType> = count_types
+                .iter()
+                .filter(|c| !share_node_table || **c != CountType::Bp)
+                .copied()
+                .collect();
+            shared_views.push((share_node_table, parsed_types.len()));
+            parsed_views.extend(parsed_types.into_iter().map(|c| (*abacus_aux, c)));
+        }
+        let mut data = bufreader_from_gfa_files(gfa_files)?;
+        let mut tables =
+            parse_gfa_paths_walks_views(&mut data, graph_aux, &parsed_views)?.into_iter();
+        let mut parsed_views = parsed_views.into_iter();
+
+        let mut res = Vec::new();
+        for (share_node_table, n) in shared_views {
+            let mut abaci = Vec::new();
+            let mut paths_len = None;
+            for ((abacus_aux, count_type), (item_table, exclude_table, subset_covered_bps, lens)) in
+                parsed_views.by_ref().take(n).zip(tables.by_ref().take(n))
+            {
+                abaci.push(Self::item_table_to_abacus(
+                    abacus_aux,
+                    graph_aux,
+                    count_type,
+                    &item_table,
+                    exclude_table,
+                    subset_covered_bps,
+                ));
+                if share_node_table && count_type == CountType::Node {
+                    abaci.push(Self::item_table_to_abacus(
+                        abacus_aux,
+                        graph_aux,
+                        CountType::Bp,
+                        &item_table,
+                        None,
+                        None,
+                    ));
+                }
+                if count_type != CountType::Edge {
+                    paths_len = Some(lens);
+                }
+            }
+            res.push((abaci, paths_len));
+        }
+        Ok(res)
+    }
+
+    // removes countables that are supported by fewer than min_groups groups or that (in case
+    // of nodes and bps) are shorter than min_node_len; like skipped zero-length nodes, removed
+    // countables are marked by coverage CountSize::MAX and left out of the histogram rather than
+    // reported as uncovered. Returns the number of removed countables and the number of bps they
+    // span.
+    pub fn filter_artifacts(
+        &mut self,
+        min_groups: usize,
+        min_node_len: usize,
+        graph_aux: &GraphAuxilliary,
+    ) -> (usize, usize) {
+        let check_len = min_node_len > 0 && self.count != CountType::Edge;
+        if min_groups <= 1 && !check_len {
+            return (0, 0);
+        }
+        if min_node_len > 0 && !check_len {
+            log::warn!("minimum node length filter does not apply to edge counts, ignoring it");
+        }
+
+        let mut removed = 0;
+        let mut removed_bps = 0;
+        // countable with ID "0" is special and must not be touched
+        for (id, cov) in self.countable.iter_mut().enumerate().skip(1) {
+            if *cov == 0 || *cov == CountSize::MAX {
+                continue;
+            }
+            if (*cov as usize) < min_groups
+                || (check_len && (graph_aux.node_lens[id] as usize) < min_node_len)
+            {
+                *cov = CountSize::MAX;
+                removed += 1;
+                if self.count != CountType::Edge {
+                    removed_bps += graph_aux.node_lens[id] as usize;
+                }
+            }
+        }
+
+        log::info!(
+            "removed {} countables of type {} ({} bp) with coverage < {} or length < {}",
+            removed,
+            self.count,
+            removed_bps,
+            min_groups,
+            min_node_len
+        );
+        (removed, removed_bps)
+    }
+
+    // drops zero-length nodes from the countables of a node or bp abacus; like the countable with
+    // ID 0, dropped countables are marked by coverage CountSize::MAX and left out of histograms
+    pub fn skip_zero_length_nodes(&mut self, graph_aux: &GraphAuxilliary) -> usize {
+        if self.count == CountType::Edge {
+            return 0;
         }
-        Ok(abaci)
+        let mut skipped = 0;
+        for (id, cov) in self.countable.iter_mut().enumerate().skip(1) {
+            if graph_aux.node_lens[id] == 0 {
+                *cov = CountSize::MAX;
+                skipped += 1;
+            }
+        }
+        log::info!(
+            "skipped {} zero-length nodes in {} counts",
+            skipped,
+            self.count
+        );
+        skipped
+    }
+
+    // for an edge abacus, reports for each node side its structural out-degree, its out-degree
+    // restricted to edges that are traversed by at least one group, and the total number of group
+    // traversals of these edges; the first entry of a node refers to leaving it in forward
+    // orientation (i.e., from its end), the second to leaving it in reverse orientation (i.e., from
+    // its start). An edge u o1 -> v o2 leaves u in orientation o1 and, read backwards, v in the
+    // opposite of o2; an edge that leaves the same node side both ways is counted once. The first
+    // element corresponds to the (non-existing) node with ID 0.
+    pub fn node_branching(&self, graph_aux: &GraphAuxilliary) -> Vec<[(u32, u32, u64); 2]> {
+        assert!(
+            self.count == CountType::Edge,
+            "node branching requires edge abacus"
+        );
+        let side = |o: Orientation| match o {
+            Orientation::Forward => 0,
+            Orientation::Backward => 1,
+        };
+        let mut res: Vec<[(u32, u32, u64); 2]> = vec![[(0, 0, 0); 2]; graph_aux.node_count + 1];
+        for (e, eid) in graph_aux
+            .edge2id
+            .as_ref()
+            .expect("node branching requires edge2id map in GraphAuxilliary")
+            .iter()
+        {
+            let cov = self.countable[eid.0 as usize];
+            let mut sides = vec![(e.0, side(e.1))];
+            if (e.2, side(e.3.flip())) != sides[0] {
+                sides.push((e.2, side(e.3.flip())));
+            }
+            for (v, o) in sides {
+                let x = &mut res[v.0 as usize][o];
+                x.0 += 1;
+                if cov > 0 {
+                    x.1 += 1;
+                    x.2 += cov as u64;
+                }
+            }
+        }
+        res
+    }
+
+    // fraction of groups covering each countable; the first element corresponds to the
+    // (non-existing) countable with ID 0
+    pub fn relative_coverage(&self) -> Vec<f64> {
+        let n = self.groups.len().max(1) as f64;
+        let mut res: Vec<f64> = self.countable.iter().map(|c| *c as f64 / n).collect();
+        res[0] = 0.0;
+        res
+    }
+
+    // difference between the relative coverages of this and the other abacus
+    pub fn coverage_delta(&self, other: &AbacusByTotal) -> Vec<f64> {
+        self.relative_coverage()
+            .into_iter()
+            .zip(other.relative_coverage())
+            .map(|(a, b)| a - b)
+            .collect()
+    }
+
+    // coverage histograms of the nodes (or bps) lying in each of the given windows (half-open
+    // intervals of path coordinates) along the given path; nodes that are visited repeatedly
+    // within a window are counted once, and nodes overlapping a window border contribute only
+    // the bps inside the window
+    pub fn window_hists(
+        &self,
+        path: &[(ItemId, Orientation)],
+        windows: &[(usize, usize)],
+        count: CountType,
+        graph_aux: &GraphAuxilliary,
+    ) -> Vec<Hist> {
+        log::info!("constructing histograms of {} windows", windows.len());
+        let mut offsets = vec![0];
+        for (id, _) in path {
+            offsets.push(offsets.last().unwrap() + graph_aux.node_len(id) as usize);
+        }
+
+        windows
+            .par_iter()
+            .map(|&(start, end)| {
+                let mut overlaps: HashMap<ItemIdSize, usize> = HashMap::default();
+                // first node of the path that ends after the window start
+                let mut i = offsets.partition_point(|&x| x <= start).saturating_sub(1);
+                while i < path.len() && offsets[i] < end {
+                    let o = usize::min(end, offsets[i + 1]) - usize::max(start, offsets[i]);
+                    let x = overlaps.entry(path[i].0 .0).or_insert(0);
+                    *x = usize::max(*x, o);
+                    i += 1;
+                }
+                let mut coverage = vec![0; self.groups.len() + 1];
+                for (id, o) in overlaps {
+                    let c = usize::min(self.countable[id as usize] as usize, self.groups.len());
+                    coverage[c] += if count == CountType::Bp { o } else { 1 };
+                }
+                Hist { count, coverage }
+            })
+            .collect()
     }
 
     pub fn construct_hist(&self) -> Vec<usize> {
@@ -694,7 +1816,7 @@ impl AbacusByTotal {
 
         for (i, cov) in self.countable.iter().enumerate() {
             if *cov as usize >= hist.len() {
-                if i != 0 {
+                if i != 0 && *cov != CountSize::MAX {
                     log::warn!("coverage {} of item {} exceeds the number of groups {}, it'll be ignored in the count", cov, i, self.groups.len());
                 }
             } else {
@@ -711,7 +1833,7 @@ impl AbacusByTotal {
         let mut hist: Vec<usize> = vec![0; self.groups.len() + 1];
         for (id, cov) in self.countable.iter().enumerate() {
             if *cov as usize >= hist.len() {
-                if id != 0 {
+                if id != 0 && *cov != CountSize::MAX {
                     log::info!("coverage {} of item {} exceeds the number of groups {}, it'll be ignored in the count", cov, id, self.groups.len());
                 }
             } else {
@@ -722,14 +1844,182 @@ impl AbacusByTotal {
         // subtract uncovered bps
         let uncovered_bps = self.uncovered_bps.as_ref().unwrap();
         for (id, uncov) in uncovered_bps.iter() {
-            hist[self.countable[*id as usize] as usize] -= uncov;
+            let cov = self.countable[*id as usize] as usize;
+            if cov >= hist.len() {
+                continue;
+            }
+            hist[cov] -= uncov;
             // add uncovered bps to 0-coverage count
             hist[0] += uncov;
         }
+
+        // distribute the bps of partially covered nodes by their exact coverage; nodes that
+        // have been filtered out are left alone
+        if let Some(exact_bps) = &self.exact_bps {
+            for (id, bps) in exact_bps.iter() {
+                let cov = self.countable[*id as usize] as usize;
+                if cov == 0 || cov >= hist.len() {
+                    continue;
+                }
+                hist[cov] -= graph_aux.node_lens[*id as usize] as usize;
+                for (c, bp) in bps.iter().enumerate() {
+                    hist[c] += bp;
+                }
+            }
+        }
         hist
     }
 }
 
+// Incrementally builds an AbacusByTotal from paths that are fed one at a time, e.g., from a
+// generator or a network stream, decoupling the counting from GFA files. Paths of the same
+// group must be pushed consecutively. Low-memory mode feeds the paths of the GFA file through it.
+pub struct AbacusStream<'a> {
+    count: CountType,
+    graph_aux: &'a GraphAuxilliary,
+    countable: Vec<CountSize>,
+    last: Vec<ItemIdSize>,
+    groups: Vec<String>,
+    visited: HashSet<String>,
+}
+
+impl<'a> AbacusStream<'a> {
+    pub fn new(graph_aux: &'a GraphAuxilliary, count: CountType) -> Result<Self, Error> {
+        if count == CountType::All {
+            let msg = "abacus stream requires a single count type, but \"all\" is given";
+            log::error!("{}", &msg);
+            return Err(Error::new(ErrorKind::InvalidInput, msg));
+        }
+        if count == CountType::Edge && graph_aux.edge2id.is_none() {
+            let msg = "abacus stream for edges requires edge2id map in GraphAuxilliary";
+            log::error!("{}", &msg);
+            return Err(Error::new(ErrorKind::InvalidInput, msg));
+        }
+        let n = graph_aux.number_of_items(&count) + 1;
+        let mut countable: Vec<CountSize> = vec![0; n];
+        // countable with ID "0" is special and should not be considered in coverage histogram
+        countable[0] = CountSize::MAX;
+        Ok(Self {
+            count,
+            graph_aux,
+            countable,
+            last: vec![ItemIdSize::MAX; n],
+            groups: Vec::new(),
+            visited: HashSet::new(),
+        })
+    }
+
+    // returns the ID of the given group, which becomes the current group, or None if the group
+    // has been entered before and left since
+    fn enter_group(&mut self, group: &str) -> Option<ItemIdSize> {
+        if self.groups.last().map(|g| g.as_str()) != Some(group) {
+            if !self.visited.insert(group.to_string()) {
+                return None;
+            }
+            self.groups.push(group.to_string());
+        }
+        Some(self.groups.len() as ItemIdSize - 1)
+    }
+
+    fn current_group(&mut self, group: &str) -> Result<ItemIdSize, Error> {
+        self.enter_group(group).ok_or_else(|| {
+            let msg = format!("paths of group {} are not pushed consecutively to abacus stream: group is interspersed by one or more other groups", group);
+            log::error!("{}", &msg);
+            Error::new(ErrorKind::InvalidInput, msg)
+        })
+    }
+
+    pub fn push_path(&mut self, group: &str, path: &[(ItemId, Orientation)]) -> Result<(), Error> {
+        let group_id = self.current_group(group)?;
+
+        match self.count {
+            CountType::Node | CountType::Bp => {
+                for (sid, _) in path {
+                    self.cover(sid.0 as usize, group_id)?;
+                }
+            }
+            CountType::Edge => {
+                let edge2id = self.graph_aux.edge2id.as_ref().unwrap();
+                for ((sid1, o1), (sid2, o2)) in path.iter().tuple_windows() {
+                    let e = Edge::canonical(*sid1, *o1, *sid2, *o2);
+                    let eid = edge2id.get(&e).ok_or_else(|| {
+                        let msg = format!("unknown edge {} in path of group {}", &e, group);
+                        log::error!("{}", &msg);
+                        Error::new(ErrorKind::InvalidData, msg)
+                    })?;
+                    self.cover(eid.0 as usize, group_id)?;
+                }
+            }
+            CountType::All => unreachable!("inadmissable count type"),
+        }
+        Ok(())
+    }
+
+    // pushes a path given in the format of a GFA P-line sequence, e.g. "1+,2-,3+"
+    pub fn push_gfa_path(&mut self, group: &str, seq: &[u8]) -> Result<(), Error> {
+        let mut path = Vec::new();
+        for node in seq.split(|&x| x == b',').filter(|x| !x.is_empty()) {
+            let (sid, o) = node.split_at(node.len() - 1);
+            let sid = self.graph_aux.node2id.get(sid).ok_or_else(|| {
+                let msg = format!("unknown node {}", String::from_utf8_lossy(sid));
+                log::error!("{}", &msg);
+                Error::new(ErrorKind::InvalidData, msg)
+            })?;
+            if o[0] != b'+' && o[0] != b'-' {
+                let msg = format!(
+                    "unknown orientation of segment {}",
+                    String::from_utf8_lossy(node)
+                );
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+            path.push((*sid, Orientation::from_pm(o[0])));
+        }
+        self.push_path(group, &path)
+    }
+
+    // pushes the countables, given by their numerical IDs, of a path of the given group
+    pub fn push_items(&mut self, group: &str, items: &[ItemIdSize]) -> Result<(), Error> {
+        let group_id = self.current_group(group)?;
+        for &sid in items {
+            self.cover(sid as usize, group_id)?;
+        }
+        Ok(())
+    }
+
+    fn cover(&mut self, sid: usize, group_id: ItemIdSize) -> Result<(), Error> {
+        if sid == 0 || sid >= self.countable.len() {
+            let msg = format!(
+                "countable ID {} is out of range: abacus stream has countables 1 to {}",
+                sid,
+                self.countable.len() - 1
+            );
+            log::error!("{}", &msg);
+            return Err(Error::new(ErrorKind::InvalidInput, msg));
+        }
+        if self.last[sid] != group_id {
+            self.countable[sid] += 1;
+            self.last[sid] = group_id;
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self) -> AbacusByTotal {
+        log::info!(
+            "abacus stream has {} path groups and {} countables",
+            self.groups.len(),
+            self.countable.len() - 1
+        );
+        AbacusByTotal {
+            count: self.count,
+            countable: self.countable,
+            uncovered_bps: Some(HashMap::default()),
+            exact_bps: None,
+            groups: self.groups,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AbacusByGroup<'a> {
     pub count: CountType,
@@ -751,7 +2041,7 @@ impl<'a> AbacusByGroup<'a> {
     ) -> Result<Self, Error> {
         log::info!("parsing path + walk sequences");
         let (item_table, exclude_table, subset_covered_bps, _paths_len) =
-            parse_gfa_paths_walks(data, abacus_aux, graph_aux, &count);
+            parse_gfa_paths_walks(data, abacus_aux, graph_aux, &count)?;
 
         let mut path_order: Vec<(ItemIdSize, GroupSize)> = Vec::new();
         let mut groups: Vec<String> = Vec::new();
@@ -815,7 +2105,11 @@ impl<'a> AbacusByGroup<'a> {
                 for j in start..end {
                     let sid = item_table.items[i][j] as usize;
                     if &last[sid] != group_id
-                        && (exclude_table.is_none() || !exclude_table.as_ref().unwrap().items[sid])
+                        && (exclude_table.is_none()
+                            || !exclude_table
+                                .as_ref()
+                                .unwrap()
+                                .is_active(&ItemId(sid as ItemIdSize)))
                     {
                         unsafe {
                             (*r_ptr.0)[sid] += 1;
@@ -970,6 +2264,335 @@ impl<'a> AbacusByGroup<'a> {
         res
     }
 
+    // growth where the first `anchors` groups are always added first (in their given order) and
+    // the growth beyond them is averaged over all permutations of the remaining groups
+    pub fn calc_anchored_growth(
+        &self,
+        anchors: usize,
+        t_coverage: &Threshold,
+        t_quorum: &Threshold,
+    ) -> Vec<f64> {
+        let n = self.groups.len();
+        let k = usize::min(anchors, n);
+        let mut res = self.calc_growth(t_coverage, t_quorum);
+        if k == n {
+            return res;
+        }
+
+        let c = usize::max(1, t_coverage.to_absolute(n));
+        let q = f64::max(0.0, t_quorum.to_relative(n));
+
+        // total weight of countables by the number of anchors and of remaining groups covering
+        // them
+        let mut weights = vec![vec![0.0; n - k + 1]; k + 1];
+        let mut it = self.r.iter().tuple_windows().enumerate();
+        // ignore first entry
+        it.next();
+        for (i, (&start, &end)) in it {
+            if end - start >= c {
+                let a = self.c[start..end]
+                    .iter()
+                    .filter(|g| (**g as usize) < k)
+                    .count();
+                weights[a][end - start - a] += self.item_weight(i) as f64;
+            }
+        }
+
+        for j in 1..n - k + 1 {
+            let m = k + j;
+            let t = usize::max(1, (m as f64 * q).ceil() as usize);
+            let mut y = 0.0;
+            for (a, row) in weights.iter().enumerate() {
+                for (r, w) in row.iter().enumerate() {
+                    if *w > 0.0 {
+                        y += w * hypergeom_at_least(n - k, r, j, t.saturating_sub(a));
+                    }
+                }
+            }
+            res[m - 1] = y;
+        }
+        res
+    }
+
+    pub fn calc_all_growths(
+        &self,
+        hist_aux: &HistAuxilliary,
+        anchors: Option<usize>,
+    ) -> Vec<Vec<f64>> {
+        let mut growths: Vec<Vec<f64>> = hist_aux
+            .coverage
+            .par_iter()
+            .zip(&hist_aux.quorum)
+            .map(|(c, q)| {
+                log::info!(
+                    "calculating ordered growth for coverage >= {} and quorum >= {}",
+                    &c,
+                    &q
+                );
+                match anchors {
+                    Some(k) => self.calc_anchored_growth(k, c, q),
+                    None => self.calc_growth(c, q),
+                }
+            })
+            .collect();
+        // insert empty row for 0 element
+        for g in &mut growths {
+            g.insert(0, f64::NAN);
+        }
+        growths
+    }
+
+    // growth for the order of groups in which the g-th group is at position rank[g]; countables must
+    // reach the coverage threshold across all groups (i.e., a priori) and the quorum within the
+    // first m groups
+    pub fn calc_growth_permuted(
+        &self,
+        rank: &[usize],
+        t_coverage: &Threshold,
+        t_quorum: &Threshold,
+    ) -> Vec<f64> {
+        let n = self.groups.len();
+        let c = usize::max(1, t_coverage.to_absolute(n));
+        let q = f64::max(0.0, t_quorum.to_relative(n));
+
+        // largest number of groups m for which k covering groups satisfy the quorum
+        let max_groups = |k: usize| -> usize {
+            if q == 0.0 {
+                return n;
+            }
+            let mut m = f64::min(n as f64, (k as f64 / q).floor()) as usize;
+            while m < n && ((m + 1) as f64 * q).ceil() as usize <= k {
+                m += 1;
+            }
+            while m > 0 && (m as f64 * q).ceil() as usize > k {
+                m -= 1;
+            }
+            m
+        };
+
+        // diff[m-1] holds the change in growth between m-1 and m groups
+        let mut diff = vec![0.0; n + 1];
+        let mut ranks: Vec<usize> = Vec::new();
+        let mut it = self.r.iter().tuple_windows().enumerate();
+        // ignore first entry
+        it.next();
+        for (i, (&start, &end)) in it {
+            if end - start < c {
+                continue;
+            }
+            let w = self.item_weight(i) as f64;
+            ranks.clear();
+            ranks.extend(self.c[start..end].iter().map(|g| rank[*g as usize]));
+            ranks.sort_unstable();
+            // exactly k covering groups are among the first m groups for all m in (ranks[k-1],
+            // ranks[k]]
+            for k in 1..ranks.len() + 1 {
+                let lo = ranks[k - 1] + 1;
+                let hi = usize::min(ranks.get(k).copied().unwrap_or(n), max_groups(k));
+                if lo <= hi {
+                    diff[lo - 1] += w;
+                    diff[hi] -= w;
+                }
+            }
+        }
+        diff.truncate(n);
+        diff.iter()
+            .scan(0.0, |acc, d| {
+                *acc += d;
+                Some(*acc)
+            })
+            .collect()
+    }
+
+    // growth curves for `samples` random orders of groups, indexed by threshold pair and sample
+    pub fn calc_sampled_growths(
+        &self,
+        hist_aux: &HistAuxilliary,
+        samples: usize,
+        seed: u64,
+    ) -> Vec<Vec<Vec<f64>>> {
+        log::info!(
+            "calculating growth for {} random orders of groups (seed {})",
+            samples,
+            seed
+        );
+        hist_aux
+            .coverage
+            .iter()
+            .zip(&hist_aux.quorum)
+            .map(|(c, q)| {
+                (0..samples)
+                    .into_par_iter()
+                    .map(|s| self.calc_growth_permuted(&self.random_order(seed, s), c, q))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // growth averaged over `samples` random orders of groups (Monte-Carlo estimate of the growth
+    // over all orders), in the format of `calc_all_growths`
+    pub fn calc_sampled_mean_growths(
+        &self,
+        hist_aux: &HistAuxilliary,
+        samples: usize,
+        seed: u64,
+    ) -> Vec<Vec<f64>> {
+        log::info!(
+            "estimating growth from {} random orders of groups (seed {})",
+            samples,
+            seed
+        );
+        let n = self.groups.len();
+        hist_aux
+            .coverage
+            .iter()
+            .zip(&hist_aux.quorum)
+            .map(|(c, q)| {
+                let sum = (0..samples)
+                    .into_par_iter()
+                    .map(|s| self.calc_growth_permuted(&self.random_order(seed, s), c, q))
+                    .reduce(
+                        || vec![0.0; n],
+                        |mut a, b| {
+                            a.iter_mut().zip(b).for_each(|(x, y)| *x += y);
+                            a
+                        },
+                    );
+                std::iter::once(f64::NAN)
+                    .chain(sum.into_iter().map(|x| x / samples as f64))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // the s-th random order of groups, drawn from a generator seeded with seed + s so that
+    // results do not depend on the number of threads
+    fn random_order(&self, seed: u64, s: usize) -> Vec<usize> {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(s as u64));
+        let mut rank: Vec<usize> = (0..self.groups.len()).collect();
+        rank.shuffle(&mut rng);
+        rank
+    }
+
+    // removes countables that are covered by fewer than min_groups groups or that are shorter than
+    // min_node_len bp, analogous to AbacusByTotal::filter_artifacts
+    pub fn filter_artifacts(&mut self, min_groups: usize, min_node_len: usize) {
+        let check_len = min_node_len > 0 && self.count != CountType::Edge;
+        if min_groups <= 1 && !check_len {
+            return;
+        }
+
+        let mut r = Vec::with_capacity(self.r.len());
+        let mut c = self.c[..self.r[0]].to_vec();
+        let mut v = self.v.as_ref().map(|v| v[..self.r[0]].to_vec());
+        for (i, (&start, &end)) in self.r.iter().tuple_windows().enumerate() {
+            r.push(c.len());
+            // countable with ID "0" is special and must not be touched
+            if i == 0
+                || (end - start >= min_groups
+                    && (!check_len || self.graph_aux.node_lens[i] as usize >= min_node_len))
+            {
+                c.extend_from_slice(&self.c[start..end]);
+                if let (Some(v), Some(old)) = (v.as_mut(), self.v.as_ref()) {
+                    v.extend_from_slice(&old[start..end]);
+                }
+            }
+        }
+        r.push(c.len());
+        self.r = r;
+        self.c = c;
+        self.v = v;
+    }
+
+    // counts (or bps) of each group that are private to the group (coverage 1), shared with some
+    // (shell), or shared with all groups (core)
+    pub fn coverage_classes(&self) -> Vec<[usize; 3]> {
+        let mut res = vec![[0; 3]; self.groups.len()];
+
+        let mut it = self.r.iter().tuple_windows().enumerate();
+        // ignore first entry
+        it.next();
+        for (i, (&start, &end)) in it {
+            let class = if end - start == self.groups.len() {
+                2
+            } else if end - start == 1 {
+                0
+            } else {
+                1
+            };
+            let x = self.item_weight(i);
+            for g in &self.c[start..end] {
+                res[*g as usize][class] += x;
+            }
+        }
+        res
+    }
+
+    // number of countables (or bps) covered by each group only, together with the total number
+    // of countables (or bps) of each group
+    pub fn private_countables(&self) -> Vec<(usize, usize)> {
+        self.coverage_classes()
+            .iter()
+            .map(|c| (c[0], c.iter().sum()))
+            .collect()
+    }
+
+    // whether the countable with the given ID is covered by a single group only
+    pub fn is_private(&self, i: usize) -> bool {
+        self.r[i + 1] - self.r[i] == 1
+    }
+
+    // number of countables (or bps) shared by each pair of groups; the diagonal holds the
+    // number of countables (or bps) of each group
+    pub fn pairwise_intersections(&self) -> Vec<Vec<usize>> {
+        let n = self.groups.len();
+        let mut res = vec![vec![0; n]; n];
+
+        let mut it = self.r.iter().tuple_windows().enumerate();
+        // ignore first entry
+        it.next();
+        for (i, (&start, &end)) in it {
+            let x = self.item_weight(i);
+            let groups = &self.c[start..end];
+            for g1 in groups {
+                for g2 in groups {
+                    res[*g1 as usize][*g2 as usize] += x;
+                }
+            }
+        }
+        res
+    }
+
+    // similarity matrix of groups, derived from their pairwise intersections
+    pub fn similarities(&self, metric: SimilarityMetric) -> Vec<Vec<f64>> {
+        let intersections = self.pairwise_intersections();
+        intersections
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, x)| metric.apply(*x, intersections[i][i], intersections[j][j]))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // hierarchical clustering of the groups by their distances, i.e., one minus their similarity
+    pub fn similarity_tree(&self, metric: SimilarityMetric, method: ClusterMethod) -> Tree {
+        let dist = distances_from_similarities(&self.similarities(metric));
+        Tree::build(method, &self.groups, &dist)
+    }
+
+    fn item_weight(&self, i: usize) -> usize {
+        match self.count {
+            CountType::Node | CountType::Edge => 1,
+            CountType::Bp => (self.graph_aux.node_lens[i] as usize)
+                .saturating_sub(*self.uncovered_bps.get(&(i as ItemIdSize)).unwrap_or(&0)),
+            CountType::All => unreachable!("inadmissible count type"),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn write_rcv<W: Write>(&self, out: &mut BufWriter<W>) -> Result<(), Error> {
         write!(out, "{}", self.r[0])?;
@@ -992,121 +2615,258 @@ impl<'a> AbacusByGroup<'a> {
         Ok(())
     }
 
-    pub fn to_tsv<W: Write>(&self, total: bool, out: &mut BufWriter<W>) -> Result<(), Error> {
+    // calls f with the identifier and the counts in each group (or their total) of each
+    // countable, in the order of their numerical ids
+    fn for_each_row<F>(&self, total: bool, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&str, &[usize]) -> Result<(), Error>,
+    {
         // create mapping from numerical node ids to original node identifiers
-        log::info!("reporting coverage table");
         let dummy = Vec::new();
         let mut id2node: Vec<&Vec<u8>> = vec![&dummy; self.graph_aux.node_count + 1];
         for (node, id) in self.graph_aux.node2id.iter() {
             id2node[id.0 as usize] = node;
         }
-
+        let dummy_edge = Edge(
+            ItemId(0),
+            Orientation::default(),
+            ItemId(0),
+            Orientation::default(),
+        );
+        let mut id2edge: Vec<&Edge> = Vec::new();
         match self.count {
-            CountType::Node | CountType::Bp => {
-                write!(out, "node")?;
-                if total {
-                    write!(out, "\ttotal")?;
-                } else {
-                    for group in self.groups.iter() {
-                        write!(out, "\t{}", group)?;
-                    }
-                }
-                writeln!(out)?;
-
-                let mut it = self.r.iter().tuple_windows().enumerate();
-                // ignore first entry
-                it.next();
-                for (i, (&start, &end)) in it {
-                    let bp = if self.count == CountType::Bp {
-                        self.graph_aux.node_lens[i] as usize
-                            - *self.uncovered_bps.get(&(i as ItemIdSize)).unwrap_or(&0)
-                    } else {
-                        1
-                    };
-                    write!(out, "{}", std::str::from_utf8(id2node[i]).unwrap())?;
-                    if total {
-                        // we never need to look into the actual value in self.v, because we
-                        // know it must be non-zero, which is sufficient
-                        writeln!(out, "\t{}", end - start)?;
-                    } else {
-                        let mut k = start;
-                        for j in 0 as GroupSize..self.groups.len() as GroupSize {
-                            if k == end || j < self.c[k] {
-                                write!(out, "\t0")?;
-                            } else if j == self.c[k] {
-                                match &self.v {
-                                    None => write!(out, "\t{}", bp),
-                                    Some(v) => write!(out, "\t{}", v[k] as usize * bp),
-                                }?;
-                                k += 1;
-                            }
-                        }
-                        writeln!(out)?;
-                    }
-                }
-            }
+            CountType::Node | CountType::Bp => (),
             CountType::Edge => {
                 if let Some(edge2id) = &self.graph_aux.edge2id {
-                    let dummy_edge = Edge(
-                        ItemId(0),
-                        Orientation::default(),
-                        ItemId(0),
-                        Orientation::default(),
-                    );
-                    let mut id2edge: Vec<&Edge> = vec![&dummy_edge; self.graph_aux.edge_count + 1];
+                    id2edge = vec![&dummy_edge; self.graph_aux.edge_count + 1];
                     for (edge, id) in edge2id.iter() {
                         id2edge[id.0 as usize] = edge;
                     }
+                } else {
+                    return Ok(());
+                }
+            }
+            CountType::All => unreachable!("inadmissible count type"),
+        }
 
-                    write!(out, "edge")?;
-                    if total {
-                        write!(out, "\ttotal")?;
-                    } else {
-                        for group in self.groups.iter() {
-                            write!(out, "\t{}", group)?;
-                        }
-                    }
-                    writeln!(out)?;
-
-                    let mut it = self.r.iter().tuple_windows().enumerate();
-                    // ignore first entry
-                    it.next();
-                    for (i, (&start, &end)) in it {
-                        let edge = id2edge[i];
-                        write!(
-                            out,
-                            "{}{}{}{}",
-                            edge.1,
-                            std::str::from_utf8(id2node[edge.0 .0 as usize]).unwrap(),
-                            edge.3,
-                            std::str::from_utf8(id2node[edge.2 .0 as usize]).unwrap(),
-                        )?;
-                        if total {
-                            // we never need to look into the actual value in self.v, because we
-                            // know it must be non-zero, which is sufficient
-                            writeln!(out, "\t{}", end - start)?;
-                        } else {
-                            let mut k = start;
-                            for j in 0 as GroupSize..self.groups.len() as GroupSize {
-                                if k == end || j < self.c[k] {
-                                    write!(out, "\t0")?;
-                                } else if j == self.c[k] {
-                                    match &self.v {
-                                        None => write!(out, "\t1"),
-                                        Some(v) => write!(out, "\t{}", v[j as usize]),
-                                    }?;
-                                    k += 1;
-                                }
-                            }
-                            writeln!(out)?;
-                        }
+        let mut label = String::new();
+        let mut values = Vec::with_capacity(self.groups.len());
+        let mut it = self.r.iter().tuple_windows().enumerate();
+        // ignore first entry
+        it.next();
+        for (i, (&start, &end)) in it {
+            label.clear();
+            // each covered countable contributes its length (bp) or 1 (node, edge)
+            let bp = if self.count == CountType::Edge {
+                let edge = id2edge[i];
+                label.push_str(&format!(
+                    "{}{}{}{}",
+                    edge.1,
+                    std::str::from_utf8(id2node[edge.0 .0 as usize]).unwrap(),
+                    edge.3,
+                    std::str::from_utf8(id2node[edge.2 .0 as usize]).unwrap(),
+                ));
+                1
+            } else {
+                label.push_str(std::str::from_utf8(id2node[i]).unwrap());
+                if self.count == CountType::Bp {
+                    self.graph_aux.node_lens[i] as usize
+                        - *self.uncovered_bps.get(&(i as ItemIdSize)).unwrap_or(&0)
+                } else {
+                    1
+                }
+            };
+            values.clear();
+            if total {
+                // we never need to look into the actual value in self.v, because we
+                // know it must be non-zero, which is sufficient
+                values.push(end - start);
+            } else {
+                let mut k = start;
+                for j in 0 as GroupSize..self.groups.len() as GroupSize {
+                    if k == end || j < self.c[k] {
+                        values.push(0);
+                    } else if j == self.c[k] {
+                        values.push(match &self.v {
+                            None => bp,
+                            Some(v) => v[k] as usize * bp,
+                        });
+                        k += 1;
                     }
                 }
             }
-            CountType::All => unreachable!("inadmissible count type"),
+            f(&label, &values)?;
+        }
+        Ok(())
+    }
+
+    fn table_header(&self, total: bool, annotation: bool) -> Vec<String> {
+        let mut header = vec![match self.count {
+            CountType::Edge => "edge".to_string(),
+            _ => "node".to_string(),
+        }];
+        if annotation {
+            header.push("annotation".to_string());
+        }
+        if total {
+            header.push("total".to_string());
+        } else {
+            header.extend(self.groups.iter().cloned());
+        }
+        header
+    }
+
+    pub fn to_tsv<W: Write>(
+        &self,
+        total: bool,
+        annotation: Option<&NodeAnnotation>,
+        out: &mut BufWriter<W>,
+    ) -> Result<(), Error> {
+        log::info!("reporting coverage table");
+        writeln!(
+            out,
+            "{}",
+            self.table_header(total, annotation.is_some()).join("\t")
+        )?;
+        self.for_each_row(total, |label, values| {
+            write!(out, "{}", label)?;
+            if let Some(annotation) = annotation {
+                write!(
+                    out,
+                    "\t{}",
+                    annotation
+                        .get(label.as_bytes())
+                        .map(|x| &x[..])
+                        .unwrap_or(".")
+                )?;
+            }
+            for x in values {
+                write!(out, "\t{}", x)?;
+            }
+            writeln!(out)
+        })
+    }
+
+    // Writes the coverage table in long format, i.e., one row per countable and group, omitting
+    // groups that do not cover the countable
+    pub fn to_tsv_long<W: Write>(
+        &self,
+        annotation: Option<&NodeAnnotation>,
+        out: &mut BufWriter<W>,
+    ) -> Result<(), Error> {
+        log::info!("reporting coverage table in long format");
+        let mut header = self.table_header(true, annotation.is_some());
+        header.pop();
+        header.push("group".to_string());
+        header.push("coverage".to_string());
+        writeln!(out, "{}", header.join("\t"))?;
+        self.for_each_row(false, |label, values| {
+            let annotation = annotation.map(|annotation| {
+                annotation
+                    .get(label.as_bytes())
+                    .map(|x| &x[..])
+                    .unwrap_or(".")
+            });
+            for (group, x) in self.groups.iter().zip(values) {
+                if *x == 0 {
+                    continue;
+                }
+                write!(out, "{}", label)?;
+                if let Some(annotation) = annotation {
+                    write!(out, "\t{}", annotation)?;
+                }
+                writeln!(out, "\t{}\t{}", group, x)?;
+            }
+            Ok(())
+        })
+    }
+
+    // Writes the coverage table in Apache Arrow IPC stream format, which is much more compact
+    // and faster to load than TSV for large numbers of countables and groups
+    #[cfg(feature = "arrow")]
+    pub fn to_arrow<W: Write>(
+        &self,
+        total: bool,
+        annotation: Option<&NodeAnnotation>,
+        out: &mut W,
+    ) -> Result<(), Error> {
+        use arrow::array::{ArrayRef, StringBuilder, UInt64Builder};
+        use arrow::datatypes::{DataType, Field, Schema};
+        use arrow::ipc::writer::StreamWriter;
+        use arrow::record_batch::RecordBatch;
+        use std::sync::Arc;
+
+        const BATCH_SIZE: usize = 1 << 16;
+
+        log::info!("reporting coverage table in Arrow format");
+        let header = self.table_header(total, annotation.is_some());
+        let n_labels = if annotation.is_some() { 2 } else { 1 };
+        let schema = Arc::new(Schema::new(
+            header
+                .iter()
+                .enumerate()
+                .map(|(i, h)| {
+                    if i < n_labels {
+                        Field::new(h, DataType::Utf8, false)
+                    } else {
+                        Field::new(h, DataType::UInt64, false)
+                    }
+                })
+                .collect::<Vec<_>>(),
+        ));
+        // failures to write the stream are I/O errors; any other error of the Arrow writer
+        // means that the record batches were assembled wrongly
+        let to_io_error = |e: arrow::error::ArrowError| {
+            let kind = match &e {
+                arrow::error::ArrowError::IoError(_, e) => e.kind(),
+                _ => ErrorKind::Other,
+            };
+            let msg = format!("unable to write Arrow stream: {}", e);
+            log::error!("{}", msg);
+            Error::new(kind, msg)
         };
 
-        Ok(())
+        let mut writer = StreamWriter::try_new(out, &schema).map_err(to_io_error)?;
+        let mut labels: Vec<StringBuilder> = (0..n_labels).map(|_| StringBuilder::new()).collect();
+        let mut counts: Vec<UInt64Builder> = (n_labels..header.len())
+            .map(|_| UInt64Builder::new())
+            .collect();
+        let mut rows = 0;
+        let flush = |labels: &mut Vec<StringBuilder>,
+                     counts: &mut Vec<UInt64Builder>,
+                     writer: &mut StreamWriter<&mut W>|
+         -> Result<(), Error> {
+            let columns: Vec<ArrayRef> = labels
+                .iter_mut()
+                .map(|b| Arc::new(b.finish()) as ArrayRef)
+                .chain(counts.iter_mut().map(|b| Arc::new(b.finish()) as ArrayRef))
+                .collect();
+            let batch = RecordBatch::try_new(schema.clone(), columns).map_err(to_io_error)?;
+            writer.write(&batch).map_err(to_io_error)
+        };
+        self.for_each_row(total, |label, values| {
+            labels[0].append_value(label);
+            if let Some(annotation) = annotation {
+                labels[1].append_value(
+                    annotation
+                        .get(label.as_bytes())
+                        .map(|x| &x[..])
+                        .unwrap_or("."),
+                );
+            }
+            for (b, x) in counts.iter_mut().zip(values) {
+                b.append_value(*x as u64);
+            }
+            rows += 1;
+            if rows % BATCH_SIZE == 0 {
+                flush(&mut labels, &mut counts, &mut writer)?;
+            }
+            Ok(())
+        })?;
+        if rows % BATCH_SIZE != 0 {
+            flush(&mut labels, &mut counts, &mut writer)?;
+        }
+        writer.finish().map_err(to_io_error)
     }
 }
 
@@ -1140,7 +2900,7 @@ fn quantify_uncovered_bps(
     if let Some(subset_map) = subset_covered_bps {
         for sid in subset_map.keys() {
             // ignore COMPETELY excluded nodes
-            if exclude_table.is_none() || !exclude_table.as_ref().unwrap().items[sid.0 as usize] {
+            if exclude_table.is_none() || !exclude_table.as_ref().unwrap().is_active(sid) {
                 let l = graph_aux.node_len(sid) as usize;
                 let covered = subset_map.total_coverage(
                     sid,
@@ -1160,13 +2920,219 @@ fn quantify_uncovered_bps(
     res
 }
 
+// counts the bps of each node that is covered only partially by some path, or excluded only
+// partially, by the number of groups that cover them; unlike quantify_uncovered_bps, the covered
+// intervals of each group are kept apart, at the cost of looking up the nodes of every path
+fn quantify_exact_bps(
+    item_table: &ItemTable,
+    exclude_table: &Option<ActiveTable>,
+    path_groups: &[(usize, usize)],
+    n_groups: usize,
+    graph_aux: &GraphAuxilliary,
+) -> HashMap<ItemIdSize, Vec<usize>> {
+    let mut partial: HashSet<ItemIdSize> = HashSet::new();
+    if let Some(covered) = &item_table.covered_bps {
+        for c in covered.iter() {
+            partial.extend(c.keys().map(|sid| sid.0));
+        }
+    }
+    if let Some(ex) = exclude_table.as_ref().filter(|ex| ex.with_annotation()) {
+        for i in 1..graph_aux.node_count + 1 {
+            let sid = ItemId(i as ItemIdSize);
+            if !ex.is_active(&sid)
+                && !ex
+                    .get_active_intervals(&sid, graph_aux.node_len(&sid) as usize)
+                    .is_empty()
+            {
+                partial.insert(sid.0);
+            }
+        }
+    }
+    log::info!(
+        "counting bps of {} partially covered nodes exactly",
+        partial.len()
+    );
+
+    // covered intervals of each group, merged over its paths
+    let mut by_group = vec![IntervalContainer::new(); n_groups];
+    for (path_id, group_id) in path_groups.iter() {
+        for sid in item_table.path_items(*path_id) {
+            if !partial.contains(&sid) {
+                continue;
+            }
+            let sid = ItemId(sid);
+            let covered = item_table
+                .covered_bps
+                .as_ref()
+                .and_then(|c| c[*path_id].get(&sid));
+            match covered {
+                Some(v) => {
+                    for (a, b) in v.iter() {
+                        by_group[*group_id].add(sid, *a, *b);
+                    }
+                }
+                None => by_group[*group_id].add(sid, 0, graph_aux.node_len(&sid) as usize),
+            }
+        }
+    }
+
+    let mut res = HashMap::default();
+    for id in partial {
+        let sid = ItemId(id);
+        let l = graph_aux.node_len(&sid) as usize;
+        let excluded = match exclude_table {
+            Some(ex) => ex.get_active_intervals(&sid, l),
+            None => Vec::new(),
+        };
+        let group_intervals: Vec<&[(usize, usize)]> =
+            by_group.iter().filter_map(|c| c.get(&sid)).collect();
+        // split the node at all interval boundaries and count the groups covering each piece
+        let mut bounds: Vec<usize> = vec![0, l];
+        for (a, b) in group_intervals
+            .iter()
+            .flat_map(|v| v.iter())
+            .chain(&excluded)
+        {
+            bounds.push(usize::min(*a, l));
+            bounds.push(usize::min(*b, l));
+        }
+        bounds.sort_unstable();
+        bounds.dedup();
+        let contains =
+            |v: &[(usize, usize)], x: usize, y: usize| v.iter().any(|(a, b)| *a <= x && y <= *b);
+        let mut bps = vec![0; n_groups + 1];
+        for (x, y) in bounds.iter().tuple_windows() {
+            let cov = if contains(&excluded, *x, *y) {
+                0
+            } else {
+                group_intervals
+                    .iter()
+                    .filter(|v| contains(v, *x, *y))
+                    .count()
+            };
+            bps[cov] += y - x;
+        }
+        res.insert(id, bps);
+    }
+    res
+}
+
+// Numbers of groups that traverse each node only in forward, only in reverse, or in both
+// orientations, e.g., to quantify inversion polymorphism
+#[derive(Debug, Clone)]
+pub struct NodeStrands {
+    pub groups: Vec<String>,
+    // indexed by numerical node IDs, the first element is the "zero" element
+    pub forward: Vec<CountSize>,
+    pub reverse: Vec<CountSize>,
+    pub both: Vec<CountSize>,
+}
+
+impl NodeStrands {
+    pub fn from_gfa<R: std::io::Read>(
+        data: &mut BufReader<R>,
+        abacus_aux: &AbacusAuxilliary,
+        graph_aux: &GraphAuxilliary,
+    ) -> Result<Self, Error> {
+        if abacus_aux.include_coords.is_some() || abacus_aux.exclude_coords.is_some() {
+            let msg = "strand analysis supports only subset and exclude lists of nodes";
+            log::error!("{}", msg);
+            return Err(Error::new(ErrorKind::Unsupported, msg));
+        }
+
+        // orientations (bit 0: forward, bit 1: reverse) in which groups traverse each node, as
+        // list of (group, orientations) pairs; paths of the same group usually appear
+        // consecutively, so that the group is found at the end of the list
+        let mut visits: Vec<Vec<(u32, u8)>> = vec![Vec::new(); graph_aux.node_count + 1];
+        let mut groups: Vec<String> = Vec::new();
+        let mut group_ids: HashMap<String, u32> = HashMap::default();
+        let included = |sid: ItemId| {
+            abacus_aux
+                .include_nodes
+                .as_ref()
+                .map_or(true, |x| x[sid.0 as usize])
+                && !abacus_aux
+                    .exclude_nodes
+                    .as_ref()
+                    .map_or(false, |x| x[sid.0 as usize])
+        };
+
+        log::info!("counting node orientations per group..");
+        parse_gfa_paths_walks_oriented(data, graph_aux, |path_seg, sids| {
+            let group = abacus_aux.groups.get(&path_seg.clear_coords()).unwrap();
+            let g = *group_ids.entry(group.clone()).or_insert_with(|| {
+                groups.push(group.clone());
+                groups.len() as u32 - 1
+            });
+            for (sid, o) in sids {
+                if !included(sid) {
+                    continue;
+                }
+                let bit = match o {
+                    Orientation::Forward => 1,
+                    Orientation::Backward => 2,
+                };
+                let v = &mut visits[sid.0 as usize];
+                match v.iter_mut().rev().find(|(h, _)| *h == g) {
+                    Some((_, b)) => *b |= bit,
+                    None => v.push((g, bit)),
+                }
+            }
+            Ok(())
+        })?;
+
+        let mut res = Self {
+            groups,
+            forward: vec![0; visits.len()],
+            reverse: vec![0; visits.len()],
+            both: vec![0; visits.len()],
+        };
+        for (sid, v) in visits.iter().enumerate() {
+            for (_, b) in v {
+                match b {
+                    1 => res.forward[sid] += 1,
+                    2 => res.reverse[sid] += 1,
+                    _ => res.both[sid] += 1,
+                }
+            }
+        }
+        log::info!(
+            "{} of {} nodes are traversed in both orientations",
+            (1..visits.len()).filter(|&i| res.is_polymorphic(i)).count(),
+            visits.len() - 1
+        );
+        Ok(res)
+    }
+
+    // whether the node is traversed in both orientations, by the same or by different groups
+    pub fn is_polymorphic(&self, sid: usize) -> bool {
+        self.both[sid] > 0 || (self.forward[sid] > 0 && self.reverse[sid] > 0)
+    }
+
+    // Histograms of the numbers of groups that traverse nodes only in forward, only in
+    // reverse, and in both orientations
+    pub fn histograms(&self) -> [Vec<usize>; 3] {
+        let mut hists = [(); 3].map(|_| vec![0; self.groups.len() + 1]);
+        for (h, counts) in hists
+            .iter_mut()
+            .zip([&self.forward, &self.reverse, &self.both])
+        {
+            for c in &counts[1..] {
+                h[*c as usize] += 1;
+            }
+        }
+        hists
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::hist::Hist;
 
     fn setup_test_data_cdbg() -> (GraphAuxilliary, Params, String) {
         let test_gfa_file = "test/cdbg.gfa";
-        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Node);
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Node, '#').unwrap();
         let params = Params::test_default_histgrowth();
         (graph_aux, params, test_gfa_file.to_string())
     }
@@ -1179,6 +3145,7 @@ mod tests {
             count: CountType::Node,
             countable: vec![CountSize::MAX, 6, 4, 4, 2, 1],
             uncovered_bps: Some(HashMap::default()),
+            exact_bps: None,
             groups: vec![
                 "a#1#h1".to_string(),
                 "b#1#h1".to_string(),
@@ -1189,9 +3156,9 @@ mod tests {
             ],
         };
 
-        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str());
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
         let abacus_by_total =
-            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node);
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node).unwrap();
         assert_eq!(
             abacus_by_total.count, test_abacus_by_total.count,
             "Expected CountType to match Node"
@@ -1210,27 +3177,120 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_abacus_by_total_from_gfa_low_mem() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let mut path_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let expected =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus_by_total =
+            AbacusByTotal::from_gfa_low_mem(&mut data, &path_aux, &graph_aux, CountType::Node)
+                .unwrap();
+        assert_eq!(abacus_by_total.countable, expected.countable);
+        assert_eq!(abacus_by_total.uncovered_bps, expected.uncovered_bps);
+        assert_eq!(abacus_by_total.groups, expected.groups);
+
+        // paths a#1#h1 and c#1#h1 of the same group are separated by path b#1#h1
+        for (path, group) in path_aux.groups.iter_mut() {
+            if path.sample == "c" && path.haplotype.as_deref() == Some("1") {
+                *group = "a#1#h1".to_string();
+            }
+        }
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let res =
+            AbacusByTotal::from_gfa_low_mem(&mut data, &path_aux, &graph_aux, CountType::Node);
+        assert_eq!(res.unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
     fn setup_test_data_chr_m(count_type: CountType) -> (GraphAuxilliary, Params, String) {
         let test_gfa_file = "test/chrM_test.gfa";
-        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, count_type);
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, count_type, '#').unwrap();
         let params = Params::Histgrowth {
             gfa_file: test_gfa_file.to_string(),
+            more_gfa_files: Vec::new(),
             count: count_type,
             positive_list: String::new(),
             negative_list: String::new(),
             groupby: String::new(),
+            groupby_column: String::new(),
             groupby_haplotype: false,
             groupby_sample: true,
+            groupby_cluster: String::new(),
+            rename: String::new(),
             coverage: "1".to_string(),
             quorum: "0".to_string(),
+            saturation: 1.0,
+            min_groups: 0,
+            min_node_len: 0,
+            zero_length_nodes: ZeroLengthNodes::Count,
+            bins: Binning::default(),
+            badge: String::new(),
+            apriori: false,
+            increments: false,
+            samples: 0,
+            bootstrap: 0,
+            seed: 0,
+            by_sequence: false,
+            subsets: Vec::new(),
             hist: false,
             output_format: OutputFormat::Table,
-            threads: 0,
+            canonicalize_edges: false,
+            best_effort: false,
+            low_mem: false,
+            exact_bp: false,
         };
 
         (graph_aux, params, test_gfa_file.to_string())
     }
 
+    #[test]
+    fn test_abacus_auxilliary_with_sequence() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_chr_m(CountType::Node);
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let seq_aux = abacus_aux.with_sequence("chrM", &graph_aux);
+        let mut data = BufReader::new(std::fs::File::open(&test_gfa_file).unwrap());
+        let abacus =
+            AbacusByTotal::from_gfa(&mut data, &seq_aux, &graph_aux, CountType::Node).unwrap();
+        assert_eq!(abacus.groups, vec!["chm13", "grch38"]);
+
+        let seq_aux = abacus_aux.with_sequence("chrX", &graph_aux);
+        assert_eq!(seq_aux.include_coords, Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_abacus_by_total_window_hists() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_chr_m(CountType::Node);
+        let path_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = BufReader::new(std::fs::File::open(&test_gfa_file).unwrap());
+        let abacus =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node).unwrap();
+        let mut data = BufReader::new(std::fs::File::open(&test_gfa_file).unwrap());
+        let path = parse_path_nodes(&mut data, "grch38#1#chrM", &graph_aux).unwrap();
+        let len: usize = path
+            .iter()
+            .map(|(id, _)| graph_aux.node_len(id) as usize)
+            .sum();
+        let nodes: HashSet<ItemId> = path.iter().map(|(id, _)| *id).collect();
+
+        // a single window spanning the path counts each of its nodes once
+        let hists = abacus.window_hists(&path, &[(0, len)], CountType::Node, &graph_aux);
+        assert_eq!(hists[0].coverage.iter().sum::<usize>(), nodes.len());
+        assert_eq!(hists[0].coverage[0], 0);
+
+        // bps of nodes crossing the border between adjacent windows are split among them
+        let windows = [(0, len / 2), (len / 2, len)];
+        let hists = abacus.window_hists(&path, &windows, CountType::Bp, &graph_aux);
+        assert_eq!(hists[0].coverage.iter().sum::<usize>(), len / 2);
+        assert_eq!(hists[1].coverage.iter().sum::<usize>(), len - len / 2);
+        for h in hists {
+            assert_eq!(h.count, CountType::Bp);
+            assert_eq!(h.coverage.len(), abacus.groups.len() + 1);
+        }
+    }
+
     #[test]
     fn test_abacus_by_total_from_chr_m_node() {
         let count_type = CountType::Node;
@@ -1396,6 +3456,7 @@ mod tests {
                 1,
             ],
             uncovered_bps: Some(HashMap::default()),
+            exact_bps: None,
             groups: vec![
                 "chm13".to_string(),
                 "grch38".to_string(),
@@ -1404,9 +3465,9 @@ mod tests {
             ],
         };
 
-        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str());
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
         let abacus_by_total =
-            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node);
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node).unwrap();
         assert_eq!(
             abacus_by_total.count, test_abacus_by_total.count,
             "Expected CountType to match Node"
@@ -1644,6 +3705,7 @@ mod tests {
                 1,
             ],
             uncovered_bps: Some(HashMap::default()),
+            exact_bps: None,
             groups: vec![
                 "chm13".to_string(),
                 "grch38".to_string(),
@@ -1652,8 +3714,9 @@ mod tests {
             ],
         };
 
-        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str());
-        let abacus_by_total = AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, count_type);
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus_by_total =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, count_type).unwrap();
         assert_eq!(
             abacus_by_total.count, test_abacus_by_total.count,
             "Expected CountType to match Edge"
@@ -1840,6 +3903,7 @@ mod tests {
                 1,
             ],
             uncovered_bps: Some(HashMap::default()),
+            exact_bps: None,
             groups: vec![
                 "chm13".to_string(),
                 "grch38".to_string(),
@@ -1848,8 +3912,9 @@ mod tests {
             ],
         };
 
-        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str());
-        let abacus_by_total = AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, count_type);
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus_by_total =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, count_type).unwrap();
         assert_eq!(
             abacus_by_total.count, test_abacus_by_total.count,
             "Expected CountType to match Edge"
@@ -1871,9 +3936,774 @@ mod tests {
         assert_eq!(hist, test_hist, "Expected same hist");
     }
 
+    #[test]
+    fn test_abacus_by_total_filter_artifacts() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let path_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let mut abacus =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node).unwrap();
+
+        // node 5 is contained in a single group, node 4 in two
+        assert_eq!(abacus.filter_artifacts(3, 0, &graph_aux), (2, 12));
+        let m = CountSize::MAX;
+        assert_eq!(abacus.countable, vec![m, 6, 4, 4, m, m]);
+        // removed countables are not reported as uncovered
+        assert_eq!(abacus.construct_hist(), vec![0, 0, 0, 0, 2, 0, 1]);
+
+        // node 3 has length 5 < 6, node 2 has length 7
+        assert_eq!(abacus.filter_artifacts(0, 6, &graph_aux), (2, 10));
+        assert_eq!(abacus.countable, vec![m, m, 4, m, m, m]);
+        assert_eq!(
+            abacus.construct_hist_bps(&graph_aux),
+            vec![0, 0, 0, 0, 7, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_abacus_stream_cdbg() {
+        let graph_aux = GraphAuxilliary::from_gfa("test/cdbg.gfa", CountType::Edge, '#').unwrap();
+        let paths: [(&str, &[u8]); 6] = [
+            ("a", b"1+,2+,3+"),
+            ("b", b"1+,2+,3+"),
+            ("c", b"1+,2+,3+"),
+            ("c", b"1+,2+"),
+            ("c", b"1+,4+,3+"),
+            ("d", b"1+,4+,5+"),
+        ];
+
+        let mut stream = AbacusStream::new(&graph_aux, CountType::Node).unwrap();
+        for (group, seq) in paths.iter() {
+            stream.push_gfa_path(group, seq).unwrap();
+        }
+        let abacus = stream.finalize();
+        assert_eq!(abacus.groups, vec!["a", "b", "c", "d"]);
+        assert_eq!(abacus.countable, vec![CountSize::MAX, 4, 3, 3, 2, 1]);
+
+        let mut stream = AbacusStream::new(&graph_aux, CountType::Edge).unwrap();
+        for (group, seq) in paths.iter() {
+            stream.push_gfa_path(group, seq).unwrap();
+        }
+        let hist = crate::hist::Hist::from_abacus(&stream.finalize(), None);
+        assert_eq!(hist.coverage, vec![2, 2, 1, 2, 0]);
+
+        let mut stream = AbacusStream::new(&graph_aux, CountType::Node).unwrap();
+        stream.push_gfa_path("a", b"1+,2+").unwrap();
+        stream.push_gfa_path("b", b"1+,2+").unwrap();
+        assert!(stream.push_gfa_path("a", b"1+").is_err());
+        assert!(stream.push_gfa_path("b", b"6+").is_err());
+        // countables outside of the graph are rejected rather than indexed
+        assert!(stream.push_items("b", &[1, 2]).is_ok());
+        let res = stream.push_items("b", &[6]);
+        assert_eq!(res.err().map(|e| e.kind()), Some(ErrorKind::InvalidInput));
+        let res = stream.push_path("b", &[(ItemId(0), Orientation::Forward)]);
+        assert_eq!(res.err().map(|e| e.kind()), Some(ErrorKind::InvalidInput));
+    }
+
+    #[test]
+    fn test_abacus_by_total_canonicalize_edges() {
+        let test_gfa_file = "test/flipped_edges.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Edge, '#').unwrap();
+        let params = Params::test_default_histgrowth();
+        let mut path_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        path_aux.canonicalize_edges = true;
+
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file).unwrap();
+        let abacus =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Edge).unwrap();
+        assert_eq!(abacus.countable, vec![CountSize::MAX, 2, 2]);
+    }
+
+    #[test]
+    fn test_node_strands() {
+        let test_gfa_file = "test/flipped_edges.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Node, '#').unwrap();
+        let params = Params::test_default_histgrowth();
+        let path_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file).unwrap();
+        let strands = NodeStrands::from_gfa(&mut data, &path_aux, &graph_aux).unwrap();
+        assert_eq!(strands.forward, vec![0, 2, 2, 1]);
+        assert_eq!(strands.reverse, vec![0, 0, 0, 1]);
+        assert!(strands.is_polymorphic(3));
+        assert!(!strands.is_polymorphic(1));
+        assert_eq!(
+            strands.histograms(),
+            [vec![0, 1, 2], vec![2, 1, 0], vec![3, 0, 0]]
+        );
+    }
+
+    #[test]
+    fn test_abacus_by_total_node_branching() {
+        let test_gfa_file = "test/cdbg.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Edge, '#').unwrap();
+        let params = Params::test_default_histgrowth();
+        let path_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file).unwrap();
+        let abacus =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Edge).unwrap();
+        // the self-loop 3+ -> 3- leaves node 3 in forward orientation either way and is hence
+        // counted once
+        assert_eq!(
+            abacus.node_branching(&graph_aux),
+            vec![
+                [(0, 0, 0), (0, 0, 0)],
+                [(2, 2, 6), (0, 0, 0)],
+                [(2, 1, 3), (1, 1, 4)],
+                [(1, 0, 0), (2, 2, 4)],
+                [(2, 2, 2), (1, 1, 2)],
+                [(0, 0, 0), (2, 1, 1)]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_abacus_by_total_exact_bp() {
+        let test_gfa_file = "test/cdbg.gfa";
+        let subset_file = std::env::temp_dir().join("panacus_test_exact_bp.bed");
+        let subset_file = subset_file.to_str().unwrap();
+        fs::write(subset_file, "a#1#h1\t0\t2\nb#1#h1\t1\t4\n").unwrap();
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Bp, '#').unwrap();
+        let mut path_aux =
+            AbacusAuxilliary::from_params(&Params::test_default_histgrowth(), &graph_aux)
+                .unwrap()
+                .with_subset(subset_file, &[test_gfa_file.to_string()], &graph_aux)
+                .unwrap();
+        let hist = |path_aux: &AbacusAuxilliary| {
+            let mut data = bufreader_from_compressed_gfa(test_gfa_file).unwrap();
+            AbacusByTotal::from_gfa(&mut data, path_aux, &graph_aux, CountType::Bp)
+                .unwrap()
+                .construct_hist_bps(&graph_aux)
+        };
+        // both groups are counted for the bps of node 1 covered by either of them...
+        assert_eq!(hist(&path_aux), vec![25, 0, 4]);
+        // ...unless bps are counted exactly
+        path_aux.exact_bp = true;
+        assert_eq!(hist(&path_aux), vec![25, 3, 1]);
+    }
+
+    #[test]
+    fn test_abaci_from_gfa_single_pass() {
+        let test_gfa_file = "test/cdbg.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::All, '#').unwrap();
+        let path_aux =
+            AbacusAuxilliary::from_params(&Params::test_default_histgrowth(), &graph_aux).unwrap();
+        for path_aux in [
+            path_aux
+                .with_subset("", &[test_gfa_file.to_string()], &graph_aux)
+                .unwrap(),
+            path_aux
+                .with_subset(
+                    "test/subset_a.txt",
+                    &[test_gfa_file.to_string()],
+                    &graph_aux,
+                )
+                .unwrap(),
+        ] {
+            let (abaci, paths_len) = AbacusByTotal::abaci_and_paths_len_from_gfa(
+                &[test_gfa_file.to_string()],
+                CountType::All,
+                &graph_aux,
+                &path_aux,
+                false,
+            )
+            .unwrap();
+            assert_eq!(abaci.len(), 3);
+            for abacus in abaci.iter() {
+                let mut data = bufreader_from_compressed_gfa(test_gfa_file).unwrap();
+                let expected =
+                    AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, abacus.count)
+                        .unwrap();
+                assert_eq!(abacus.countable, expected.countable);
+                assert_eq!(abacus.uncovered_bps, expected.uncovered_bps);
+                assert_eq!(abacus.groups, expected.groups);
+            }
+            let mut data = bufreader_from_compressed_gfa(test_gfa_file).unwrap();
+            let (_, _, _, expected) =
+                parse_gfa_paths_walks(&mut data, &path_aux, &graph_aux, &CountType::Node).unwrap();
+            assert_eq!(paths_len, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_abaci_of_views_from_gfa() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_chr_m(CountType::All);
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let seq_auxs = [
+            abacus_aux.with_sequence("chrM", &graph_aux),
+            abacus_aux.with_sequence("JAHBCA010000258.1_MT", &graph_aux),
+        ];
+        let views = [&abacus_aux, &seq_auxs[0], &seq_auxs[1]];
+        let res = AbacusByTotal::abaci_of_views_from_gfa(
+            &[test_gfa_file.to_string()],
+            CountType::All,
+            &graph_aux,
+            &views,
+            false,
+        )
+        .unwrap();
+        assert_eq!(res.len(), views.len());
+        for ((abaci, paths_len), view) in res.iter().zip(views) {
+            let (expected, expected_len) = AbacusByTotal::abaci_and_paths_len_from_gfa(
+                &[test_gfa_file.to_string()],
+                CountType::All,
+                &graph_aux,
+                view,
+                false,
+            )
+            .unwrap();
+            assert_eq!(abaci.len(), 3);
+            for (abacus, expected) in abaci.iter().zip(expected.iter()) {
+                assert_eq!(abacus.count, expected.count);
+                assert_eq!(abacus.countable, expected.countable);
+                assert_eq!(abacus.groups, expected.groups);
+            }
+            assert_eq!(paths_len, &expected_len);
+        }
+        assert_eq!(res[1].0[0].groups, vec!["chm13", "grch38"]);
+        assert_eq!(res[2].0[0].groups, vec!["HG00438"]);
+    }
+
+    #[test]
+    fn test_load_coord_list_cache() {
+        let file_name = std::env::temp_dir().join("panacus_test_coord_cache.bed");
+        let file_name = file_name.to_str().unwrap();
+        fs::write(file_name, "a#1#h1\t0\t2\nb#1#h1\t1\t4\n").unwrap();
+        let coords = AbacusAuxilliary::load_coord_list(file_name, '#').unwrap();
+        assert_eq!(coords.as_ref().map(|c| c.len()), Some(2));
+        let key = (
+            file_name.to_string(),
+            fs::metadata(file_name).unwrap().modified().ok(),
+            '#',
+        );
+        assert_eq!(COORD_LIST_CACHE.lock().unwrap().get(&key), coords.as_ref());
+        assert_eq!(
+            AbacusAuxilliary::load_coord_list(file_name, '#').unwrap(),
+            coords
+        );
+        fs::remove_file(file_name).unwrap();
+    }
+
+    #[test]
+    fn test_combine_intervals() {
+        let a = [(0, 10), (20, 30)];
+        let b = [(5, 25), (40, 50)];
+        assert_eq!(
+            combine_intervals(&a, &b, ListOp::Union),
+            vec![(0, 30), (40, 50)]
+        );
+        assert_eq!(
+            combine_intervals(&a, &b, ListOp::Intersection),
+            vec![(5, 10), (20, 25)]
+        );
+        assert_eq!(
+            combine_intervals(&a, &b, ListOp::Difference),
+            vec![(0, 5), (25, 30)]
+        );
+        assert_eq!(
+            combine_intervals(&[(0, usize::MAX)], &[(2, 3)], ListOp::Difference),
+            vec![(0, 2), (3, usize::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_list_expression() {
+        assert_eq!(
+            parse_list_expression("my list.txt").unwrap(),
+            vec![(None, "my list.txt")]
+        );
+        assert_eq!(
+            parse_list_expression("a.bed - b.bed & c.bed").unwrap(),
+            vec![
+                (None, "a.bed"),
+                (Some(ListOp::Difference), "b.bed"),
+                (Some(ListOp::Intersection), "c.bed")
+            ]
+        );
+        assert!(parse_list_expression("a.bed -").is_err());
+        assert!(parse_list_expression("a.bed + - b.bed").is_err());
+
+        let test_gfa_file = "test/cdbg.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Node, '#').unwrap();
+        let path_aux =
+            AbacusAuxilliary::from_params(&Params::test_default_histgrowth(), &graph_aux).unwrap();
+        let union = path_aux
+            .with_subset(
+                "test/subset_a.txt + test/subset_b.txt",
+                &[test_gfa_file.to_string()],
+                &graph_aux,
+            )
+            .unwrap();
+        assert_eq!(union.include_coords.unwrap().len(), 6);
+        let difference = path_aux
+            .with_subset(
+                "test/subset_a.txt - test/subset_b.txt",
+                &[test_gfa_file.to_string()],
+                &graph_aux,
+            )
+            .unwrap();
+        assert_eq!(difference.include_coords.unwrap().len(), 3);
+        let intersection = path_aux
+            .with_subset(
+                "test/subset_a.txt & test/subset_b.txt",
+                &[test_gfa_file.to_string()],
+                &graph_aux,
+            )
+            .unwrap();
+        assert_eq!(intersection.include_coords, Some(Vec::new()));
+        assert!(path_aux
+            .with_subset(
+                "test/subset_a.txt + nodes:test/nodes_exclude.txt",
+                &[test_gfa_file.to_string()],
+                &graph_aux,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_abacus_by_total_coverage_delta() {
+        let test_gfa_file = "test/cdbg.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Node, '#').unwrap();
+        let params = Params::test_default_histgrowth();
+        let path_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let path_aux_a = path_aux
+            .with_subset(
+                "test/subset_a.txt",
+                &[test_gfa_file.to_string()],
+                &graph_aux,
+            )
+            .unwrap();
+        let path_aux_b = path_aux
+            .with_subset(
+                "test/subset_b.txt",
+                &[test_gfa_file.to_string()],
+                &graph_aux,
+            )
+            .unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file).unwrap();
+        let abacus_a =
+            AbacusByTotal::from_gfa(&mut data, &path_aux_a, &graph_aux, CountType::Node).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file).unwrap();
+        let abacus_b =
+            AbacusByTotal::from_gfa(&mut data, &path_aux_b, &graph_aux, CountType::Node).unwrap();
+        assert_eq!(abacus_a.groups.len(), 3);
+        assert_eq!(abacus_b.groups.len(), 3);
+        let delta: Vec<i64> = abacus_a
+            .coverage_delta(&abacus_b)
+            .iter()
+            .map(|d| (d * 3.0).round() as i64)
+            .collect();
+        assert_eq!(delta, vec![0, 0, 2, 2, -2, -1]);
+    }
+
+    #[test]
+    fn test_abacus_by_total_node_lists() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let mut path_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        path_aux.exclude_nodes =
+            AbacusAuxilliary::load_node_list("nodes:test/nodes_exclude.txt", &[], &graph_aux)
+                .unwrap();
+        assert_eq!(
+            path_aux.exclude_nodes,
+            Some(vec![false, false, true, false, true, false])
+        );
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node).unwrap();
+        assert_eq!(abacus.countable, vec![CountSize::MAX, 6, 0, 4, 0, 1]);
+
+        // the complement as subset list
+        let path_aux = path_aux
+            .with_subset(
+                "nodes:test/nodes_exclude.txt",
+                &[test_gfa_file.to_string()],
+                &graph_aux,
+            )
+            .unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus =
+            AbacusByTotal::from_gfa(&mut data, &path_aux, &graph_aux, CountType::Node).unwrap();
+        assert_eq!(abacus.countable, vec![CountSize::MAX, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_abacus_by_group_to_tsv_long() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, false)
+                .unwrap();
+        let mut tsv = BufWriter::new(Vec::new());
+        abacus.to_tsv(false, None, &mut tsv).unwrap();
+        let tsv = String::from_utf8(tsv.into_inner().unwrap()).unwrap();
+        let mut long = BufWriter::new(Vec::new());
+        abacus.to_tsv_long(None, &mut long).unwrap();
+        let long = String::from_utf8(long.into_inner().unwrap()).unwrap();
+
+        // the long format lists exactly the non-zero cells of the matrix
+        let mut lines = tsv.lines();
+        let groups: Vec<&str> = lines.next().unwrap().split('\t').skip(1).collect();
+        let mut expected = vec!["node\tgroup\tcoverage".to_string()];
+        for line in lines {
+            let mut row = line.split('\t');
+            let node = row.next().unwrap();
+            for (group, x) in groups.iter().zip(row) {
+                if x != "0" {
+                    expected.push(format!("{}\t{}\t{}", node, group, x));
+                }
+            }
+        }
+        assert_eq!(long.lines().collect::<Vec<_>>(), expected);
+        assert!(expected.len() > 1);
+    }
+
+    #[test]
+    fn test_abacus_auxilliary_lift_coord_list() {
+        let (graph_aux, _, test_gfa_file) = setup_test_data_cdbg();
+        // a#1#h1 traverses nodes 1 and 2 within [0, 6), d#1#h1 traverses node 5 within [16, 17)
+        let listed = AbacusAuxilliary::load_node_list(
+            "liftover:test/liftover.bed",
+            &[test_gfa_file.to_string()],
+            &graph_aux,
+        )
+        .unwrap();
+        assert_eq!(listed, Some(vec![false, true, true, false, false, true]));
+    }
+
+    #[test]
+    fn test_abacus_by_group_coverage_classes() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, true)
+                .unwrap();
+        assert_eq!(
+            abacus.coverage_classes(),
+            vec![
+                [0, 2, 1],
+                [0, 2, 1],
+                [0, 2, 1],
+                [0, 1, 1],
+                [0, 2, 1],
+                [1, 1, 1]
+            ]
+        );
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_abacus_by_group_to_arrow() {
+        use arrow::array::{Array, StringArray, UInt64Array};
+        use arrow::ipc::reader::StreamReader;
+
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, false)
+                .unwrap();
+        let mut tsv = BufWriter::new(Vec::new());
+        abacus.to_tsv(false, None, &mut tsv).unwrap();
+        let tsv = String::from_utf8(tsv.into_inner().unwrap()).unwrap();
+        let mut ipc = Vec::new();
+        abacus.to_arrow(false, None, &mut ipc).unwrap();
+
+        // the Arrow stream holds the same table as the TSV output
+        let batches: Vec<_> = StreamReader::try_new(&ipc[..], None)
+            .unwrap()
+            .map(|b| b.unwrap())
+            .collect();
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        let header: Vec<String> = batch
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        let mut lines = tsv.lines();
+        assert_eq!(header.join("\t"), lines.next().unwrap());
+        let nodes = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        for (i, line) in lines.enumerate() {
+            let mut row = vec![nodes.value(i).to_string()];
+            for j in 1..batch.num_columns() {
+                let counts = batch
+                    .column(j)
+                    .as_any()
+                    .downcast_ref::<UInt64Array>()
+                    .unwrap();
+                row.push(counts.value(i).to_string());
+            }
+            assert_eq!(row.join("\t"), line);
+        }
+        assert_eq!(nodes.len(), graph_aux.node_count);
+    }
+
+    #[test]
+    fn test_abacus_by_group_pairwise_intersections() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, false)
+                .unwrap();
+        let intersections = abacus.pairwise_intersections();
+        assert_eq!(intersections[0], vec![3, 3, 3, 2, 2, 1]);
+        assert_eq!(intersections[5], vec![1, 1, 1, 1, 2, 3]);
+        for (i, row) in intersections.iter().enumerate() {
+            for (j, x) in row.iter().enumerate() {
+                assert_eq!(*x, intersections[j][i]);
+            }
+        }
+
+        let jaccard = abacus.similarities(SimilarityMetric::Jaccard);
+        assert_eq!(jaccard[0][0], 1.0);
+        assert_eq!(jaccard[0][5], 0.2);
+        let containment = abacus.similarities(SimilarityMetric::Containment);
+        assert_eq!(containment[0][3], 2.0 / 3.0);
+    }
+
+    #[test]
+    fn test_abacus_by_group_private_countables() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, false)
+                .unwrap();
+        assert_eq!(
+            abacus.private_countables(),
+            vec![(0, 3), (0, 3), (0, 3), (0, 2), (0, 3), (1, 3)]
+        );
+        assert!(abacus.is_private(5));
+        assert!(!abacus.is_private(1));
+    }
+
+    #[test]
+    fn test_abacus_by_group_permuted_growth() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let mut abacus_group =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, false)
+                .unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let hist = Hist::from_abacus(
+            &AbacusByTotal::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node).unwrap(),
+            None,
+        );
+
+        // averaged over all orders, growth equals the (a priori) growth of the histogram
+        let n = abacus_group.groups.len();
+        for (c, q) in [(1, 0.0), (1, 1.0), (2, 0.5)] {
+            let t_coverage = Threshold::Absolute(c);
+            let t_quorum = Threshold::Relative(q);
+            let mut avg = vec![0.0; n];
+            let mut orders = 0.0;
+            for rank in (0..n).permutations(n) {
+                let growth = abacus_group.calc_growth_permuted(&rank, &t_coverage, &t_quorum);
+                for (a, g) in avg.iter_mut().zip(growth) {
+                    *a += g;
+                }
+                orders += 1.0;
+            }
+            let expected = hist
+                .apply_coverage_threshold(&t_coverage)
+                .calc_growth(&Threshold::Absolute(1), &t_quorum);
+            for (a, e) in avg.iter().zip(&expected) {
+                assert!((a / orders - e).abs() < 1e-9, "{:?} vs {:?}", avg, expected);
+            }
+        }
+
+        // the identity order yields the ordered growth
+        let identity: Vec<usize> = (0..n).collect();
+        let t_coverage = Threshold::Absolute(1);
+        let t_quorum = Threshold::Relative(0.0);
+        assert_eq!(
+            abacus_group.calc_growth_permuted(&identity, &t_coverage, &t_quorum),
+            abacus_group.calc_growth(&t_coverage, &t_quorum)
+        );
+
+        // samples are reproducible and removing countables of low coverage is the same as
+        // requiring their coverage
+        let hist_aux = HistAuxilliary {
+            quorum: vec![Threshold::Relative(0.0)],
+            coverage: vec![Threshold::Absolute(2)],
+            names: vec![None; 1],
+            saturation: 1.0,
+            apriori: false,
+            increments: false,
+        };
+        let sampled = abacus_group.calc_sampled_growths(&hist_aux, 5, 42);
+        assert_eq!(sampled.len(), 1);
+        assert_eq!(sampled[0].len(), 5);
+        let mean = abacus_group.calc_sampled_mean_growths(&hist_aux, 5, 42);
+        assert!(mean[0][0].is_nan());
+        for (k, x) in mean[0][1..].iter().enumerate() {
+            let expected = sampled[0].iter().map(|g| g[k]).sum::<f64>() / 5.0;
+            assert!((x - expected).abs() < 1e-9);
+        }
+        abacus_group.filter_artifacts(2, 0);
+        assert_eq!(
+            sampled,
+            abacus_group.calc_sampled_growths(
+                &HistAuxilliary {
+                    coverage: vec![Threshold::Absolute(1)],
+                    names: vec![None; 1],
+                    ..hist_aux
+                },
+                5,
+                42
+            )
+        );
+    }
+
+    #[test]
+    fn test_abacus_by_group_sampled_growth_seed() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus_group =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, false)
+                .unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let hist = Hist::from_abacus(
+            &AbacusByTotal::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node).unwrap(),
+            None,
+        );
+        let hist_aux = HistAuxilliary {
+            quorum: vec![Threshold::Relative(0.0), Threshold::Relative(0.5)],
+            coverage: vec![Threshold::Absolute(1), Threshold::Absolute(2)],
+            names: vec![None; 2],
+            saturation: 1.0,
+            apriori: true,
+            increments: false,
+        };
+
+        // a fixed seed reproduces the same orders and hence the same curve
+        let mean = abacus_group.calc_sampled_mean_growths(&hist_aux, 20, 7);
+        assert_eq!(
+            format!("{:?}", mean),
+            format!(
+                "{:?}",
+                abacus_group.calc_sampled_mean_growths(&hist_aux, 20, 7)
+            )
+        );
+        assert_eq!(
+            abacus_group.random_order(7, 3),
+            abacus_group.random_order(7, 3)
+        );
+        assert!((0..20).any(|s| abacus_group.random_order(7, s) != abacus_group.random_order(8, s)));
+
+        // with many samples, the estimate approaches the exact growth over all orders
+        let exact = hist.calc_all_growths(&hist_aux);
+        let mean = abacus_group.calc_sampled_mean_growths(&hist_aux, 2000, 7);
+        for (m, e) in mean.iter().zip(&exact) {
+            assert_eq!(m.len(), e.len());
+            for (x, y) in m[1..].iter().zip(&e[1..]) {
+                assert!((x - y).abs() < 0.1, "{:?} vs {:?}", m, e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_abacus_by_group_anchored_growth() {
+        let (graph_aux, params, test_gfa_file) = setup_test_data_cdbg();
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let abacus_group =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, false)
+                .unwrap();
+        let mut data = bufreader_from_compressed_gfa(test_gfa_file.as_str()).unwrap();
+        let hist = Hist::from_abacus(
+            &AbacusByTotal::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node).unwrap(),
+            None,
+        );
+
+        // without anchors, growth is averaged over all permutations
+        for q in [0.0, 0.5, 1.0] {
+            let t_coverage = Threshold::Absolute(1);
+            let t_quorum = Threshold::Relative(q);
+            let anchored = abacus_group.calc_anchored_growth(0, &t_coverage, &t_quorum);
+            let expected = hist.calc_growth(&t_coverage, &t_quorum);
+            for (a, e) in anchored.iter().zip(&expected) {
+                assert!((a - e).abs() < 1e-9, "{:?} vs {:?}", anchored, expected);
+            }
+        }
+
+        // with all groups as anchors, growth follows the given order
+        let t_coverage = Threshold::Absolute(1);
+        let t_quorum = Threshold::Relative(0.0);
+        assert_eq!(
+            abacus_group.calc_anchored_growth(6, &t_coverage, &t_quorum),
+            abacus_group.calc_growth(&t_coverage, &t_quorum)
+        );
+        // a#1#h1 covers nodes 1-3; any of the remaining groups but c#1#h2 adds further nodes
+        let growth = abacus_group.calc_anchored_growth(1, &t_coverage, &t_quorum);
+        assert_eq!(growth[0], 3.0);
+        assert!((growth[1] - 3.6).abs() < 1e-9);
+        assert!((growth[5] - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zero_length_nodes() {
+        let test_gfa_file = "test/zero_length.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::All, '#').unwrap();
+        assert_eq!(graph_aux.node_lens, vec![0, 4, 0, 3]);
+        assert_eq!(graph_aux.zero_length_nodes(), 1);
+        assert!(graph_aux
+            .check_zero_length_nodes(ZeroLengthNodes::Count)
+            .is_ok());
+        assert!(graph_aux
+            .check_zero_length_nodes(ZeroLengthNodes::Error)
+            .is_err());
+
+        // skipped nodes are no countables at all, rather than uncovered ones
+        let path_aux =
+            AbacusAuxilliary::from_params(&Params::test_default_histgrowth(), &graph_aux).unwrap();
+        let mut abaci = AbacusByTotal::abaci_from_gfa(
+            &[test_gfa_file.to_string()],
+            CountType::All,
+            &graph_aux,
+            &path_aux,
+            false,
+        )
+        .unwrap();
+        let hists = |abaci: &[AbacusByTotal]| -> Vec<Vec<usize>> {
+            abaci
+                .iter()
+                .map(|a| Hist::from_abacus(a, Some(&graph_aux)).coverage)
+                .collect()
+        };
+        assert_eq!(
+            hists(&abaci),
+            vec![vec![0, 1, 2], vec![0, 0, 7], vec![0, 3, 0]]
+        );
+        let skipped: Vec<usize> = abaci
+            .iter_mut()
+            .map(|a| a.skip_zero_length_nodes(&graph_aux))
+            .collect();
+        assert_eq!(skipped, vec![1, 1, 0]);
+        assert_eq!(
+            hists(&abaci),
+            vec![vec![0, 0, 2], vec![0, 0, 7], vec![0, 3, 0]]
+        );
+
+        let mut info = graph_aux.graph_info(&path_aux.groups);
+        assert_eq!((info.node_count, info.shortest_node), (3, 0));
+        info.skip_zero_length_nodes();
+        assert_eq!((info.node_count, info.shortest_node), (2, 3));
+        assert_eq!(info.n50_node, 4);
+    }
+
     fn setup_test_data() -> (GraphAuxilliary, Params, String) {
         let test_gfa_file = "test/cdbg.gfa";
-        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Node);
+        let graph_aux = GraphAuxilliary::from_gfa(test_gfa_file, CountType::Node, '#').unwrap();
         let params = Params::test_default_histgrowth();
         (graph_aux, params, test_gfa_file.to_string())
     }
@@ -1897,7 +4727,7 @@ mod tests {
     fn test_path_auxilliary_load_groups_by_sample() {
         let (graph_aux, _, _) = setup_test_data();
 
-        let result = AbacusAuxilliary::load_groups("", false, true, &graph_aux);
+        let result = AbacusAuxilliary::load_groups("", "", false, true, "", &graph_aux);
         assert!(
             result.is_ok(),
             "Expected successful group loading by sample"
@@ -1914,7 +4744,7 @@ mod tests {
     fn test_path_auxilliary_load_groups_by_haplotype() {
         let (graph_aux, _, _) = setup_test_data();
 
-        let result = AbacusAuxilliary::load_groups("", true, false, &graph_aux);
+        let result = AbacusAuxilliary::load_groups("", "", true, false, "", &graph_aux);
         let groups = result.unwrap();
         let mut group_count = HashSet::new();
         for (_, g) in groups {
@@ -1923,15 +4753,130 @@ mod tests {
         assert_eq!(group_count.len(), 5, "Expected 5 groups based on haplotype");
     }
 
+    #[test]
+    fn test_path_auxilliary_load_groups_by_cluster() {
+        let (graph_aux, _, _) = setup_test_data();
+
+        let result = AbacusAuxilliary::load_groups(
+            "",
+            "",
+            false,
+            false,
+            "test/test_clusters.txt",
+            &graph_aux,
+        );
+        assert!(
+            result.is_ok(),
+            "Expected successful group loading by cluster"
+        );
+        let groups = result.unwrap();
+        for (path, cluster) in [
+            ("a#1#h1", "a"),
+            ("b#1#h1", "a"),
+            ("c#1#h1", "c"),
+            ("c#1#h2", "c"),
+            ("c#2#h1", "c#2#h1"),
+            ("d#1#h1", "c"),
+        ] {
+            assert_eq!(groups[&PathSegment::from_str(path, '#')], cluster);
+        }
+    }
+
+    #[test]
+    fn test_path_auxilliary_load_groups_error_line() {
+        let (graph_aux, _, _) = setup_test_data();
+        let file_name = std::env::temp_dir().join("panacus_test_conflicting_groups.txt");
+        // blank lines do not shift the number of the offending line
+        fs::write(&file_name, "a#1#h1\tG1\n\n\na#1#h1\tG2\n").unwrap();
+        let res = AbacusAuxilliary::load_groups(
+            file_name.to_str().unwrap(),
+            "",
+            false,
+            false,
+            "",
+            &graph_aux,
+        );
+        assert!(res
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("error in line 4:"));
+        fs::remove_file(file_name).unwrap();
+    }
+
+    #[test]
+    fn test_path_auxilliary_rename_groups() {
+        let (graph_aux, _, _) = setup_test_data();
+
+        let groups = AbacusAuxilliary::load_groups("", "", false, false, "", &graph_aux).unwrap();
+        let result = AbacusAuxilliary::rename_groups(groups, "test/test_rename.txt");
+        assert!(result.is_ok(), "Expected successful renaming of groups");
+        let groups = result.unwrap();
+        for (path, group) in [
+            ("a#1#h1", "Alpha"),
+            ("b#1#h1", "sample_b"),
+            ("c#1#h1", "sample_c"),
+            ("c#1#h2", "sample_c"),
+            ("c#2#h1", "c#2#h1"),
+            ("d#1#h1", "sample_d"),
+        ] {
+            assert_eq!(groups[&PathSegment::from_str(path, '#')], group);
+        }
+    }
+
+    #[test]
+    fn test_path_auxilliary_rename_before_subset() {
+        let (graph_aux, _, _) = setup_test_data();
+        let dir = std::env::temp_dir();
+        let rename_file = dir.join("panacus_test_rename_merge.txt");
+        let subset_file = dir.join("panacus_test_rename_subset.txt");
+        fs::write(&rename_file, "a\tab\nb\tab\n").unwrap();
+        let path_aux = |subset: &str| {
+            fs::write(&subset_file, subset).unwrap();
+            let mut params = Params::test_default_histgrowth();
+            if let Params::Histgrowth {
+                groupby_sample,
+                rename,
+                positive_list,
+                ..
+            } = &mut params
+            {
+                *groupby_sample = true;
+                *rename = rename_file.to_str().unwrap().to_string();
+                *positive_list = subset_file.to_str().unwrap().to_string();
+            }
+            AbacusAuxilliary::from_params(&params, &graph_aux)
+        };
+
+        // the merged group is included as a whole
+        let mut include = path_aux("ab\n").unwrap().include_coords.unwrap();
+        include.sort();
+        assert_eq!(
+            include,
+            vec![
+                PathSegment::from_str("a#1#h1", '#'),
+                PathSegment::from_str("b#1#h1", '#')
+            ]
+        );
+        // groups cannot be referred to by their old identifiers
+        let res = path_aux("a\n");
+        assert_eq!(res.err().map(|e| e.kind()), Some(ErrorKind::InvalidInput));
+        // paths are still referred to by their names
+        assert!(path_aux("a#1#h1\n").is_ok());
+
+        fs::remove_file(rename_file).unwrap();
+        fs::remove_file(subset_file).unwrap();
+    }
+
     #[test]
     fn test_complement_with_group_assignments_valid() {
         let groups = HashMap::from([
-            (PathSegment::from_str("a#1#h1"), "G1".to_string()),
-            (PathSegment::from_str("b#1#h1"), "G1".to_string()),
-            (PathSegment::from_str("c#1#h1"), "G2".to_string()),
+            (PathSegment::from_str("a#1#h1", '#'), "G1".to_string()),
+            (PathSegment::from_str("b#1#h1", '#'), "G1".to_string()),
+            (PathSegment::from_str("c#1#h1", '#'), "G2".to_string()),
         ]);
 
-        let coords = Some(vec![PathSegment::from_str("G1")]);
+        let coords = Some(vec![PathSegment::from_str("G1", '#')]);
         let result = AbacusAuxilliary::complement_with_group_assignments(coords, &groups);
         assert!(
             result.is_ok(),
@@ -1953,11 +4898,11 @@ mod tests {
     #[test]
     fn test_complement_with_group_assignments_invalid() {
         let groups = HashMap::from([
-            (PathSegment::from_str("a#0"), "G1".to_string()),
-            (PathSegment::from_str("b#0"), "G1".to_string()),
+            (PathSegment::from_str("a#0", '#'), "G1".to_string()),
+            (PathSegment::from_str("b#0", '#'), "G1".to_string()),
         ]);
 
-        let coords = Some(vec![PathSegment::from_str("G1:1-5")]);
+        let coords = Some(vec![PathSegment::from_str("G1:1-5", '#')]);
         let result = AbacusAuxilliary::complement_with_group_assignments(coords, &groups);
         assert!(
             result.is_err(),
@@ -1974,6 +4919,7 @@ mod tests {
                 "seq1".to_string(),
                 Some(0),
                 Some(100),
+                '#',
             ),
             PathSegment::new(
                 "sample".to_string(),
@@ -1981,6 +4927,7 @@ mod tests {
                 "seq1".to_string(),
                 Some(50),
                 Some(150),
+                '#',
             ),
             PathSegment::new(
                 "sample".to_string(),
@@ -1988,6 +4935,7 @@ mod tests {
                 "seq2".to_string(),
                 Some(0),
                 Some(100),
+                '#',
             ),
         ];
 
@@ -2014,14 +4962,18 @@ mod tests {
         let (graph_aux, _, _) = setup_test_data();
 
         let path_aux = AbacusAuxilliary {
-            groups: AbacusAuxilliary::load_groups("", false, false, &graph_aux).unwrap(),
+            groups: AbacusAuxilliary::load_groups("", "", false, false, "", &graph_aux).unwrap(),
             include_coords: None,
             exclude_coords: Some(vec![
-                PathSegment::from_str("a#1#h1"),
-                PathSegment::from_str("b#1#h1"),
-                PathSegment::from_str("b#1#h1"),
+                PathSegment::from_str("a#1#h1", '#'),
+                PathSegment::from_str("b#1#h1", '#'),
+                PathSegment::from_str("b#1#h1", '#'),
             ]), //duplicates do not cause any error
             order: None,
+            canonicalize_edges: false,
+            exact_bp: false,
+            include_nodes: None,
+            exclude_nodes: None,
         };
         let ordered_paths = path_aux.get_path_order(&graph_aux.path_segments);
         assert_eq!(
@@ -2035,13 +4987,17 @@ mod tests {
     fn test_path_auxilliary_count_groups() {
         let path_aux = AbacusAuxilliary {
             groups: HashMap::from([
-                (PathSegment::from_str("a#1#h1"), "G1".to_string()),
-                (PathSegment::from_str("b#1#h1"), "G1".to_string()),
-                (PathSegment::from_str("c#1#h1"), "G2".to_string()),
+                (PathSegment::from_str("a#1#h1", '#'), "G1".to_string()),
+                (PathSegment::from_str("b#1#h1", '#'), "G1".to_string()),
+                (PathSegment::from_str("c#1#h1", '#'), "G2".to_string()),
             ]),
             include_coords: None,
             exclude_coords: None,
             order: None,
+            canonicalize_edges: false,
+            exact_bp: false,
+            include_nodes: None,
+            exclude_nodes: None,
         };
 
         assert_eq!(path_aux.count_groups(), 2, "Expected 2 unique groups");
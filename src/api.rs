@@ -0,0 +1,259 @@
+/* standard use */
+use std::io::{Error, ErrorKind};
+
+/* private use */
+use crate::abacus::{AbacusAuxilliary, AbacusByTotal, AbacusOptions};
+use crate::graph::{GraphAuxilliary, Info, DEFAULT_PANSN_DELIMITER};
+use crate::hist::{GrowthResult, Hist, HistAuxilliary};
+use crate::io::{bufreader_from_gfa_files, gfa_file_list, parse_gfa_paths_walks};
+use crate::util::CountType;
+
+/// Grouping of paths whose counts are merged
+#[derive(Debug, Clone, PartialEq)]
+pub enum Grouping {
+    /// every path forms its own group
+    Path,
+    /// paths are grouped by the sample of their PanSN name
+    Sample,
+    /// paths are grouped by the sample and haplotype of their PanSN name
+    Haplotype,
+    /// paths are grouped by the path-group mapping given in a tab-separated two-column file
+    File(String),
+}
+
+impl Default for Grouping {
+    fn default() -> Self {
+        Grouping::Path
+    }
+}
+
+/// Builder for running panacus from other Rust tools; results are returned as plain structs
+/// instead of being written out
+///
+/// ```no_run
+/// use panacus::{CountType, Grouping, PanacusBuilder};
+///
+/// let hist = PanacusBuilder::new("graph.gfa")
+///     .subset("genes.bed")
+///     .grouping(Grouping::Sample)
+///     .hist(CountType::Node)?;
+/// println!("{:?}", hist.coverage);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PanacusBuilder {
+    gfa_file: String,
+    more_gfa_files: Vec<String>,
+    subset: String,
+    exclude: String,
+    grouping: Grouping,
+    canonicalize_edges: bool,
+    pansn_delimiter: char,
+}
+
+impl PanacusBuilder {
+    pub fn new(gfa_file: &str) -> Self {
+        Self {
+            gfa_file: gfa_file.to_string(),
+            more_gfa_files: Vec::new(),
+            subset: String::new(),
+            exclude: String::new(),
+            grouping: Grouping::default(),
+            canonicalize_edges: false,
+            pansn_delimiter: DEFAULT_PANSN_DELIMITER,
+        }
+    }
+
+    /// Adds further graphs (e.g., of other chromosomes) that are analyzed together with the first
+    /// one as a single pangenome, just as the further GFA files given on the command line
+    pub fn more_gfa_files(mut self, gfa_files: &[&str]) -> Self {
+        self.more_gfa_files
+            .extend(gfa_files.iter().map(|f| f.to_string()));
+        self
+    }
+
+    /// Restricts counting to the paths, path coordinates, or nodes given in the file, just as
+    /// `--subset` does
+    pub fn subset(mut self, file: &str) -> Self {
+        self.subset = file.to_string();
+        self
+    }
+
+    /// Excludes the countables that intersect with the paths, path coordinates, or nodes given in
+    /// the file, just as `--exclude` does
+    pub fn exclude(mut self, file: &str) -> Self {
+        self.exclude = file.to_string();
+        self
+    }
+
+    pub fn grouping(mut self, grouping: Grouping) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    pub fn canonicalize_edges(mut self, canonicalize_edges: bool) -> Self {
+        self.canonicalize_edges = canonicalize_edges;
+        self
+    }
+
+    /// Sets the delimiter of the sample, haplotype, and sequence components of path names, just
+    /// as `--pansn` does
+    pub fn pansn_delimiter(mut self, delimiter: char) -> Self {
+        self.pansn_delimiter = delimiter;
+        self
+    }
+
+    /// Computes the coverage histogram of the given count type
+    pub fn hist(&self, count: CountType) -> Result<Hist, Error> {
+        if count == CountType::All {
+            let msg = "histogram requires a single count type, not \"all\"";
+            log::error!("{}", msg);
+            return Err(Error::new(ErrorKind::InvalidInput, msg));
+        }
+        let graph_aux =
+            GraphAuxilliary::from_gfa_files(&self.gfa_files(), count, self.pansn_delimiter)?;
+        let abacus_aux = AbacusAuxilliary::from_options(&self.abacus_options(), &graph_aux)?;
+        let mut data = bufreader_from_gfa_files(&self.gfa_files())?;
+        let abacus = AbacusByTotal::from_gfa(&mut data, &abacus_aux, &graph_aux, count)?;
+        Ok(Hist::from_abacus(&abacus, Some(&graph_aux)))
+    }
+
+    /// Computes graph, path, and (unless every path forms its own group) group statistics, just as
+    /// the `info` command does
+    pub fn info(&self) -> Result<Info, Error> {
+        let graph_aux = GraphAuxilliary::from_gfa_files(
+            &self.gfa_files(),
+            CountType::All,
+            self.pansn_delimiter,
+        )?;
+        let abacus_aux = AbacusAuxilliary::from_options(&self.abacus_options(), &graph_aux)?;
+        let mut data = bufreader_from_gfa_files(&self.gfa_files())?;
+        let (_, _, _, paths_len) =
+            parse_gfa_paths_walks(&mut data, &abacus_aux, &graph_aux, &CountType::Node)?;
+        Ok(graph_aux.info(
+            &paths_len,
+            &abacus_aux.groups,
+            self.grouping != Grouping::Path,
+        ))
+    }
+
+    /// Computes the growth curves of the given count type for the given coverage and quorum
+    /// thresholds, which are specified as in `--coverage` and `--quorum`, e.g. "1,2" and "0,0.9"
+    pub fn growth(
+        &self,
+        count: CountType,
+        coverage: &str,
+        quorum: &str,
+    ) -> Result<GrowthResult, Error> {
+        let hist_aux = HistAuxilliary::parse_params(quorum, coverage, 1.0, false)?;
+        let hist = self.hist(count)?;
+        Ok(GrowthResult::new(
+            count,
+            &hist.calc_all_growths(&hist_aux),
+            &hist_aux,
+            Vec::new(),
+        ))
+    }
+
+    fn gfa_files(&self) -> Vec<String> {
+        gfa_file_list(&self.gfa_file, &self.more_gfa_files)
+    }
+
+    fn abacus_options(&self) -> AbacusOptions {
+        AbacusOptions {
+            gfa_file: self.gfa_file.clone(),
+            more_gfa_files: self.more_gfa_files.clone(),
+            positive_list: self.subset.clone(),
+            negative_list: self.exclude.clone(),
+            groupby: match &self.grouping {
+                Grouping::File(file) => file.clone(),
+                _ => String::new(),
+            },
+            groupby_haplotype: self.grouping == Grouping::Haplotype,
+            groupby_sample: self.grouping == Grouping::Sample,
+            canonicalize_edges: self.canonicalize_edges,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panacus_builder_hist() {
+        let hist = PanacusBuilder::new("test/cdbg.gfa")
+            .hist(CountType::Node)
+            .unwrap();
+        assert_eq!(hist.coverage, vec![0, 1, 1, 0, 2, 0, 1]);
+
+        let hist = PanacusBuilder::new("test/cdbg.gfa")
+            .grouping(Grouping::Sample)
+            .hist(CountType::Node)
+            .unwrap();
+        assert_eq!(hist.coverage.iter().sum::<usize>(), 5);
+        assert_eq!(hist.coverage.len(), 5);
+
+        assert!(PanacusBuilder::new("test/cdbg.gfa")
+            .hist(CountType::All)
+            .is_err());
+    }
+
+    #[test]
+    fn test_panacus_builder_info() {
+        let info = PanacusBuilder::new("test/cdbg.gfa").info().unwrap();
+        assert_eq!(info.graph_info.node_count, 5);
+        assert_eq!(info.path_info.no_paths, 6);
+        assert!(info.group_info.is_none());
+
+        let info = PanacusBuilder::new("test/cdbg.gfa")
+            .grouping(Grouping::Sample)
+            .info()
+            .unwrap();
+        assert_eq!(info.group_info.unwrap().groups.len(), 4);
+
+        // node IDs of further graphs do not collide with those of the first one
+        let info = PanacusBuilder::new("test/cdbg.gfa")
+            .more_gfa_files(&["test/chrM_test.gfa"])
+            .info()
+            .unwrap();
+        assert_eq!(info.graph_info.node_count, 5 + 154);
+    }
+
+    #[test]
+    fn test_panacus_builder_pansn_delimiter() {
+        let gfa_file = std::env::temp_dir().join("panacus_test_builder_pansn.gfa");
+        std::fs::write(
+            &gfa_file,
+            "S\t1\tACGT\nS\t2\tAC\nP\ta|1|c\t1+,2+\t*\nP\ta|2|c\t1+\t*\nP\tb|1|c\t2+\t*\n",
+        )
+        .unwrap();
+        let groups = |delimiter: char| {
+            PanacusBuilder::new(gfa_file.to_str().unwrap())
+                .grouping(Grouping::Sample)
+                .pansn_delimiter(delimiter)
+                .info()
+                .unwrap()
+                .group_info
+                .unwrap()
+                .groups
+                .len()
+        };
+        assert_eq!(groups('|'), 2);
+        // each builder parses path names by its own delimiter
+        assert_eq!(groups('#'), 3);
+        std::fs::remove_file(&gfa_file).unwrap();
+    }
+
+    #[test]
+    fn test_panacus_builder_growth() {
+        let growth = PanacusBuilder::new("test/cdbg.gfa")
+            .growth(CountType::Node, "1,2", "0")
+            .unwrap();
+        assert_eq!(growth.values.len(), 2);
+        assert_eq!(growth.values[0].len(), 6);
+        // all 5 nodes are eventually covered by at least one path
+        assert!((growth.values[0][5] - 5.0).abs() < 1e-9);
+    }
+}
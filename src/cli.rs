@@ -7,16 +7,20 @@ use std::str::FromStr;
 
 /* external crate */
 use clap::{crate_version, Parser, Subcommand};
+use flate2::{write::GzEncoder, Compression};
 use rayon::prelude::*;
-use strum::VariantNames;
+use strum::{IntoEnumIterator, VariantNames};
 
 /* private use */
 use crate::abacus::*;
+use crate::api::{Grouping, PanacusBuilder};
 use crate::graph::*;
 use crate::hist::*;
 use crate::html::*;
 use crate::io::*;
+use crate::plot::{write_growth_comparison_chart, write_histgrowth_chart, write_private_chart};
 use crate::util::*;
+use crate::vcf::*;
 
 pub enum RequireThreshold {
     Absolute,
@@ -54,6 +58,71 @@ macro_rules! clap_enum_variants_no_all {
 struct Command {
     #[clap(subcommand)]
     cmd: Params,
+    #[clap(
+        long,
+        global = true,
+        help = "Delimiter of the sample, haplotype, and sequence components of path names following the PanSN naming scheme (sample#haplotype#sequence)",
+        default_value = "#",
+        value_parser = parse_pansn_delimiter
+    )]
+    pansn: char,
+    #[clap(
+        long,
+        global = true,
+        help = "Keep the output of each run in the given directory and reuse it in later runs with the same parameters and unchanged input files",
+        default_value = ""
+    )]
+    cache_dir: String,
+    #[clap(
+        long,
+        global = true,
+        help = "Write output (e.g., the HTML report) to the given file instead of stdout",
+        default_value = ""
+    )]
+    output_file: String,
+    #[clap(
+        long,
+        global = true,
+        help = "If the run fails, write the error category (config, parse, io, internal), its exit code (2, 3, 4, 5), and message as JSON object to the given file",
+        default_value = ""
+    )]
+    error_json: String,
+    #[clap(
+        long,
+        global = true,
+        help = "Load the third-party scripts of HTML reports (Bootstrap, Chart.js) from a CDN instead of embedding them"
+    )]
+    link_assets: bool,
+    #[clap(
+        long,
+        global = true,
+        help = "Write the scripts and stylesheets of HTML reports to the given directory and load them from there instead of embedding them; the directory is referenced as given, i.e., relative paths are relative to the location of the report",
+        default_value = "",
+        conflicts_with = "link_assets"
+    )]
+    split_assets: String,
+    #[clap(
+        long,
+        global = true,
+        help = "Override the embedded HTML report template (report_template.html), stylesheet (custom.css), logo (panacus-illustration-small.png), and symbols (symbols.svg) by the files of the same name in the given directory",
+        default_value = ""
+    )]
+    template_dir: String,
+    #[clap(
+        long,
+        global = true,
+        value_name = "FILE[=CAPTION]",
+        help = "Append the given tab-separated (or, if ending in .csv, comma-separated) table with header, e.g., of external QC results, to HTML reports, captioned by CAPTION or else the file name; can be given multiple times"
+    )]
+    report_table: Vec<String>,
+    #[clap(
+        short,
+        long,
+        global = true,
+        help = "Run in parallel on N threads (0 for number of CPU cores)",
+        default_value = "0"
+    )]
+    threads: usize,
 }
 
 #[derive(Subcommand, Debug)]
@@ -62,15 +131,20 @@ pub enum Params {
     Info {
         #[clap(
             index = 1,
-            help = "graph in GFA1 format, accepts also compressed (.gz) file",
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
             required = true
         )]
         gfa_file: String,
+        #[clap(
+            index = 2,
+            help = "further graphs in GFA1 format (e.g., of other chromosomes) that are analyzed together with the first one as a single pangenome; the node IDs of the i-th graph are prefixed by \"i:\""
+        )]
+        more_gfa_files: Vec<String>,
         #[clap(
             name = "subset",
             short,
             long,
-            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)",
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         positive_list: String,
@@ -78,7 +152,7 @@ pub enum Params {
             name = "exclude",
             short,
             long,
-            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file; all intersecting bp/node/edge will be exluded also in other paths not part of the given list",
+            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; all intersecting bp/node/edge will be exluded also in other paths not part of the given list; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         negative_list: String,
@@ -89,6 +163,12 @@ pub enum Params {
             default_value = ""
         )]
         groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
         #[clap(
             short = 'H',
             long,
@@ -101,32 +181,72 @@ pub enum Params {
             help = "Merge counts from paths belonging to same sample"
         )]
         groupby_sample: bool,
-        #[clap(short, long, help = "Choose output format: table (tab-separated-values) or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), json, or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
         output_format: OutputFormat,
         #[clap(
-            short,
             long,
-            help = "Run in parallel on N threads (0 for number of CPU cores)",
-            default_value = "0"
+            help = "Report only the given comma-separated list of columns (in the given order) of the output table",
+            default_value = ""
+        )]
+        columns: String,
+        #[clap(
+            long,
+            help = "Report only rows of the output table that satisfy all given comma-separated conditions of the form <column><op><value>, where <op> is one of ==, !=, <, <=, >, >=, e.g., \"total>=2\"",
+            default_value = ""
+        )]
+        filter: String,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(long, help = "Handling of zero-length nodes: count them as nodes of 0 bp, skip them, or fail", default_value = "count", ignore_case = true, value_parser = clap_enum_variants!(ZeroLengthNodes),)]
+        zero_length_nodes: ZeroLengthNodes,
+        #[clap(
+            long,
+            help = "Report also the node degree distribution, i.e., the number of nodes of each degree, in table and JSON output"
+        )]
+        degree_distribution: bool,
+        #[clap(
+            long,
+            help = "Binning of node length, path length, component size, and node degree distributions in HTML report, given as number of bins (e.g., 50) or as bin width prefixed by \"w\" (e.g., w1000)",
+            default_value = "50"
         )]
-        threads: usize,
+        bins: Binning,
     },
 
     #[clap(alias = "hg", about = "Run hist and growth. Return the growth curve")]
     Histgrowth {
         #[clap(
             index = 1,
-            help = "graph in GFA1 format, accepts also compressed (.gz) file",
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
             required = true
         )]
         gfa_file: String,
+        #[clap(
+            index = 2,
+            help = "further graphs in GFA1 format (e.g., of other chromosomes) that are analyzed together with the first one as a single pangenome; the node IDs of the i-th graph are prefixed by \"i:\""
+        )]
+        more_gfa_files: Vec<String>,
         #[clap(short, long, help = "Graph quantity to be counted", default_value = "node", ignore_case = true, value_parser = clap_enum_variants!(CountType),)]
         count: CountType,
         #[clap(
             name = "subset",
             short,
             long,
-            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)",
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         positive_list: String,
@@ -134,7 +254,7 @@ pub enum Params {
             name = "exclude",
             short,
             long,
-            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file",
+            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         negative_list: String,
@@ -145,6 +265,12 @@ pub enum Params {
             default_value = ""
         )]
         groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
         #[clap(
             short = 'H',
             long,
@@ -157,48 +283,154 @@ pub enum Params {
             help = "Merge counts from paths belonging to same sample"
         )]
         groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
         #[clap(
             short = 'l',
             long,
-            help = "Ignore all countables with a coverage lower than the specified threshold. The coverage of a countable corresponds to the number of path/walk that contain it. Repeated appearances of a countable in the same path/walk are counted as one. You can pass a comma-separated list of coverage thresholds, each one will produce a separated growth curve (e.g., --coverage 2,3). Use --quorum to set a threshold in conjunction with each coverage (e.g., --quorum 0.5,0.9)",
+            help = "Ignore all countables with a coverage lower than the specified threshold. The coverage of a countable corresponds to the number of path/walk that contain it. Repeated appearances of a countable in the same path/walk are counted as one. You can pass a comma-separated list of coverage thresholds, each one will produce a separated growth curve (e.g., --coverage 2,3). Use --quorum to set a threshold in conjunction with each coverage (e.g., --quorum 0.5,0.9). Thresholds can be named in the form <name>=<threshold> (e.g., --quorum core=1,shell=0.15), names are reported in output headers and legends. Ranges are given as <start>..<end> or <start>:<end>:<step> (e.g., --coverage 1..10 --quorum 0:1:0.1), in which case growth curves are produced for all combinations of coverage and quorum thresholds",
             default_value = "1"
         )]
         coverage: String,
         #[clap(
             short,
             long,
-            help = "Unlike the --coverage parameter, which specifies a minimum constant number of paths for all growth point m (1 <= m <= num_paths), --quorum adjust the threshold based on m. At each m, a countable is counted in the average growth if the countable is contained in at least floor(m*quorum) paths. Example: A quorum of 0.9 requires a countable to be in 90% of paths for each subset size m. At m=10, it must appear in at least 9 paths. At m=100, it must appear in at least 90 paths. A quorum of 1 (100%) requires presence in all paths of the subset, corresponding to the core. Default: 0, a countable counts if it is present in any path at each growth point. Specify multiple quorum values with a comma-separated list (e.g., --quorum 0.5,0.9). Use --coverage to set static path thresholds in conjunction with variable quorum percentages (e.g., --coverage 5,10).",
+            help = "Unlike the --coverage parameter, which specifies a minimum constant number of paths for all growth point m (1 <= m <= num_paths), --quorum adjust the threshold based on m. At each m, a countable is counted in the average growth if the countable is contained in at least floor(m*quorum) paths. Example: A quorum of 0.9 requires a countable to be in 90% of paths for each subset size m. At m=10, it must appear in at least 9 paths. At m=100, it must appear in at least 90 paths. A quorum of 1 (100%) requires presence in all paths of the subset, corresponding to the core. Default: 0, a countable counts if it is present in any path at each growth point. Specify multiple quorum values with a comma-separated list (e.g., --quorum 0.5,0.9), optionally named in the form <name>=<quorum> (e.g., --quorum core=1,soft-core=0.95,shell=0.15). Use --coverage to set static path thresholds in conjunction with variable quorum percentages (e.g., --coverage 5,10).",
             default_value = "0"
         )]
         quorum: String,
+        #[clap(
+            long,
+            help = "Remove countables that are covered by fewer than N groups before computing the histogram; removed countables are reported in the output header and left out of the histogram",
+            default_value = "0"
+        )]
+        min_groups: usize,
+        #[clap(
+            long,
+            help = "Remove nodes (and their bps) shorter than L bp before computing the histogram; applies to node and bp counts only",
+            default_value = "0"
+        )]
+        min_node_len: usize,
         #[clap(short = 'a', long, help = "Also include histogram in output")]
         hist: bool,
-        #[clap(short, long, help = "Choose output format: table (tab-separated-values) or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), json, html report, plot (SVG chart), or vega (Vega-Lite specification)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
         output_format: OutputFormat,
         #[clap(
-            short,
             long,
-            help = "Run in parallel on N threads (0 for number of CPU cores)",
+            help = "Treat edges that connect the same nodes but are traversed in an orientation not matching any L-line as identical to the known edge, instead of failing"
+        )]
+        canonicalize_edges: bool,
+        #[clap(long, help = "Handling of zero-length nodes: count them as nodes of 0 bp, skip them, or fail", default_value = "count", ignore_case = true, value_parser = clap_enum_variants!(ZeroLengthNodes),)]
+        zero_length_nodes: ZeroLengthNodes,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(
+            long,
+            help = "Compute the coverage histogram with a single counter per countable while streaming over the paths, greatly reducing memory usage; requires that paths of the same group appear consecutively in the GFA file and does not support subset or exclude lists"
+        )]
+        low_mem: bool,
+        #[clap(
+            long,
+            help = "Count the bps of nodes that are only partially covered by subset coordinates, or partially excluded, exactly by keeping track of the covered intervals of each path, at the cost of additional memory; by default, each group that covers any part of such a node is counted for all of its bps that are covered by any group"
+        )]
+        exact_bp: bool,
+        #[clap(
+            long,
+            help = "Binning of node length, path length, component size, and node degree distributions in HTML report, given as number of bins (e.g., 50) or as bin width prefixed by \"w\" (e.g., w1000)",
+            default_value = "50"
+        )]
+        bins: Binning,
+        #[clap(
+            long,
+            help = "Growth per added group (in countables) below which the pangenome is considered saturated, used to estimate the number of additional groups needed to reach saturation",
+            default_value = "1"
+        )]
+        saturation: f64,
+        #[clap(
+            long,
+            help = "Additionally write headline metrics (nodes, bp, groups, core %, openness) of the first count type to FILE, as SVG badge if FILE ends with \".svg\" and as JSON otherwise",
+            value_name = "FILE",
+            default_value = ""
+        )]
+        badge: String,
+        #[clap(
+            long,
+            help = "Apply coverage thresholds a priori, i.e., only count items whose coverage across all groups reaches the threshold, rather than the coverage within each subset of groups when a quorum is set"
+        )]
+        apriori: bool,
+        #[clap(
+            long,
+            help = "Additionally report the number of new countables added by each group (the increments of the growth curves), shown on a secondary axis of the growth plot in html reports"
+        )]
+        increments: bool,
+        #[clap(
+            long,
+            help = "Estimate growth by averaging over N randomly sampled orders of groups instead of calculating it exactly, which is faster for large numbers of groups (coverage thresholds are applied a priori)",
+            value_name = "N",
+            default_value = "0"
+        )]
+        samples: usize,
+        #[clap(
+            long,
+            help = "Additionally report the 2.5, 50, and 97.5 percentiles of the growth over N randomly sampled orders of groups (coverage thresholds are applied a priori)",
+            value_name = "N",
             default_value = "0"
         )]
-        threads: usize,
+        bootstrap: usize,
+        #[clap(
+            long,
+            help = "Seed of the random number generator used to sample orders of groups",
+            default_value = "0"
+        )]
+        seed: u64,
+        #[clap(
+            long,
+            help = "Additionally split the analysis by the sequence (e.g., chromosome) component of PanSN path names (sample#haplotype#sequence) and report one section per sequence next to the whole graph"
+        )]
+        by_sequence: bool,
+        #[clap(
+            long,
+            help = "Calculate one growth curve for each of the given subset lists (each in any of the formats accepted by --subset, e.g., exons.bed introns.bed intergenic.bed) and report them side by side and overlaid in a single plot",
+            value_name = "FILE",
+            num_args = 1..,
+            conflicts_with = "subset"
+        )]
+        subsets: Vec<String>,
     },
 
     #[clap(alias = "h", about = "Calculate coverage histogram")]
     Hist {
         #[clap(
             index = 1,
-            help = "graph in GFA1 format, accepts also compressed (.gz) file",
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
             required = true
         )]
         gfa_file: String,
+        #[clap(
+            index = 2,
+            help = "further graphs in GFA1 format (e.g., of other chromosomes) that are analyzed together with the first one as a single pangenome; the node IDs of the i-th graph are prefixed by \"i:\""
+        )]
+        more_gfa_files: Vec<String>,
         #[clap(short, long, help = "Graph quantity to be counted", default_value = "node", ignore_case = true, value_parser = clap_enum_variants!(CountType),)]
         count: CountType,
         #[clap(
             name = "subset",
             short,
             long,
-            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)",
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         positive_list: String,
@@ -206,7 +438,7 @@ pub enum Params {
             name = "exclude",
             short,
             long,
-            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file; all intersecting bp/node/edge will be exluded also in other paths not part of the given list",
+            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; all intersecting bp/node/edge will be exluded also in other paths not part of the given list; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         negative_list: String,
@@ -217,6 +449,12 @@ pub enum Params {
             default_value = ""
         )]
         groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
         #[clap(
             short = 'H',
             long,
@@ -229,50 +467,133 @@ pub enum Params {
             help = "Merge counts from paths belonging to same sample"
         )]
         groupby_sample: bool,
-        #[clap(short, long, help = "Choose output format: table (tab-separated-values) or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
-        output_format: OutputFormat,
         #[clap(
-            short,
             long,
-            help = "Run in parallel on N threads (0 for number of CPU cores)",
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            long,
+            help = "Remove countables that are covered by fewer than N groups before computing the histogram; removed countables are reported in the output header and left out of the histogram",
+            default_value = "0"
+        )]
+        min_groups: usize,
+        #[clap(
+            long,
+            help = "Remove nodes (and their bps) shorter than L bp before computing the histogram; applies to node and bp counts only",
             default_value = "0"
         )]
-        threads: usize,
+        min_node_len: usize,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), json, html report, plot (SVG chart), or vega (Vega-Lite specification)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+        #[clap(
+            long,
+            help = "Treat edges that connect the same nodes but are traversed in an orientation not matching any L-line as identical to the known edge, instead of failing"
+        )]
+        canonicalize_edges: bool,
+        #[clap(long, help = "Handling of zero-length nodes: count them as nodes of 0 bp, skip them, or fail", default_value = "count", ignore_case = true, value_parser = clap_enum_variants!(ZeroLengthNodes),)]
+        zero_length_nodes: ZeroLengthNodes,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(
+            long,
+            help = "Compute the coverage histogram with a single counter per countable while streaming over the paths, greatly reducing memory usage; requires that paths of the same group appear consecutively in the GFA file and does not support subset or exclude lists"
+        )]
+        low_mem: bool,
+        #[clap(
+            long,
+            help = "Count the bps of nodes that are only partially covered by subset coordinates, or partially excluded, exactly by keeping track of the covered intervals of each path, at the cost of additional memory; by default, each group that covers any part of such a node is counted for all of its bps that are covered by any group"
+        )]
+        exact_bp: bool,
+        #[clap(
+            long,
+            help = "Binning of node length, path length, component size, and node degree distributions in HTML report, given as number of bins (e.g., 50) or as bin width prefixed by \"w\" (e.g., w1000)",
+            default_value = "50"
+        )]
+        bins: Binning,
+        #[clap(
+            long,
+            help = "Additionally write headline metrics (nodes, bp, groups, core %, openness) of the first count type to FILE, as SVG badge if FILE ends with \".svg\" and as JSON otherwise",
+            value_name = "FILE",
+            default_value = ""
+        )]
+        badge: String,
+        #[clap(
+            long,
+            help = "Additionally report the edge histogram separately for each orientation of edges (>>, ><, <>, <<, leading from the node with the smaller ID), e.g., to expose inversion-rich regions; requires count type edge or all"
+        )]
+        edge_orientation: bool,
     },
 
     #[clap(alias = "g", about = "Calculate growth curve from coverage histogram")]
     Growth {
         #[clap(
             index = 1,
-            help = "Coverage histogram as tab-separated value (tsv) file",
+            help = "Coverage histogram as tab-separated value (tsv) file; if several files are given (e.g., histograms of different graphs), their growth curves are reported side by side and plotted together",
             required = true
         )]
-        hist_file: String,
+        hist_files: Vec<String>,
         #[clap(
             short = 'l',
             long,
-            help = "Ignore all countables with a coverage lower than the specified threshold. The coverage of a countable corresponds to the number of path/walk that contain it. Repeated appearances of a countable in the same path/walk are counted as one. You can pass a comma-separated list of coverage thresholds, each one will produce a separated growth curve (e.g., --coverage 2,3). Use --quorum to set a threshold in conjunction with each coverage (e.g., --quorum 0.5,0.9)",
+            help = "Ignore all countables with a coverage lower than the specified threshold. The coverage of a countable corresponds to the number of path/walk that contain it. Repeated appearances of a countable in the same path/walk are counted as one. You can pass a comma-separated list of coverage thresholds, each one will produce a separated growth curve (e.g., --coverage 2,3). Use --quorum to set a threshold in conjunction with each coverage (e.g., --quorum 0.5,0.9). Thresholds can be named in the form <name>=<threshold> (e.g., --quorum core=1,shell=0.15), names are reported in output headers and legends. Ranges are given as <start>..<end> or <start>:<end>:<step> (e.g., --coverage 1..10 --quorum 0:1:0.1), in which case growth curves are produced for all combinations of coverage and quorum thresholds",
             default_value = "1"
         )]
         coverage: String,
         #[clap(
             short,
             long,
-            help = "Unlike the --coverage parameter, which specifies a minimum constant number of paths for all growth point m (1 <= m <= num_paths), --quorum adjust the threshold based on m. At each m, a countable is counted in the average growth if the countable is contained in at least floor(m*quorum) paths. Example: A quorum of 0.9 requires a countable to be in 90% of paths for each subset size m. At m=10, it must appear in at least 9 paths. At m=100, it must appear in at least 90 paths. A quorum of 1 (100%) requires presence in all paths of the subset, corresponding to the core. Default: 0, a countable counts if it is present in any path at each growth point. Specify multiple quorum values with a comma-separated list (e.g., --quorum 0.5,0.9). Use --coverage to set static path thresholds in conjunction with variable quorum percentages (e.g., --coverage 5,10).",
+            help = "Unlike the --coverage parameter, which specifies a minimum constant number of paths for all growth point m (1 <= m <= num_paths), --quorum adjust the threshold based on m. At each m, a countable is counted in the average growth if the countable is contained in at least floor(m*quorum) paths. Example: A quorum of 0.9 requires a countable to be in 90% of paths for each subset size m. At m=10, it must appear in at least 9 paths. At m=100, it must appear in at least 90 paths. A quorum of 1 (100%) requires presence in all paths of the subset, corresponding to the core. Default: 0, a countable counts if it is present in any path at each growth point. Specify multiple quorum values with a comma-separated list (e.g., --quorum 0.5,0.9), optionally named in the form <name>=<quorum> (e.g., --quorum core=1,soft-core=0.95,shell=0.15). Use --coverage to set static path thresholds in conjunction with variable quorum percentages (e.g., --coverage 5,10).",
             default_value = "0"
         )]
         quorum: String,
         #[clap(short = 'a', long, help = "Also include histogram in output")]
         hist: bool,
-        #[clap(short, long, help = "Choose output format: table (tab-separated-values) or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), json, html report, plot (SVG chart), or vega (Vega-Lite specification)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
         output_format: OutputFormat,
         #[clap(
-            short,
             long,
-            help = "Run in parallel on N threads (0 for number of CPU cores)",
-            default_value = "0"
+            help = "Growth per added group (in countables) below which the pangenome is considered saturated, used to estimate the number of additional groups needed to reach saturation",
+            default_value = "1"
         )]
-        threads: usize,
+        saturation: f64,
+        #[clap(
+            long,
+            help = "Apply coverage thresholds a priori, i.e., only count items whose coverage across all groups reaches the threshold, rather than the coverage within each subset of groups when a quorum is set"
+        )]
+        apriori: bool,
+        #[clap(
+            long,
+            help = "Additionally report the number of new countables added by each group (the increments of the growth curves), shown on a secondary axis of the growth plot in html reports"
+        )]
+        increments: bool,
+    },
+
+    #[clap(
+        about = "Combine coverage histograms of several tables, e.g., of disjoint chunks (such as chromosomes) of a graph, by adding or subtracting them"
+    )]
+    HistOps {
+        #[clap(index = 1, help = "Operation: add (sum histograms of all tables) or subtract (subtract histograms of all further tables from those of the first one)", ignore_case = true, value_parser = clap_enum_variants!(HistOperation),)]
+        operation: HistOperation,
+        #[clap(
+            index = 2,
+            help = "Coverage histograms as tab-separated value (tsv) or JSON files, as reported by hist; histograms must be over the same groups",
+            required = true,
+            num_args = 2..
+        )]
+        hist_files: Vec<String>,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), or json", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
     },
 
     #[clap(
@@ -282,7 +603,7 @@ pub enum Params {
     OrderedHistgrowth {
         #[clap(
             index = 1,
-            help = "graph in GFA1 format, accepts also compressed (.gz) file",
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
             required = true
         )]
         gfa_file: String,
@@ -296,11 +617,16 @@ pub enum Params {
             default_value = ""
         )]
         order: String,
+        #[clap(
+            long,
+            help = "Keep only the first N groups of the order fixed (e.g., the reference and key assemblies) and report the growth averaged over all permutations of the remaining groups"
+        )]
+        anchors: Option<usize>,
         #[clap(
             name = "subset",
             short,
             long,
-            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file). If the \"order\" option is not used, the subset list will also indicate the order of paths/groups in the histogram.",
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\". If the \"order\" option is not used, the subset list will also indicate the order of paths/groups in the histogram. Several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"a.txt + b.txt\".",
             default_value = ""
         )]
         positive_list: String,
@@ -308,7 +634,7 @@ pub enum Params {
             name = "exclude",
             short,
             long,
-            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file",
+            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         negative_list: String,
@@ -319,6 +645,12 @@ pub enum Params {
             default_value = ""
         )]
         groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
         #[clap(
             short = 'H',
             long,
@@ -331,39 +663,71 @@ pub enum Params {
             help = "Merge counts from paths belonging to same sample"
         )]
         groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
         #[clap(
             short,
             long,
-            help = "List of quorum fractions of the form <level1>,<level2>,... Number of values must be one or match that of coverage setting",
+            help = "List of quorum fractions of the form <level1>,<level2>,... Number of values must be one or match that of coverage setting; ranges (<start>:<end>:<step>) produce all combinations with the coverage setting",
             default_value = "0"
         )]
         quorum: String,
         #[clap(
             short = 'l',
             long,
-            help = "List of absolute coverage thresholds of the form <level1>,<level2>,... Number of values must be one or match that of quorum setting",
+            help = "List of absolute coverage thresholds of the form <level1>,<level2>,... Number of values must be one or match that of quorum setting; ranges (<start>..<end>) produce all combinations with the quorum setting",
             default_value = "1"
         )]
         coverage: String,
-        #[clap(short, long, help = "Choose output format: table (tab-separated-values) or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
         output_format: OutputFormat,
         #[clap(
-            short,
             long,
-            help = "Run in parallel on N threads (0 for number of CPU cores)",
-            default_value = "0"
+            help = "Treat edges that connect the same nodes but are traversed in an orientation not matching any L-line as identical to the known edge, instead of failing"
+        )]
+        canonicalize_edges: bool,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(
+            long,
+            help = "Binning of node length, path length, component size, and node degree distributions in HTML report, given as number of bins (e.g., 50) or as bin width prefixed by \"w\" (e.g., w1000)",
+            default_value = "50"
+        )]
+        bins: Binning,
+        #[clap(
+            long,
+            help = "Growth per added group (in countables) below which the pangenome is considered saturated, used to estimate the number of additional groups needed to reach saturation",
+            default_value = "1"
         )]
-        threads: usize,
+        saturation: f64,
     },
 
-    #[clap(about = "Compute coverage table for count type")]
+    #[clap(alias = "abacus", about = "Compute coverage table for count type")]
     Table {
         #[clap(
             index = 1,
-            help = "graph in GFA1 format, accepts also compressed (.gz) file",
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
             required = true
         )]
         gfa_file: String,
+        #[clap(
+            index = 2,
+            help = "further graphs in GFA1 format (e.g., of other chromosomes) that are analyzed together with the first one as a single pangenome; the node IDs of the i-th graph are prefixed by \"i:\""
+        )]
+        more_gfa_files: Vec<String>,
         #[clap(short, long, help = "Graph quantity to be counted", default_value = "node", ignore_case = true, value_parser = clap_enum_variants_no_all!(CountType),)]
         count: CountType,
         #[clap(
@@ -373,11 +737,17 @@ pub enum Params {
             help = "Summarize by totaling presence/absence over all groups"
         )]
         total: bool,
+        #[clap(
+            long,
+            help = "Report the table in long format, listing one row per countable and group with non-zero coverage",
+            conflicts_with = "total"
+        )]
+        long: bool,
         #[clap(
             name = "subset",
             short,
             long,
-            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)",
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         positive_list: String,
@@ -385,7 +755,7 @@ pub enum Params {
             name = "exclude",
             short,
             long,
-            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file",
+            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
             default_value = ""
         )]
         negative_list: String,
@@ -396,6 +766,12 @@ pub enum Params {
             default_value = ""
         )]
         groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
         #[clap(
             short = 'H',
             long,
@@ -409,39 +785,933 @@ pub enum Params {
         )]
         groupby_sample: bool,
         #[clap(
-            short,
             long,
-            help = "Run in parallel on N threads (0 for number of CPU cores)",
-            default_value = "0"
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            long,
+            help = "Treat edges that connect the same nodes but are traversed in an orientation not matching any L-line as identical to the known edge, instead of failing"
+        )]
+        canonicalize_edges: bool,
+        #[clap(
+            long,
+            help = "Report only the given comma-separated list of columns (in the given order) of the output table",
+            default_value = ""
+        )]
+        columns: String,
+        #[clap(
+            long,
+            help = "Report only rows of the output table that satisfy all given comma-separated conditions of the form <column><op><value>, where <op> is one of ==, !=, <, <=, >, >=, e.g., \"total>=2\"",
+            default_value = ""
+        )]
+        filter: String,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), or arrow (Apache Arrow IPC stream, requires building with feature \"arrow\")", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+        #[clap(
+            long,
+            help = "Report how often the paths of each group visit a countable instead of its presence (1) or absence (0); for bp, values are multiplied by node length"
+        )]
+        counts: bool,
+        #[clap(long, help = "Compress output with gzip")]
+        gzip: bool,
+        #[clap(
+            long,
+            help = "Join labels (e.g., gene names) from given tab-separated two-column file of the form <node> <label> onto reported nodes; nodes without label are marked by \".\"",
+            default_value = ""
         )]
-        threads: usize,
+        annotation: String,
     },
-    //#[clap(
-    //    alias = "C",
-    //    about = "Calculate the histogram and growth of a Compacted de Bruijn Graph"
-    //)]
-    //Cdbg {
-    //    #[clap(
-    //        index = 1,
-    //        help = "graph in GFA1 format, accepts also compressed (.gz) file representing a compacted de Bruijn graph",
-    //        required = true
-    //    )]
-    //    gfa_file: String,
-    //    #[clap(short, long, help = "Value of k for cdBG", default_value = "")]
-    //    k: usize,
-    //    #[clap(
-    //        name = "subset",
-    //        short,
-    //        long,
-    //        help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file)",
-    //        default_value = ""
+    #[clap(
+        alias = "b",
+        about = "Compare structural out-degree of nodes with the out-degree supported by paths (branchiness)"
+    )]
+    Branching {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(
+            name = "subset",
+            short,
+            long,
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        positive_list: String,
+        #[clap(
+            name = "exclude",
+            short,
+            long,
+            help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        negative_list: String,
+        #[clap(
+            short,
+            long,
+            help = "Merge counts from paths by path-group mapping from given tab-separated two-column file",
+            default_value = ""
+        )]
+        groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            short = 'n',
+            long,
+            help = "Report structural out-degree, path-supported out-degree, and number of branch traversals for each node in forward (+) and reverse (-) orientation instead of the out-degree histogram"
+        )]
+        per_node: bool,
+        #[clap(
+            long,
+            help = "Treat edges that connect the same nodes but are traversed in an orientation not matching any L-line as identical to the known edge, instead of failing"
+        )]
+        canonicalize_edges: bool,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), or latex (booktabs table)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        about = "Detect simple bubbles and superbubbles and report their sizes and numbers of alleles"
+    )]
+    Bubbles {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(
+            long,
+            help = "Give up the search for a superbubble starting at a node after visiting the given number of nodes",
+            default_value = "1000"
+        )]
+        max_size: usize,
+        #[clap(
+            long,
+            help = "Report numbers of bubbles and their size and allele distributions instead of one row per bubble"
+        )]
+        summary: bool,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+        #[clap(
+            long,
+            help = "Binning of bubble size and allele distributions in HTML report, given as number of bins (e.g., 50) or as bin width prefixed by \"w\" (e.g., w1000)",
+            default_value = "50"
+        )]
+        bins: Binning,
+    },
+    #[clap(
+        alias = "d",
+        about = "Compare node coverage between two subsets of paths (e.g., cases vs. controls)"
+    )]
+    Delta {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(
+            name = "subset-a",
+            long,
+            help = "First subset of paths, given as list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            required = true
+        )]
+        positive_list: String,
+        #[clap(
+            long,
+            help = "Second subset of paths, given as list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            required = true
+        )]
+        subset_b: String,
+        #[clap(
+            name = "exclude",
+            short,
+            long,
+            help = "Exclude nodes that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        negative_list: String,
+        #[clap(
+            short,
+            long,
+            help = "Merge counts from paths by path-group mapping from given tab-separated two-column file",
+            default_value = ""
+        )]
+        groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            short,
+            long,
+            help = "Instead of per-node deltas, report differential regions (BED format) along the given reference path",
+            default_value = ""
+        )]
+        reference: String,
+        #[clap(
+            short = 'd',
+            long,
+            help = "Minimum absolute difference between the fractions of groups covering a node in either subset for the node to be considered differential",
+            default_value = "0.5"
+        )]
+        min_delta: f64,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), or latex (booktabs table)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        alias = "q",
+        about = "List nodes whose coverage satisfies the given query"
+    )]
+    Query {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(
+            short = 'l',
+            long,
+            help = "Comma-separated list of conditions on node coverage of the form <op><value>, where <op> is one of ==, !=, <, <=, >, >= and <value> is either an integer (number of groups) or a fraction of groups within [0,1], e.g., \">=0.95\" or \">1,<5\"",
+            required = true
+        )]
+        coverage: String,
+        #[clap(
+            name = "subset",
+            short,
+            long,
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        positive_list: String,
+        #[clap(
+            name = "exclude",
+            short,
+            long,
+            help = "Exclude nodes that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        negative_list: String,
+        #[clap(
+            short,
+            long,
+            help = "Merge counts from paths by path-group mapping from given tab-separated two-column file",
+            default_value = ""
+        )]
+        groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(short = 'L', long, help = "Also report the length of each node")]
+        lengths: bool,
+        #[clap(long, help = "Also report the sequence of each node")]
+        sequences: bool,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), or latex (booktabs table)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+        #[clap(
+            long,
+            help = "Join labels (e.g., gene names) from given tab-separated two-column file of the form <node> <label> onto reported nodes; nodes without label are marked by \".\"",
+            default_value = ""
+        )]
+        annotation: String,
+    },
+    #[clap(
+        about = "Count the groups that traverse each node only in forward, only in reverse, or in both orientations (e.g., to quantify inversion polymorphism)"
+    )]
+    Strand {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(
+            name = "subset",
+            short,
+            long,
+            help = "Produce counts by subsetting the graph to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        positive_list: String,
+        #[clap(
+            name = "exclude",
+            short,
+            long,
+            help = "Exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        negative_list: String,
+        #[clap(
+            short,
+            long,
+            help = "Merge counts from paths by path-group mapping from given tab-separated two-column file",
+            default_value = ""
+        )]
+        groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            short = 'n',
+            long,
+            help = "Report the numbers of groups traversing each node only in forward, only in reverse, and in both orientations for all nodes traversed in both orientations, instead of the histograms"
+        )]
+        per_node: bool,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), or latex (booktabs table)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        alias = "p",
+        about = "Report the numbers of countables shared by (intersection) and contained in either (union) of each pair of groups"
+    )]
+    Pairwise {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(short, long, help = "Graph quantity to be counted", default_value = "all", ignore_case = true, value_parser = clap_enum_variants!(CountType),)]
+        count: CountType,
+        #[clap(
+            name = "subset",
+            short,
+            long,
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        positive_list: String,
+        #[clap(
+            name = "exclude",
+            short,
+            long,
+            help = "Exclude nodes that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        negative_list: String,
+        #[clap(
+            short,
+            long,
+            help = "Merge counts from paths by path-group mapping from given tab-separated two-column file",
+            default_value = ""
+        )]
+        groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            short,
+            long,
+            help = "Report the numbers of countables shared by each pair of groups as square matrix (e.g., for building distance trees) instead of the table of intersections and unions"
+        )]
+        matrix: bool,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), or html report (heatmap of shared countables)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        about = "Report the numbers of countables (e.g., nodes or bp) private to each group, i.e., not covered by any other group"
+    )]
+    Private {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(short, long, help = "Graph quantity to be counted", default_value = "all", ignore_case = true, value_parser = clap_enum_variants!(CountType),)]
+        count: CountType,
+        #[clap(
+            name = "subset",
+            short,
+            long,
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        positive_list: String,
+        #[clap(
+            name = "exclude",
+            short,
+            long,
+            help = "Exclude nodes that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        negative_list: String,
+        #[clap(
+            short,
+            long,
+            help = "Merge counts from paths by path-group mapping from given tab-separated two-column file",
+            default_value = ""
+        )]
+        groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(
+            short,
+            long,
+            help = "Instead of the counts per group, report the regions (BED format) along the given reference path that consist of nodes private to its group",
+            default_value = ""
+        )]
+        reference: String,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), html report, plot (SVG chart), or vega (Vega-Lite specification)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        alias = "s",
+        about = "Calculate the pairwise similarity of groups and report it as square matrix"
+    )]
+    Similarity {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(short, long, help = "Graph quantity to be counted", default_value = "node", ignore_case = true, value_parser = clap_enum_variants!(CountType),)]
+        count: CountType,
+        #[clap(short, long, help = "Similarity measure: jaccard (intersection over union), cosine (intersection over geometric mean of sizes), or containment (fraction of row group contained in column group)", default_value = "jaccard", ignore_case = true, value_parser = clap_enum_variants!(SimilarityMetric),)]
+        metric: SimilarityMetric,
+        #[clap(long, help = "Cluster groups hierarchically by their distances (one minus similarity) using upgma (average linkage) or nj (neighbor joining), and report the tree in Newick format or, in the html report, as dendrogram", ignore_case = true, value_parser = clap_enum_variants!(ClusterMethod),)]
+        tree: Option<ClusterMethod>,
+        #[clap(
+            name = "subset",
+            short,
+            long,
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        positive_list: String,
+        #[clap(
+            name = "exclude",
+            short,
+            long,
+            help = "Exclude nodes that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        negative_list: String,
+        #[clap(
+            short,
+            long,
+            help = "Merge counts from paths by path-group mapping from given tab-separated two-column file",
+            default_value = ""
+        )]
+        groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        about = "Compare graphs (e.g., built with different parameters) by their info statistics and growth curves"
+    )]
+    Compare {
+        #[clap(
+            index = 1,
+            help = "graphs in GFA1 format, accept also compressed (.gz) files; differences are reported relative to the first graph",
+            required = true,
+            num_args = 2..
+        )]
+        gfa_files: Vec<String>,
+        #[clap(short, long, help = "Graph quantity to be counted", default_value = "node", ignore_case = true, value_parser = clap_enum_variants_no_all!(CountType),)]
+        count: CountType,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            short = 'l',
+            long,
+            help = "Ignore all countables with a coverage lower than the specified threshold; a comma-separated list of thresholds or ranges produces one growth curve each (see histgrowth)",
+            default_value = "1"
+        )]
+        coverage: String,
+        #[clap(
+            short,
+            long,
+            help = "Count a countable at growth point m only if it is contained in at least floor(m*quorum) groups; a comma-separated list of quorums or ranges produces one growth curve each (see histgrowth)",
+            default_value = "0"
+        )]
+        quorum: String,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), json, html report, plot (SVG chart), or vega (Vega-Lite specification)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        alias = "w",
+        about = "Calculate coverage histogram and growth in windows along a reference path"
+    )]
+    Windows {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(short, long, help = "Graph quantity to be counted, either node or bp", default_value = "node", ignore_case = true, value_parser = clap_enum_variants_no_all!(CountType),)]
+        count: CountType,
+        #[clap(
+            short,
+            long,
+            help = "Reference path along which windows are placed",
+            required = true
+        )]
+        reference: String,
+        #[clap(
+            short,
+            long,
+            help = "Size (in bp) of consecutive, non-overlapping windows along the reference path",
+            default_value = "100000"
+        )]
+        window_size: usize,
+        #[clap(
+            long,
+            help = "Instead of fixed-size windows, use the intervals of the reference path given in the 3-column BED file as windows",
+            default_value = ""
+        )]
+        bed: String,
+        #[clap(
+            name = "subset",
+            short,
+            long,
+            help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        positive_list: String,
+        #[clap(
+            name = "exclude",
+            short,
+            long,
+            help = "Exclude nodes that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+            default_value = ""
+        )]
+        negative_list: String,
+        #[clap(
+            short,
+            long,
+            help = "Merge counts from paths by path-group mapping from given tab-separated two-column file",
+            default_value = ""
+        )]
+        groupby: String,
+        #[clap(
+            long,
+            help = "Interpret the file given by --groupby as table with header, whose first column lists paths, and merge counts by the values of the column with the given name (e.g., population)",
+            default_value = ""
+        )]
+        groupby_column: String,
+        #[clap(
+            short = 'H',
+            long,
+            help = "Merge counts from paths belonging to same haplotype"
+        )]
+        groupby_haplotype: bool,
+        #[clap(
+            short = 'S',
+            long,
+            help = "Merge counts from paths belonging to same sample"
+        )]
+        groupby_sample: bool,
+        #[clap(
+            long,
+            help = "Merge counts from paths by clusters given in tab-separated two-column file of the form <representative> <member>, where members are path, haplotype (sample#haplotype), or sample identifiers; clusters are named by their representative",
+            default_value = ""
+        )]
+        groupby_cluster: String,
+        #[clap(
+            long,
+            help = "Rename groups according to given tab-separated two-column file of the form <old> <new>; if <old> is enclosed in slashes (/.../), it is interpreted as regular expression and <new> may refer to its capture groups ($1, $2, ...); subset, exclude, and order lists refer to groups by their new names",
+            default_value = ""
+        )]
+        rename: String,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), or html report", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        about = "Calculate node coverage histogram and growth of read alignments (GAF) to the graph, e.g., to assess how well short-read samples cover the pangenome"
+    )]
+    Gaf {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(
+            index = 2,
+            help = "Read alignments to the graph in GAF format, one file per sample, accepts also compressed (.gz) files; a node is covered by a sample if it is traversed by any of its alignments",
+            required = true,
+            num_args = 1..
+        )]
+        gaf_files: Vec<String>,
+        #[clap(
+            long,
+            help = "Ignore alignments with a mapping quality lower than the given threshold",
+            default_value = "0"
+        )]
+        min_mapq: u8,
+        #[clap(
+            short = 'l',
+            long,
+            help = "Ignore all nodes covered by fewer samples than the specified threshold; a comma-separated list of thresholds or ranges produces one growth curve each (see histgrowth)",
+            default_value = "1"
+        )]
+        coverage: String,
+        #[clap(
+            short,
+            long,
+            help = "Count a node at growth point m only if it is covered by at least floor(m*quorum) samples; a comma-separated list of quorums or ranges produces one growth curve each (see histgrowth)",
+            default_value = "0"
+        )]
+        quorum: String,
+        #[clap(short = 'a', long, help = "Also include histogram in output")]
+        hist: bool,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records and GAF alignments (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+        #[clap(short, long, help = "Choose output format: table (tab-separated-values), csv (comma-separated-values), latex (booktabs table), json, html report, plot (SVG chart), or vega (Vega-Lite specification)", default_value = "table", ignore_case = true, value_parser = clap_enum_variants!(OutputFormat),)]
+        output_format: OutputFormat,
+    },
+    #[clap(
+        about = "Synthesize walks of the haplotypes of all samples of a VCF along a reference path of a graph constructed from it (e.g., with vg construct) and report them as GFA W-lines"
+    )]
+    VcfPaths {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(
+            index = 2,
+            help = "Phased variant calls in VCF format, accepts also compressed (.gz) file; unphased genotypes are read in the order given (with a warning), missing alleles are taken to be those of the reference",
+            required = true
+        )]
+        vcf_file: String,
+        #[clap(
+            short,
+            long,
+            help = "Name of the reference path (or walk, given as sample#haplotype#sequence) along which variants are placed; it must traverse its nodes in forward orientation",
+            required = true
+        )]
+        reference: String,
+        #[clap(
+            long,
+            help = "Place only variants of the given chromosome (CHROM column of VCF); default: sequence name of reference path",
+            default_value = ""
+        )]
+        chrom: String,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+    },
+    #[clap(about = "Index nodes, edges, and paths of a GFA file to speed up subsequent runs")]
+    Index {
+        #[clap(
+            index = 1,
+            help = "graph in GFA1 format, accepts also compressed (.gz) file, or \"-\" to read it from standard input",
+            required = true
+        )]
+        gfa_file: String,
+        #[clap(
+            short,
+            long,
+            help = "Write index to given file instead of <gfa_file>.pidx",
+            default_value = ""
+        )]
+        output: String,
+        #[clap(
+            long,
+            help = "Skip truncated or corrupt GFA records (with a warning) instead of aborting"
+        )]
+        best_effort: bool,
+    },
+    //#[clap(
+    //    alias = "C",
+    //    about = "Calculate the histogram and growth of a Compacted de Bruijn Graph"
+    //)]
+    //Cdbg {
+    //    #[clap(
+    //        index = 1,
+    //        help = "graph in GFA1 format, accepts also compressed (.gz) file representing a compacted de Bruijn graph",
+    //        required = true
+    //    )]
+    //    gfa_file: String,
+    //    #[clap(short, long, help = "Value of k for cdBG", default_value = "")]
+    //    k: usize,
+    //    #[clap(
+    //        name = "subset",
+    //        short,
+    //        long,
+    //        help = "Produce counts by subsetting the graph to a given list of paths (1-column list) or path coordinates (3- or 12-column BED file), or to the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
+    //        default_value = ""
     //    )]
     //    positive_list: String,
     //    #[clap(
     //        name = "exclude",
     //        short,
     //        long,
-    //        help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file",
+    //        help = "Exclude bp/node/edge in growth count that intersect with paths (1-column list) or path coordinates (3- or 12-column BED-file) provided by the given file, or exclude the nodes listed in a file given as \"nodes:<file>\" or traversed by reference path coordinates given as \"liftover:<file>\"; several lists can be combined from left to right by union (+), intersection (&), and difference (-), e.g., \"repeats.bed - genes.bed\"",
     //        default_value = ""
     //    )]
     //    negative_list: String,
@@ -486,32 +1756,199 @@ impl Params {
     pub fn test_default_histgrowth() -> Self {
         Params::Histgrowth {
             gfa_file: String::new(),
+            more_gfa_files: Vec::new(),
             count: CountType::Node,
             positive_list: String::new(),
             negative_list: String::new(),
             groupby: String::new(),
+            groupby_column: String::new(),
             groupby_haplotype: false,
             groupby_sample: false,
+            groupby_cluster: String::new(),
+            rename: String::new(),
             coverage: "1".to_string(),
             quorum: "0".to_string(),
+            saturation: 1.0,
+            min_groups: 0,
+            min_node_len: 0,
+            zero_length_nodes: ZeroLengthNodes::Count,
+            bins: Binning::default(),
+            badge: String::new(),
+            apriori: false,
+            increments: false,
+            samples: 0,
+            bootstrap: 0,
+            seed: 0,
+            by_sequence: false,
+            subsets: Vec::new(),
             hist: false,
             output_format: OutputFormat::Table,
-            threads: 0,
+            canonicalize_edges: false,
+            best_effort: false,
+            low_mem: false,
+            exact_bp: false,
         }
     }
 }
 
-pub fn read_params() -> Params {
-    Command::parse().cmd
+// histograms of one view on the graph, along with the numbers of nodes and bps of each path, if
+// known, and the comments on countables removed by --min-groups and --min-node-len
+type ViewHists = (Vec<Hist>, Option<PathsLen>, Vec<String>);
+
+// comment on the countables removed by filter_artifacts, reported in the output metadata
+fn removed_countables_comment(
+    count: CountType,
+    (removed, removed_bps): (usize, usize),
+    min_groups: usize,
+    min_node_len: usize,
+) -> Option<String> {
+    if min_groups <= 1 && min_node_len == 0 {
+        return None;
+    }
+    Some(match count {
+        CountType::Edge => format!(
+            "removed {} countables of type {} with coverage < {}",
+            removed, count, min_groups
+        ),
+        _ => format!(
+            "removed {} countables of type {} ({} bp) with coverage < {} or length < {}",
+            removed, count, removed_bps, min_groups, min_node_len
+        ),
+    })
+}
+
+// returns the parameters of the command, the PanSN delimiter, and the cache directory, if any
+pub fn read_params() -> (Params, char, String, String, String) {
+    let command = Command::parse();
+    set_number_of_threads(command.threads);
+    set_report_assets(if !command.split_assets.is_empty() {
+        ReportAssets::Split(command.split_assets)
+    } else if command.link_assets {
+        ReportAssets::Cdn
+    } else {
+        ReportAssets::Embed
+    });
+    if !command.template_dir.is_empty() {
+        set_template_dir(&command.template_dir);
+    }
+    set_report_tables(
+        command
+            .report_table
+            .iter()
+            .map(|t| match t.split_once('=') {
+                Some((file, caption)) => (caption.to_string(), file.to_string()),
+                None => (t.clone(), t.clone()),
+            })
+            .collect(),
+    );
+    (
+        command.cmd,
+        command.pansn,
+        command.cache_dir,
+        command.output_file,
+        command.error_json,
+    )
+}
+
+fn parse_pansn_delimiter(s: &str) -> Result<char, String> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_punctuation() => Ok(c),
+        _ => Err(format!(
+            "PanSN delimiter must be a single punctuation character, but is \"{}\"",
+            s
+        )),
+    }
+}
+
+// thresholds can be given as ranges, either as "<start>..<end>" (integers, inclusive) or as
+// "<start>:<end>:<step>"
+pub fn is_threshold_range(threshold_str: &str) -> bool {
+    threshold_str.split(',').any(|el| {
+        let value = el.split_once('=').map_or(el, |(_, v)| v);
+        value.contains("..") || value.contains(':')
+    })
+}
+
+// expands the ranges of a comma-separated threshold list into single values, each together with
+// its name if given in the form <name>=<threshold>
+fn expand_threshold_ranges(threshold_str: &str) -> Result<Vec<(Option<String>, String)>, Error> {
+    let invalid = |el: &str| {
+        let msg = format!(
+            "invalid threshold range \"{}\" in \"{}\": expected <start>..<end> with integers or <start>:<end>:<step> with a positive step",
+            el, threshold_str
+        );
+        log::error!("{}", &msg);
+        Error::new(ErrorKind::InvalidData, msg)
+    };
+    let mut res = Vec::new();
+    for el in threshold_str.split(',') {
+        let (name, el) = match el.split_once('=') {
+            Some((name, value)) => {
+                if name.trim().is_empty() || is_threshold_range(value) {
+                    let msg = format!(
+                        "invalid named threshold \"{}\" in \"{}\": expected <name>=<threshold> with a non-empty name and a single threshold value",
+                        el, threshold_str
+                    );
+                    log::error!("{}", &msg);
+                    return Err(Error::new(ErrorKind::InvalidData, msg));
+                }
+                (Some(name.trim().to_string()), value.trim())
+            }
+            None => (None, el.trim()),
+        };
+        if let Some((start, end)) = el.split_once("..") {
+            let start = usize::from_str(start.trim()).map_err(|_| invalid(el))?;
+            let end = usize::from_str(end.trim()).map_err(|_| invalid(el))?;
+            if start > end {
+                return Err(invalid(el));
+            }
+            res.extend((start..=end).map(|x| (None, x.to_string())));
+        } else if el.contains(':') {
+            let bounds: Vec<f64> = el
+                .split(':')
+                .map(|x| f64::from_str(x.trim()))
+                .collect::<Result<_, _>>()
+                .map_err(|_| invalid(el))?;
+            if bounds.len() != 3 || bounds[2].is_nan() || bounds[2] <= 0.0 || bounds[0] > bounds[1]
+            {
+                return Err(invalid(el));
+            }
+            let (start, end, step) = (bounds[0], bounds[1], bounds[2]);
+            // steps are counted rather than accumulated to avoid rounding errors piling up, and the
+            // end is included if it is hit up to rounding
+            let n = ((end - start) / step + 1e-9).floor() as usize;
+            res.extend((0..=n).map(|k| {
+                let x = start + k as f64 * step;
+                (None, format!("{}", (x * 1e9).round() / 1e9))
+            }));
+        } else {
+            res.push((name, el.to_string()));
+        }
+    }
+    Ok(res)
 }
 
 pub fn parse_threshold_cli(
     threshold_str: &str,
     require: RequireThreshold,
 ) -> Result<Vec<Threshold>, Error> {
+    Ok(parse_named_threshold_cli(threshold_str, require)?
+        .into_iter()
+        .map(|(_, t)| t)
+        .collect())
+}
+
+pub type NamedThreshold = (Option<String>, Threshold);
+
+// like parse_threshold_cli, but also reports the names of thresholds given as <name>=<threshold>
+pub fn parse_named_threshold_cli(
+    threshold_str: &str,
+    require: RequireThreshold,
+) -> Result<Vec<NamedThreshold>, Error> {
     let mut thresholds = Vec::new();
 
-    for (i, el) in threshold_str.split(',').enumerate() {
+    for (i, (name, el)) in expand_threshold_ranges(threshold_str)?.iter().enumerate() {
         let rel_val = match f64::from_str(el.trim()) {
             Ok(t) => {
                 if (0.0..=1.0).contains(&t) {
@@ -537,7 +1974,8 @@ pub fn parse_threshold_cli(
             )),
         };
 
-        thresholds.push(
+        thresholds.push((
+            name.clone(),
             match require {
                 RequireThreshold::Absolute => Threshold::Absolute(usize::from_str(el.trim()).map_err(|_|
                     Error::new(
@@ -552,8 +1990,8 @@ pub fn parse_threshold_cli(
         } else {
             Threshold::Relative(rel_val?)
             }
-            }
-            );
+            },
+        ));
     }
     Ok(thresholds)
 }
@@ -561,73 +1999,343 @@ pub fn parse_threshold_cli(
 // set number of threads can be run only once, otherwise it throws an error of the
 // GlobalPoolAlreadyInitialized, which unfortunately is not pub therefore we cannot catch it.
 // https://github.com/rayon-rs/rayon/issues/878
-// We run this function in the main otherwise in the tests the second time we run the function
-// "run" it will crush
-pub fn set_number_of_threads(params: &Params) {
-    if let Params::Histgrowth { threads, .. }
-    | Params::Hist { threads, .. }
-    | Params::Info { threads, .. }
-    | Params::OrderedHistgrowth { threads, .. }
-    | Params::Table { threads, .. }
-    //| Params::Cdbg { threads, .. }
-    = params {
-        //if num_threads is 0 then the Rayon will select
-        //the number of threads to the core number automatically
-        rayon::ThreadPoolBuilder::new()
-            .num_threads(*threads)
-            .build_global()
-            .expect("Failed to initialize global thread pool");
-        log::info!("running panacus on {} threads", rayon::current_num_threads());
-    }
+// We run this function when reading the command line parameters, otherwise in the tests the
+// second time we run the function "run" it will crush
+fn set_number_of_threads(threads: usize) {
+    //if num_threads is 0 then the Rayon will select
+    //the number of threads to the core number automatically
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .expect("Failed to initialize global thread pool");
+    log::info!(
+        "running panacus on {} threads",
+        rayon::current_num_threads()
+    );
 }
 
-// make sure either group, groupby-sample, or groupby-haplotype is set
+// make sure either group, groupby-sample, groupby-haplotype, or groupby-cluster is set
 pub fn validate_single_groupby_option(
     groupby: &str,
     groupby_haplotype: bool,
     groupby_sample: bool,
+    groupby_cluster: &str,
 ) -> Result<(), Error> {
     let mut c = 0;
     c += (!groupby.is_empty()) as u8;
     c += (groupby_haplotype) as u8;
     c += (groupby_sample) as u8;
+    c += (!groupby_cluster.is_empty()) as u8;
     if c > 1 {
-        let msg = "At most one option of groupby, groupby-haplotype, and groupby-sample can be set at once, but at least two are given.";
+        let msg = "At most one option of groupby, groupby-haplotype, groupby-sample, and groupby-cluster can be set at once, but at least two are given.";
         log::error!("{}", &msg);
         return Err(Error::new(ErrorKind::InvalidInput, msg));
     }
     Ok(())
 }
 
-pub fn run<W: Write>(params: Params, out: &mut BufWriter<W>) -> Result<(), Error> {
+pub fn run<W: Write>(params: Params, pansn: char, out: &mut BufWriter<W>) -> Result<(), Error> {
+    if let Params::Table { gzip: true, .. } = params {
+        // compression applies to the final output, i.e., after any conversion to CSV
+        let mut gz = BufWriter::new(GzEncoder::new(&mut *out, Compression::default()));
+        let res = run_formatted(params, pansn, &mut gz);
+        remove_stdin_gfa();
+        res?;
+        gz.into_inner().map_err(|e| e.into_error())?.finish()?;
+        return Ok(());
+    }
+    let res = run_formatted(params, pansn, out);
+    remove_stdin_gfa();
+    res
+}
+
+// reuses the output of an earlier run with the same arguments and unchanged input files, if
+// there is one in the cache directory, and otherwise runs the command and caches its output
+pub fn run_cached<W: Write>(
+    params: Params,
+    pansn: char,
+    cache_dir: &str,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-") {
+        log::warn!("input is read from standard input, hence the output is not cached");
+        return run(params, pansn, out);
+    }
+    let cache_file = Path::new(cache_dir).join(format!("{:016x}", cache_key(&params, &args)));
+    if cache_file.exists() {
+        log::info!("reusing output cached in {}", cache_file.display());
+    } else {
+        fs::create_dir_all(cache_dir)?;
+        // output is only cached once the run completed successfully
+        let tmp_file = cache_file.with_extension("tmp");
+        let mut cached = BufWriter::new(fs::File::create(&tmp_file)?);
+        if let Err(e) = run(params, pansn, &mut cached).and_then(|_| cached.flush()) {
+            drop(cached);
+            fs::remove_file(&tmp_file)?;
+            return Err(e);
+        }
+        drop(cached);
+        fs::rename(&tmp_file, &cache_file)?;
+        log::info!("cached output in {}", cache_file.display());
+    }
+    std::io::copy(&mut fs::File::open(&cache_file)?, out)?;
+    Ok(())
+}
+
+// global options that name outputs of a run rather than inputs and hence do not affect its key
+const CACHE_IGNORED_OPTIONS: [&str; 3] = ["--cache-dir", "--output-file", "--error-json"];
+
+// key of a run: the parameters, all command line arguments (including global options that are not
+// part of the parameters, such as those of the HTML report), and the contents of any input file
+// or input directory named in the command line arguments
+fn cache_key(params: &Params, args: &[String]) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    crate_version!().hash(&mut hasher);
+    format!("{:?}", params).hash(&mut hasher);
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        let (option, value) = match arg.split_once('=') {
+            Some((option, value)) if option.starts_with("--") => (option, Some(value)),
+            _ => (&arg[..], None),
+        };
+        if CACHE_IGNORED_OPTIONS.contains(&option) {
+            if value.is_none() {
+                args.next();
+            }
+            continue;
+        }
+        arg.hash(&mut hasher);
+        // the directory of split assets is written, not read, by the run
+        if option == "--split-assets" {
+            if value.is_none() {
+                args.next().hash(&mut hasher);
+            }
+            continue;
+        }
+        let value = value.unwrap_or(arg);
+        // subset and exclude lists may combine several files by set operations
+        for value in std::iter::once(value).chain(value.split_whitespace()) {
+            let file_name = value
+                .strip_prefix("nodes:")
+                .or_else(|| value.strip_prefix("liftover:"))
+                .unwrap_or(value);
+            hash_input_content(Path::new(file_name), &mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+// hashes the content of the given file, or of all files within the given directory, if it exists
+fn hash_input_content<H: std::hash::Hasher>(path: &Path, hasher: &mut H) {
+    if path.is_dir() {
+        let mut entries: Vec<_> = match fs::read_dir(path) {
+            Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+            Err(_) => return,
+        };
+        entries.sort();
+        for entry in entries {
+            if entry.is_file() {
+                hasher.write(entry.to_string_lossy().as_bytes());
+                hash_input_content(&entry, hasher);
+            }
+        }
+    } else if let Ok(mut file) = fs::File::open(path) {
+        let mut buf = vec![0; 1 << 16];
+        while let Ok(n) = std::io::Read::read(&mut file, &mut buf) {
+            if n == 0 {
+                break;
+            }
+            hasher.write(&buf[..n]);
+        }
+    }
+}
+
+fn run_formatted<W: Write>(
+    params: Params,
+    pansn: char,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    let output_format = match params {
+        Params::Info { output_format, .. }
+        | Params::Histgrowth { output_format, .. }
+        | Params::Hist { output_format, .. }
+        | Params::Growth { output_format, .. }
+        | Params::HistOps { output_format, .. }
+        | Params::OrderedHistgrowth { output_format, .. }
+        | Params::Table { output_format, .. }
+        | Params::Branching { output_format, .. }
+        | Params::Bubbles { output_format, .. }
+        | Params::Delta { output_format, .. }
+        | Params::Query { output_format, .. }
+        | Params::Pairwise { output_format, .. }
+        | Params::Strand { output_format, .. }
+        | Params::Private { output_format, .. }
+        | Params::Similarity { output_format, .. }
+        | Params::Windows { output_format, .. }
+        | Params::Compare { output_format, .. }
+        | Params::Gaf { output_format, .. } => output_format,
+        Params::Index { .. } | Params::VcfPaths { .. } => OutputFormat::Table,
+    };
+    if let OutputFormat::Plot | OutputFormat::Vega = output_format {
+        if !matches!(
+            params,
+            Params::Hist { .. }
+                | Params::Histgrowth { .. }
+                | Params::Growth { .. }
+                | Params::Compare { .. }
+                | Params::Private { .. }
+                | Params::Gaf { .. }
+        ) {
+            let msg =
+                "plot and vega output are supported only by hist, histgrowth, growth, compare, private, and gaf";
+            log::error!("{}", msg);
+            return Err(Error::new(ErrorKind::Unsupported, msg));
+        }
+    }
+    if output_format == OutputFormat::Arrow && !matches!(params, Params::Table { .. }) {
+        let msg = "arrow output is supported only by table";
+        log::error!("{}", msg);
+        return Err(Error::new(ErrorKind::Unsupported, msg));
+    }
+    if output_format == OutputFormat::Csv {
+        // tables are reported as tab-separated values and converted on the fly
+        let mut csv = BufWriter::new(CsvWriter::new(&mut *out));
+        run_report(params, pansn, &mut csv)?;
+        csv.into_inner().map_err(|e| e.into_error())?.finish()?;
+        return Ok(());
+    }
+    if output_format == OutputFormat::Latex {
+        let mut latex = BufWriter::new(LatexWriter::new(&mut *out));
+        run_report(params, pansn, &mut latex)?;
+        latex.into_inner().map_err(|e| e.into_error())?.finish()?;
+        return Ok(());
+    }
+    run_report(params, pansn, out)
+}
+
+fn run_report<W: Write>(
+    mut params: Params,
+    pansn: char,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    // several graphs (e.g., one per chromosome) are read one after another as a single graph
+    let mut more_gfa_files = Vec::new();
+    if let Params::Histgrowth {
+        more_gfa_files: ref mut files,
+        ..
+    }
+    | Params::Hist {
+        more_gfa_files: ref mut files,
+        ..
+    }
+    | Params::Info {
+        more_gfa_files: ref mut files,
+        ..
+    }
+    | Params::Table {
+        more_gfa_files: ref mut files,
+        ..
+    } = params
+    {
+        for f in files.iter_mut() {
+            *f = stdin_gfa_file(f)?;
+        }
+        more_gfa_files = files.clone();
+    }
+
     if let Params::Histgrowth {
         ref groupby,
         groupby_haplotype,
         groupby_sample,
+        ref groupby_cluster,
         ..
     }
     | Params::Hist {
         ref groupby,
         groupby_haplotype,
         groupby_sample,
+        ref groupby_cluster,
         ..
     }
     | Params::Info {
         ref groupby,
         groupby_haplotype,
         groupby_sample,
+        ref groupby_cluster,
         ..
     }
     | Params::OrderedHistgrowth {
         ref groupby,
         groupby_haplotype,
         groupby_sample,
+        ref groupby_cluster,
         ..
     }
     | Params::Table {
         ref groupby,
         groupby_haplotype,
         groupby_sample,
+        ref groupby_cluster,
+        ..
+    }
+    | Params::Branching {
+        ref groupby,
+        groupby_haplotype,
+        groupby_sample,
+        ref groupby_cluster,
+        ..
+    }
+    | Params::Delta {
+        ref groupby,
+        groupby_haplotype,
+        groupby_sample,
+        ref groupby_cluster,
+        ..
+    }
+    | Params::Query {
+        ref groupby,
+        groupby_haplotype,
+        groupby_sample,
+        ref groupby_cluster,
+        ..
+    }
+    | Params::Pairwise {
+        ref groupby,
+        groupby_haplotype,
+        groupby_sample,
+        ref groupby_cluster,
+        ..
+    }
+    | Params::Strand {
+        ref groupby,
+        groupby_haplotype,
+        groupby_sample,
+        ref groupby_cluster,
+        ..
+    }
+    | Params::Private {
+        ref groupby,
+        groupby_haplotype,
+        groupby_sample,
+        ref groupby_cluster,
+        ..
+    }
+    | Params::Similarity {
+        ref groupby,
+        groupby_haplotype,
+        groupby_sample,
+        ref groupby_cluster,
+        ..
+    }
+    | Params::Windows {
+        ref groupby,
+        groupby_haplotype,
+        groupby_sample,
+        ref groupby_cluster,
         ..
     }
     //| Params::Cdbg {
@@ -638,43 +2346,447 @@ pub fn run<W: Write>(params: Params, out: &mut BufWriter<W>) -> Result<(), Error
     //}
     = params
     {
-        validate_single_groupby_option(groupby, groupby_haplotype, groupby_sample)?;
+        validate_single_groupby_option(
+            groupby,
+            groupby_haplotype,
+            groupby_sample,
+            groupby_cluster,
+        )?;
+    }
+
+    if let Params::Index { ref gfa_file, .. } = params {
+        // the index is stored next to the GFA file
+        if gfa_file == "-" {
+            let msg = "index cannot be built for a graph read from standard input";
+            log::error!("{}", msg);
+            return Err(Error::new(ErrorKind::InvalidInput, msg));
+        }
+    }
+
+    if let Params::Histgrowth {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Hist {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Info {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::OrderedHistgrowth {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Table {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Branching {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Bubbles {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Delta {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Query {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Pairwise {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Strand {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Private {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Similarity {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Windows {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Gaf {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::VcfPaths {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    }
+    | Params::Index {
+        ref mut gfa_file,
+        best_effort,
+        ..
+    } = params
+    {
+        *gfa_file = stdin_gfa_file(gfa_file)?;
+        // corrupt records are detected by the parsers themselves; only in best-effort mode, the
+        // GFA is scanned beforehand to report all records that will be skipped
+        set_best_effort(best_effort);
+        if best_effort {
+            let mut data = bufreader_from_gfa_files(&gfa_file_list(gfa_file, &more_gfa_files))?;
+            check_gfa(&mut data, true)?;
+        }
+        // malformed path and walk records are only detected during analysis
+        let gfa_file = gfa_file.clone();
+        return run_analysis(params, pansn, out).map_err(|e| {
+            match e.get_ref().map(|inner| inner.is::<GraphParseError>()) {
+                Some(true) => Error::new(e.kind(), format!("{}, {}", gfa_file, e)),
+                _ => e,
+            }
+        });
     }
+    run_analysis(params, pansn, out)
+}
 
+fn run_analysis<W: Write>(
+    params: Params,
+    pansn: char,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
     match params {
         Params::Histgrowth {
             ref gfa_file,
+            ref more_gfa_files,
             count,
             output_format,
+            min_groups,
+            min_node_len,
+            zero_length_nodes,
+            ref bins,
+            ref badge,
+            samples,
+            bootstrap,
+            seed,
+            by_sequence,
+            low_mem,
+            ref subsets,
             ..
         } => {
+            let gfa_files = gfa_file_list(gfa_file, more_gfa_files);
+            if !subsets.is_empty() && (by_sequence || samples > 0 || bootstrap > 0) {
+                let msg =
+                    "--subsets cannot be combined with --by-sequence, --samples, or --bootstrap";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
             //Hist
             let graph_aux = match output_format {
-                OutputFormat::Html => GraphAuxilliary::from_gfa(gfa_file, CountType::All),
-                _ => GraphAuxilliary::from_gfa(gfa_file, count),
+                OutputFormat::Html => {
+                    GraphAuxilliary::from_gfa_files(&gfa_files, CountType::All, pansn)?
+                }
+                _ => GraphAuxilliary::from_gfa_files(&gfa_files, count, pansn)?,
             };
+            graph_aux.check_zero_length_nodes(zero_length_nodes)?;
             let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
-            let abaci = AbacusByTotal::abaci_from_gfa(gfa_file, count, &graph_aux, &abacus_aux)?;
-            let mut hists = Vec::new();
-            for abacus in abaci {
-                hists.push(Hist::from_abacus(&abacus, Some(&graph_aux)));
+            // numbers of nodes and bps of each path are reported along with the histograms if they
+            // are determined in the same pass; all views are counted in a single pass over the GFA
+            let hists_from_gfa = |views: &[&AbacusAuxilliary]| -> Result<Vec<ViewHists>, Error> {
+                let views = AbacusByTotal::abaci_of_views_from_gfa(
+                    &gfa_files, count, &graph_aux, views, low_mem,
+                )?;
+                Ok(views
+                    .into_iter()
+                    .map(|(mut abaci, paths_len)| {
+                        let mut removed = Vec::new();
+                        for abacus in abaci.iter_mut() {
+                            if zero_length_nodes == ZeroLengthNodes::Skip {
+                                abacus.skip_zero_length_nodes(&graph_aux);
+                            }
+                            removed.extend(removed_countables_comment(
+                                abacus.count,
+                                abacus.filter_artifacts(min_groups, min_node_len, &graph_aux),
+                                min_groups,
+                                min_node_len,
+                            ));
+                        }
+                        let hists = abaci
+                            .iter()
+                            .map(|abacus| Hist::from_abacus(abacus, Some(&graph_aux)))
+                            .collect();
+                        (hists, paths_len, removed)
+                    })
+                    .collect())
+            };
+            if !subsets.is_empty() {
+                let hist_aux = HistAuxilliary::from_params(&params)?;
+                // the masks of all subsets are loaded up front and counted in a single pass
+                let subset_auxs = subsets
+                    .iter()
+                    .map(|subset| abacus_aux.with_subset(subset, &gfa_files, &graph_aux))
+                    .collect::<Result<Vec<AbacusAuxilliary>, Error>>()?;
+                log::info!(
+                    "calculating histograms and growth of {} subsets",
+                    subsets.len()
+                );
+                let views: Vec<&AbacusAuxilliary> = subset_auxs.iter().collect();
+                let mut comparison = Vec::new();
+                for ((subset, subset_aux), (hists, _, removed)) in subsets
+                    .iter()
+                    .zip(subset_auxs.iter())
+                    .zip(hists_from_gfa(&views)?)
+                {
+                    let mut metadata = metadata_comments();
+                    metadata.extend(subset_aux.bp_counting_mode(count));
+                    metadata.extend(removed);
+                    comparison.push(GrowthComparison {
+                        file: subset.clone(),
+                        results: HistGrowthResults {
+                            growths: hists
+                                .par_iter()
+                                .map(|h| {
+                                    GrowthResult::new(
+                                        h.count,
+                                        &h.calc_all_growths(&hist_aux),
+                                        &hist_aux,
+                                        metadata.clone(),
+                                    )
+                                })
+                                .collect(),
+                            hists: hists
+                                .iter()
+                                .map(|h| HistResult::new(h, metadata.clone()))
+                                .collect(),
+                            edge_orientations: Vec::new(),
+                        },
+                    });
+                }
+                log::info!("reporting growth comparison of {} subsets", subsets.len());
+                match output_format {
+                    OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                        write_growth_comparison_table(&comparison, out)?
+                    }
+                    OutputFormat::Json => write_json(&comparison, out)?,
+                    OutputFormat::Html => write_growth_comparison_html(&comparison, out)?,
+                    OutputFormat::Plot | OutputFormat::Vega => {
+                        write_growth_comparison_chart(&comparison, output_format, out)?
+                    }
+                    OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
+                }
+                return Ok(());
+            }
+            let mut seqids: Vec<&String> = Vec::new();
+            if by_sequence {
+                for p in graph_aux.path_segments.iter() {
+                    match &p.seqid {
+                        Some(s) if !seqids.contains(&s) => seqids.push(s),
+                        Some(_) => (),
+                        None => {
+                            let msg = format!(
+                                "splitting by sequence requires PanSN path names (sample#haplotype#sequence), but path {} has no sequence component",
+                                p
+                            );
+                            log::error!("{}", &msg);
+                            return Err(Error::new(ErrorKind::InvalidInput, msg));
+                        }
+                    }
+                }
+            }
+            let sequence_auxs: Vec<AbacusAuxilliary> = seqids
+                .iter()
+                .map(|seqid| abacus_aux.with_sequence(seqid, &graph_aux))
+                .collect();
+            let mut views = vec![&abacus_aux];
+            views.extend(sequence_auxs.iter());
+            let mut view_hists = hists_from_gfa(&views)?.into_iter();
+            let (hists, paths_len, removed) = view_hists.next().unwrap();
+            if !badge.is_empty() {
+                if let Some(h) = hists.first() {
+                    let bp = graph_aux.node_lens.iter().map(|l| *l as u64).sum();
+                    write_badge(&Badge::new(h, graph_aux.node_count, bp), badge)?;
+                }
             }
             //Growth
             let hist_aux = HistAuxilliary::from_params(&params)?;
             let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
-            let growths: Vec<(CountType, Vec<Vec<f64>>)> = hists
-                .par_iter()
-                .map(|h| (h.count, h.calc_all_growths(&hist_aux)))
-                .collect();
+            let mut sections: Vec<SequenceSection> = Vec::new();
+            for (seqid, (hists, _, _)) in seqids.iter().zip(view_hists) {
+                // sequences whose paths are all excluded have no groups
+                if hists.iter().any(|h| h.coverage.len() < 2) {
+                    continue;
+                }
+                log::info!("calculating growth of sequence {}", seqid);
+                let growths = hists
+                    .par_iter()
+                    .map(|h| (h.count, h.calc_all_growths(&hist_aux)))
+                    .collect();
+                sections.push((seqid.to_string(), hists, growths));
+            }
+            // sampling orders of groups requires to know which group covers which countable
+            let mut group_abaci = Vec::new();
+            if samples > 0 || bootstrap > 0 {
+                for h in hists.iter() {
+                    let mut data = bufreader_from_gfa_files(&gfa_files)?;
+                    let mut abacus = AbacusByGroup::from_gfa(
+                        &mut data,
+                        &abacus_aux,
+                        &graph_aux,
+                        h.count,
+                        false,
+                    )?;
+                    let min_node_len = match zero_length_nodes {
+                        ZeroLengthNodes::Skip if h.count != CountType::Edge => {
+                            usize::max(min_node_len, 1)
+                        }
+                        _ => min_node_len,
+                    };
+                    abacus.filter_artifacts(min_groups, min_node_len);
+                    group_abaci.push(abacus);
+                }
+            }
+            let growths: Vec<(CountType, Vec<Vec<f64>>)> = if samples > 0 {
+                group_abaci
+                    .iter()
+                    .map(|a| {
+                        (
+                            a.count,
+                            a.calc_sampled_mean_growths(&hist_aux, samples, seed),
+                        )
+                    })
+                    .collect()
+            } else {
+                hists
+                    .par_iter()
+                    .map(|h| (h.count, h.calc_all_growths(&hist_aux)))
+                    .collect()
+            };
+            let bootstraps: Vec<Option<GrowthBootstrap>> = if bootstrap > 0 {
+                group_abaci
+                    .iter()
+                    .map(|a| {
+                        let curves = a.calc_sampled_growths(&hist_aux, bootstrap, seed);
+                        Some(GrowthBootstrap::from_samples(&curves, seed))
+                    })
+                    .collect()
+            } else {
+                vec![None; hists.len()]
+            };
             log::info!("reporting histgrowth table");
+            let mut metadata = metadata_comments();
+            metadata.extend(abacus_aux.bp_counting_mode(count));
+            metadata.extend(removed);
+            let mut growth_metadata = metadata.clone();
+            if samples > 0 {
+                growth_metadata.push(format!(
+                    "growth is estimated from {} random orders of groups (seed {}), applying coverage thresholds a priori",
+                    samples, seed
+                ));
+            }
+            let results = HistGrowthResults {
+                hists: hists
+                    .iter()
+                    .map(|h| HistResult::new(h, metadata.clone()))
+                    .collect(),
+                growths: growths
+                    .iter()
+                    .zip(&bootstraps)
+                    .map(|((count, g), b)| GrowthResult {
+                        bootstrap: b.clone(),
+                        ..GrowthResult::new(*count, g, &hist_aux, growth_metadata.clone())
+                    })
+                    .collect(),
+
+                edge_orientations: Vec::new(),
+            };
+            if by_sequence && output_format != OutputFormat::Html {
+                // the whole graph is reported first, followed by one entry per sequence
+                let mut comparison = vec![GrowthComparison {
+                    file: filename.to_string(),
+                    results,
+                }];
+                for (seqid, hists, growths) in sections {
+                    comparison.push(GrowthComparison {
+                        file: seqid,
+                        results: HistGrowthResults {
+                            hists: hists
+                                .iter()
+                                .map(|h| HistResult::new(h, metadata.clone()))
+                                .collect(),
+                            growths: growths
+                                .iter()
+                                .map(|(count, g)| {
+                                    GrowthResult::new(*count, g, &hist_aux, metadata.clone())
+                                })
+                                .collect(),
+                            edge_orientations: Vec::new(),
+                        },
+                    });
+                }
+                log::info!("reporting growth of {} sequences", comparison.len() - 1);
+                match output_format {
+                    OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                        write_growth_comparison_table(&comparison, out)?
+                    }
+                    OutputFormat::Json => write_json(&comparison, out)?,
+                    OutputFormat::Plot | OutputFormat::Vega => {
+                        write_growth_comparison_chart(&comparison, output_format, out)?
+                    }
+                    OutputFormat::Html => unreachable!(),
+                    OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
+                }
+                return Ok(());
+            }
             match output_format {
-                OutputFormat::Table => write_histgrowth_table(&hists, &growths, &hist_aux, out)?,
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                    write_histgrowth_table(&results.hists, &results.growths, out)?
+                }
+                OutputFormat::Json => write_json(&results, out)?,
+                OutputFormat::Plot | OutputFormat::Vega => write_histgrowth_chart(
+                    &results.hists,
+                    &results.growths,
+                    &[],
+                    output_format,
+                    out,
+                )?,
                 OutputFormat::Html => {
-                    let mut data = bufreader_from_compressed_gfa(gfa_file);
-                    let (_, _, _, paths_len) =
-                        parse_gfa_paths_walks(&mut data, &abacus_aux, &graph_aux, &CountType::Node);
+                    let paths_len = match paths_len {
+                        Some(paths_len) => paths_len,
+                        None => {
+                            let mut data = bufreader_from_gfa_files(&gfa_files)?;
+                            parse_gfa_paths_walks(
+                                &mut data,
+                                &abacus_aux,
+                                &graph_aux,
+                                &CountType::Node,
+                            )?
+                            .3
+                        }
+                    };
 
-                    let info = graph_aux.info(&paths_len, &abacus_aux.groups, true);
+                    let mut info = graph_aux.info(&paths_len, &abacus_aux.groups, true);
+                    if zero_length_nodes == ZeroLengthNodes::Skip {
+                        info.graph_info.skip_zero_length_nodes();
+                    }
                     write_histgrowth_html(
                         &Some(hists),
                         &growths,
@@ -682,75 +2794,181 @@ pub fn run<W: Write>(params: Params, out: &mut BufWriter<W>) -> Result<(), Error
                         filename,
                         None,
                         Some(info),
+                        bins,
+                        &bootstraps,
+                        &sections,
                         out,
                     )?
                 }
+                OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
             };
         }
         Params::Hist {
             ref gfa_file,
+            ref more_gfa_files,
             count,
             output_format,
+            min_groups,
+            min_node_len,
+            zero_length_nodes,
+            ref bins,
+            ref badge,
+            low_mem,
+            edge_orientation,
             ..
         } => {
+            let gfa_files = gfa_file_list(gfa_file, more_gfa_files);
+            if edge_orientation && !matches!(count, CountType::Edge | CountType::All) {
+                let msg = "--edge-orientation requires count type edge or all";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::InvalidInput, msg));
+            }
             let graph_aux = match output_format {
-                OutputFormat::Html => GraphAuxilliary::from_gfa(gfa_file, CountType::All),
-                _ => GraphAuxilliary::from_gfa(gfa_file, count),
+                OutputFormat::Html => {
+                    GraphAuxilliary::from_gfa_files(&gfa_files, CountType::All, pansn)?
+                }
+                _ => GraphAuxilliary::from_gfa_files(&gfa_files, count, pansn)?,
             };
+            graph_aux.check_zero_length_nodes(zero_length_nodes)?;
             let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
-            let abaci = AbacusByTotal::abaci_from_gfa(gfa_file, count, &graph_aux, &abacus_aux)?;
+            let (mut abaci, paths_len) = AbacusByTotal::abaci_and_paths_len_from_gfa(
+                &gfa_files,
+                count,
+                &graph_aux,
+                &abacus_aux,
+                low_mem,
+            )?;
+            let mut removed = Vec::new();
+            for abacus in abaci.iter_mut() {
+                if zero_length_nodes == ZeroLengthNodes::Skip {
+                    abacus.skip_zero_length_nodes(&graph_aux);
+                }
+                removed.extend(removed_countables_comment(
+                    abacus.count,
+                    abacus.filter_artifacts(min_groups, min_node_len, &graph_aux),
+                    min_groups,
+                    min_node_len,
+                ));
+            }
             let mut hists = Vec::new();
+            let mut edge_orientations = Vec::new();
             for abacus in abaci {
                 hists.push(Hist::from_abacus(&abacus, Some(&graph_aux)));
+                if edge_orientation && abacus.count == CountType::Edge {
+                    edge_orientations = EdgeOrientationHist::from_abacus(&abacus, &graph_aux);
+                }
+            }
+            if !badge.is_empty() {
+                if let Some(h) = hists.first() {
+                    let bp = graph_aux.node_lens.iter().map(|l| *l as u64).sum();
+                    write_badge(&Badge::new(h, graph_aux.node_count, bp), badge)?;
+                }
             }
 
             let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
+            let mut metadata = metadata_comments();
+            metadata.extend(abacus_aux.bp_counting_mode(count));
+            metadata.extend(removed);
+            let results = HistGrowthResults {
+                hists: hists
+                    .iter()
+                    .map(|h| HistResult::new(h, metadata.clone()))
+                    .collect(),
+                growths: Vec::new(),
+                edge_orientations,
+            };
             match output_format {
-                OutputFormat::Table => write_hist_table(&hists, out)?,
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                    write_hist_table(&results.hists, &results.edge_orientations, out)?
+                }
+                OutputFormat::Json => write_json(&results, out)?,
+                OutputFormat::Plot | OutputFormat::Vega => write_histgrowth_chart(
+                    &results.hists,
+                    &[],
+                    &results.edge_orientations,
+                    output_format,
+                    out,
+                )?,
                 OutputFormat::Html => {
-                    let mut data = bufreader_from_compressed_gfa(gfa_file);
-                    let (_, _, _, paths_len) =
-                        parse_gfa_paths_walks(&mut data, &abacus_aux, &graph_aux, &CountType::Node);
+                    let paths_len = match paths_len {
+                        Some(paths_len) => paths_len,
+                        None => {
+                            let mut data = bufreader_from_gfa_files(&gfa_files)?;
+                            parse_gfa_paths_walks(
+                                &mut data,
+                                &abacus_aux,
+                                &graph_aux,
+                                &CountType::Node,
+                            )?
+                            .3
+                        }
+                    };
 
-                    let info = graph_aux.info(&paths_len, &abacus_aux.groups, true);
-                    write_hist_html(&hists, filename, Some(info), out)?
+                    let mut info = graph_aux.info(&paths_len, &abacus_aux.groups, true);
+                    if zero_length_nodes == ZeroLengthNodes::Skip {
+                        info.graph_info.skip_zero_length_nodes();
+                    }
+                    write_hist_html(
+                        &hists,
+                        &results.edge_orientations,
+                        filename,
+                        Some(info),
+                        bins,
+                        out,
+                    )?
                 }
+                OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
             };
         }
         Params::Growth {
-            ref hist_file,
+            ref hist_files,
             output_format,
             hist,
             ..
         } => {
             let hist_aux = HistAuxilliary::from_params(&params)?;
-            log::info!("loading coverage histogram from {}", hist_file);
-            let mut data = BufReader::new(fs::File::open(hist_file)?);
-            let (coverages, comments) = parse_hists(&mut data)?;
-            let hists: Vec<Hist> = coverages
-                .into_iter()
-                .map(|(count, coverage)| Hist { count, coverage })
-                .collect();
+            if hist_files.len() > 1 {
+                let mut comparison = Vec::new();
+                for hist_file in hist_files {
+                    let (hist_results, growths) = growths_from_hist_file(hist_file, &hist_aux)?;
+                    comparison.push(GrowthComparison {
+                        file: hist_file.clone(),
+                        results: growth_results(hist_results, &growths, hist, &hist_aux),
+                    });
+                }
+                log::info!("reporting growth comparison of {} files", hist_files.len());
+                match output_format {
+                    OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                        write_growth_comparison_table(&comparison, out)?
+                    }
+                    OutputFormat::Json => write_json(&comparison, out)?,
+                    OutputFormat::Html => write_growth_comparison_html(&comparison, out)?,
+                    OutputFormat::Plot | OutputFormat::Vega => {
+                        write_growth_comparison_chart(&comparison, output_format, out)?
+                    }
+                    OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
+                }
+                return Ok(());
+            }
+            let hist_file = &hist_files[0];
+            let (hist_results, growths) = growths_from_hist_file(hist_file, &hist_aux)?;
+            let hists: Vec<Hist> = hist_results.iter().map(|h| h.to_hist()).collect();
 
             let filename = Path::new(&hist_file).file_name().unwrap().to_str().unwrap();
-            let growths: Vec<(CountType, Vec<Vec<f64>>)> = hists
-                .par_iter()
-                .map(|h| (h.count, h.calc_all_growths(&hist_aux)))
-                .collect();
             log::info!("reporting histgrowth table");
+            let results = growth_results(hist_results, &growths, hist, &hist_aux);
             match output_format {
-                OutputFormat::Table => {
-                    for c in comments {
-                        out.write_all(&c[..])?;
-                        out.write_all(b"\n")?;
-                    }
-                    if hist {
-                        write_histgrowth_table(&hists, &growths, &hist_aux, out)?
-                    } else {
-                        let hists = Vec::new();
-                        write_histgrowth_table(&hists, &growths, &hist_aux, out)?
-                    }
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                    write_histgrowth_table(&results.hists, &results.growths, out)?
                 }
+                OutputFormat::Json => write_json(&results, out)?,
+                OutputFormat::Plot | OutputFormat::Vega => write_histgrowth_chart(
+                    &results.hists,
+                    &results.growths,
+                    &[],
+                    output_format,
+                    out,
+                )?,
                 OutputFormat::Html => {
                     if hist {
                         write_histgrowth_html(
@@ -760,96 +2978,736 @@ pub fn run<W: Write>(params: Params, out: &mut BufWriter<W>) -> Result<(), Error
                             filename,
                             None,
                             None,
+                            &Binning::default(),
+                            &[],
+                            &[],
                             out,
                         )?
                     } else {
                         write_histgrowth_html(
-                            &None, &growths, &hist_aux, filename, None, None, out,
+                            &None,
+                            &growths,
+                            &hist_aux,
+                            filename,
+                            None,
+                            None,
+                            &Binning::default(),
+                            &[],
+                            &[],
+                            out,
                         )?
                     }
                 }
+                OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
             };
         }
         Params::Info {
             ref gfa_file,
+            ref more_gfa_files,
             output_format,
+            ref columns,
+            ref filter,
+            ref bins,
+            degree_distribution,
+            zero_length_nodes,
             ..
         } => {
-            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::All);
+            let gfa_files = gfa_file_list(gfa_file, more_gfa_files);
+            let graph_aux = GraphAuxilliary::from_gfa_files(&gfa_files, CountType::All, pansn)?;
+            graph_aux.check_zero_length_nodes(zero_length_nodes)?;
 
             let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
-            let mut data = bufreader_from_compressed_gfa(gfa_file);
+            let mut data = bufreader_from_gfa_files(&gfa_files)?;
             let (_, _, _, paths_len) =
-                parse_gfa_paths_walks(&mut data, &abacus_aux, &graph_aux, &CountType::Node);
+                parse_gfa_paths_walks(&mut data, &abacus_aux, &graph_aux, &CountType::Node)?;
+            let (path_records, walk_records) =
+                count_path_walk_records(&mut bufreader_from_gfa_files(&gfa_files)?)?;
+            let info = |has_groups: bool| {
+                let mut info = graph_aux.info(&paths_len, &abacus_aux.groups, has_groups);
+                if zero_length_nodes == ZeroLengthNodes::Skip {
+                    info.graph_info.skip_zero_length_nodes();
+                }
+                info.sample_info.path_records = Some(path_records);
+                info.sample_info.walk_records = Some(walk_records);
+                if degree_distribution {
+                    info.graph_info.degree_distribution = graph_aux.degree_distribution();
+                }
+                info
+            };
 
             match output_format {
-                OutputFormat::Table => {
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
                     let has_groups = match params {
                         Params::Info {
                             ref groupby,
                             groupby_haplotype,
                             groupby_sample,
+                            ref groupby_cluster,
                             ..
-                        } => !groupby.is_empty() || groupby_haplotype || groupby_sample,
+                        } => {
+                            !groupby.is_empty()
+                                || groupby_haplotype
+                                || groupby_sample
+                                || !groupby_cluster.is_empty()
+                        }
                         _ => false,
                     };
-                    let info = graph_aux.info(&paths_len, &abacus_aux.groups, has_groups);
-                    write_info(info, out)?
+                    let mut filtered =
+                        BufWriter::new(TableFilter::new(&mut *out, columns, filter)?);
+                    write_info(info(has_groups), &mut filtered)?;
+                    filtered
+                        .into_inner()
+                        .map_err(|e| e.into_error())?
+                        .finish()?;
                 }
                 OutputFormat::Html => {
-                    let info = graph_aux.info(&paths_len, &abacus_aux.groups, true);
                     let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
-                    write_info_html(filename, info, out)?
+                    write_info_html(filename, info(true), bins, out)?
                 }
+                OutputFormat::Json => write_json(&info(true), out)?,
+                OutputFormat::Plot | OutputFormat::Vega => {
+                    unreachable!("plot and vega output are rejected for info")
+                }
+                OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
+            };
+        }
+        Params::HistOps {
+            operation,
+            ref hist_files,
+            output_format,
+        } => {
+            if output_format == OutputFormat::Html {
+                let msg = "hist-ops supports only table, CSV, and JSON output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            let tables = hist_files
+                .iter()
+                .map(|f| hist_results_from_file(f))
+                .collect::<Result<Vec<_>, Error>>()?;
+            log::info!(
+                "combining histograms of {} tables ({})",
+                tables.len(),
+                operation
+            );
+            let mut hists = combine_hist_results(&tables, operation)?;
+            // combined histograms inherit the metadata of the first table
+            let mut metadata = hists
+                .first()
+                .map(|h| h.metadata.clone())
+                .unwrap_or_default();
+            metadata.extend(metadata_comments());
+            for h in hists.iter_mut() {
+                h.metadata = metadata.clone();
+            }
+            let results = HistGrowthResults {
+                hists,
+                growths: Vec::new(),
+                edge_orientations: Vec::new(),
             };
+            match output_format {
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                    write_hist_table(&results.hists, &results.edge_orientations, out)?
+                }
+                OutputFormat::Json => write_json(&results, out)?,
+                OutputFormat::Html | OutputFormat::Plot | OutputFormat::Vega => {
+                    unreachable!("html, plot, and vega output are rejected for hist-ops")
+                }
+                OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
+            }
         }
         Params::OrderedHistgrowth {
             ref gfa_file,
             count,
             output_format,
+            ref bins,
+            anchors,
             ..
         } => {
             let graph_aux = match output_format {
-                OutputFormat::Html => GraphAuxilliary::from_gfa(gfa_file, CountType::All),
-                _ => GraphAuxilliary::from_gfa(gfa_file, count),
+                OutputFormat::Html => GraphAuxilliary::from_gfa(gfa_file, CountType::All, pansn)?,
+                _ => GraphAuxilliary::from_gfa(gfa_file, count, pansn)?,
             };
             let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
-            let mut data = bufreader_from_compressed_gfa(gfa_file);
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
             let abacus = AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, count, true)?;
             let hist_aux = HistAuxilliary::from_params(&params)?;
             match output_format {
-                OutputFormat::Table => {
-                    write_ordered_histgrowth_table(&abacus, &hist_aux, out)?;
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                    write_ordered_histgrowth_table(&abacus, &hist_aux, anchors, out)?;
                 }
                 OutputFormat::Html => {
-                    let mut data = bufreader_from_compressed_gfa(gfa_file);
-                    let (_, _, _, paths_len) =
-                        parse_gfa_paths_walks(&mut data, &abacus_aux, &graph_aux, &CountType::Node);
+                    let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+                    let (_, _, _, paths_len) = parse_gfa_paths_walks(
+                        &mut data,
+                        &abacus_aux,
+                        &graph_aux,
+                        &CountType::Node,
+                    )?;
 
                     let info = graph_aux.info(&paths_len, &abacus_aux.groups, true);
                     write_ordered_histgrowth_html(
                         &abacus,
                         &hist_aux,
+                        anchors,
                         gfa_file,
-                        count,
                         Some(info),
+                        bins,
                         out,
                     )?;
                 }
+                OutputFormat::Json => {
+                    let msg = "ordered-histgrowth does not support JSON output";
+                    log::error!("{}", msg);
+                    return Err(Error::new(ErrorKind::Unsupported, msg));
+                }
+                OutputFormat::Plot | OutputFormat::Vega => {
+                    unreachable!("plot and vega output are rejected for ordered-histgrowth")
+                }
+                OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
             }
         }
         Params::Table {
             ref gfa_file,
+            ref more_gfa_files,
             count,
             total,
+            long,
+            counts,
+            ref columns,
+            ref filter,
+            ref annotation,
+            output_format,
+            ..
+        } => {
+            let gfa_files = gfa_file_list(gfa_file, more_gfa_files);
+            if let OutputFormat::Html | OutputFormat::Json = output_format {
+                let msg = "table supports only table and CSV output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            if count == CountType::Edge && !annotation.is_empty() {
+                let msg = "node annotation cannot be joined onto an edge table";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            if output_format == OutputFormat::Arrow {
+                if cfg!(not(feature = "arrow")) {
+                    let msg = "arrow output requires panacus to be built with feature \"arrow\"";
+                    log::error!("{}", msg);
+                    return Err(Error::new(ErrorKind::Unsupported, msg));
+                }
+                if long {
+                    let msg = "arrow output does not support the long table format";
+                    log::error!("{}", msg);
+                    return Err(Error::new(ErrorKind::Unsupported, msg));
+                }
+                if !columns.is_empty() || !filter.is_empty() {
+                    let msg = "arrow output does not support selecting or filtering columns";
+                    log::error!("{}", msg);
+                    return Err(Error::new(ErrorKind::Unsupported, msg));
+                }
+            }
+            let annotation = load_annotation(annotation)?;
+            let graph_aux = GraphAuxilliary::from_gfa_files(&gfa_files, count, pansn)?;
+            let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
+            let mut data = bufreader_from_gfa_files(&gfa_files)?;
+            let abacus =
+                AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, count, counts)?;
+
+            #[cfg(feature = "arrow")]
+            if output_format == OutputFormat::Arrow {
+                return abacus.to_arrow(total, annotation.as_ref(), out);
+            }
+            let mut filtered = BufWriter::new(TableFilter::new(&mut *out, columns, filter)?);
+            if long {
+                abacus.to_tsv_long(annotation.as_ref(), &mut filtered)?;
+            } else {
+                abacus.to_tsv(total, annotation.as_ref(), &mut filtered)?;
+            }
+            filtered
+                .into_inner()
+                .map_err(|e| e.into_error())?
+                .finish()?;
+        }
+        Params::Branching {
+            ref gfa_file,
+            per_node,
+            output_format,
+            ..
+        } => {
+            if let OutputFormat::Html | OutputFormat::Json = output_format {
+                let msg = "branching supports only table and CSV output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Edge, pansn)?;
+            let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let abacus =
+                AbacusByTotal::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Edge)?;
+            let branching = abacus.node_branching(&graph_aux);
+            write_branching_table(&branching, per_node, &graph_aux, out)?;
+        }
+        Params::Bubbles {
+            ref gfa_file,
+            max_size,
+            summary,
+            output_format,
+            ref bins,
+            ..
+        } => {
+            if output_format == OutputFormat::Json {
+                let msg = "bubbles does not support JSON output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Edge, pansn)?;
+            let bubbles = graph_aux.bubbles(max_size);
+            match output_format {
+                OutputFormat::Html => {
+                    let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
+                    write_bubbles_html(&bubbles, &graph_aux, bins, filename, out)?
+                }
+                _ => write_bubbles_table(&bubbles, summary, &graph_aux, out)?,
+            }
+        }
+        Params::Delta {
+            ref gfa_file,
+            ref subset_b,
+            ref reference,
+            min_delta,
+            output_format,
+            ..
+        } => {
+            if let OutputFormat::Html | OutputFormat::Json = output_format {
+                let msg = "delta supports only table and CSV output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Node, pansn)?;
+            let abacus_aux_a = AbacusAuxilliary::from_params(&params, &graph_aux)?;
+            let abacus_aux_b =
+                abacus_aux_a.with_subset(subset_b, std::slice::from_ref(gfa_file), &graph_aux)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let abacus_a =
+                AbacusByTotal::from_gfa(&mut data, &abacus_aux_a, &graph_aux, CountType::Node)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let abacus_b =
+                AbacusByTotal::from_gfa(&mut data, &abacus_aux_b, &graph_aux, CountType::Node)?;
+            if reference.is_empty() {
+                write_delta_table(&abacus_a, &abacus_b, &graph_aux, out)?;
+            } else {
+                let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+                let path = parse_path_nodes(&mut data, reference, &graph_aux)?;
+                let delta = abacus_a.coverage_delta(&abacus_b);
+                write_delta_regions(&delta, &path, reference, min_delta, &graph_aux, out)?;
+            }
+        }
+        Params::Query {
+            ref gfa_file,
+            ref coverage,
+            lengths,
+            sequences,
+            ref annotation,
+            output_format,
+            ..
+        } => {
+            if let OutputFormat::Html | OutputFormat::Json = output_format {
+                let msg = "query supports only table and CSV output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            let conditions = parse_coverage_query(coverage)?;
+            let annotation = load_annotation(annotation)?;
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Node, pansn)?;
+            let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let abacus =
+                AbacusByTotal::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            write_query_nodes(
+                &mut data,
+                &abacus,
+                &conditions,
+                lengths,
+                sequences,
+                annotation.as_ref(),
+                &graph_aux,
+                out,
+            )?;
+        }
+        Params::Strand {
+            ref gfa_file,
+            per_node,
+            output_format,
+            ..
+        } => {
+            if let OutputFormat::Html | OutputFormat::Json = output_format {
+                let msg = "strand supports only table and CSV output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Node, pansn)?;
+            let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let strands = NodeStrands::from_gfa(&mut data, &abacus_aux, &graph_aux)?;
+            write_strand_table(&strands, per_node, &graph_aux, out)?;
+        }
+        Params::Private {
+            ref gfa_file,
+            count,
+            ref reference,
+            output_format,
             ..
         } => {
-            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, count);
+            if output_format == OutputFormat::Json {
+                let msg = "private does not support JSON output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            if !reference.is_empty()
+                && !matches!(
+                    output_format,
+                    OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex
+                )
+            {
+                let msg =
+                    "private regions along a reference path support only table and CSV output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            // private regions along the reference path are made up of nodes
+            let count = if reference.is_empty() {
+                count
+            } else {
+                CountType::Node
+            };
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, count, pansn)?;
+            let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
+            let mut abaci = Vec::new();
+            for count_type in CountType::iter() {
+                if count_type == CountType::All || (count != CountType::All && count != count_type)
+                {
+                    continue;
+                }
+                let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+                abaci.push(AbacusByGroup::from_gfa(
+                    &mut data,
+                    &abacus_aux,
+                    &graph_aux,
+                    count_type,
+                    false,
+                )?);
+            }
+            if !reference.is_empty() {
+                let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+                let path = parse_path_nodes(&mut data, reference, &graph_aux)?;
+                write_private_regions(&abaci[0], &path, reference, &graph_aux, out)?;
+                return Ok(());
+            }
+            match output_format {
+                OutputFormat::Html => {
+                    let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
+                    write_private_html(&abaci, filename, out)?
+                }
+                OutputFormat::Plot | OutputFormat::Vega => {
+                    write_private_chart(&abaci, output_format, out)?
+                }
+                _ => write_private_table(&abaci, out)?,
+            }
+        }
+        Params::Pairwise {
+            ref gfa_file,
+            count,
+            matrix,
+            output_format,
+            ..
+        } => {
+            if output_format == OutputFormat::Json {
+                let msg = "pairwise does not support JSON output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, count, pansn)?;
+            let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
+            let mut abaci = Vec::new();
+            for count_type in CountType::iter() {
+                if count_type == CountType::All || (count != CountType::All && count != count_type)
+                {
+                    continue;
+                }
+                let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+                abaci.push(AbacusByGroup::from_gfa(
+                    &mut data,
+                    &abacus_aux,
+                    &graph_aux,
+                    count_type,
+                    false,
+                )?);
+            }
+            match output_format {
+                OutputFormat::Html => {
+                    let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
+                    write_pairwise_html(&abaci, filename, out)?
+                }
+                _ if matrix => write_pairwise_matrix(&abaci, out)?,
+                _ => write_pairwise_table(&abaci, out)?,
+            }
+        }
+        Params::Similarity {
+            ref gfa_file,
+            count,
+            metric,
+            tree,
+            output_format,
+            ..
+        } => {
+            if output_format == OutputFormat::Json {
+                let msg = "similarity supports only table, CSV, and HTML output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            if tree.is_some()
+                && output_format != OutputFormat::Table
+                && output_format != OutputFormat::Html
+            {
+                let msg = "similarity trees support only table (Newick) and HTML output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            if count == CountType::All {
+                let msg = "similarity requires a single count type (node, bp, or edge)";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::InvalidInput, msg));
+            }
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, count, pansn)?;
+            let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let abacus = AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, count, false)?;
+            match output_format {
+                OutputFormat::Html => {
+                    let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
+                    write_similarity_html(&abacus, metric, tree, filename, out)?
+                }
+                _ => match tree {
+                    Some(method) => write_similarity_tree(&abacus, metric, method, out)?,
+                    None => write_similarity_table(&abacus, metric, out)?,
+                },
+            }
+        }
+        Params::Compare {
+            ref gfa_files,
+            count,
+            groupby_haplotype,
+            groupby_sample,
+            ref coverage,
+            ref quorum,
+            output_format,
+        } => {
+            validate_single_groupby_option("", groupby_haplotype, groupby_sample, "")?;
+            let grouping = if groupby_haplotype {
+                Grouping::Haplotype
+            } else if groupby_sample {
+                Grouping::Sample
+            } else {
+                Grouping::Path
+            };
+            let hist_aux = HistAuxilliary::parse_params(quorum, coverage, 1.0, false)?;
+            let mut comparison = Vec::new();
+            for gfa_file in gfa_files {
+                log::info!("computing info and growth of {}", gfa_file);
+                let builder = PanacusBuilder::new(gfa_file).grouping(grouping.clone());
+                let growths = vec![(count, builder.hist(count)?.calc_all_growths(&hist_aux))];
+                comparison.push(GraphComparison {
+                    info: builder.info()?,
+                    growth: GrowthComparison {
+                        file: gfa_file.clone(),
+                        results: growth_results(Vec::new(), &growths, false, &hist_aux),
+                    },
+                });
+            }
+            match output_format {
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                    write_graph_comparison_table(&comparison, out)?
+                }
+                OutputFormat::Json => write_json(&comparison, out)?,
+                OutputFormat::Html => write_graph_comparison_html(&comparison, out)?,
+                OutputFormat::Plot | OutputFormat::Vega => {
+                    let growths: Vec<GrowthComparison> =
+                        comparison.into_iter().map(|c| c.growth).collect();
+                    write_growth_comparison_chart(&growths, output_format, out)?
+                }
+                OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
+            }
+        }
+        Params::Windows {
+            ref gfa_file,
+            count,
+            ref reference,
+            window_size,
+            ref bed,
+            output_format,
+            ..
+        } => {
+            if output_format == OutputFormat::Json {
+                let msg = "windows supports only table, CSV, and HTML output";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::Unsupported, msg));
+            }
+            if count == CountType::Edge {
+                let msg = "windows supports only node and bp counts";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::InvalidInput, msg));
+            }
+            if bed.is_empty() && window_size == 0 {
+                let msg = "window size must be positive";
+                log::error!("{}", msg);
+                return Err(Error::new(ErrorKind::InvalidInput, msg));
+            }
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Node, pansn)?;
             let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux)?;
-            let mut data = BufReader::new(fs::File::open(gfa_file)?);
-            let abacus = AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, count, total)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let abacus =
+                AbacusByTotal::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let path = parse_path_nodes(&mut data, reference, &graph_aux)?;
+            let path_len = path
+                .iter()
+                .map(|(id, _)| graph_aux.node_len(id) as usize)
+                .sum();
+            let windows = if bed.is_empty() {
+                (0..path_len)
+                    .step_by(window_size)
+                    .map(|start| (start, usize::min(start + window_size, path_len)))
+                    .collect()
+            } else {
+                log::info!("loading windows from {}", bed);
+                let mut data = BufReader::new(fs::File::open(bed)?);
+                parse_bed_windows(&mut data, reference, path_len, pansn)
+            };
+            let hists = abacus.window_hists(&path, &windows, count, &graph_aux);
+            match output_format {
+                OutputFormat::Html => {
+                    let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
+                    write_windows_html(&hists, &windows, reference, filename, out)?
+                }
+                _ => write_windows_table(&hists, &windows, reference, out)?,
+            }
+        }
+        Params::Gaf {
+            ref gfa_file,
+            ref gaf_files,
+            min_mapq,
+            ref coverage,
+            ref quorum,
+            hist,
+            best_effort,
+            output_format,
+        } => {
+            let hist_aux = HistAuxilliary::parse_params(quorum, coverage, 1.0, false)?;
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Node, pansn)?;
+            // each GAF file is a sample
+            let covered = gaf_files
+                .par_iter()
+                .map(|gaf_file| {
+                    let mut data = bufreader_from_compressed_gaf(gaf_file)?;
+                    let (covered, n) =
+                        parse_gaf_nodes(&mut data, &graph_aux, min_mapq, best_effort)?;
+                    log::info!(
+                        "{} alignments of {} cover {} nodes",
+                        n,
+                        gaf_file,
+                        covered.iter().filter(|x| **x).count()
+                    );
+                    Ok(covered)
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+            let h = Hist::from_covered_nodes(&covered);
+            let growths = vec![(h.count, h.calc_all_growths(&hist_aux))];
+            let mut metadata = metadata_comments();
+            metadata.push(format!("minimum mapping quality {}", min_mapq));
 
-            abacus.to_tsv(total, out)?;
+            let filename = Path::new(&gfa_file).file_name().unwrap().to_str().unwrap();
+            log::info!("reporting histgrowth table");
+            let results = HistGrowthResults {
+                hists: if hist {
+                    vec![HistResult::new(&h, metadata.clone())]
+                } else {
+                    Vec::new()
+                },
+                growths: growths
+                    .iter()
+                    .map(|(count, g)| GrowthResult::new(*count, g, &hist_aux, metadata.clone()))
+                    .collect(),
+                edge_orientations: Vec::new(),
+            };
+            match output_format {
+                OutputFormat::Table | OutputFormat::Csv | OutputFormat::Latex => {
+                    write_histgrowth_table(&results.hists, &results.growths, out)?
+                }
+                OutputFormat::Json => write_json(&results, out)?,
+                OutputFormat::Plot | OutputFormat::Vega => write_histgrowth_chart(
+                    &results.hists,
+                    &results.growths,
+                    &[],
+                    output_format,
+                    out,
+                )?,
+                OutputFormat::Html => write_histgrowth_html(
+                    &hist.then(|| vec![h]),
+                    &growths,
+                    &hist_aux,
+                    filename,
+                    None,
+                    None,
+                    &Binning::default(),
+                    &[],
+                    &[],
+                    out,
+                )?,
+                OutputFormat::Arrow => unreachable!("arrow output is supported only by table"),
+            }
+        }
+        Params::VcfPaths {
+            ref gfa_file,
+            ref vcf_file,
+            ref reference,
+            ref chrom,
+            ..
+        } => {
+            let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Node, pansn)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let path = parse_path_nodes(&mut data, reference, &graph_aux)?;
+            let mut data = bufreader_from_compressed_gfa(gfa_file)?;
+            let graph = ReferenceGraph::from_gfa(&mut data, &path, &graph_aux)?;
+            // VCF positions are relative to the chromosome, which the reference path may cover
+            // only from some position onwards
+            let segment = PathSegment::from_str(reference, pansn);
+            let chrom = if chrom.is_empty() {
+                segment.seqid.as_ref().unwrap_or(&segment.sample)
+            } else {
+                chrom
+            };
+            let mut data = bufreader_from_compressed_vcf(vcf_file)?;
+            let haplotypes =
+                parse_vcf_haplotypes(&mut data, &graph, chrom, segment.start.unwrap_or(0))?;
+            write_vcf_walks(&haplotypes, &graph, chrom, out)?;
+        }
+        Params::Index {
+            ref gfa_file,
+            ref output,
+            ..
+        } => {
+            // the index always comprises edges so that it serves all count types
+            let graph_aux = GraphAuxilliary::parse_gfa(gfa_file, true, pansn)?;
+            let output = if output.is_empty() {
+                index_file_name(gfa_file)
+            } else {
+                output.clone()
+            };
+            log::info!("writing index to {}", output);
+            graph_aux.write_index(gfa_file, BufWriter::new(fs::File::create(&output)?))?;
         } //Params::Cdbg {
           //    ref gfa_file, k, ..
           //} => {
@@ -894,10 +3752,153 @@ pub fn run<W: Write>(params: Params, out: &mut BufWriter<W>) -> Result<(), Error
     Ok(())
 }
 
+// growth curves of each count type, given as columns as calculated by Hist::calc_all_growths
+type Growths = Vec<(CountType, Vec<Vec<f64>>)>;
+
+// loads the coverage histograms of the given file and calculates their growth curves
+fn hist_results_from_file(hist_file: &str) -> Result<Vec<HistResult>, Error> {
+    log::info!("loading coverage histogram from {}", hist_file);
+    let mut data = BufReader::new(fs::File::open(hist_file)?);
+    parse_hist_results(&mut data)
+}
+
+fn growths_from_hist_file(
+    hist_file: &str,
+    hist_aux: &HistAuxilliary,
+) -> Result<(Vec<HistResult>, Growths), Error> {
+    let hist_results = hist_results_from_file(hist_file)?;
+    let growths = hist_results
+        .par_iter()
+        .map(|h| (h.count, h.to_hist().calc_all_growths(hist_aux)))
+        .collect();
+    Ok((hist_results, growths))
+}
+
+fn growth_results(
+    hist_results: Vec<HistResult>,
+    growths: &[(CountType, Vec<Vec<f64>>)],
+    hist: bool,
+    hist_aux: &HistAuxilliary,
+) -> HistGrowthResults {
+    // growths inherit the metadata of the histograms they are calculated from
+    let mut metadata = hist_results
+        .first()
+        .map(|h| h.metadata.clone())
+        .unwrap_or_default();
+    metadata.extend(metadata_comments());
+    HistGrowthResults {
+        hists: if hist { hist_results } else { Vec::new() },
+        growths: growths
+            .iter()
+            .map(|(count, g)| GrowthResult::new(*count, g, hist_aux, metadata.clone()))
+            .collect(),
+        edge_orientations: Vec::new(),
+    }
+}
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cache_key() {
+        let params = Params::test_default_histgrowth();
+        let file_name = std::env::temp_dir().join("panacus_test_cache_key.txt");
+        let file_name = file_name.to_str().unwrap();
+        fs::write(file_name, "a#1#h1\n").unwrap();
+        let args = vec!["histgrowth".to_string(), format!("--subset={}", file_name)];
+        let key = cache_key(&params, &args);
+        assert_eq!(key, cache_key(&params, &args));
+        assert_ne!(
+            key,
+            cache_key(&Params::test_default_histgrowth(), &args[..1])
+        );
+
+        // changes of input files invalidate the key, even if size and timestamp are retained
+        let modified = fs::metadata(file_name).unwrap().modified().unwrap();
+        fs::write(file_name, "b#1#h1\n").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(file_name)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_ne!(key, cache_key(&params, &args));
+
+        // global options that are not part of the parameters are keyed...
+        let mut linked = args.clone();
+        linked.insert(0, "--link-assets".to_string());
+        assert_ne!(cache_key(&params, &args), cache_key(&params, &linked));
+        // ...except those naming outputs
+        let mut output = args.clone();
+        output.extend(["--output-file".to_string(), "report.html".to_string()]);
+        assert_eq!(cache_key(&params, &args), cache_key(&params, &output));
+        fs::remove_file(file_name).unwrap();
+    }
+
+    #[test]
+    fn test_histgrowth_subsets() {
+        let histgrowth = |positive_list: &str, subsets: &[&str]| -> Vec<GrowthComparison> {
+            let mut params = Params::test_default_histgrowth();
+            if let Params::Histgrowth {
+                gfa_file,
+                count,
+                positive_list: p,
+                subsets: s,
+                output_format,
+                ..
+            } = &mut params
+            {
+                *gfa_file = "test/cdbg.gfa".to_string();
+                *count = CountType::All;
+                *p = positive_list.to_string();
+                *s = subsets.iter().map(|x| x.to_string()).collect();
+                *output_format = OutputFormat::Json;
+            }
+            // the analysis needs more stack in debug builds than test threads have by default
+            let out = std::thread::Builder::new()
+                .stack_size(64 << 20)
+                .spawn(move || {
+                    let mut out = BufWriter::new(Vec::new());
+                    run_analysis(params, DEFAULT_PANSN_DELIMITER, &mut out).unwrap();
+                    out.into_inner().unwrap()
+                })
+                .unwrap()
+                .join()
+                .unwrap();
+            if subsets.is_empty() {
+                vec![GrowthComparison {
+                    file: positive_list.to_string(),
+                    results: serde_json::from_slice(&out).unwrap(),
+                }]
+            } else {
+                serde_json::from_slice(&out).unwrap()
+            }
+        };
+
+        let subsets = ["test/subset_a.txt", "test/subset_b.txt"];
+        let comparison = histgrowth("", &subsets);
+        assert_eq!(comparison.len(), 2);
+        // each subset is counted as if it were given on its own by --subset
+        for (c, subset) in comparison.iter().zip(subsets) {
+            let expected = histgrowth(subset, &[]).pop().unwrap();
+            assert_eq!(c.file, subset);
+            assert_eq!(c.results.hists.len(), 3);
+            for (h, e) in c.results.hists.iter().zip(&expected.results.hists) {
+                assert_eq!(h.count, e.count);
+                assert_eq!(h.coverage, e.coverage);
+            }
+            for (g, e) in c.results.growths.iter().zip(&expected.results.growths) {
+                assert_eq!(g.count, e.count);
+                // growth curves start with NaN, which never compares equal
+                assert_eq!(format!("{:?}", g.columns()), format!("{:?}", e.columns()));
+            }
+        }
+        assert_ne!(
+            comparison[0].results.hists[0].coverage,
+            comparison[1].results.hists[0].coverage
+        );
+    }
+
     #[test]
     fn test_parse_threshold_cli_relative_success() {
         let threshold_str = "0.2,0.5,0.9";
@@ -938,39 +3939,109 @@ mod tests {
         assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidData);
     }
 
+    #[test]
+    fn test_parse_threshold_cli_ranges() {
+        assert_eq!(
+            parse_threshold_cli("1..3,5", RequireThreshold::Absolute).unwrap(),
+            vec![
+                Threshold::Absolute(1),
+                Threshold::Absolute(2),
+                Threshold::Absolute(3),
+                Threshold::Absolute(5)
+            ]
+        );
+        let quorum = parse_threshold_cli("0:1:0.1", RequireThreshold::Relative).unwrap();
+        assert_eq!(quorum.len(), 11);
+        assert_eq!(quorum[3], Threshold::Relative(0.3));
+        assert_eq!(quorum[10], Threshold::Relative(1.0));
+        assert_eq!(
+            parse_threshold_cli("2:6:2", RequireThreshold::Absolute).unwrap(),
+            vec![
+                Threshold::Absolute(2),
+                Threshold::Absolute(4),
+                Threshold::Absolute(6)
+            ]
+        );
+        assert!(parse_threshold_cli("3..1", RequireThreshold::Absolute).is_err());
+        assert!(parse_threshold_cli("0:1:0", RequireThreshold::Relative).is_err());
+        assert!(parse_threshold_cli("0:1", RequireThreshold::Relative).is_err());
+        assert!(is_threshold_range("1,2..4") && !is_threshold_range("0.5,0.9"));
+    }
+
+    #[test]
+    fn test_parse_named_threshold_cli() {
+        assert_eq!(
+            parse_named_threshold_cli("core=1,0.5,shell = 0.15", RequireThreshold::Relative)
+                .unwrap(),
+            vec![
+                (Some("core".to_string()), Threshold::Relative(1.0)),
+                (None, Threshold::Relative(0.5)),
+                (Some("shell".to_string()), Threshold::Relative(0.15))
+            ]
+        );
+        assert_eq!(
+            parse_threshold_cli("core=1", RequireThreshold::Relative).unwrap(),
+            vec![Threshold::Relative(1.0)]
+        );
+        assert!(parse_named_threshold_cli("=1", RequireThreshold::Relative).is_err());
+        assert!(parse_named_threshold_cli("all=1..3", RequireThreshold::Absolute).is_err());
+        assert!(!is_threshold_range("core=1,shell=0.15"));
+    }
+
+    #[test]
+    fn test_parse_pansn_delimiter() {
+        assert_eq!(parse_pansn_delimiter("#"), Ok('#'));
+        assert_eq!(parse_pansn_delimiter("|"), Ok('|'));
+        assert!(parse_pansn_delimiter("").is_err());
+        assert!(parse_pansn_delimiter("##").is_err());
+        assert!(parse_pansn_delimiter("a").is_err());
+        assert!(parse_pansn_delimiter("\t").is_err());
+    }
+
     #[test]
     fn test_validate_single_groupby_option() {
         let test_cases = vec![
             // Valid cases
-            ("", false, false, true),       // None set
-            ("group1", false, false, true), // Only groupby is set
-            ("", true, false, true),        // Only groupby_haplotype is set
-            ("", false, true, true),        // Only groupby_sample is set
+            ("", false, false, "", true),       // None set
+            ("group1", false, false, "", true), // Only groupby is set
+            ("", true, false, "", true),        // Only groupby_haplotype is set
+            ("", false, true, "", true),        // Only groupby_sample is set
+            ("", false, false, "clust", true),  // Only groupby_cluster is set
             // Invalid cases
-            ("group1", true, false, false), // groupby and groupby_haplotype set
-            ("group1", false, true, false), // groupby and groupby_sample set
-            ("", true, true, false),        // groupby_haplotype and groupby_sample set
-            ("group1", true, true, false),  // All options set
+            ("group1", true, false, "", false), // groupby and groupby_haplotype set
+            ("group1", false, true, "", false), // groupby and groupby_sample set
+            ("", true, true, "", false),        // groupby_haplotype and groupby_sample set
+            ("group1", false, false, "clust", false), // groupby and groupby_cluster set
+            ("", false, true, "clust", false),  // groupby_sample and groupby_cluster set
+            ("group1", true, true, "clust", false), // All options set
         ];
 
-        for (test_groupby, test_groupby_haplotype, test_groupby_sample, should_pass) in test_cases {
+        for (
+            test_groupby,
+            test_groupby_haplotype,
+            test_groupby_sample,
+            test_groupby_cluster,
+            should_pass,
+        ) in test_cases
+        {
             //let mut params = Params::test_default_histgrowth();
             let result = validate_single_groupby_option(
                 test_groupby,
                 test_groupby_haplotype,
                 test_groupby_sample,
+                test_groupby_cluster,
             );
             if should_pass {
                 assert!(
                     result.is_ok(),
-                    "Expected OK, but got error for input: groupby = '{}', groupby_haplotype = {}, groupby_sample = {}",
-                    test_groupby, test_groupby_haplotype, test_groupby_sample
+                    "Expected OK, but got error for input: groupby = '{}', groupby_haplotype = {}, groupby_sample = {}, groupby_cluster = '{}'",
+                    test_groupby, test_groupby_haplotype, test_groupby_sample, test_groupby_cluster
                 );
             } else {
                 assert!(
                     result.is_err(),
-                    "Expected error, but got OK for input: groupby = '{}', groupby_haplotype = {}, groupby_sample = {}",
-                    test_groupby, test_groupby_haplotype, test_groupby_sample
+                    "Expected error, but got OK for input: groupby = '{}', groupby_haplotype = {}, groupby_sample = {}, groupby_cluster = '{}'",
+                    test_groupby, test_groupby_haplotype, test_groupby_sample, test_groupby_cluster
                 );
             }
         }
@@ -0,0 +1,279 @@
+// Hierarchical clustering of groups by their pairwise distances. Like the growth module, the
+// functions here operate on plain matrices and labels only, so that trees can be built for any
+// group similarity computed elsewhere.
+
+use crate::util::ClusterMethod;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode {
+    // only leaves carry a label
+    pub label: Option<String>,
+    // children of the node together with the lengths of the branches leading to them
+    pub children: Vec<(usize, f64)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tree {
+    pub nodes: Vec<TreeNode>,
+    pub root: usize,
+}
+
+// turns a (possibly asymmetric) similarity matrix with values between 0 and 1 into a symmetric
+// distance matrix by averaging both directions
+pub fn distances_from_similarities(similarities: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = similarities.len();
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    if i == j {
+                        0.0
+                    } else {
+                        1.0 - (similarities[i][j] + similarities[j][i]) / 2.0
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl Tree {
+    pub fn build(method: ClusterMethod, labels: &[String], dist: &[Vec<f64>]) -> Self {
+        match method {
+            ClusterMethod::Upgma => Self::upgma(labels, dist),
+            ClusterMethod::Nj => Self::neighbor_joining(labels, dist),
+        }
+    }
+
+    fn with_leaves(labels: &[String]) -> Self {
+        let mut nodes: Vec<TreeNode> = labels
+            .iter()
+            .map(|l| TreeNode {
+                label: Some(l.clone()),
+                children: Vec::new(),
+            })
+            .collect();
+        if nodes.is_empty() {
+            nodes.push(TreeNode {
+                label: None,
+                children: Vec::new(),
+            });
+        }
+        Tree { nodes, root: 0 }
+    }
+
+    fn join(&mut self, children: Vec<(usize, f64)>) -> usize {
+        self.nodes.push(TreeNode {
+            label: None,
+            children: children.into_iter().map(|(c, l)| (c, l.max(0.0))).collect(),
+        });
+        self.nodes.len() - 1
+    }
+
+    // pair of active clusters (i < j) that minimizes the given score; ties are broken by order
+    fn closest_pair<F: Fn(usize, usize) -> f64>(m: usize, score: F) -> (usize, usize) {
+        let mut best = (0, 1);
+        let mut best_score = f64::INFINITY;
+        for i in 0..m {
+            for j in i + 1..m {
+                let s = score(i, j);
+                if s < best_score {
+                    best_score = s;
+                    best = (i, j);
+                }
+            }
+        }
+        best
+    }
+
+    // removes the clusters at positions i < j from the distance matrix and appends a new one with
+    // the given distances to the remaining clusters
+    fn merge_rows(d: &mut Vec<Vec<f64>>, i: usize, j: usize, mut new_row: Vec<f64>) {
+        for row in d.iter_mut() {
+            row.remove(j);
+            row.remove(i);
+        }
+        d.remove(j);
+        d.remove(i);
+        new_row.remove(j);
+        new_row.remove(i);
+        for (row, x) in d.iter_mut().zip(new_row.iter()) {
+            row.push(*x);
+        }
+        new_row.push(0.0);
+        d.push(new_row);
+    }
+
+    // rooted, ultrametric tree built by average linkage
+    pub fn upgma(labels: &[String], dist: &[Vec<f64>]) -> Self {
+        let mut tree = Self::with_leaves(labels);
+        let mut d: Vec<Vec<f64>> = dist.to_vec();
+        let mut active: Vec<usize> = (0..labels.len()).collect();
+        let mut sizes: Vec<f64> = vec![1.0; labels.len()];
+        let mut heights: Vec<f64> = vec![0.0; labels.len()];
+
+        while active.len() > 1 {
+            let (i, j) = Self::closest_pair(active.len(), |i, j| d[i][j]);
+            let h = d[i][j] / 2.0;
+            let (ni, nj) = (active[i], active[j]);
+            let u = tree.join(vec![(ni, h - heights[i]), (nj, h - heights[j])]);
+            let (si, sj) = (sizes[i], sizes[j]);
+            let new_row: Vec<f64> = (0..active.len())
+                .map(|k| (si * d[i][k] + sj * d[j][k]) / (si + sj))
+                .collect();
+            Self::merge_rows(&mut d, i, j, new_row);
+            sizes.remove(j);
+            sizes.remove(i);
+            heights.remove(j);
+            heights.remove(i);
+            active.remove(j);
+            active.remove(i);
+            active.push(u);
+            sizes.push(si + sj);
+            heights.push(h);
+        }
+        if let Some(&root) = active.first() {
+            tree.root = root;
+        }
+        tree
+    }
+
+    // neighbor-joining tree, rooted at the final join of the last two clusters; negative branch
+    // lengths are set to zero
+    pub fn neighbor_joining(labels: &[String], dist: &[Vec<f64>]) -> Self {
+        let mut tree = Self::with_leaves(labels);
+        let mut d: Vec<Vec<f64>> = dist.to_vec();
+        let mut active: Vec<usize> = (0..labels.len()).collect();
+
+        while active.len() > 2 {
+            let m = active.len();
+            let r: Vec<f64> = d.iter().map(|row| row.iter().sum()).collect();
+            let (i, j) = Self::closest_pair(m, |i, j| (m - 2) as f64 * d[i][j] - r[i] - r[j]);
+            let li = d[i][j] / 2.0 + (r[i] - r[j]) / (2.0 * (m - 2) as f64);
+            let lj = d[i][j] - li;
+            let u = tree.join(vec![(active[i], li), (active[j], lj)]);
+            let new_row: Vec<f64> = (0..m)
+                .map(|k| (d[i][k] + d[j][k] - d[i][j]) / 2.0)
+                .collect();
+            Self::merge_rows(&mut d, i, j, new_row);
+            active.remove(j);
+            active.remove(i);
+            active.push(u);
+        }
+        if active.len() == 2 {
+            let l = d[0][1] / 2.0;
+            tree.root = tree.join(vec![(active[0], l), (active[1], l)]);
+        } else if let Some(&root) = active.first() {
+            tree.root = root;
+        }
+        tree
+    }
+
+    // leaves in the order in which they are visited from the root
+    pub fn leaf_order(&self) -> Vec<usize> {
+        let mut res = Vec::new();
+        let mut stack = vec![self.root];
+        while let Some(v) = stack.pop() {
+            if self.nodes[v].children.is_empty() {
+                if self.nodes[v].label.is_some() {
+                    res.push(v);
+                }
+            } else {
+                stack.extend(self.nodes[v].children.iter().rev().map(|(c, _)| *c));
+            }
+        }
+        res
+    }
+
+    pub fn to_newick(&self) -> String {
+        let mut res = String::new();
+        self.write_newick(self.root, &mut res);
+        res.push(';');
+        res
+    }
+
+    fn write_newick(&self, v: usize, res: &mut String) {
+        let node = &self.nodes[v];
+        if node.children.is_empty() {
+            if let Some(label) = &node.label {
+                res.push_str(&newick_label(label));
+            }
+            return;
+        }
+        res.push('(');
+        for (k, (c, l)) in node.children.iter().enumerate() {
+            if k > 0 {
+                res.push(',');
+            }
+            self.write_newick(*c, res);
+            res.push_str(&format!(":{:.4}", l));
+        }
+        res.push(')');
+    }
+}
+
+// labels with characters that are special in Newick are enclosed in single quotes
+fn newick_label(label: &str) -> String {
+    if label
+        .chars()
+        .any(|c| c.is_whitespace() || "()[]':;,".contains(c))
+    {
+        format!("'{}'", label.replace('\'', "''"))
+    } else {
+        label.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(n: usize) -> Vec<String> {
+        (0..n)
+            .map(|i| ((b'a' + i as u8) as char).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_upgma() {
+        let dist = vec![
+            vec![0.0, 0.2, 0.6, 0.6],
+            vec![0.2, 0.0, 0.6, 0.6],
+            vec![0.6, 0.6, 0.0, 0.4],
+            vec![0.6, 0.6, 0.4, 0.0],
+        ];
+        let tree = Tree::upgma(&labels(4), &dist);
+        assert_eq!(
+            tree.to_newick(),
+            "((a:0.1000,b:0.1000):0.2000,(c:0.2000,d:0.2000):0.1000);"
+        );
+    }
+
+    #[test]
+    fn test_neighbor_joining() {
+        // additive distances of the tree ((a:1,b:2):1,c:3,d:4) (unrooted)
+        let dist = vec![
+            vec![0.0, 3.0, 5.0, 6.0],
+            vec![3.0, 0.0, 6.0, 7.0],
+            vec![5.0, 6.0, 0.0, 7.0],
+            vec![6.0, 7.0, 7.0, 0.0],
+        ];
+        let tree = Tree::neighbor_joining(&labels(4), &dist);
+        assert_eq!(
+            tree.to_newick(),
+            "((a:1.0000,b:2.0000):0.5000,(c:3.0000,d:4.0000):0.5000);"
+        );
+        assert_eq!(tree.leaf_order(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_distances_and_labels() {
+        let sim = vec![vec![1.0, 0.5], vec![0.7, 1.0]];
+        let d = distances_from_similarities(&sim);
+        assert!((d[0][1] - 0.4).abs() < 1e-9 && (d[1][0] - 0.4).abs() < 1e-9);
+        assert_eq!(d[0][0], 0.0);
+        let tree = Tree::upgma(&["x y".to_string(), "it's".to_string()], &d);
+        assert_eq!(tree.to_newick(), "('x y':0.2000,'it''s':0.2000);");
+        assert_eq!(Tree::upgma(&labels(1), &[vec![0.0]]).to_newick(), "a;");
+    }
+}
@@ -1,21 +1,37 @@
 /* standard use */
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
-use std::io::BufRead;
+use std::fs;
+use std::io::{BufReader, Read, Write};
+use std::io::{Error, ErrorKind};
 use std::str::{self, FromStr};
+use std::time::UNIX_EPOCH;
 use std::{fmt, usize};
 
 /* private use */
-use crate::io::bufreader_from_compressed_gfa;
+use crate::io::{
+    best_effort, bufreader_from_gfa_files, check_gfa_record, check_parsed_record_at,
+    mmap_uncompressed_gfa, read_gfa_line, GraphParseError, LastRecord,
+};
 use crate::util::*;
 use crate::util::{CountType, ItemIdSize};
 
-static PATHID_PANSN: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^([^#]+)(#[^#]+)?(#[^#].*)?$").unwrap());
 static PATHID_COORDS: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(.+):([0-9]+)-([0-9]+)$").unwrap());
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// delimiter of the sample, haplotype, and sequence components of PanSN path names, unless another
+// one is given
+pub const DEFAULT_PANSN_DELIMITER: char = '#';
+
+fn default_pansn_delimiter() -> char {
+    DEFAULT_PANSN_DELIMITER
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Orientation {
     Forward,
     Backward,
@@ -80,7 +96,9 @@ impl PartialEq<u8> for Orientation {
     }
 }
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 pub struct ItemId(pub ItemIdSize);
 
 impl fmt::Display for ItemId {
@@ -89,45 +107,42 @@ impl fmt::Display for ItemId {
     }
 }
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(
+    Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 pub struct Edge(pub ItemId, pub Orientation, pub ItemId, pub Orientation);
 
 impl Edge {
-    pub fn from_link(data: &[u8], node2id: &HashMap<Vec<u8>, ItemId>, canonical: bool) -> Self {
-        let (start, mut iter) = match data[0] {
-            b'L' => (2, data[2..].iter()),
-            _ => (0, data.iter()),
+    pub fn from_link(
+        data: &[u8],
+        node2id: &HashMap<Vec<u8>, ItemId>,
+        canonical: bool,
+    ) -> Result<Self, GraphParseError> {
+        let mut fields = data
+            .strip_prefix(b"L\t")
+            .unwrap_or(data)
+            .split(|&x| x == b'\t' || x == b'\n' || x == b'\r');
+        let mut next_segment = || {
+            let name = fields.next().unwrap_or_default();
+            let id = node2id.get(name).ok_or_else(|| {
+                GraphParseError::UnknownNode(String::from_utf8_lossy(name).to_string())
+            })?;
+            match fields.next() {
+                Some(b"+") => Ok((*id, Orientation::Forward)),
+                Some(b"-") => Ok((*id, Orientation::Backward)),
+                _ => Err(GraphParseError::UnknownOrientation(
+                    String::from_utf8_lossy(name).to_string(),
+                )),
+            }
         };
+        let (u, o1) = next_segment()?;
+        let (v, o2) = next_segment()?;
 
-        let end = start + iter.position(|&x| x == b'\t').unwrap();
-        let u = node2id.get(&data[start..end]).unwrap_or_else(|| {
-            panic!(
-                "unknown node {}",
-                str::from_utf8(&data[start..end]).unwrap()
-            )
-        });
-
-        // we know that 3rd colum is either '+' or '-', so it has always length 1; still, we
-        // need to advance in the buffer (and  therefore call iter.position(..))
-        iter.position(|&x| x == b'\t');
-        let o1 = Orientation::from_pm(data[end + 1]);
-
-        let start = end + 3;
-        let end = start + iter.position(|&x| x == b'\t').unwrap();
-
-        let v = node2id.get(&data[start..end]).unwrap_or_else(|| {
-            panic!(
-                "unknown node {}",
-                str::from_utf8(&data[start..end]).unwrap()
-            )
-        });
-        let o2 = Orientation::from_pm(data[end + 1]);
-
-        if canonical {
-            Self::canonical(*u, o1, *v, o2)
+        Ok(if canonical {
+            Self::canonical(u, o1, v, o2)
         } else {
-            Self(*u, o1, *v, o2)
-        }
+            Self(u, o1, v, o2)
+        })
     }
 
     #[allow(dead_code)]
@@ -170,22 +185,161 @@ pub struct GraphAuxilliary {
     pub edge_count: usize,
     pub degree: Option<Vec<u32>>,
     // pub extremities: Option<Vec<(u64, u64)>>,
+    // delimiter of the PanSN path names, by which also path names of other files are parsed
+    pub pansn_delimiter: char,
+}
+
+// location at which an index of the given GFA file is looked up
+pub fn index_file_name(gfa_file: &str) -> String {
+    format!("{}.pidx", gfa_file)
+}
+
+// number of bytes of the GFA file whose records are parsed at once
+const GFA_CHUNK_SIZE: usize = 1 << 24;
+
+// numerical IDs and lengths of nodes, paths, and extremities of node sequences
+type NodeIndex = (
+    HashMap<Vec<u8>, ItemId>,
+    Vec<PathSegment>,
+    Vec<u32>,
+    Option<Vec<(u64, u64)>>,
+);
+
+// numerical IDs of edges, number of edges, and degrees of nodes
+type EdgeIndex = (HashMap<Edge, ItemId>, usize, Vec<u32>);
+
+// L-lines kept by parse_nodes_gfa for indexing edges once all segments are known; their leading
+// fields (up to the orientation of the second segment) are stored back to back in a single
+// buffer, rather than in one allocation per link
+#[derive(Debug, Default)]
+pub struct LinkArena {
+    data: Vec<u8>,
+    // line number of each link and the offset in data at which it ends
+    links: Vec<(usize, usize)>,
+}
+
+impl LinkArena {
+    pub fn push(&mut self, line: usize, link: &[u8]) {
+        self.data.extend_from_slice(link);
+        self.links.push((line, self.data.len()));
+    }
+
+    // links along with their line numbers, in the order of the file
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[u8])> {
+        let starts = std::iter::once(0).chain(self.links.iter().map(|(_, end)| *end));
+        self.links
+            .iter()
+            .zip(starts)
+            .map(move |((line, end), start)| (*line, &self.data[start..*end]))
+    }
+}
+
+// the parts of S/L/P/W records of the GFA file that make up GraphAuxilliary
+enum GfaRecord<'a> {
+    Segment(Vec<u8>, u32, Option<(u64, u64)>),
+    Link(&'a [u8]),
+    Path(PathSegment),
+    Other,
+}
+
+// serialized form of GraphAuxilliary, written by the index command
+#[derive(Serialize, Deserialize)]
+struct GraphIndex {
+    version: String,
+    pansn_delimiter: char,
+    // node names ordered by their numerical IDs, starting with ID 1
+    nodes: Vec<String>,
+    node_lens: Vec<u32>,
+    edges: Option<Vec<(Edge, ItemId)>>,
+    degree: Option<Vec<u32>>,
+    path_segments: Vec<PathSegment>,
+    // the GFA file the index was built from; a mismatch in size or modification time marks the
+    // index as stale, even if the GFA file has been replaced by one with an older timestamp
+    gfa_stamp: GfaStamp,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+struct GfaStamp {
+    size: u64,
+    // modification time in seconds and nanoseconds since the UNIX epoch
+    modified: (u64, u32),
+}
+
+impl GfaStamp {
+    fn of(gfa_file: &str) -> Result<Self, Error> {
+        let metadata = fs::metadata(gfa_file)?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Ok(Self {
+            size: metadata.len(),
+            modified: (modified.as_secs(), modified.subsec_nanos()),
+        })
+    }
 }
 
 impl GraphAuxilliary {
-    pub fn from_gfa(gfa_file: &str, count_type: CountType) -> Self {
-        let (node2id, path_segments, node_lens, _extremities) =
-            Self::parse_nodes_gfa(gfa_file, None);
+    pub fn from_gfa(
+        gfa_file: &str,
+        count_type: CountType,
+        pansn_delimiter: char,
+    ) -> Result<Self, Error> {
         let index_edges: bool = (count_type == CountType::Edge) | (count_type == CountType::All);
+        match Self::from_index_file(gfa_file, index_edges, pansn_delimiter) {
+            Some(graph_aux) => Ok(graph_aux),
+            None => Self::parse_gfa(gfa_file, index_edges, pansn_delimiter),
+        }
+    }
+
+    // several GFA files (e.g., one per chromosome) are read as a single graph, for which there is
+    // no index file
+    pub fn from_gfa_files(
+        gfa_files: &[String],
+        count_type: CountType,
+        pansn_delimiter: char,
+    ) -> Result<Self, Error> {
+        match gfa_files {
+            [gfa_file] => Self::from_gfa(gfa_file, count_type, pansn_delimiter),
+            _ => Self::parse_gfa_files(
+                gfa_files,
+                (count_type == CountType::Edge) | (count_type == CountType::All),
+                pansn_delimiter,
+            ),
+        }
+    }
+
+    pub fn parse_gfa(
+        gfa_file: &str,
+        index_edges: bool,
+        pansn_delimiter: char,
+    ) -> Result<Self, Error> {
+        Self::parse_gfa_files(&[gfa_file.to_string()], index_edges, pansn_delimiter)
+    }
+
+    fn parse_gfa_files(
+        gfa_files: &[String],
+        index_edges: bool,
+        pansn_delimiter: char,
+    ) -> Result<Self, Error> {
+        // links may refer to segments further down in the file, hence they are indexed only
+        // after all segments are known
+        let mut links = LinkArena::default();
+        let (node2id, path_segments, node_lens, _extremities) = Self::parse_nodes_gfa(
+            gfa_files,
+            None,
+            index_edges.then(|| &mut links),
+            pansn_delimiter,
+        )?;
         let (edge2id, edge_count, degree) = if index_edges {
-            let (edge2id, edge_count, degree) = Self::parse_edge_gfa(gfa_file, &node2id);
+            let (edge2id, edge_count, degree) = Self::index_edges(&links, &node2id)?;
             (Some(edge2id), edge_count, Some(degree))
         } else {
             (None, 0, None)
         };
         let node_count = node2id.len();
 
-        Self {
+        Ok(Self {
             node2id,
             node_lens,
             edge2id,
@@ -194,12 +348,13 @@ impl GraphAuxilliary {
             edge_count,
             degree,
             // extremities,
-        }
+            pansn_delimiter,
+        })
     }
 
     // pub fn from_cdbg_gfa(gfa_file: &str, k: usize) -> Self {
     //     let (node2id, path_segments, node_lens, extremities) =
-    //         Self::parse_nodes_gfa(gfa_file, Some(k));
+    //         Self::parse_nodes_gfa(gfa_file, Some(k), None);
     //     let (edge2id, edge_count, degree) = (None, 0, None);
     //     let node_count = node2id.len();
 
@@ -215,13 +370,119 @@ impl GraphAuxilliary {
     //     }
     // }
 
+    // loads the index of the GFA file if there is one that has been built from the GFA file in its
+    // current state and that comprises everything required
+    fn from_index_file(gfa_file: &str, index_edges: bool, pansn_delimiter: char) -> Option<Self> {
+        let index_file = index_file_name(gfa_file);
+        let data = BufReader::new(MultiGzDecoder::new(fs::File::open(&index_file).ok()?));
+        log::info!("loading index {}", index_file);
+        match Self::read_index(data, gfa_file, index_edges, pansn_delimiter) {
+            Ok(graph_aux) => Some(graph_aux),
+            Err(e) => {
+                log::warn!("ignoring index {}: {}", index_file, e);
+                None
+            }
+        }
+    }
+
+    pub fn read_index<R: Read>(
+        data: R,
+        gfa_file: &str,
+        index_edges: bool,
+        pansn_delimiter: char,
+    ) -> Result<Self, Error> {
+        let index: GraphIndex = serde_json::from_reader(data)?;
+        if index.gfa_stamp != GfaStamp::of(gfa_file)? {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "index was built from a different version of {} (size or modification time differ)",
+                    gfa_file
+                ),
+            ));
+        }
+        if index.version != env!("CARGO_PKG_VERSION") {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("index was built by panacus {}", index.version),
+            ));
+        }
+        if index.pansn_delimiter != pansn_delimiter {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "index was built with PanSN delimiter \"{}\"",
+                    index.pansn_delimiter
+                ),
+            ));
+        }
+        if index_edges && index.edges.is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "index does not comprise edges",
+            ));
+        }
+        let node2id: HashMap<Vec<u8>, ItemId> = index
+            .nodes
+            .into_iter()
+            .enumerate()
+            .map(|(i, n)| (n.into_bytes(), ItemId(i as ItemIdSize + 1)))
+            .collect();
+        let node_count = node2id.len();
+        let (edge2id, edge_count, degree) = match index.edges {
+            Some(edges) if index_edges => {
+                let edge_count = edges.len();
+                (Some(edges.into_iter().collect()), edge_count, index.degree)
+            }
+            _ => (None, 0, None),
+        };
+        Ok(Self {
+            node2id,
+            node_lens: index.node_lens,
+            edge2id,
+            path_segments: index.path_segments,
+            node_count,
+            edge_count,
+            degree,
+            pansn_delimiter,
+        })
+    }
+
+    pub fn write_index<W: Write>(&self, gfa_file: &str, out: W) -> Result<(), Error> {
+        let mut nodes = vec![String::new(); self.node_count];
+        for (n, id) in self.node2id.iter() {
+            nodes[id.0 as usize - 1] = String::from_utf8(n.clone()).map_err(|_| {
+                let msg = "some node identifier is not UTF-8";
+                log::error!("{}", msg);
+                Error::new(ErrorKind::InvalidData, msg)
+            })?;
+        }
+        let index = GraphIndex {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            pansn_delimiter: self.pansn_delimiter,
+            nodes,
+            node_lens: self.node_lens.clone(),
+            edges: self
+                .edge2id
+                .as_ref()
+                .map(|x| x.iter().map(|(e, id)| (*e, *id)).collect()),
+            degree: self.degree.clone(),
+            path_segments: self.path_segments.clone(),
+            gfa_stamp: GfaStamp::of(gfa_file)?,
+        };
+        let mut gz = GzEncoder::new(out, Compression::default());
+        serde_json::to_writer(&mut gz, &index)?;
+        gz.finish()?;
+        Ok(())
+    }
+
     pub fn node_len(&self, v: &ItemId) -> u32 {
         self.node_lens[v.0 as usize]
     }
 
     pub fn info(
         &self,
-        paths_len: &HashMap<PathSegment, (u32, u32)>,
+        paths_len: &HashMap<PathSegment, (u64, u64)>,
         groups: &HashMap<PathSegment, String>,
         has_groups: bool,
     ) -> Info {
@@ -230,22 +491,51 @@ impl GraphAuxilliary {
                 graph_info: self.graph_info(groups),
                 path_info: self.path_info(paths_len),
                 group_info: Some(self.group_info(paths_len, groups)),
+                sample_info: self.sample_info(paths_len),
             }
         } else {
             Info {
                 graph_info: self.graph_info(groups),
                 path_info: self.path_info(paths_len),
                 group_info: None,
+                sample_info: self.sample_info(paths_len),
             }
         }
     }
 
+    pub fn sample_info(&self, paths_len: &HashMap<PathSegment, (u64, u64)>) -> SampleInfo {
+        let mut samples: HashMap<&str, SampleStats> = HashMap::new();
+        let mut haplotypes: HashSet<(&str, Option<&str>)> = HashSet::new();
+        for (k, v) in paths_len {
+            let stats = samples.entry(&k.sample).or_insert_with(|| SampleStats {
+                sample: k.sample.clone(),
+                paths: 0,
+                node_len: 0,
+                bp_len: 0,
+            });
+            stats.paths += 1;
+            stats.node_len += v.0;
+            stats.bp_len += v.1;
+            haplotypes.insert((&k.sample, k.haplotype.as_deref()));
+        }
+        let mut samples: Vec<SampleStats> = samples.into_values().collect();
+        samples.sort_by(|a, b| a.sample.cmp(&b.sample));
+
+        SampleInfo {
+            path_records: None,
+            walk_records: None,
+            sample_count: samples.len(),
+            haplotype_count: haplotypes.len(),
+            samples,
+        }
+    }
+
     pub fn group_info(
         &self,
-        paths_len: &HashMap<PathSegment, (u32, u32)>,
+        paths_len: &HashMap<PathSegment, (u64, u64)>,
         groups: &HashMap<PathSegment, String>,
     ) -> GroupInfo {
-        let mut group_map: HashMap<String, (u32, u32)> = HashMap::new();
+        let mut group_map: HashMap<String, (u64, u64)> = HashMap::new();
         for (k, v) in paths_len {
             let group = if !groups.contains_key(k) {
                 let k = k.clear_coords();
@@ -336,28 +626,36 @@ impl GraphAuxilliary {
             shortest_node: *node_lens_sorted.iter().min().unwrap(),
             average_node: averageu32(&node_lens_sorted),
             median_node: median_already_sorted(&node_lens_sorted),
-            n50_node: n50_already_sorted(&node_lens_sorted).unwrap(),
-            basepairs: self.node_lens.iter().sum(),
+            n50_node: n50_already_sorted(&node_lens_sorted).unwrap_or(0),
+            basepairs: self.node_lens.iter().map(|x| *x as u64).sum(),
             group_count: groups.values().collect::<HashSet<_>>().len(),
+            degree_distribution: None,
+            node_lens: node_lens_sorted,
+            component_sizes: components,
+            degrees: degree[1..].to_vec(),
         }
     }
 
-    pub fn path_info(&self, paths_len: &HashMap<PathSegment, (u32, u32)>) -> PathInfo {
+    // statistics of all paths; if there are none (e.g., in a graph without P/W lines), all
+    // lengths are reported as 0
+    pub fn path_info(&self, paths_len: &HashMap<PathSegment, (u64, u64)>) -> PathInfo {
         //println!("\tDistribution of Strands in the Paths/Walks: TODO +/-");
         let paths_bp_len: Vec<_> = paths_len.values().map(|x| x.1).collect();
         let paths_len: Vec<_> = paths_len.values().map(|x| x.0).collect();
+        let len_info = |lens: &[u64]| LenInfo {
+            longest: *lens.iter().max().unwrap_or(&0),
+            shortest: *lens.iter().min().unwrap_or(&0),
+            average: if lens.is_empty() {
+                0.0
+            } else {
+                averageu64(lens)
+            },
+        };
         PathInfo {
             no_paths: paths_len.len(),
-            node_len: LenInfo {
-                longest: *paths_len.iter().max().unwrap(),
-                shortest: *paths_len.iter().min().unwrap(),
-                average: averageu32(&paths_len),
-            },
-            bp_len: LenInfo {
-                longest: *paths_bp_len.iter().max().unwrap(),
-                shortest: *paths_bp_len.iter().min().unwrap(),
-                average: averageu32(&paths_bp_len),
-            },
+            node_len: len_info(&paths_len),
+            bp_len: len_info(&paths_bp_len),
+            bp_lens: paths_bp_len,
         }
     }
 
@@ -369,47 +667,61 @@ impl GraphAuxilliary {
         }
     }
 
-    pub fn parse_edge_gfa(
-        gfa_file: &str,
+    // indexes the links kept by parse_nodes_gfa along with their line numbers; in best-effort mode,
+    // links between unknown nodes (e.g., of skipped corrupt records) are skipped
+    pub fn index_edges(
+        links: &LinkArena,
         node2id: &HashMap<Vec<u8>, ItemId>,
-    ) -> (HashMap<Edge, ItemId>, usize, Vec<u32>) {
+    ) -> Result<EdgeIndex, GraphParseError> {
         let mut edge2id = HashMap::default();
         let mut degree: Vec<u32> = vec![0; node2id.len() + 1];
         let mut edge_id: ItemIdSize = 1;
 
-        let mut buf = vec![];
-        let mut data = bufreader_from_compressed_gfa(gfa_file);
-        while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
-            if buf[0] == b'L' {
-                let edge = Edge::from_link(&buf[..], node2id, true);
-                if let std::collections::hash_map::Entry::Vacant(e) = edge2id.entry(edge) {
-                    degree[edge.0 .0 as usize] += 1;
-                    //if e.0.0 != e.2.0 {
-                    degree[edge.2 .0 as usize] += 1;
-                    //}
-                    e.insert(ItemId(edge_id));
-                    edge_id += 1;
-                } else {
-                    log::warn!("edge {} is duplicated in GFA", &edge);
+        for (line, link) in links.iter() {
+            let edge = match Edge::from_link(link, node2id, true) {
+                Ok(edge) => edge,
+                Err(e) if best_effort() => {
+                    log::warn!("skipping link in line {}: {}", line, e);
+                    continue;
                 }
+                Err(e) => return Err(e.in_line(line)),
+            };
+            if let Entry::Vacant(e) = edge2id.entry(edge) {
+                degree[edge.0 .0 as usize] += 1;
+                //if e.0.0 != e.2.0 {
+                degree[edge.2 .0 as usize] += 1;
+                //}
+                e.insert(ItemId(edge_id));
+                edge_id += 1;
+            } else {
+                log::warn!("edge {} is duplicated in GFA", &edge);
             }
-            buf.clear();
         }
         let edge_count = edge2id.len();
         log::info!("found: {} edges", edge_count);
 
-        (edge2id, edge_count, degree)
+        Ok((edge2id, edge_count, degree))
     }
 
+    // indexes nodes and paths; if links are requested, the L-lines are kept (up to the
+    // orientation of their second segment) for indexing edges afterwards
     pub fn parse_nodes_gfa(
-        gfa_file: &str,
+        gfa_files: &[String],
         k: Option<usize>,
-    ) -> (
-        HashMap<Vec<u8>, ItemId>,
-        Vec<PathSegment>,
-        Vec<u32>,
-        Option<Vec<(u64, u64)>>,
-    ) {
+        links: Option<&mut LinkArena>,
+        pansn_delimiter: char,
+    ) -> Result<NodeIndex, Error> {
+        Self::parse_nodes_gfa_chunked(gfa_files, k, links, pansn_delimiter, GFA_CHUNK_SIZE)
+    }
+
+    // parse_nodes_gfa with chunks of (at least) the given number of bytes
+    fn parse_nodes_gfa_chunked(
+        gfa_files: &[String],
+        k: Option<usize>,
+        mut links: Option<&mut LinkArena>,
+        pansn_delimiter: char,
+        chunk_size: usize,
+    ) -> Result<NodeIndex, Error> {
         let mut node2id: HashMap<Vec<u8>, ItemId> = HashMap::default();
         let mut path_segments: Vec<PathSegment> = Vec::new();
         let mut node_lens: Vec<u32> = Vec::new();
@@ -419,38 +731,96 @@ impl GraphAuxilliary {
         node_lens.push(u32::MIN); // add empty element to node_lens to make it in sync with node_id
         let mut node_id = 1; // important: id must be > 0, otherwise counting procedure will produce errors
 
-        let mut buf = vec![];
-        let mut data = bufreader_from_compressed_gfa(gfa_file);
-        while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
-            if buf[0] == b'S' {
-                let mut iter = buf[2..].iter();
-                let offset = iter.position(|&x| x == b'\t').unwrap();
-                if node2id
-                    .insert(buf[2..offset + 2].to_vec(), ItemId(node_id))
-                    .is_some()
+        // records are parsed in parallel within newline-aligned chunks of the file, whereas
+        // numerical IDs are assigned sequentially in the order of the file
+        let with_links = links.is_some();
+        let mut line = 0;
+        let mut offset = 0;
+        let mut last_complete = LastRecord::default();
+        let mut index_chunk = |chunk: &[u8]| -> Result<(), GraphParseError> {
+            let records: Vec<(GfaRecord, bool)> = chunk
+                .split_inclusive(|&x| x == b'\n')
+                .collect::<Vec<&[u8]>>()
+                .par_iter()
+                .map(|buf| {
+                    let valid = check_gfa_record(buf).is_ok();
+                    let record = if valid {
+                        Self::parse_record(buf, k, with_links, pansn_delimiter)
+                    } else {
+                        GfaRecord::Other
+                    };
+                    (record, valid)
+                })
+                .collect();
+            for ((record, valid), buf) in records
+                .into_iter()
+                .zip(chunk.split_inclusive(|&x| x == b'\n'))
+            {
+                line += 1;
+                offset += buf.len();
+                // corrupt records are reported here rather than in a separate pass over the file
+                if !valid && !check_parsed_record_at(buf, line, offset - buf.len(), &last_complete)?
                 {
-                    panic!(
-                        "Segment with ID {} occurs multiple times in GFA",
-                        str::from_utf8(&buf[2..offset + 2]).unwrap()
-                    )
+                    continue;
+                }
+                last_complete.update(line, buf);
+                match record {
+                    GfaRecord::Segment(name, len, ext) => {
+                        if let Some(ext) = ext {
+                            extremities.push(ext);
+                        }
+                        match node2id.entry(name) {
+                            Entry::Occupied(e) => {
+                                return Err(GraphParseError::DuplicateNode(
+                                    String::from_utf8_lossy(e.key()).to_string(),
+                                )
+                                .in_line(line))
+                            }
+                            Entry::Vacant(e) => e.insert(ItemId(node_id)),
+                        };
+                        node_lens.push(len);
+                        node_id += 1;
+                    }
+                    GfaRecord::Link(link) => links.as_mut().unwrap().push(line, link),
+                    GfaRecord::Path(path_segment) => path_segments.push(path_segment),
+                    GfaRecord::Other => (),
                 }
-                let start_sequence = offset + 3;
-                let offset = iter
-                    .position(|&x| x == b'\t' || x == b'\n' || x == b'\r')
-                    .unwrap();
-                if k.is_some() {
-                    let (left, right) =
-                        get_extremities(&buf[start_sequence..start_sequence + offset], k.unwrap());
-                    extremities.push((left, right));
+            }
+            Ok(())
+        };
+
+        let mmap = match gfa_files {
+            [gfa_file] => mmap_uncompressed_gfa(gfa_file),
+            _ => None,
+        };
+        if let Some(mmap) = mmap {
+            // records of a single uncompressed file are parsed in place
+            let mut start = 0;
+            while start < mmap.len() {
+                let end = usize::min(start + chunk_size, mmap.len());
+                let end = mmap[end..]
+                    .iter()
+                    .position(|&x| x == b'\n')
+                    .map_or(mmap.len(), |i| end + i + 1);
+                index_chunk(&mmap[start..end])?;
+                start = end;
+            }
+        } else {
+            let mut chunk = vec![];
+            let mut data = bufreader_from_gfa_files(gfa_files)?;
+            let mut lines_read = 0;
+            loop {
+                let n = read_gfa_line(&mut data, &mut chunk, lines_read)?;
+                lines_read += 1;
+                if n > 0 && chunk.len() < chunk_size {
+                    continue;
+                }
+                index_chunk(&chunk)?;
+                chunk.clear();
+                if n == 0 {
+                    break;
                 }
-                node_lens.push(offset as u32);
-                node_id += 1;
-            } else if buf[0] == b'P' {
-                path_segments.push(Self::parse_path_segment(&buf));
-            } else if buf[0] == b'W' {
-                path_segments.push(Self::parse_walk_segment(&buf));
             }
-            buf.clear();
         }
 
         log::info!(
@@ -462,23 +832,98 @@ impl GraphAuxilliary {
             log::warn!("graph does not contain any annotated paths (P/W lines)");
         }
 
-        (
+        Ok((
             node2id,
             path_segments,
             node_lens,
             if k.is_none() { None } else { Some(extremities) },
-        )
+        ))
+    }
+
+    // parses a record that has passed check_gfa_record
+    fn parse_record(
+        buf: &[u8],
+        k: Option<usize>,
+        with_links: bool,
+        pansn_delimiter: char,
+    ) -> GfaRecord<'_> {
+        if buf[0] == b'S' {
+            let mut iter = buf[2..].iter();
+            let offset = iter.position(|&x| x == b'\t').unwrap();
+            let name = buf[2..offset + 2].to_vec();
+            let start_sequence = offset + 3;
+            // the last record of the file may lack a trailing newline
+            let offset = iter
+                .position(|&x| x == b'\t' || x == b'\n' || x == b'\r')
+                .unwrap_or(buf.len() - start_sequence);
+            let extremities =
+                k.map(|k| get_extremities(&buf[start_sequence..start_sequence + offset], k));
+            let len = if &buf[start_sequence..start_sequence + offset] == b"*" {
+                // sequence is omitted, length is given by optional LN tag
+                Self::parse_length_tag(&buf[start_sequence + offset..])
+            } else {
+                offset as u32
+            };
+            GfaRecord::Segment(name, len, extremities)
+        } else if buf[0] == b'L' && with_links {
+            let end = buf
+                .iter()
+                .enumerate()
+                .filter(|(_, &x)| x == b'\t')
+                .nth(4)
+                .map_or(buf.len(), |(i, _)| i);
+            GfaRecord::Link(&buf[..end])
+        } else if buf[0] == b'P' {
+            GfaRecord::Path(Self::parse_path_segment(buf, pansn_delimiter))
+        } else if buf[0] == b'W' {
+            GfaRecord::Path(Self::parse_walk_segment(buf, pansn_delimiter))
+        } else {
+            GfaRecord::Other
+        }
+    }
+
+    fn parse_length_tag(data: &[u8]) -> u32 {
+        data.split(|&x| x == b'\t' || x == b'\n' || x == b'\r')
+            .find(|x| x.starts_with(b"LN:i:"))
+            .and_then(|x| str::from_utf8(&x[5..]).ok())
+            .and_then(|x| u32::from_str(x).ok())
+            .unwrap_or(0)
+    }
+
+    pub fn zero_length_nodes(&self) -> usize {
+        self.node_lens[1..].iter().filter(|&x| *x == 0).count()
     }
 
-    pub fn parse_path_segment(data: &[u8]) -> PathSegment {
+    pub fn check_zero_length_nodes(&self, policy: ZeroLengthNodes) -> Result<(), Error> {
+        let n = self.zero_length_nodes();
+        if n > 0 {
+            match policy {
+                ZeroLengthNodes::Count => log::warn!(
+                    "graph contains {} zero-length nodes, which are counted as nodes of 0 bp",
+                    n
+                ),
+                ZeroLengthNodes::Skip => {
+                    log::info!("graph contains {} zero-length nodes, which are skipped", n)
+                }
+                ZeroLengthNodes::Error => {
+                    let msg = format!("graph contains {} zero-length nodes", n);
+                    log::error!("{}", &msg);
+                    return Err(Error::new(ErrorKind::InvalidData, msg));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn parse_path_segment(data: &[u8], pansn_delimiter: char) -> PathSegment {
         let mut iter = data.iter();
         let start = iter.position(|&x| x == b'\t').unwrap() + 1;
         let offset = iter.position(|&x| x == b'\t').unwrap();
         let path_name = str::from_utf8(&data[start..start + offset]).unwrap();
-        PathSegment::from_str(path_name)
+        PathSegment::from_str(path_name, pansn_delimiter)
     }
 
-    pub fn parse_walk_segment(data: &[u8]) -> PathSegment {
+    pub fn parse_walk_segment(data: &[u8], pansn_delimiter: char) -> PathSegment {
         let mut six_col: Vec<&str> = Vec::with_capacity(6);
 
         let mut it = data.iter();
@@ -505,6 +950,7 @@ impl GraphAuxilliary {
             six_col[3].to_string(),
             seq_start,
             seq_end,
+            pansn_delimiter,
         )
     }
 
@@ -544,31 +990,134 @@ impl GraphAuxilliary {
     //     (left << 2) | right
     // }
 
-    //#[allow(dead_code)]
-    //pub fn degree_distribution(&self) -> Option<Vec<u32>> {
-    //    match &self.degree {
-    //        Some(degree) => {
-    //            let mut hist: Vec<u32> = vec![0,1];
-    //            for i in 1..self.node_count+1 {
-    //                if degree[i] as usize >= hist.len() {
-    //                    hist.resize(degree[i] as usize +1, 0);
-    //                }
-    //                hist[degree[i] as usize] += 1;
-    //            }
-    //            Some(hist)
-    //        }
-    //        None => None
-    //    }
-    //}
+    // number of nodes per degree, i.e., the i-th entry is the number of nodes of degree i
+    pub fn degree_distribution(&self) -> Option<Vec<usize>> {
+        self.degree.as_ref().map(|degree| {
+            let mut hist: Vec<usize> = Vec::new();
+            for d in &degree[1..] {
+                if *d as usize >= hist.len() {
+                    hist.resize(*d as usize + 1, 0);
+                }
+                hist[*d as usize] += 1;
+            }
+            hist
+        })
+    }
+
+    // detects superbubbles (Onodera et al., 2013) in the bidirected graph, i.e., pairs of oriented
+    // source and sink nodes enclosing an acyclic subgraph that is entered only through the source
+    // and left only through the sink; nested superbubbles are reported separately. The search
+    // from each source is abandoned after visiting max_size nodes.
+    pub fn bubbles(&self, max_size: usize) -> Vec<Bubble> {
+        let edge2id = self
+            .edge2id
+            .as_ref()
+            .expect("bubble detection requires edge2id map in GraphAuxilliary");
+
+        // oriented node (v, o) is represented by 2v (forward) or 2v+1 (backward) so that flipping
+        // its orientation amounts to x ^ 1
+        let key =
+            |v: ItemId, o: Orientation| 2 * v.0 as usize + (o == Orientation::Backward) as usize;
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); 2 * (self.node_count + 1)];
+        for e in edge2id.keys() {
+            children[key(e.0, e.1)].push(key(e.2, e.3));
+            if e.flip() != *e {
+                children[key(e.2, e.3) ^ 1].push(key(e.0, e.1) ^ 1);
+            }
+        }
+
+        let mut res: Vec<Bubble> = (2..children.len())
+            .into_par_iter()
+            .filter_map(|s| {
+                let (t, order, alleles) = Self::superbubble(&children, s, max_size)?;
+                // each superbubble is found from both ends, (s, t) and (t^1, s^1)
+                if s > t ^ 1 {
+                    return None;
+                }
+                let internal = &order[1..];
+                let simple = internal
+                    .iter()
+                    .all(|x| children[*x] == [t] && children[x ^ 1] == [s ^ 1]);
+                let orientation = |x: usize| match x & 1 {
+                    0 => Orientation::Forward,
+                    _ => Orientation::Backward,
+                };
+                Some(Bubble {
+                    source: (ItemId((s / 2) as ItemIdSize), orientation(s)),
+                    sink: (ItemId((t / 2) as ItemIdSize), orientation(t)),
+                    nodes: internal.len(),
+                    bp: internal.iter().map(|x| self.node_lens[x / 2] as u64).sum(),
+                    alleles,
+                    simple,
+                })
+            })
+            .collect();
+        res.sort_by_key(|b| (b.source.0, b.sink.0));
+        res
+    }
+
+    // returns the sink of the superbubble with source s, the source and internal nodes in
+    // topological order, and the number of distinct source-sink walks
+    fn superbubble(
+        children: &[Vec<usize>],
+        s: usize,
+        max_size: usize,
+    ) -> Option<(usize, Vec<usize>, u64)> {
+        let mut stack = vec![s];
+        let mut seen: HashSet<usize> = HashSet::from([s]);
+        let mut visited: HashSet<usize> = HashSet::new();
+        let mut order = Vec::new();
+        let mut walks: HashMap<usize, u64> = HashMap::from([(s, 1)]);
+
+        while let Some(v) = stack.pop() {
+            seen.remove(&v);
+            // a superbubble must not traverse a node in both orientations
+            if visited.contains(&(v ^ 1)) || visited.len() >= max_size {
+                return None;
+            }
+            visited.insert(v);
+            order.push(v);
+            if children[v].is_empty() {
+                // tip
+                return None;
+            }
+            let w = walks[&v];
+            for &u in &children[v] {
+                if u == s || u == s ^ 1 {
+                    // cycle through the source
+                    return None;
+                }
+                seen.insert(u);
+                let n = walks.entry(u).or_insert(0);
+                *n = n.saturating_add(w);
+                // parents of u are the flipped children of u^1
+                if children[u ^ 1].iter().all(|p| visited.contains(&(p ^ 1))) {
+                    stack.push(u);
+                }
+            }
+            if stack.len() == 1 && seen.len() == 1 && seen.contains(&stack[0]) {
+                let t = stack[0];
+                if children[t].contains(&s) || order.len() == 1 {
+                    // either a cycle, or a plain edge without any nodes in between
+                    return None;
+                }
+                return Some((t, order, walks[&t]));
+            }
+        }
+        None
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Eq, Ord)]
+#[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Eq, Ord, Serialize, Deserialize)]
 pub struct PathSegment {
     pub sample: String,
     pub haplotype: Option<String>,
     pub seqid: Option<String>,
     pub start: Option<usize>,
     pub end: Option<usize>,
+    // PanSN delimiter by which the path name is put together again
+    #[serde(default = "default_pansn_delimiter")]
+    pub delimiter: char,
 }
 
 impl PathSegment {
@@ -578,6 +1127,7 @@ impl PathSegment {
         seqid: String,
         start: Option<usize>,
         end: Option<usize>,
+        delimiter: char,
     ) -> Self {
         Self {
             sample,
@@ -585,88 +1135,114 @@ impl PathSegment {
             seqid: Some(seqid),
             start,
             end,
+            delimiter,
         }
     }
 
-    pub fn from_str(s: &str) -> Self {
+    // splits a PanSN path name into sample, haplotype, and sequence; names that do not follow
+    // the scheme (e.g., because one of the first two components is empty) make up the sample
+    fn split_pansn(s: &str, delimiter: char) -> Option<(&str, Option<&str>, Option<&str>)> {
+        if s.is_empty() || s.starts_with(delimiter) {
+            return None;
+        }
+        let (sample, rest) = match s.split_once(delimiter) {
+            None => return Some((s, None, None)),
+            Some(x) => x,
+        };
+        if rest.is_empty() || rest.starts_with(delimiter) {
+            return None;
+        }
+        // only the sequence may contain the delimiter itself, but not start with it; otherwise,
+        // everything after the sample is taken as haplotype
+        Some(match rest.split_once(delimiter) {
+            Some((haplotype, seqid)) if !seqid.is_empty() && !seqid.starts_with(delimiter) => {
+                (sample, Some(haplotype), Some(seqid))
+            }
+            _ => (sample, Some(rest), None),
+        })
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str, delimiter: char) -> Self {
         let mut res = PathSegment {
             sample: s.to_string(),
             haplotype: None,
             seqid: None,
             start: None,
             end: None,
+            delimiter,
         };
 
-        if let Some(c) = PATHID_PANSN.captures(s) {
-            let segments: Vec<&str> = c.iter().filter_map(|x| x.map(|y| y.as_str())).collect();
-            // first capture group is the string itself
-            match segments.len() {
-                4 => {
-                    res.sample = segments[1].to_string();
-                    res.haplotype = Some(segments[2][1..].to_string());
-                    match PATHID_COORDS.captures(&segments[3][1..]) {
-                        None => {
-                            res.seqid = Some(segments[3][1..].to_string());
-                        }
-                        Some(cc) => {
-                            res.seqid = Some(cc.get(1).unwrap().as_str().to_string());
-                            res.start = usize::from_str(cc.get(2).unwrap().as_str()).ok();
-                            res.end = usize::from_str(cc.get(3).unwrap().as_str()).ok();
-                            log::debug!("path has coordinates {} ", res);
-                        }
+        match Self::split_pansn(s, delimiter) {
+            Some((sample, Some(haplotype), Some(seqid))) => {
+                res.sample = sample.to_string();
+                res.haplotype = Some(haplotype.to_string());
+                match PATHID_COORDS.captures(seqid) {
+                    None => {
+                        res.seqid = Some(seqid.to_string());
                     }
-                }
-                3 => {
-                    res.sample = segments[1].to_string();
-                    match PATHID_COORDS.captures(&segments[2][1..]) {
-                        None => {
-                            res.haplotype = Some(segments[2][1..].to_string());
-                        }
-                        Some(cc) => {
-                            res.haplotype = Some(cc.get(1).unwrap().as_str().to_string());
-                            res.start = usize::from_str(cc.get(2).unwrap().as_str()).ok();
-                            res.end = usize::from_str(cc.get(3).unwrap().as_str()).ok();
-                            log::debug!("path has coordinates {} ", res);
-                        }
+                    Some(cc) => {
+                        res.seqid = Some(cc.get(1).unwrap().as_str().to_string());
+                        res.start = usize::from_str(cc.get(2).unwrap().as_str()).ok();
+                        res.end = usize::from_str(cc.get(3).unwrap().as_str()).ok();
+                        log::debug!("path has coordinates {} ", res);
                     }
                 }
-                2 => {
-                    if let Some(cc) = PATHID_COORDS.captures(segments[1]) {
-                        res.sample = cc.get(1).unwrap().as_str().to_string();
+            }
+            Some((sample, Some(haplotype), None)) => {
+                res.sample = sample.to_string();
+                match PATHID_COORDS.captures(haplotype) {
+                    None => {
+                        res.haplotype = Some(haplotype.to_string());
+                    }
+                    Some(cc) => {
+                        res.haplotype = Some(cc.get(1).unwrap().as_str().to_string());
                         res.start = usize::from_str(cc.get(2).unwrap().as_str()).ok();
                         res.end = usize::from_str(cc.get(3).unwrap().as_str()).ok();
-                        log::debug!("path has coordinates {}", res);
+                        log::debug!("path has coordinates {} ", res);
                     }
                 }
-                _ => (),
             }
+            Some((sample, None, _)) => {
+                if let Some(cc) = PATHID_COORDS.captures(sample) {
+                    res.sample = cc.get(1).unwrap().as_str().to_string();
+                    res.start = usize::from_str(cc.get(2).unwrap().as_str()).ok();
+                    res.end = usize::from_str(cc.get(3).unwrap().as_str()).ok();
+                    log::debug!("path has coordinates {}", res);
+                }
+            }
+            None => (),
         }
         res
     }
 
-    pub fn from_str_start_end(s: &str, start: usize, end: usize) -> Self {
-        let mut segment = Self::from_str(s);
+    pub fn from_str_start_end(s: &str, start: usize, end: usize, delimiter: char) -> Self {
+        let mut segment = Self::from_str(s, delimiter);
         segment.start = Some(start);
         segment.end = Some(end);
         segment
     }
 
     pub fn id(&self) -> String {
+        let d = self.delimiter;
         if self.haplotype.is_some() {
             format!(
-                "{}#{}{}",
+                "{}{}{}{}",
                 self.sample,
+                d,
                 self.haplotype.as_ref().unwrap(),
                 if self.seqid.is_some() {
-                    "#".to_owned() + self.seqid.as_ref().unwrap().as_str()
+                    d.to_string() + self.seqid.as_ref().unwrap().as_str()
                 } else {
                     "".to_string()
                 }
             )
         } else if self.seqid.is_some() {
             format!(
-                "{}#*#{}",
+                "{}{}*{}{}",
                 self.sample,
+                d,
+                d,
                 self.seqid.as_ref().unwrap().as_str()
             )
         } else {
@@ -681,6 +1257,7 @@ impl PathSegment {
             seqid: self.seqid.clone(),
             start: None,
             end: None,
+            delimiter: self.delimiter,
         }
     }
 
@@ -722,6 +1299,7 @@ impl fmt::Display for PathSegment {
     }
 }
 
+#[derive(Serialize)]
 pub struct GraphInfo {
     pub node_count: usize,
     pub edge_count: usize,
@@ -738,30 +1316,133 @@ pub struct GraphInfo {
     pub average_node: f32,
     pub median_node: f64,
     pub n50_node: u32,
-    pub basepairs: u32,
+    pub basepairs: u64,
     pub group_count: usize,
+    // number of nodes per degree, reported only on request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degree_distribution: Option<Vec<usize>>,
+    // raw distributions, reported in HTML only
+    #[serde(skip)]
+    pub node_lens: Vec<u32>,
+    #[serde(skip)]
+    pub component_sizes: Vec<u32>,
+    #[serde(skip)]
+    pub degrees: Vec<u32>,
+}
+
+impl GraphInfo {
+    // leaves zero-length nodes out of the node count and the node length statistics, e.g., if
+    // they are placeholders that would otherwise distort the N50
+    pub fn skip_zero_length_nodes(&mut self) {
+        let n = self.node_lens.iter().filter(|x| **x == 0).count();
+        if n == 0 || n == self.node_lens.len() {
+            return;
+        }
+        // node lengths are sorted in decreasing order
+        self.node_lens.truncate(self.node_lens.len() - n);
+        self.node_count -= n;
+        self.shortest_node = *self.node_lens.last().unwrap();
+        self.average_node = averageu32(&self.node_lens);
+        self.median_node = median_already_sorted(&self.node_lens);
+        self.n50_node = n50_already_sorted(&self.node_lens).unwrap_or(0);
+    }
+}
+
+// superbubble between oriented source and sink nodes; nodes and bp are those strictly between
+// source and sink, and alleles is the number of distinct walks from source to sink
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bubble {
+    pub source: (ItemId, Orientation),
+    pub sink: (ItemId, Orientation),
+    pub nodes: usize,
+    pub bp: u64,
+    pub alleles: u64,
+    // all walks from source to sink consist of a single node or a plain edge
+    pub simple: bool,
 }
 
+#[derive(Serialize)]
 pub struct PathInfo {
     pub no_paths: usize,
     pub node_len: LenInfo,
     pub bp_len: LenInfo,
+    // raw distribution, reported in HTML only
+    #[serde(skip)]
+    pub bp_lens: Vec<u64>,
 }
 
+#[derive(Serialize)]
 pub struct LenInfo {
-    pub longest: u32,
-    pub shortest: u32,
+    pub longest: u64,
+    pub shortest: u64,
     pub average: f32,
 }
 
+#[derive(Serialize)]
 pub struct GroupInfo {
-    pub groups: HashMap<String, (u32, u32)>,
+    pub groups: HashMap<String, (u64, u64)>,
 }
 
+#[derive(Serialize)]
+pub struct SampleStats {
+    pub sample: String,
+    pub paths: usize,
+    pub node_len: u64,
+    pub bp_len: u64,
+}
+
+#[derive(Serialize)]
+pub struct SampleInfo {
+    // numbers of P- and W-lines, only known if the GFA file has been scanned for them
+    pub path_records: Option<usize>,
+    pub walk_records: Option<usize>,
+    pub sample_count: usize,
+    pub haplotype_count: usize,
+    pub samples: Vec<SampleStats>,
+}
+
+// side-by-side rows of the info tables of several graphs, keyed by their feature, category, and
+// countable columns; values that are missing in a graph (e.g., of groups not present in it) are
+// None
+pub fn compare_infos(infos: &[&Info]) -> Vec<(String, Vec<Option<String>>)> {
+    let mut rows: Vec<(String, Vec<Option<String>>)> = Vec::new();
+    let mut row_index: HashMap<String, usize> = HashMap::new();
+    for (i, info) in infos.iter().enumerate() {
+        for line in info.to_string().lines().skip(1) {
+            let (key, value) = line.rsplit_once('\t').unwrap();
+            let r = *row_index.entry(key.to_string()).or_insert_with(|| {
+                rows.push((key.to_string(), vec![None; infos.len()]));
+                rows.len() - 1
+            });
+            rows[r].1[i] = Some(value.to_string());
+        }
+    }
+    rows
+}
+
+// difference b - a of two values of an info table, if both are numbers
+pub fn info_delta(a: &str, b: &str) -> Option<String> {
+    if let (Ok(a), Ok(b)) = (a.parse::<i64>(), b.parse::<i64>()) {
+        return Some((b - a).to_string());
+    }
+    // the difference is given with the precision of the more precise value
+    let precision = |x: &str| x.split_once('.').map(|(_, f)| f.len()).unwrap_or(0);
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(x), Ok(y)) => Some(format!(
+            "{:.*}",
+            usize::max(precision(a), precision(b)),
+            y - x
+        )),
+        _ => None,
+    }
+}
+
+#[derive(Serialize)]
 pub struct Info {
     pub graph_info: GraphInfo,
     pub path_info: PathInfo,
     pub group_info: Option<GroupInfo>,
+    pub sample_info: SampleInfo,
 }
 
 impl fmt::Display for Info {
@@ -809,6 +1490,11 @@ impl fmt::Display for Info {
         writeln!(f, "node\tN50 node\tbp\t{}", self.graph_info.n50_node)?;
         writeln!(f, "node\tmax\tdegree\t{}", self.graph_info.max_degree)?;
         writeln!(f, "node\tmin\tdegree\t{}", self.graph_info.min_degree)?;
+        if let Some(hist) = &self.graph_info.degree_distribution {
+            for (d, n) in hist.iter().enumerate() {
+                writeln!(f, "degree\t{}\tnode\t{}", d, n)?;
+            }
+        }
         writeln!(f, "path\taverage\tbp\t{}", self.path_info.bp_len.average)?;
         writeln!(
             f,
@@ -835,6 +1521,405 @@ impl fmt::Display for Info {
                 write!(f, "group\t{}\tnode\t{}", k, v.0)?;
             }
         }
+        let sample_info = &self.sample_info;
+        if let Some(n) = sample_info.path_records {
+            write!(f, "\nsample\ttotal\tP-line\t{}", n)?;
+        }
+        if let Some(n) = sample_info.walk_records {
+            write!(f, "\nsample\ttotal\tW-line\t{}", n)?;
+        }
+        write!(f, "\nsample\ttotal\tsample\t{}", sample_info.sample_count)?;
+        write!(
+            f,
+            "\nsample\ttotal\thaplotype\t{}",
+            sample_info.haplotype_count
+        )?;
+        for s in &sample_info.samples {
+            write!(f, "\nsample\t{}\tpath\t{}", s.sample, s.paths)?;
+            write!(f, "\nsample\t{}\tnode\t{}", s.sample, s.node_len)?;
+            write!(f, "\nsample\t{}\tbp\t{}", s.sample, s.bp_len)?;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abacus::{AbacusAuxilliary, AbacusOptions};
+    use crate::io::{bufreader_from_compressed_gfa, parse_gfa_paths_walks, set_best_effort};
+
+    #[test]
+    fn test_path_segment_pansn_delimiter() {
+        let path = PathSegment::from_str("a|1|chr1:5-10", '|');
+        assert_eq!(path.sample, "a");
+        assert_eq!(path.haplotype.as_deref(), Some("1"));
+        assert_eq!(path.seqid.as_deref(), Some("chr1"));
+        assert_eq!(path.coords(), Some((5, 10)));
+        assert_eq!(path.to_string(), "a|1|chr1:5-10");
+        // the sequence may contain the delimiter, but not start with it
+        assert_eq!(
+            PathSegment::from_str("a|1|chr|1", '|').seqid.as_deref(),
+            Some("chr|1")
+        );
+        assert_eq!(PathSegment::from_str("a||chr1", '|').sample, "a||chr1");
+        // names are not split by any other delimiter
+        assert_eq!(PathSegment::from_str("a|1|chr1", '#').sample, "a|1|chr1");
+    }
+
+    #[test]
+    fn test_graph_auxilliary_sample_info() {
+        let graph_aux = GraphAuxilliary::from_gfa("test/cdbg.gfa", CountType::Node, '#').unwrap();
+        let paths_len: HashMap<PathSegment, (u64, u64)> = HashMap::from([
+            (PathSegment::from_str("a#1#h1", '#'), (3, 17)),
+            (PathSegment::from_str("c#1#h1", '#'), (2, 12)),
+            (PathSegment::from_str("c#1#h2", '#'), (1, 5)),
+            (PathSegment::from_str("c#2#h1", '#'), (4, 24)),
+        ]);
+        let sample_info = graph_aux.sample_info(&paths_len);
+        assert_eq!(sample_info.sample_count, 2);
+        assert_eq!(sample_info.haplotype_count, 3);
+        assert_eq!(sample_info.path_records, None);
+        let c = &sample_info.samples[1];
+        assert_eq!(
+            (c.sample.as_str(), c.paths, c.node_len, c.bp_len),
+            ("c", 3, 7, 41)
+        );
+    }
+
+    #[test]
+    fn test_graph_auxilliary_degree_distribution() {
+        let graph_aux = GraphAuxilliary::parse_gfa("test/cdbg.gfa", true, '#').unwrap();
+        let hist = graph_aux.degree_distribution().unwrap();
+        assert_eq!(hist.iter().sum::<usize>(), graph_aux.node_count);
+        let degree = graph_aux.degree.as_ref().unwrap();
+        assert_eq!(hist.len(), *degree.iter().max().unwrap() as usize + 1);
+        assert_eq!(
+            GraphAuxilliary::parse_gfa("test/cdbg.gfa", false, '#')
+                .unwrap()
+                .degree_distribution(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_graph_auxilliary_bubbles() {
+        // superbubble 1 -> 6 with nested simple bubble 2 -> 5 (SNP 3/4) and a deletion edge
+        // 1 -> 6, followed by simple bubble 6 -> 8 whose sink is entered in reverse orientation
+        let gfa_file = std::env::temp_dir().join("panacus_test_bubbles.gfa");
+        let gfa_file = gfa_file.to_str().unwrap();
+        fs::write(
+            gfa_file,
+            "S\t1\tA\nS\t2\tCC\nS\t3\tG\nS\t4\tT\nS\t5\tAA\nS\t6\tC\nS\t7\tGGG\nS\t8\tT\n\
+             L\t1\t+\t2\t+\t0M\nL\t2\t+\t3\t+\t0M\nL\t2\t+\t4\t+\t0M\nL\t3\t+\t5\t+\t0M\n\
+             L\t4\t+\t5\t+\t0M\nL\t5\t+\t6\t+\t0M\nL\t1\t+\t6\t+\t0M\nL\t6\t+\t7\t+\t0M\n\
+             L\t7\t+\t8\t-\t0M\nL\t8\t+\t6\t-\t0M\n",
+        )
+        .unwrap();
+        let graph_aux = GraphAuxilliary::parse_gfa(gfa_file, true, '#').unwrap();
+        fs::remove_file(gfa_file).unwrap();
+
+        let node = |n: &str| graph_aux.node2id[n.as_bytes()];
+        let bubbles = graph_aux.bubbles(1000);
+        let summary: Vec<_> = bubbles
+            .iter()
+            .map(|b| (b.source, b.sink, b.nodes, b.bp, b.alleles, b.simple))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    (node("1"), Orientation::Forward),
+                    (node("6"), Orientation::Forward),
+                    4,
+                    6,
+                    3,
+                    false
+                ),
+                (
+                    (node("2"), Orientation::Forward),
+                    (node("5"), Orientation::Forward),
+                    2,
+                    2,
+                    2,
+                    true
+                ),
+                (
+                    (node("6"), Orientation::Forward),
+                    (node("8"), Orientation::Backward),
+                    1,
+                    3,
+                    2,
+                    true
+                ),
+            ]
+        );
+        // the search for the outer superbubble is abandoned early
+        assert_eq!(graph_aux.bubbles(3).len(), 2);
+    }
+
+    #[test]
+    fn test_compare_infos() {
+        let graph_aux = GraphAuxilliary::from_gfa("test/cdbg.gfa", CountType::All, '#').unwrap();
+        let groups = HashMap::new();
+        let info = |paths_len: &[(&str, (u64, u64))]| {
+            let paths_len: HashMap<_, _> = paths_len
+                .iter()
+                .map(|(p, l)| (PathSegment::from_str(p, '#'), *l))
+                .collect();
+            graph_aux.info(&paths_len, &groups, false)
+        };
+        let rows = compare_infos(&[
+            &info(&[("a#1#h1", (3, 17))]),
+            &info(&[("a#1#h1", (3, 17)), ("b#1#h1", (2, 12))]),
+        ]);
+        let row = |key: &str| &rows.iter().find(|(k, _)| k == key).unwrap().1;
+        assert_eq!(
+            row("graph\ttotal\tpath"),
+            &vec![Some("1".to_string()), Some("2".to_string())]
+        );
+        assert_eq!(row("sample\tb\tpath"), &vec![None, Some("1".to_string())]);
+
+        assert_eq!(info_delta("2", "5"), Some("3".to_string()));
+        assert_eq!(info_delta("2.5", "2"), Some("-0.5".to_string()));
+        assert_eq!(info_delta("16.166666", "16"), Some("-0.166666".to_string()));
+        assert_eq!(info_delta("2", "n/a"), None);
+    }
+
+    #[test]
+    fn test_graph_auxilliary_malformed_graph() {
+        let gfa_file = std::env::temp_dir().join("panacus_test_malformed_graph.gfa");
+        let gfa_file = gfa_file.to_str().unwrap();
+        // links to unknown segments...
+        fs::write(gfa_file, "S\t1\tACG\nS\t2\tT\nL\t1\t+\t3\t+\t*\n").unwrap();
+        let err = GraphAuxilliary::parse_gfa(gfa_file, true, '#').unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line 3: unknown node 3");
+        // ...and duplicate segments are reported as parse errors
+        fs::write(gfa_file, "S\t1\tACG\nS\t2\tT\nS\t1\tA\n").unwrap();
+        let err = GraphAuxilliary::parse_gfa(gfa_file, false, '#').unwrap_err();
+        fs::remove_file(gfa_file).unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "line 3: segment 1 occurs multiple times in GFA"
+        );
+    }
+
+    #[test]
+    fn test_graph_auxilliary_corrupt_records() {
+        let gfa_file = std::env::temp_dir().join("panacus_test_corrupt_records.gfa");
+        let gfa_file = gfa_file.to_str().unwrap();
+        fs::write(gfa_file, "S\t1\tACG\nS\t2\nS\t3\tT\nP\tp\t1+,3+\t*\n").unwrap();
+
+        // a file cut off in the middle of the sequence of a path
+        let truncated_file = std::env::temp_dir().join("panacus_test_truncated_path.gfa");
+        let truncated_file = truncated_file.to_str().unwrap();
+        fs::write(
+            truncated_file,
+            "S\t1\tACG\nS\t2\tT\nP\tp\t1+,2+\t*\nP\tq\t1+,",
+        )
+        .unwrap();
+
+        // corrupt records are detected while indexing the graph...
+        let err = GraphAuxilliary::parse_gfa(gfa_file, false, '#').unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+        let err = GraphAuxilliary::parse_gfa(truncated_file, false, '#').unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "corrupt GFA record at byte 26 (line 4): unexpected end of file in the middle of the record (path record lacks its overlaps column); last complete record: line 3: P p 1+,2+ *"
+        );
+        // ...and skipped in best-effort mode
+        set_best_effort(true);
+        let graph_aux = GraphAuxilliary::parse_gfa(gfa_file, false, '#');
+        let truncated_aux = GraphAuxilliary::parse_gfa(truncated_file, false, '#');
+        let tables = truncated_aux.as_ref().map(|graph_aux| {
+            let abacus_aux = AbacusAuxilliary::from_options(
+                &AbacusOptions {
+                    gfa_file: truncated_file.to_string(),
+                    ..Default::default()
+                },
+                graph_aux,
+            )
+            .unwrap();
+            let mut data = bufreader_from_compressed_gfa(truncated_file).unwrap();
+            parse_gfa_paths_walks(&mut data, &abacus_aux, graph_aux, &CountType::Node)
+        });
+        set_best_effort(false);
+        fs::remove_file(gfa_file).unwrap();
+        fs::remove_file(truncated_file).unwrap();
+        assert_eq!(graph_aux.unwrap().node_count, 2);
+        let (_, _, _, paths_len) = tables.unwrap().unwrap();
+        assert_eq!(paths_len.len(), 1);
+        assert_eq!(
+            truncated_aux.unwrap().path_segments,
+            vec![PathSegment::from_str("p", '#')]
+        );
+    }
+
+    #[test]
+    fn test_path_info_without_paths() {
+        let graph_aux = GraphAuxilliary::from_gfa("test/cdbg.gfa", CountType::Node, '#').unwrap();
+        let path_info = graph_aux.path_info(&HashMap::new());
+        assert_eq!(path_info.no_paths, 0);
+        assert_eq!(path_info.node_len.longest, 0);
+        assert_eq!(path_info.bp_len.average, 0.0);
+    }
+
+    #[test]
+    fn test_link_arena() {
+        let mut links = LinkArena::default();
+        links.push(3, b"L\t1\t+\t2\t-");
+        links.push(7, b"L\t2\t+\t10\t+");
+        let expected: Vec<(usize, &[u8])> = vec![(3, b"L\t1\t+\t2\t-"), (7, b"L\t2\t+\t10\t+")];
+        assert_eq!(links.iter().collect::<Vec<_>>(), expected);
+        // all links share a single buffer
+        assert_eq!(links.data.len(), 19);
+    }
+
+    #[test]
+    fn test_parse_nodes_gfa_chunked() {
+        let gfa_file = "test/cdbg.gfa";
+        let gz_file = std::env::temp_dir().join("panacus_test_chunked.gfa.gz");
+        let gz_file = gz_file.to_str().unwrap();
+        let mut gz = GzEncoder::new(fs::File::create(gz_file).unwrap(), Compression::default());
+        gz.write_all(&fs::read(gfa_file).unwrap()).unwrap();
+        gz.finish().unwrap();
+
+        let parse = |file: &str, chunk_size: usize| {
+            let mut links = LinkArena::default();
+            let (node2id, path_segments, node_lens, _) = GraphAuxilliary::parse_nodes_gfa_chunked(
+                &[file.to_string()],
+                None,
+                Some(&mut links),
+                '#',
+                chunk_size,
+            )
+            .unwrap();
+            let links: Vec<(usize, Vec<u8>)> =
+                links.iter().map(|(l, link)| (l, link.to_vec())).collect();
+            (node2id, path_segments, node_lens, links)
+        };
+        let expected = parse(gfa_file, GFA_CHUNK_SIZE);
+        // node IDs follow the order of S-lines in the file
+        for i in 1..6 {
+            assert_eq!(expected.0[i.to_string().as_bytes()], ItemId(i));
+        }
+        assert_eq!(expected.0.len(), 5);
+        assert_eq!(expected.1.len(), 6);
+        assert_eq!(expected.3.len(), 13);
+        // chunks end within records of both uncompressed (memory-mapped) and compressed files,
+        // which must nevertheless be parsed exactly once and in order
+        for chunk_size in [1, 7, 16, 50] {
+            for file in [gfa_file, gz_file] {
+                assert_eq!(
+                    parse(file, chunk_size),
+                    expected,
+                    "chunk size {}",
+                    chunk_size
+                );
+            }
+        }
+        fs::remove_file(gz_file).unwrap();
+    }
+
+    #[test]
+    fn test_graph_auxilliary_links_before_segments() {
+        let gfa_file = std::env::temp_dir().join("panacus_test_links_before_segments.gfa");
+        let gfa_file = gfa_file.to_str().unwrap();
+        fs::write(
+            gfa_file,
+            "L\t2\t-\t1\t-\t0M\tID:Z:x\nS\t1\tACG\nS\t2\tT\nL\t1\t+\t2\t+\t*\n",
+        )
+        .unwrap();
+        let graph_aux = GraphAuxilliary::parse_gfa(gfa_file, true, '#').unwrap();
+        fs::remove_file(gfa_file).unwrap();
+
+        // both links describe the same edge
+        assert_eq!(graph_aux.edge_count, 1);
+        assert_eq!(
+            graph_aux.edge2id,
+            Some(HashMap::from([(
+                Edge(
+                    ItemId(1),
+                    Orientation::Forward,
+                    ItemId(2),
+                    Orientation::Forward
+                ),
+                ItemId(1)
+            )]))
+        );
+        assert_eq!(graph_aux.degree, Some(vec![0, 1, 1]));
+    }
+
+    #[test]
+    fn test_graph_auxilliary_path_info_long_paths() {
+        let graph_aux = GraphAuxilliary::parse_gfa("test/cdbg.gfa", false, '#').unwrap();
+        let paths_len = HashMap::from([
+            (PathSegment::from_str("a#1#h1", '#'), (3, 6_000_000_000)),
+            (PathSegment::from_str("b#1#h1", '#'), (5, 4_000_000_000)),
+        ]);
+        let path_info = graph_aux.path_info(&paths_len);
+        assert_eq!(path_info.bp_len.longest, 6_000_000_000);
+        assert_eq!(path_info.bp_len.shortest, 4_000_000_000);
+        assert_eq!(path_info.bp_len.average, 5e9);
+
+        let groups = HashMap::from([
+            (PathSegment::from_str("a#1#h1", '#'), "G".to_string()),
+            (PathSegment::from_str("b#1#h1", '#'), "G".to_string()),
+        ]);
+        let group_info = graph_aux.group_info(&paths_len, &groups);
+        assert_eq!(group_info.groups["G"], (8, 10_000_000_000));
+    }
+
+    #[test]
+    fn test_graph_auxilliary_index() {
+        let graph_aux = GraphAuxilliary::parse_gfa("test/cdbg.gfa", true, '#').unwrap();
+        let mut data = Vec::new();
+        graph_aux.write_index("test/cdbg.gfa", &mut data).unwrap();
+
+        let index =
+            GraphAuxilliary::read_index(MultiGzDecoder::new(&data[..]), "test/cdbg.gfa", true, '#')
+                .unwrap();
+        assert_eq!(index.node2id, graph_aux.node2id);
+        assert_eq!(index.node_lens, graph_aux.node_lens);
+        assert_eq!(index.edge2id, graph_aux.edge2id);
+        assert_eq!(index.path_segments, graph_aux.path_segments);
+        assert_eq!(index.edge_count, graph_aux.edge_count);
+        assert_eq!(index.degree, graph_aux.degree);
+
+        let index = GraphAuxilliary::read_index(
+            MultiGzDecoder::new(&data[..]),
+            "test/cdbg.gfa",
+            false,
+            '#',
+        )
+        .unwrap();
+        assert_eq!(index.node_count, graph_aux.node_count);
+        assert!(index.edge2id.is_none());
+    }
+
+    #[test]
+    fn test_graph_auxilliary_stale_index() {
+        let gfa_file = std::env::temp_dir().join("panacus_test_stale_index.gfa");
+        let gfa_file = gfa_file.to_str().unwrap();
+        fs::copy("test/cdbg.gfa", gfa_file).unwrap();
+        let graph_aux = GraphAuxilliary::parse_gfa(gfa_file, true, '#').unwrap();
+        let mut data = Vec::new();
+        graph_aux.write_index(gfa_file, &mut data).unwrap();
+        assert!(
+            GraphAuxilliary::read_index(MultiGzDecoder::new(&data[..]), gfa_file, true, '#')
+                .is_ok()
+        );
+
+        // a GFA file modified while keeping an older timestamp (e.g., by cp -p) is detected
+        let mut gfa = fs::OpenOptions::new().append(true).open(gfa_file).unwrap();
+        gfa.write_all(b"P\tx\t1+\t*\n").unwrap();
+        gfa.set_modified(UNIX_EPOCH).unwrap();
+        drop(gfa);
+        let res = GraphAuxilliary::read_index(MultiGzDecoder::new(&data[..]), gfa_file, true, '#');
+        fs::remove_file(gfa_file).unwrap();
+        assert!(res.is_err());
+    }
+}
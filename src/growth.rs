@@ -0,0 +1,160 @@
+// Growth curves calculated from coverage histograms. The functions in this module operate on
+// plain slices and numbers only and use neither file I/O, logging, nor threads, so that they can
+// be compiled on their own; growth-wasm/ compiles them to WebAssembly for recomputing growth in
+// the HTML report.
+//
+// Throughout, coverage[i] is the number of countables covered by exactly i groups, i.e., a
+// histogram over n groups has length n+1. The returned growth curves have length n, where the
+// (m-1)-th entry is the expected number of countables among m groups.
+
+// binomial coefficient "n choose k" in log2 space
+pub fn choose(n: usize, k: usize) -> f64 {
+    let mut res: f64 = 0.0;
+    if k > n {
+        return 0.0;
+    }
+
+    let k = if k > n - k { n - k } else { k };
+
+    let n = n as f64;
+
+    for i in 0..k {
+        res += (n - i as f64).log2();
+        res -= (i as f64 + 1.0).log2();
+    }
+    res
+}
+
+// growth of countables that are covered by at least c groups and by at least the given fraction
+// of the groups drawn (quorum between 0 and 1)
+pub fn growth(coverage: &[usize], c: usize, quorum: f64) -> Vec<f64> {
+    let n = coverage.len().saturating_sub(1);
+    if n == 0 {
+        return Vec::new();
+    }
+    let q = usize::max(1, (n as f64 * quorum).ceil() as usize);
+    if q == 1 {
+        growth_union(coverage, c)
+    } else if q >= n {
+        growth_core(coverage, c)
+    } else {
+        growth_quorum(coverage, c, quorum)
+    }
+}
+
+pub fn growth_union(coverage: &[usize], c: usize) -> Vec<f64> {
+    let n = coverage.len() - 1; // hist array has length n+1: from 0..n (both included)
+    let c = usize::max(1, c);
+
+    let mut pangrowth: Vec<f64> = vec![0.0; n];
+    let mut n_fall_m: f64 = 0.0;
+    let tot = coverage[usize::min(c, n + 1)..].iter().sum::<usize>() as f64;
+
+    // perc_mult[i] contains the percentage of combinations that
+    // have an item of multiplicity i
+    let mut perc_mult: Vec<f64> = Vec::with_capacity(n + 1);
+    perc_mult.resize(n + 1, 0.0);
+
+    for m in 1..n + 1 {
+        let mut y: f64 = 0.0;
+        n_fall_m += (n as f64 - m as f64 + 1.0).log2();
+        for i in c..n - m + 1 {
+            perc_mult[i] += (n as f64 - m as f64 - i as f64 + 1.0).log2();
+            y += ((coverage[i] as f64).log2() + perc_mult[i] - n_fall_m).exp2();
+        }
+
+        pangrowth[m - 1] = tot - y;
+    }
+
+    pangrowth
+}
+
+pub fn growth_core(coverage: &[usize], c: usize) -> Vec<f64> {
+    let n = coverage.len() - 1; // hist array has length n+1: from 0..n (both included)
+    let c = usize::max(1, c);
+    let mut n_fall_m: f64 = 0.0;
+    let mut pangrowth: Vec<f64> = vec![0.0; n];
+
+    // In perc_mult[i] is contained the percentage of combinations
+    // that have an item of multiplicity i
+    let mut perc_mult: Vec<f64> = Vec::with_capacity(n + 1);
+    perc_mult.resize(n + 1, 0.0);
+
+    for m in 1..n + 1 {
+        let mut y: f64 = 0.0;
+        n_fall_m += (n as f64 - m as f64 + 1.0).log2();
+        for i in usize::max(m, c)..n + 1 {
+            perc_mult[i] += (i as f64 - m as f64 + 1.0).log2();
+            y += ((coverage[i] as f64).log2() + perc_mult[i] - n_fall_m).exp2();
+        }
+        pangrowth[m - 1] = y;
+    }
+
+    pangrowth
+}
+
+pub fn growth_quorum(coverage: &[usize], c: usize, quorum: f64) -> Vec<f64> {
+    let n = coverage.len() - 1; // hist array has length n+1: from [0..n]
+    let c = usize::max(1, c);
+    let mut pangrowth: Vec<f64> = vec![0.0; n];
+
+    let mut n_fall_m: f64 = 0.0;
+    let mut m_fact: f64 = 0.0;
+
+    let mut perc_mult: Vec<f64> = vec![0.0; n + 1];
+    let mut q: Vec<Vec<f64>> = vec![vec![0.0; n + 1]; n + 1];
+
+    for m in 1..n + 1 {
+        m_fact += (m as f64).log2();
+        let m_quorum = (m as f64 * quorum).ceil() as usize;
+
+        //100% quorum
+        let mut yl: f64 = 0.0;
+        n_fall_m += (n as f64 - m as f64 + 1.0).log2();
+        for i in usize::max(m, c)..n + 1 {
+            perc_mult[i] += (i as f64 - m as f64 + 1.0).log2();
+            yl += ((coverage[i] as f64).log2() + perc_mult[i] - n_fall_m).exp2();
+        }
+
+        //[m_quorum, 100) quorum
+        let mut yr: f64 = 0.0;
+        for i in m_quorum..n {
+            let mut sum_q = 0.0;
+            let mut add = false;
+            for j in usize::max(m_quorum, c)..m {
+                if n + j + 1 > i + m && j <= i {
+                    if q[i][j] == 0.0 {
+                        q[i][j] = choose(i, j);
+                    }
+                    q[i][j] += (n as f64 - i as f64 - m as f64 + 1.0 + j as f64).log2();
+                    q[i][j] -= (m as f64 - j as f64).log2();
+                    sum_q += (q[i][j] + m_fact - n_fall_m).exp2();
+                    add = true;
+                }
+            }
+            if add {
+                yr += ((coverage[i] as f64).log2() + sum_q.log2()).exp2();
+            }
+        }
+        pangrowth[m - 1] = yl + yr;
+    }
+    pangrowth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_growth() {
+        let coverage = vec![0, 3, 2, 0, 4];
+        assert_eq!(growth(&coverage, 1, 0.0), growth_union(&coverage, 1));
+        assert_eq!(growth(&coverage, 1, 1.0), growth_core(&coverage, 1));
+        assert_eq!(growth(&coverage, 1, 0.5), growth_quorum(&coverage, 1, 0.5));
+        // all countables are covered once all groups are drawn, but only the ones covered by
+        // all groups are core
+        assert!((growth(&coverage, 1, 0.0)[3] - 9.0).abs() < 1e-9);
+        assert!((growth(&coverage, 1, 1.0)[3] - 4.0).abs() < 1e-9);
+        assert!(growth(&[0], 1, 0.0).is_empty());
+    }
+}
@@ -4,12 +4,15 @@ use std::io::{Error, ErrorKind};
 
 /* external crate */
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 /* private use */
 use crate::abacus::AbacusByTotal;
 use crate::cli;
-use crate::graph::GraphAuxilliary;
-use crate::util::{CountType, Threshold};
+use crate::graph::{GraphAuxilliary, Info, Orientation};
+use crate::growth;
+pub use crate::growth::choose;
+use crate::util::{CountType, HistOperation, Threshold};
 
 #[derive(Debug, Clone)]
 pub struct Hist {
@@ -17,21 +20,16 @@ pub struct Hist {
     pub coverage: Vec<usize>,
 }
 
-pub fn choose(n: usize, k: usize) -> f64 {
-    let mut res: f64 = 0.0;
-    if k > n {
-        return 0.0;
+// probability that a random draw of k out of n elements, r of which are marked, contains at
+// least x marked elements (hypergeometric tail)
+pub fn hypergeom_at_least(n: usize, r: usize, k: usize, x: usize) -> f64 {
+    if x == 0 {
+        return 1.0;
     }
-
-    let k = if k > n - k { n - k } else { k };
-
-    let n = n as f64;
-
-    for i in 0..k {
-        res += (n - i as f64).log2();
-        res -= (i as f64 + 1.0).log2();
-    }
-    res
+    let total = choose(n, k);
+    (usize::max(x, (k + r).saturating_sub(n))..usize::min(r, k) + 1)
+        .map(|i| (choose(r, i) + choose(n - r, k - i) - total).exp2())
+        .sum()
 }
 
 impl Hist {
@@ -47,6 +45,20 @@ impl Hist {
         }
     }
 
+    // Node coverage histogram of the given groups, where covered[i][j] tells whether group i
+    // covers the node of ID j (ID 0 is not assigned to any node)
+    pub fn from_covered_nodes(covered: &[Vec<bool>]) -> Self {
+        let mut coverage = vec![0; covered.len() + 1];
+        let node_count = covered.first().map(|c| c.len()).unwrap_or(1) - 1;
+        for j in 1..=node_count {
+            coverage[covered.iter().filter(|c| c[j]).count()] += 1;
+        }
+        Self {
+            count: CountType::Node,
+            coverage,
+        }
+    }
+
     pub fn calc_growth(&self, t_coverage: &Threshold, t_quorum: &Threshold) -> Vec<f64> {
         let n = self.coverage.len() - 1;
 
@@ -71,11 +83,17 @@ impl Hist {
             .zip(&hist_aux.quorum)
             .map(|(c, q)| {
                 log::info!(
-                    "calculating growth for coverage >= {} and quorum >= {}",
+                    "calculating growth for coverage >= {} and quorum >= {}{}",
                     &c,
-                    &q
+                    &q,
+                    if hist_aux.apriori { " (a priori)" } else { "" }
                 );
-                self.calc_growth(c, q)
+                if hist_aux.apriori {
+                    self.apply_coverage_threshold(c)
+                        .calc_growth(&Threshold::Absolute(1), q)
+                } else {
+                    self.calc_growth(c, q)
+                }
             })
             .collect();
         // insert empty row for 0 element
@@ -85,104 +103,37 @@ impl Hist {
         growths
     }
 
-    pub fn calc_growth_union(&self, t_coverage: &Threshold) -> Vec<f64> {
-        let n = self.coverage.len() - 1; // hist array has length n+1: from 0..n (both included)
-        let c = usize::max(1, t_coverage.to_absolute(n));
-
-        let mut pangrowth: Vec<f64> = vec![0.0; n];
-        let mut n_fall_m: f64 = 0.0;
-        let tot = self.coverage[c..].iter().sum::<usize>() as f64;
-
-        // perc_mult[i] contains the percentage of combinations that
-        // have an item of multiplicity i
-        let mut perc_mult: Vec<f64> = Vec::with_capacity(n + 1);
-        perc_mult.resize(n + 1, 0.0);
-
-        for m in 1..n + 1 {
-            let mut y: f64 = 0.0;
-            n_fall_m += (n as f64 - m as f64 + 1.0).log2();
-            for i in c..n - m + 1 {
-                perc_mult[i] += (n as f64 - m as f64 - i as f64 + 1.0).log2();
-                y += ((self.coverage[i] as f64).log2() + perc_mult[i] - n_fall_m).exp2();
-            }
-
-            pangrowth[m - 1] = tot - y;
+    // histogram of the countables whose coverage across all groups reaches the threshold
+    pub fn apply_coverage_threshold(&self, t_coverage: &Threshold) -> Self {
+        let c = t_coverage.to_absolute(self.coverage.len() - 1);
+        Self {
+            count: self.count,
+            coverage: self
+                .coverage
+                .iter()
+                .enumerate()
+                .map(|(i, x)| if i < c { 0 } else { *x })
+                .collect(),
         }
+    }
 
-        pangrowth
+    pub fn calc_growth_union(&self, t_coverage: &Threshold) -> Vec<f64> {
+        let n = self.coverage.len() - 1; // hist array has length n+1: from 0..n (both included)
+        growth::growth_union(&self.coverage, t_coverage.to_absolute(n))
     }
 
     pub fn calc_growth_core(&self, t_coverage: &Threshold) -> Vec<f64> {
         let n = self.coverage.len() - 1; // hist array has length n+1: from 0..n (both included)
-        let c = usize::max(1, t_coverage.to_absolute(n + 1));
-        let mut n_fall_m: f64 = 0.0;
-        let mut pangrowth: Vec<f64> = vec![0.0; n];
-
-        // In perc_mult[i] is contained the percentage of combinations
-        // that have an item of multiplicity i
-        let mut perc_mult: Vec<f64> = Vec::with_capacity(n + 1);
-        perc_mult.resize(n + 1, 0.0);
-
-        for m in 1..n + 1 {
-            let mut y: f64 = 0.0;
-            n_fall_m += (n as f64 - m as f64 + 1.0).log2();
-            for i in usize::max(m, c)..n + 1 {
-                perc_mult[i] += (i as f64 - m as f64 + 1.0).log2();
-                y += ((self.coverage[i] as f64).log2() + perc_mult[i] - n_fall_m).exp2();
-            }
-            pangrowth[m - 1] = y;
-        }
-
-        pangrowth
+        growth::growth_core(&self.coverage, t_coverage.to_absolute(n + 1))
     }
 
     pub fn calc_growth_quorum(&self, t_coverage: &Threshold, t_quorum: &Threshold) -> Vec<f64> {
         let n = self.coverage.len() - 1; // hist array has length n+1: from [0..n]
-        let c = usize::max(1, t_coverage.to_absolute(n));
-        let quorum = t_quorum.to_relative(n);
-        let mut pangrowth: Vec<f64> = vec![0.0; n];
-
-        let mut n_fall_m: f64 = 0.0;
-        let mut m_fact: f64 = 0.0;
-
-        let mut perc_mult: Vec<f64> = vec![0.0; n + 1];
-        let mut q: Vec<Vec<f64>> = vec![vec![0.0; n + 1]; n + 1];
-
-        for m in 1..n + 1 {
-            m_fact += (m as f64).log2();
-            let m_quorum = (m as f64 * quorum).ceil() as usize;
-
-            //100% quorum
-            let mut yl: f64 = 0.0;
-            n_fall_m += (n as f64 - m as f64 + 1.0).log2();
-            for i in usize::max(m, c)..n + 1 {
-                perc_mult[i] += (i as f64 - m as f64 + 1.0).log2();
-                yl += ((self.coverage[i] as f64).log2() + perc_mult[i] - n_fall_m).exp2();
-            }
-
-            //[m_quorum, 100) quorum
-            let mut yr: f64 = 0.0;
-            for i in m_quorum..n {
-                let mut sum_q = 0.0;
-                let mut add = false;
-                for j in usize::max(m_quorum, c)..m {
-                    if n + j + 1 > i + m && j <= i {
-                        if q[i][j] == 0.0 {
-                            q[i][j] = choose(i, j);
-                        }
-                        q[i][j] += (n as f64 - i as f64 - m as f64 + 1.0 + j as f64).log2();
-                        q[i][j] -= (m as f64 - j as f64).log2();
-                        sum_q += (q[i][j] + m_fact - n_fall_m).exp2();
-                        add = true;
-                    }
-                }
-                if add {
-                    yr += ((self.coverage[i] as f64).log2() + sum_q.log2()).exp2();
-                }
-            }
-            pangrowth[m - 1] = yl + yr;
-        }
-        pangrowth
+        growth::growth_quorum(
+            &self.coverage,
+            t_coverage.to_absolute(n),
+            t_quorum.to_relative(n),
+        )
     }
 
     #[allow(dead_code)]
@@ -196,37 +147,512 @@ impl Hist {
     }
 }
 
+// Coverage histogram of the edges of one orientation, e.g., `><` for edges that connect the ends
+// of two nodes; orientations refer to edges in canonical form, i.e., leading from the node with
+// the smaller numerical ID to the other one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdgeOrientationHist {
+    pub orientation: String,
+    pub coverage: Vec<usize>,
+}
+
+impl EdgeOrientationHist {
+    pub fn from_abacus(abacus: &AbacusByTotal, graph_aux: &GraphAuxilliary) -> Vec<Self> {
+        log::info!("constructing edge histogram by orientation..");
+        let edge2id = graph_aux
+            .edge2id
+            .as_ref()
+            .expect("edge orientations require edge2id map in GraphAuxilliary");
+        let mut orientations = vec![None; edge2id.len() + 1];
+        for (e, id) in edge2id.iter() {
+            if let Some(o) = orientations.get_mut(id.0 as usize) {
+                *o = Some((e.1, e.3));
+            }
+        }
+
+        let classes = [
+            (Orientation::Forward, Orientation::Forward),
+            (Orientation::Forward, Orientation::Backward),
+            (Orientation::Backward, Orientation::Forward),
+            (Orientation::Backward, Orientation::Backward),
+        ];
+        let mut res: Vec<Self> = classes
+            .iter()
+            .map(|(o1, o2)| Self {
+                orientation: format!("{}{}", o1, o2),
+                coverage: vec![0; abacus.groups.len() + 1],
+            })
+            .collect();
+        for (id, cov) in abacus.countable.iter().enumerate() {
+            if let Some(Some(o)) = orientations.get(id) {
+                let i = classes.iter().position(|c| c == o).unwrap();
+                if let Some(c) = res[i].coverage.get_mut(*cov as usize) {
+                    *c += 1;
+                }
+            }
+        }
+        res
+    }
+}
+
+// Coverage histogram of a single count type, as reported by `hist` and read back by `growth`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistResult {
+    pub count: CountType,
+    pub coverage: Vec<usize>,
+    #[serde(default)]
+    pub metadata: Vec<String>,
+}
+
+impl HistResult {
+    pub fn new(hist: &Hist, metadata: Vec<String>) -> Self {
+        Self {
+            count: hist.count,
+            coverage: hist.coverage.clone(),
+            metadata,
+        }
+    }
+
+    pub fn to_hist(&self) -> Hist {
+        Hist {
+            count: self.count,
+            coverage: self.coverage.clone(),
+        }
+    }
+}
+
+// Combines the coverage histograms of several tables count type by count type, e.g., to merge
+// the histograms of disjoint chunks of a graph (such as per-chromosome graphs) by summation;
+// subtraction removes the histograms of all further tables from those of the first one
+pub fn combine_hist_results(
+    tables: &[Vec<HistResult>],
+    operation: HistOperation,
+) -> Result<Vec<HistResult>, Error> {
+    let mut res = tables[0].clone();
+    for (i, table) in tables.iter().enumerate().skip(1) {
+        if table.len() != res.len() || table.iter().zip(&res).any(|(a, b)| a.count != b.count) {
+            let msg = format!(
+                "count types of table {} ({}) do not match those of the first table ({})",
+                i + 1,
+                table
+                    .iter()
+                    .map(|h| h.count.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                res.iter()
+                    .map(|h| h.count.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            log::error!("{}", &msg);
+            return Err(Error::new(ErrorKind::InvalidData, msg));
+        }
+        for (h, other) in res.iter_mut().zip(table) {
+            // the histogram of n groups reports coverages 0..n
+            if h.coverage.len() != other.coverage.len() {
+                let msg = format!(
+                    "{} histogram of table {} is over {} groups, but that of the first table over {}; histograms can be combined only if they are over the same groups",
+                    h.count,
+                    i + 1,
+                    other.coverage.len() - 1,
+                    h.coverage.len() - 1
+                );
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+            let count = h.count;
+            for (c, (x, y)) in h.coverage.iter_mut().zip(&other.coverage).enumerate() {
+                *x = match operation {
+                    HistOperation::Add => *x + y,
+                    HistOperation::Subtract => x.checked_sub(*y).ok_or_else(|| {
+                        let msg = format!(
+                            "subtracting table {} yields a negative number of {}s of coverage {}",
+                            i + 1,
+                            count,
+                            c
+                        );
+                        log::error!("{}", &msg);
+                        Error::new(ErrorKind::InvalidData, msg)
+                    })?,
+                };
+            }
+        }
+    }
+    Ok(res)
+}
+
+// Growth curves of a single count type, one for each pair of coverage and quorum thresholds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GrowthResult {
+    pub count: CountType,
+    pub coverage: Vec<Threshold>,
+    pub quorum: Vec<Threshold>,
+    // names[i] is the (optional) name of the i-th pair of thresholds
+    #[serde(default, skip_serializing_if = "all_unnamed")]
+    pub names: Vec<Option<String>>,
+    // values[i][k] is the growth under the i-th pair of thresholds for k+1 groups
+    pub values: Vec<Vec<f64>>,
+    #[serde(default)]
+    pub apriori: bool,
+    #[serde(default)]
+    pub summary: Vec<GrowthSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bootstrap: Option<GrowthBootstrap>,
+    // increments[i][k] is the number of countables added by the (k+1)-th group under the i-th pair
+    // of thresholds, if requested; like the reported growth, it is based on whole countables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub increments: Option<Vec<Vec<f64>>>,
+    #[serde(default)]
+    pub metadata: Vec<String>,
+}
+
+impl GrowthResult {
+    pub fn new(
+        count: CountType,
+        columns: &[Vec<f64>],
+        hist_aux: &HistAuxilliary,
+        metadata: Vec<String>,
+    ) -> Self {
+        let values: Vec<Vec<f64>> = columns
+            .iter()
+            .map(|col| col.iter().skip(1).copied().collect())
+            .collect();
+        Self {
+            count,
+            coverage: hist_aux.coverage.clone(),
+            quorum: hist_aux.quorum.clone(),
+            names: hist_aux.names.clone(),
+            increments: if hist_aux.increments {
+                Some(
+                    values
+                        .iter()
+                        .map(|v| {
+                            growth_increments(&v.iter().map(|x| x.floor()).collect::<Vec<f64>>())
+                        })
+                        .collect(),
+                )
+            } else {
+                None
+            },
+            // drop the (undefined) growth for 0 groups so that values are always finite
+            values,
+            apriori: hist_aux.apriori,
+            summary: GrowthSummary::from_columns(columns, hist_aux),
+            bootstrap: None,
+            metadata,
+        }
+    }
+
+    // name of the i-th pair of thresholds, if any
+    pub fn name(&self, i: usize) -> Option<&String> {
+        self.names.get(i).and_then(|n| n.as_ref())
+    }
+
+    // growth columns as produced by `Hist::calc_all_growths`, i.e., including the row for 0
+    // groups
+    pub fn columns(&self) -> Vec<Vec<f64>> {
+        self.values
+            .iter()
+            .map(|v| std::iter::once(f64::NAN).chain(v.iter().copied()).collect())
+            .collect()
+    }
+}
+
+fn all_unnamed(names: &[Option<String>]) -> bool {
+    names.iter().all(|n| n.is_none())
+}
+
+// Fit of Heaps' law to a growth curve, i.e., the N-th group is expected to add k * N^(-alpha) new
+// countables, and the resulting estimate of how many more groups are needed until the growth per
+// group drops below the saturation threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GrowthSummary {
+    pub coverage: Threshold,
+    pub quorum: Threshold,
+    pub saturation: f64,
+    pub k: Option<f64>,
+    pub alpha: Option<f64>,
+    pub genomes_to_saturation: Option<u64>,
+    // total number of groups from which on each group is expected to add less than `saturation`
+    // new countables
+    #[serde(default)]
+    pub saturation_point: Option<u64>,
+}
+
+impl GrowthSummary {
+    // summarizes growth columns as produced by `Hist::calc_all_growths`, i.e., including the row
+    // for 0 groups
+    pub fn from_columns(columns: &[Vec<f64>], hist_aux: &HistAuxilliary) -> Vec<Self> {
+        columns
+            .iter()
+            .zip(&hist_aux.coverage)
+            .zip(&hist_aux.quorum)
+            .map(|((col, c), q)| {
+                let growth = &col[usize::min(1, col.len())..];
+                let fit = fit_heaps_law(growth);
+                Self {
+                    coverage: *c,
+                    quorum: *q,
+                    saturation: hist_aux.saturation,
+                    k: fit.map(|(k, _)| k),
+                    alpha: fit.map(|(_, alpha)| alpha),
+                    genomes_to_saturation: fit.and_then(|(k, alpha)| {
+                        genomes_to_saturation(k, alpha, growth.len(), hist_aux.saturation)
+                    }),
+                    saturation_point: fit
+                        .and_then(|(k, alpha)| saturation_point(k, alpha, hist_aux.saturation)),
+                }
+            })
+            .collect()
+    }
+}
+
+// least-squares fit of k * N^(-alpha) to the increments of a growth curve in log-log space, where
+// growth[i] is the growth for i+1 groups; returns (k, alpha)
+pub fn fit_heaps_law(growth: &[f64]) -> Option<(f64, f64)> {
+    let points: Vec<(f64, f64)> = growth
+        .windows(2)
+        .enumerate()
+        .filter_map(|(i, w)| {
+            let d = w[1] - w[0];
+            if d > 0.0 && d.is_finite() {
+                Some((((i + 2) as f64).ln(), d.ln()))
+            } else {
+                None
+            }
+        })
+        .collect();
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    if sxx == 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    Some(((mean_y - slope * mean_x).exp(), -slope))
+}
+
+// smallest number of groups N from which on each further group is expected to contribute less
+// than `saturation` new countables; None if growth does not slow down
+pub fn saturation_point(k: f64, alpha: f64, saturation: f64) -> Option<u64> {
+    if alpha <= 0.0 {
+        return None;
+    }
+    // k * N^(-alpha) < saturation holds for all N > (k / saturation)^(1 / alpha)
+    let n = (k / saturation).powf(1.0 / alpha).floor() + 1.0;
+    if !n.is_finite() || n >= u64::MAX as f64 {
+        return None;
+    }
+    Some(n as u64)
+}
+
+// number of groups that must be added to the given ones until the next group is expected to
+// contribute less than `saturation` new countables; None if growth does not slow down
+pub fn genomes_to_saturation(k: f64, alpha: f64, groups: usize, saturation: f64) -> Option<u64> {
+    saturation_point(k, alpha, saturation).map(|n| n.saturating_sub(groups as u64))
+}
+
+// number of countables added by each group of a growth curve (starting with 1 group)
+pub fn growth_increments(growth: &[f64]) -> Vec<f64> {
+    growth
+        .iter()
+        .enumerate()
+        .map(|(k, x)| if k == 0 { *x } else { x - growth[k - 1] })
+        .collect()
+}
+
+pub const BOOTSTRAP_PERCENTILES: [f64; 3] = [2.5, 50.0, 97.5];
+
+// Percentiles of growth curves calculated for randomly sampled orders of groups
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GrowthBootstrap {
+    pub samples: usize,
+    pub seed: u64,
+    pub percentiles: Vec<f64>,
+    // values[i][p][k] is the p-th percentile of the growth under the i-th pair of thresholds for
+    // k+1 groups
+    pub values: Vec<Vec<Vec<f64>>>,
+}
+
+impl GrowthBootstrap {
+    // summarizes sampled growth curves, where curves[i][s][k] is the growth of the s-th sample
+    // under the i-th pair of thresholds for k+1 groups
+    pub fn from_samples(curves: &[Vec<Vec<f64>>], seed: u64) -> Self {
+        let values = curves
+            .iter()
+            .map(|samples| {
+                let n = samples.first().map(|s| s.len()).unwrap_or(0);
+                let sorted: Vec<Vec<f64>> = (0..n)
+                    .map(|k| {
+                        let mut x: Vec<f64> = samples.iter().map(|s| s[k]).collect();
+                        x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                        x
+                    })
+                    .collect();
+                BOOTSTRAP_PERCENTILES
+                    .iter()
+                    .map(|p| sorted.iter().map(|x| percentile(x, *p)).collect())
+                    .collect()
+            })
+            .collect();
+        Self {
+            samples: curves.first().map(|c| c.len()).unwrap_or(0),
+            seed,
+            percentiles: BOOTSTRAP_PERCENTILES.to_vec(),
+            values,
+        }
+    }
+}
+
+// p-th percentile (0 <= p <= 100) of sorted values, linearly interpolated between closest ranks
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let x = p / 100.0 * (sorted.len() - 1) as f64;
+    let (lo, hi) = (x.floor() as usize, x.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (x - lo as f64)
+}
+
+// Headline metrics of a pangenome graph, meant for embedding in dataset registries and READMEs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Badge {
+    pub nodes: usize,
+    pub bp: u64,
+    pub groups: usize,
+    pub count: CountType,
+    // share of countables (in %) that are covered by all groups
+    pub core_percent: f64,
+    // exponent gamma of the pangenome growth N^gamma; > 0 means open, close to 0 means closed
+    pub openness: Option<f64>,
+}
+
+impl Badge {
+    pub fn new(hist: &Hist, nodes: usize, bp: u64) -> Self {
+        let groups = hist.coverage.len().saturating_sub(1);
+        let total: usize = hist.coverage.iter().skip(1).sum();
+        let core_percent = if total > 0 {
+            100.0 * hist.coverage[groups] as f64 / total as f64
+        } else {
+            0.0
+        };
+        let openness = fit_heaps_law(&hist.calc_growth_union(&Threshold::Absolute(1)))
+            .map(|(_, alpha)| 1.0 - alpha);
+        Self {
+            nodes,
+            bp,
+            groups,
+            count: hist.count,
+            core_percent,
+            openness,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HistGrowthResults {
+    #[serde(default)]
+    pub hists: Vec<HistResult>,
+    #[serde(default)]
+    pub growths: Vec<GrowthResult>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edge_orientations: Vec<EdgeOrientationHist>,
+}
+
+// Histograms and growth curves calculated from one of several compared histogram files
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GrowthComparison {
+    pub file: String,
+    #[serde(flatten)]
+    pub results: HistGrowthResults,
+}
+
+// Info and growth curves of one of several compared graphs
+#[derive(Serialize)]
+pub struct GraphComparison {
+    pub info: Info,
+    #[serde(flatten)]
+    pub growth: GrowthComparison,
+}
+
 pub struct HistAuxilliary {
     pub quorum: Vec<Threshold>,
     pub coverage: Vec<Threshold>,
+    // optional names of the pairs of coverage and quorum thresholds, e.g., "core" or "shell"
+    pub names: Vec<Option<String>>,
+    pub saturation: f64,
+    // apply coverage thresholds to the coverage across all groups before calculating growth
+    pub apriori: bool,
+    // additionally report the increments of growth curves
+    pub increments: bool,
 }
 
 impl HistAuxilliary {
     pub fn from_params(params: &cli::Params) -> Result<Self, Error> {
         match params {
             cli::Params::Histgrowth {
-                quorum, coverage, ..
+                quorum,
+                coverage,
+                saturation,
+                apriori,
+                increments,
+                ..
             }
             | cli::Params::Growth {
-                quorum, coverage, ..
-            }
-            | cli::Params::OrderedHistgrowth {
-                quorum, coverage, ..
-            } => Self::parse_params(quorum, coverage),
+                quorum,
+                coverage,
+                saturation,
+                apriori,
+                increments,
+                ..
+            } => Ok(Self {
+                increments: *increments,
+                ..Self::parse_params(quorum, coverage, *saturation, *apriori)?
+            }),
+            cli::Params::OrderedHistgrowth {
+                quorum,
+                coverage,
+                saturation,
+                ..
+            } => Self::parse_params(quorum, coverage, *saturation, false),
             _ => Err(Error::new(ErrorKind::Other, "not implemented")),
         }
     }
 
-    fn parse_params(quorum: &str, coverage: &str) -> Result<Self, Error> {
+    pub fn parse_params(
+        quorum: &str,
+        coverage: &str,
+        saturation: f64,
+        apriori: bool,
+    ) -> Result<Self, Error> {
+        if saturation.is_nan() || saturation <= 0.0 {
+            let msg = format!(
+                "saturation must be a positive number, but is {}",
+                saturation
+            );
+            log::error!("{}", &msg);
+            return Err(Error::new(ErrorKind::InvalidInput, msg));
+        }
+
         let mut quorum_thresholds = Vec::new();
         if !quorum.is_empty() {
-            quorum_thresholds = cli::parse_threshold_cli(quorum, cli::RequireThreshold::Relative)?;
+            quorum_thresholds =
+                cli::parse_named_threshold_cli(quorum, cli::RequireThreshold::Relative)?;
             log::debug!(
                 "loaded {} quorum thresholds: {}",
                 quorum_thresholds.len(),
                 quorum_thresholds
                     .iter()
-                    .map(|t| format!("{}", t))
+                    .map(|(_, t)| format!("{}", t))
                     .collect::<Vec<String>>()
                     .join(", ")
             );
@@ -241,13 +667,13 @@ impl HistAuxilliary {
         let mut coverage_thresholds = Vec::new();
         if !coverage.is_empty() {
             coverage_thresholds =
-                cli::parse_threshold_cli(coverage, cli::RequireThreshold::Absolute)?;
+                cli::parse_named_threshold_cli(coverage, cli::RequireThreshold::Absolute)?;
             log::debug!(
                 "loaded {} coverage thresholds: {}",
                 coverage_thresholds.len(),
                 coverage_thresholds
                     .iter()
-                    .map(|t| format!("{}", t))
+                    .map(|(_, t)| format!("{}", t))
                     .collect::<Vec<String>>()
                     .join(", ")
             );
@@ -259,20 +685,52 @@ impl HistAuxilliary {
             ));
         }
 
-        if quorum_thresholds.len() != coverage_thresholds.len() {
-            if quorum_thresholds.len() == 1 {
-                quorum_thresholds = vec![quorum_thresholds[0]; coverage_thresholds.len()];
-            } else if coverage_thresholds.len() == 1 {
-                coverage_thresholds = vec![coverage_thresholds[0]; quorum_thresholds.len()];
-            } else {
-                return Err(Error::new(ErrorKind::InvalidData,
+        // thresholds given as ranges span a grid of all combinations
+        let pairs: Vec<(&cli::NamedThreshold, &cli::NamedThreshold)> = if (cli::is_threshold_range(
+            quorum,
+        )
+            || cli::is_threshold_range(coverage))
+            && quorum_thresholds.len() > 1
+            && coverage_thresholds.len() > 1
+        {
+            coverage_thresholds
+                .iter()
+                .flat_map(|c| quorum_thresholds.iter().map(move |q| (c, q)))
+                .collect()
+        } else if quorum_thresholds.len() == coverage_thresholds.len() {
+            coverage_thresholds.iter().zip(&quorum_thresholds).collect()
+        } else if quorum_thresholds.len() == 1 {
+            coverage_thresholds
+                .iter()
+                .map(|c| (c, &quorum_thresholds[0]))
+                .collect()
+        } else if coverage_thresholds.len() == 1 {
+            quorum_thresholds
+                .iter()
+                .map(|q| (&coverage_thresholds[0], q))
+                .collect()
+        } else {
+            return Err(Error::new(ErrorKind::InvalidData,
                         "number of coverage and quorum threshold must match, or either one must have a single value"));
-            }
-        }
+        };
+
+        // a pair of thresholds is named after its coverage and/or quorum threshold
+        let names = pairs
+            .iter()
+            .map(|((cn, _), (qn, _))| match (cn, qn) {
+                (Some(c), Some(q)) if c != q => Some(format!("{}/{}", c, q)),
+                (Some(n), _) | (None, Some(n)) => Some(n.clone()),
+                (None, None) => None,
+            })
+            .collect();
 
         Ok(Self {
-            quorum: quorum_thresholds,
-            coverage: coverage_thresholds,
+            quorum: pairs.iter().map(|(_, (_, q))| *q).collect(),
+            coverage: pairs.iter().map(|((_, c), _)| *c).collect(),
+            names,
+            saturation,
+            apriori,
+            increments: false,
         })
     }
 }
@@ -292,6 +750,142 @@ mod tests {
         (1..=n).fold(1.0, |acc, x| acc * x as f64)
     }
 
+    #[test]
+    fn test_growth_increments() {
+        assert_eq!(
+            growth_increments(&[2.0, 3.0, 3.0, 5.0]),
+            vec![2.0, 1.0, 0.0, 2.0]
+        );
+        let mut hist_aux = HistAuxilliary::parse_params("0", "1", 1.0, false).unwrap();
+        let columns = vec![vec![f64::NAN, 2.5, 3.9, 4.2]];
+        assert_eq!(
+            GrowthResult::new(CountType::Node, &columns, &hist_aux, Vec::new()).increments,
+            None
+        );
+        hist_aux.increments = true;
+        assert_eq!(
+            GrowthResult::new(CountType::Node, &columns, &hist_aux, Vec::new()).increments,
+            Some(vec![vec![2.0, 1.0, 1.0]])
+        );
+    }
+
+    #[test]
+    fn test_hist_from_covered_nodes() {
+        let covered = vec![
+            vec![false, true, true, true, false],
+            vec![false, true, false, false, false],
+        ];
+        let hist = Hist::from_covered_nodes(&covered);
+        assert_eq!(hist.count, CountType::Node);
+        assert_eq!(hist.coverage, vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_edge_orientation_hist() {
+        use crate::abacus::AbacusAuxilliary;
+        use crate::io::{bufreader_from_compressed_gfa, write_hist_table};
+
+        let gfa_file = "test/flipped_edges.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Edge, '#').unwrap();
+        let mut params = cli::Params::test_default_histgrowth();
+        if let cli::Params::Histgrowth {
+            canonicalize_edges, ..
+        } = &mut params
+        {
+            *canonicalize_edges = true;
+        }
+        let abacus_aux = AbacusAuxilliary::from_params(&params, &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(gfa_file).unwrap();
+        let abacus =
+            AbacusByTotal::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Edge).unwrap();
+
+        // 1+2+ is traversed by both paths, and so is 2+3-, which path b traverses as 2+3+
+        let hists = EdgeOrientationHist::from_abacus(&abacus, &graph_aux);
+        let columns: Vec<(&str, &[usize])> = hists
+            .iter()
+            .map(|h| (h.orientation.as_str(), &h.coverage[..]))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                (">>", &[0, 0, 1][..]),
+                ("><", &[0, 0, 1][..]),
+                ("<>", &[0, 0, 0][..]),
+                ("<<", &[0, 0, 0][..]),
+            ]
+        );
+
+        let hist = HistResult::new(&Hist::from_abacus(&abacus, Some(&graph_aux)), Vec::new());
+        let mut out = std::io::BufWriter::new(Vec::new());
+        write_hist_table(&[hist], &hists, &mut out).unwrap();
+        let out = String::from_utf8(out.into_inner().unwrap()).unwrap();
+        let rows: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            rows[0],
+            "panacus\thist\torientation\torientation\torientation\torientation"
+        );
+        assert_eq!(rows[1], "count\tedge\t>>\t><\t<>\t<<");
+        assert_eq!(rows.last(), Some(&"2\t2\t1\t1\t0\t0"));
+    }
+
+    #[test]
+    fn test_combine_hist_results() {
+        let hist = |count, coverage: Vec<usize>| HistResult {
+            count,
+            coverage,
+            metadata: Vec::new(),
+        };
+        let a = vec![
+            hist(CountType::Node, vec![0, 3, 1, 2]),
+            hist(CountType::Bp, vec![0, 30, 10, 20]),
+        ];
+        let b = vec![
+            hist(CountType::Node, vec![1, 2, 0, 1]),
+            hist(CountType::Bp, vec![5, 20, 0, 10]),
+        ];
+        let sum = combine_hist_results(&[a.clone(), b.clone()], HistOperation::Add).unwrap();
+        assert_eq!(sum[0].coverage, vec![1, 5, 1, 3]);
+        assert_eq!(sum[1].coverage, vec![5, 50, 10, 30]);
+        let diff = combine_hist_results(&[sum, b.clone()], HistOperation::Subtract).unwrap();
+        assert_eq!(diff, a);
+
+        // subtraction must not yield negative counts
+        assert!(combine_hist_results(&[b.clone(), a.clone()], HistOperation::Subtract).is_err());
+        // histograms must be of the same count types and over the same groups
+        assert!(combine_hist_results(&[a.clone(), b[..1].to_vec()], HistOperation::Add).is_err());
+        let c = vec![
+            hist(CountType::Node, vec![0, 1, 1]),
+            hist(CountType::Bp, vec![0, 10, 10]),
+        ];
+        assert!(combine_hist_results(&[a, c], HistOperation::Add).is_err());
+    }
+
+    #[test]
+    fn test_parse_params_names() {
+        let hist_aux = HistAuxilliary::parse_params("core=1,shell=0.15", "1", 10.0, false).unwrap();
+        assert_eq!(
+            hist_aux.names,
+            vec![Some("core".to_string()), Some("shell".to_string())]
+        );
+        assert_eq!(hist_aux.coverage, vec![Threshold::Absolute(1); 2]);
+
+        let hist_aux =
+            HistAuxilliary::parse_params("core=1,0.5", "strict=2,1", 10.0, false).unwrap();
+        assert_eq!(hist_aux.names, vec![Some("strict/core".to_string()), None]);
+
+        let hist_aux = HistAuxilliary::parse_params("0,1", "1..2", 10.0, false).unwrap();
+        assert_eq!(hist_aux.names, vec![None; 4]);
+        assert_eq!(
+            hist_aux.quorum,
+            vec![
+                Threshold::Relative(0.0),
+                Threshold::Relative(1.0),
+                Threshold::Relative(0.0),
+                Threshold::Relative(1.0)
+            ]
+        );
+    }
+
     #[test]
     fn test_choose_function() {
         assert_almost_eq(choose(5, 0), 0.0);
@@ -303,6 +897,17 @@ mod tests {
         assert_eq!(choose(5, 6), 0.0);
     }
 
+    #[test]
+    fn test_hypergeom_at_least() {
+        assert_almost_eq(hypergeom_at_least(5, 2, 3, 0), 1.0);
+        // 1 - C(3,3)/C(5,3)
+        assert_almost_eq(hypergeom_at_least(5, 2, 3, 1), 0.9);
+        // C(2,2)*C(3,1)/C(5,3)
+        assert_almost_eq(hypergeom_at_least(5, 2, 3, 2), 0.3);
+        assert_almost_eq(hypergeom_at_least(5, 2, 3, 3), 0.0);
+        assert_almost_eq(hypergeom_at_least(5, 5, 2, 2), 1.0);
+    }
+
     #[test]
     fn test_hist_calc_growth_union() {
         let hist = Hist {
@@ -352,4 +957,134 @@ mod tests {
         let growth = hist.calc_growth_quorum(&t_coverage, &t_quorum);
         assert_eq!(growth, test_growth, "Wrong growth quorum");
     }
+
+    #[test]
+    fn test_growth_result_columns() {
+        let hist = Hist {
+            count: CountType::Node,
+            coverage: vec![0, 5, 3, 2],
+        };
+        let hist_aux = HistAuxilliary {
+            quorum: vec![Threshold::Relative(0.0), Threshold::Relative(1.0)],
+            coverage: vec![Threshold::Absolute(1), Threshold::Absolute(1)],
+            names: vec![None; 2],
+            saturation: 1.0,
+            apriori: false,
+            increments: false,
+        };
+        let columns = hist.calc_all_growths(&hist_aux);
+        let growth = GrowthResult::new(hist.count, &columns, &hist_aux, Vec::new());
+        assert_eq!(
+            growth.values[0],
+            hist.calc_growth_union(&Threshold::Absolute(1))
+        );
+        assert_eq!(
+            growth.values[1],
+            hist.calc_growth_core(&Threshold::Absolute(1))
+        );
+
+        let restored = growth.columns();
+        assert!(restored.iter().all(|c| c[0].is_nan()));
+        for (r, c) in restored.iter().zip(&columns) {
+            assert_eq!(r[1..], c[1..]);
+        }
+    }
+
+    #[test]
+    fn test_growth_summary() {
+        // growth curve whose N-th group adds exactly 10 * N^(-0.5) new countables
+        let growth: Vec<f64> = (1..=5)
+            .scan(0.0, |acc, n| {
+                *acc += 10.0 * (n as f64).powf(-0.5);
+                Some(*acc)
+            })
+            .collect();
+        let (k, alpha) = fit_heaps_law(&growth).unwrap();
+        assert_almost_eq(k, 10.0);
+        assert_almost_eq(alpha, 0.5);
+        // 10 * N^(-0.5) < 1 for N > 100
+        assert_eq!(genomes_to_saturation(k, alpha, 5, 1.0), Some(96));
+        assert_eq!(genomes_to_saturation(k, alpha, 200, 1.0), Some(0));
+        assert_eq!(genomes_to_saturation(k, -0.1, 5, 1.0), None);
+        assert_eq!(saturation_point(k, alpha, 1.0), Some(101));
+
+        // core growth is not increasing, hence no fit
+        assert_eq!(fit_heaps_law(&[5.0, 3.0, 2.0]), None);
+    }
+
+    #[test]
+    fn test_apriori_growth() {
+        let hist = Hist {
+            count: CountType::Node,
+            coverage: vec![0, 5, 3, 2, 4],
+        };
+        let mut hist_aux = HistAuxilliary {
+            quorum: vec![Threshold::Relative(0.0), Threshold::Relative(0.5)],
+            coverage: vec![Threshold::Absolute(2), Threshold::Absolute(2)],
+            names: vec![None; 2],
+            saturation: 1.0,
+            apriori: false,
+            increments: false,
+        };
+        let a_posteriori = hist.calc_all_growths(&hist_aux);
+        hist_aux.apriori = true;
+        let a_priori = hist.calc_all_growths(&hist_aux);
+
+        let filtered = hist.apply_coverage_threshold(&Threshold::Absolute(2));
+        assert_eq!(filtered.coverage, vec![0, 0, 3, 2, 4]);
+        // without quorum, coverage thresholds refer to all groups in either case
+        assert_eq!(a_priori[0][1..], a_posteriori[0][1..]);
+        assert_eq!(
+            a_priori[1][1..],
+            filtered.calc_growth_quorum(&Threshold::Absolute(1), &Threshold::Relative(0.5))[..]
+        );
+        assert_ne!(a_priori[1][1..], a_posteriori[1][1..]);
+    }
+
+    #[test]
+    fn test_growth_bootstrap() {
+        assert_almost_eq(percentile(&[1.0, 2.0, 3.0, 4.0, 5.0], 50.0), 3.0);
+        assert_almost_eq(percentile(&[1.0, 2.0, 3.0, 4.0, 5.0], 2.5), 1.1);
+        assert_almost_eq(percentile(&[1.0, 2.0], 97.5), 1.975);
+        assert!(percentile(&[], 50.0).is_nan());
+
+        let curves = vec![vec![vec![2.0, 5.0], vec![1.0, 4.0], vec![3.0, 6.0]]];
+        let bootstrap = GrowthBootstrap::from_samples(&curves, 7);
+        assert_eq!(bootstrap.samples, 3);
+        assert_eq!(bootstrap.seed, 7);
+        assert_eq!(bootstrap.values[0][1], vec![2.0, 5.0]);
+        assert_almost_eq(bootstrap.values[0][0][0], 1.05);
+        assert_almost_eq(bootstrap.values[0][2][1], 5.95);
+    }
+
+    #[test]
+    fn test_badge() {
+        let hist = Hist {
+            count: CountType::Node,
+            coverage: vec![0, 5, 3, 2],
+        };
+        let badge = Badge::new(&hist, 10, 1234);
+        assert_eq!(badge.groups, 3);
+        assert_eq!(badge.nodes, 10);
+        assert_eq!(badge.bp, 1234);
+        assert_almost_eq(badge.core_percent, 20.0);
+        let (_, alpha) = fit_heaps_law(&hist.calc_growth_union(&Threshold::Absolute(1))).unwrap();
+        assert_almost_eq(badge.openness.unwrap(), 1.0 - alpha);
+
+        let empty = Badge::new(
+            &Hist {
+                count: CountType::Node,
+                coverage: vec![0],
+            },
+            0,
+            0,
+        );
+        assert_eq!(empty.groups, 0);
+        assert_eq!(empty.core_percent, 0.0);
+        assert_eq!(empty.openness, None);
+
+        // an unwritable badge file is an I/O error, not an internal one
+        let err = crate::io::write_badge(&badge, "/nonexistent/badge.svg").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    }
 }
@@ -1,16 +1,26 @@
 /* standard use */
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::fmt;
+use std::fs;
 use std::io::{BufWriter, Write};
+use std::path::Path;
 
 /* external use */
 use base64::{engine::general_purpose, Engine as _};
 use handlebars::Handlebars;
+use once_cell::sync::OnceCell;
+use serde_json::{json, Value};
 use thousands::Separable;
 use time::{macros::format_description, OffsetDateTime};
 
-use crate::graph::Info;
+use crate::graph::{compare_infos, info_delta, Bubble, GraphAuxilliary, Info, ItemId, Orientation};
 /* internal use */
+use crate::abacus::AbacusByGroup;
+use crate::cluster::Tree;
 use crate::hist::*;
+use crate::io::load_report_table;
+use crate::plot::{dendrogram_to_svg, to_svg, Chart};
 use crate::util::*;
 
 pub const BOOTSTRAP_COLOR_MODES_JS: &[u8] = include_bytes!("../etc/color-modes.min.js");
@@ -20,44 +30,204 @@ pub const CHART_JS: &[u8] = include_bytes!("../etc/chart.js");
 pub const CUSTOM_CSS: &[u8] = include_bytes!("../etc/custom.css");
 pub const CUSTOM_LIB_JS: &[u8] = include_bytes!("../etc/lib.min.js");
 pub const HOOK_AFTER_JS: &[u8] = include_bytes!("../etc/hook_after.min.js");
+// growth calculation compiled to WebAssembly (see etc/make_growth_wasm.sh)
+pub const GROWTH_WASM: &[u8] = include_bytes!("../etc/growth.wasm");
 pub const HTML_TEMPLATE: &[u8] = include_bytes!("../etc/report_template.html");
 pub const PANACUS_LOGO: &[u8] = include_bytes!("../etc/panacus-illustration-small.png");
 pub const SYMBOLS_SVG: &[u8] = include_bytes!("../etc/symbols.svg");
 
-pub fn populate_constants(vars: &mut HashMap<&str, String>) {
-    vars.insert(
-        "bootstrap_color_modes_js",
-        String::from_utf8_lossy(BOOTSTRAP_COLOR_MODES_JS).into_owned(),
-    );
-    vars.insert(
-        "bootstrap_css",
-        String::from_utf8_lossy(BOOTSTRAP_CSS).into_owned(),
-    );
-    vars.insert(
-        "bootstrap_js",
-        String::from_utf8_lossy(BOOTSTRAP_JS).into_owned(),
-    );
-    vars.insert("chart_js", String::from_utf8_lossy(CHART_JS).into_owned());
-    vars.insert(
-        "custom_css",
-        String::from_utf8_lossy(CUSTOM_CSS).into_owned(),
-    );
-    vars.insert(
-        "custom_lib_js",
-        String::from_utf8_lossy(CUSTOM_LIB_JS).into_owned(),
+// CDN locations of the unmodified third-party assets; the CSS is a custom build of Bootstrap and
+// is therefore never linked to a CDN
+const BOOTSTRAP_JS_CDN: &str =
+    "https://cdn.jsdelivr.net/npm/bootstrap@5.3.1/dist/js/bootstrap.bundle.min.js";
+const CHART_JS_CDN: &str = "https://cdn.jsdelivr.net/npm/chart.js@4.3.3/dist/chart.umd.js";
+
+// how the JS and CSS assets are included in HTML reports
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportAssets {
+    // all assets are embedded, so that the report is self-contained
+    Embed,
+    // third-party scripts are loaded from a CDN
+    Cdn,
+    // all assets are written to the given directory and loaded from there
+    Split(String),
+}
+
+// asset mode of all HTML reports of a run; it must be set before the first report is written
+static REPORT_ASSETS: OnceCell<ReportAssets> = OnceCell::new();
+
+pub fn set_report_assets(assets: ReportAssets) {
+    if REPORT_ASSETS.set(assets.clone()).is_err() && report_assets() != &assets {
+        log::warn!(
+            "report assets are already set to {:?}, ignoring {:?}",
+            report_assets(),
+            assets
+        );
+    }
+}
+
+pub fn report_assets() -> &'static ReportAssets {
+    REPORT_ASSETS.get().unwrap_or(&ReportAssets::Embed)
+}
+
+// directory whose files override the embedded report template, stylesheet, logo, and symbols of
+// the same name, e.g., for institutional branding; it must be set before the first report is
+// written
+static TEMPLATE_DIR: OnceCell<String> = OnceCell::new();
+
+pub fn set_template_dir(dir: &str) {
+    if TEMPLATE_DIR.set(dir.to_string()).is_err() && TEMPLATE_DIR.get().unwrap() != dir {
+        log::warn!(
+            "template directory is already set to {}, ignoring {}",
+            TEMPLATE_DIR.get().unwrap(),
+            dir
+        );
+    }
+}
+
+// user-provided tables, given as pairs of caption and file, that are appended to every report
+static REPORT_TABLES: OnceCell<Vec<(String, String)>> = OnceCell::new();
+
+pub fn set_report_tables(tables: Vec<(String, String)>) {
+    if REPORT_TABLES.set(tables).is_err() {
+        log::warn!("report tables are already set, ignoring");
+    }
+}
+
+fn generate_report_tables() -> Result<String, std::io::Error> {
+    let reg = Handlebars::new();
+    let table = r##"<div class="container p-3">
+    <table class="table table-striped table-hover caption-top">
+      <caption>{{caption}}</caption>
+      <thead>
+        <tr>
+{{#each header}}
+          <th scope="col">{{this}}</th>
+{{/each}}
+        </tr>
+      </thead>
+      <tbody class="table-group-divider">
+{{#each rows}}
+        <tr>{{#each this}}<td>{{this}}</td>{{/each}}</tr>
+{{/each}}
+      </tbody>
+    </table>
+</div>
+"##;
+    let mut html = String::new();
+    for (caption, file) in REPORT_TABLES.get().map(|t| &t[..]).unwrap_or(&[]) {
+        let rows = load_report_table(file)?;
+        let (header, rows) = rows
+            .split_first()
+            .map_or((&[][..], &[][..]), |(h, r)| (&h[..], r));
+        html.push_str(
+            &reg.render_template(
+                table,
+                &json!({ "caption": caption, "header": header, "rows": rows }),
+            )
+            .unwrap(),
+        );
+    }
+    Ok(html)
+}
+
+// collapsible panel listing the log messages of the run, e.g., to trace back unexpectedly low
+// counts to the subset or exclude lists
+fn generate_run_log() -> String {
+    let log = run_log();
+    if log.is_empty() {
+        return String::new();
+    }
+    format!(
+        r##"<div class="container p-3">
+    <details>
+        <summary>run log</summary>
+        <pre class="small">{}</pre>
+    </details>
+</div>
+"##,
+        handlebars::html_escape(&log.join("\n"))
+    )
+}
+
+// the file of the given name in the template directory (if any), or else the embedded default
+fn template_file(name: &str, default: &'static [u8]) -> Result<Cow<'static, [u8]>, std::io::Error> {
+    if let Some(dir) = TEMPLATE_DIR.get() {
+        let path = Path::new(dir).join(name);
+        if path.exists() {
+            log::info!("using report template file {}", path.display());
+            return fs::read(&path).map(Cow::Owned).map_err(|e| {
+                log::error!("unable to read template file {}: {}", path.display(), e);
+                e
+            });
+        }
+    }
+    Ok(Cow::Borrowed(default))
+}
+
+// HTML that includes the JS and CSS assets in the head of a report
+fn head_assets() -> Result<String, std::io::Error> {
+    let text = |data: &[u8]| String::from_utf8_lossy(data).into_owned();
+    let scripts = |bootstrap_js: &str, chart_js: &str| {
+        format!(
+            "    <script>\n{}\n{}\n{}\n\n{}\n    </script>",
+            bootstrap_js,
+            text(BOOTSTRAP_COLOR_MODES_JS),
+            chart_js,
+            text(CUSTOM_LIB_JS)
+        )
+    };
+    let custom_css = template_file("custom.css", CUSTOM_CSS)?;
+    let styles = format!(
+        "    <style>\n{}\n{}\n    </style>",
+        text(BOOTSTRAP_CSS),
+        text(&custom_css)
     );
+    match report_assets() {
+        ReportAssets::Embed => Ok(format!(
+            "{}\n{}",
+            scripts(&text(BOOTSTRAP_JS), &text(CHART_JS)),
+            styles
+        )),
+        ReportAssets::Cdn => Ok(format!(
+            "    <script src=\"{}\"></script>\n    <script src=\"{}\"></script>\n{}\n{}",
+            BOOTSTRAP_JS_CDN,
+            CHART_JS_CDN,
+            scripts("", ""),
+            styles
+        )),
+        ReportAssets::Split(dir) => {
+            let assets: [(&str, &[u8]); 6] = [
+                ("bootstrap.bundle.min.js", BOOTSTRAP_JS),
+                ("color-modes.min.js", BOOTSTRAP_COLOR_MODES_JS),
+                ("chart.js", CHART_JS),
+                ("lib.min.js", CUSTOM_LIB_JS),
+                ("bootstrap.min.css", BOOTSTRAP_CSS),
+                ("custom.css", &custom_css),
+            ];
+            fs::create_dir_all(dir)?;
+            let mut html = Vec::new();
+            for (name, data) in assets {
+                let path = Path::new(dir).join(name);
+                log::info!("writing report asset {}", path.display());
+                fs::write(&path, data)?;
+                let src = handlebars::html_escape(&path.to_string_lossy());
+                html.push(if name.ends_with(".css") {
+                    format!("    <link rel=\"stylesheet\" href=\"{}\">", src)
+                } else {
+                    format!("    <script src=\"{}\"></script>", src)
+                });
+            }
+            Ok(html.join("\n"))
+        }
+    }
+}
+
+pub fn populate_constants(vars: &mut HashMap<&str, String>) {
     vars.insert(
         "hook_after_js",
         String::from_utf8_lossy(HOOK_AFTER_JS).into_owned(),
     );
-    vars.insert(
-        "panacus_logo",
-        general_purpose::STANDARD_NO_PAD.encode(PANACUS_LOGO),
-    );
-    vars.insert(
-        "symbols_svg",
-        String::from_utf8_lossy(SYMBOLS_SVG).into_owned(),
-    );
     vars.insert(
         "version",
         option_env!("GIT_HASH")
@@ -65,6 +235,8 @@ pub fn populate_constants(vars: &mut HashMap<&str, String>) {
             .to_string(),
     );
 
+    vars.insert("threads", rayon::current_num_threads().to_string());
+
     let now = OffsetDateTime::now_utc();
     vars.insert(
         "timestamp",
@@ -75,37 +247,61 @@ pub fn populate_constants(vars: &mut HashMap<&str, String>) {
     );
 }
 
-pub fn generate_hist_tabs(hists: &[Hist]) -> String {
+// identifier of the HTML elements of a count type, distinguishing report sections (if any) in
+// the same way as the JS objects of the report do
+fn section_id(count: &CountType, section: Option<&str>) -> String {
+    match section {
+        Some(s) => format!("{}-{}", count, s),
+        None => count.to_string(),
+    }
+}
+
+pub fn generate_hist_tabs(
+    hists: &[Hist],
+    fname: &str,
+    ordered_names: Option<&Vec<String>>,
+    section: Option<&str>,
+) -> String {
     let reg = Handlebars::new();
 
     let mut tab_content = String::new();
     let mut tab_navigation = String::new();
     for (i, h) in hists.iter().enumerate() {
-        let tab = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-hist-{{count}}" role="tabpanel" aria-labelledby="nav-hist-{{count}}">
+        let tab = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-hist-{{id}}" role="tabpanel" aria-labelledby="nav-hist-{{id}}-tab" tabindex="0">
     <div class="d-flex flex-row-reverse">
         <div class="form-check form-switch">
-            <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-hist-{{count}}">
-            <label class="form-check-label" for="btn-logscale-plot-hist-{{count}}">log-scale</label>
+            <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-hist-{{id}}">
+            <label class="form-check-label" for="btn-logscale-plot-hist-{{id}}">log-scale</label>
         </div>
     </div>
-    <canvas id="chart-hist-{{count}}"></canvas>
+    <canvas id="chart-hist-{{id}}" role="img" aria-label="{{alt}}"><p>{{alt}}</p></canvas>
     <div class="d-flex flex-row-reverse">
-        <button id="btn-download-table-hist-{{count}}" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#table"></use></svg>
+        <button id="btn-download-table-hist-{{id}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
         </button>
-        <button id="btn-download-plot-hist-{{count}}" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50" width="15" height="15"><use href="#card-image"></use></svg>
+        <button id="btn-download-plot-hist-{{id}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
         </button>
+        <a id="btn-download-data-hist-{{id}}" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{fname}}_hist_{{id}}.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
     </div>
 </div>
 "##;
 
-        let nav = r##"<button class="nav-link{{#if is_first}} active{{/if}}" id="nav-hist-{{count}}-tab" data-bs-toggle="tab" data-bs-target="#nav-hist-{{count}}" type="button" role="tab" aria-controls="nav-hist-{{count}}" aria-selected="{{is_first}}">{{count}}</button>
+        let nav = r##"<button class="nav-link{{#if is_first}} active{{/if}}" id="nav-hist-{{id}}-tab" data-bs-toggle="tab" data-bs-target="#nav-hist-{{id}}" type="button" role="tab" aria-controls="nav-hist-{{id}}" aria-selected="{{is_first}}">{{count}}</button>
 "##;
 
-        let mut vars = HashMap::from([("count", format!("{}", h.count))]);
+        let mut vars = HashMap::from([
+            ("count", format!("{}", h.count)),
+            ("id", section_id(&h.count, section)),
+            ("alt", hist_alt_text(h)),
+            ("fname", fname.to_string()),
+            ("data", encode_json(&hist_json(h, ordered_names))),
+        ]);
         if i == 0 {
             vars.insert("is_first", String::from("true"));
         }
@@ -132,34 +328,419 @@ pub fn generate_hist_tabs(hists: &[Hist]) -> String {
     reg.render_template(container, &vars).unwrap()
 }
 
-pub fn generate_growth_tabs(growths: &[(CountType, Vec<Vec<f64>>)]) -> String {
+pub fn generate_coverage_class_tabs(abacus_group: &AbacusByGroup, fname: &str) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+			<button class="nav-link active" id="nav-coverageclasses-{{count}}-tab" data-bs-toggle="tab" data-bs-target="#nav-coverageclasses-{{count}}" type="button" role="tab" aria-controls="nav-coverageclasses-{{count}}" aria-selected="true">{{count}}</button>
+		</div>
+	</nav>
+	<div class="tab-pane fade show active" id="nav-coverageclasses-{{count}}" role="tabpanel" aria-labelledby="nav-coverageclasses-{{count}}-tab" tabindex="0">
+    <div class="d-flex flex-row-reverse">
+        <div class="form-check form-switch">
+            <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-coverageclasses-{{count}}">
+            <label class="form-check-label" for="btn-logscale-plot-coverageclasses-{{count}}">log-scale</label>
+        </div>
+    </div>
+    <canvas id="chart-coverageclasses-{{count}}" role="img" aria-label="{{alt}}"><p>{{alt}}</p></canvas>
+    <div class="d-flex flex-row-reverse">
+        <button id="btn-download-table-coverageclasses-{{count}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
+        </button>
+        <button id="btn-download-plot-coverageclasses-{{count}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
+        </button>
+        <a id="btn-download-data-coverageclasses-{{count}}" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{fname}}_coverageclasses_{{count}}.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
+    </div>
+	</div>
+</div>
+"##;
+
+    let classes = abacus_group.coverage_classes();
+    let vars = HashMap::from([
+        ("count", format!("{}", abacus_group.count)),
+        (
+            "alt",
+            format!(
+                "Grouped bar chart of the number of {}s of each of the {} groups that are private to the group, shared with some groups (shell), or shared with all groups (core)",
+                abacus_group.count,
+                abacus_group.groups.len()
+            ),
+        ),
+        ("fname", fname.to_string()),
+        (
+            "data",
+            encode_json(&json!({
+                "count": abacus_group.count,
+                "group": abacus_group.groups,
+                "private": classes.iter().map(|x| x[0]).collect::<Vec<_>>(),
+                "shell": classes.iter().map(|x| x[1]).collect::<Vec<_>>(),
+                "core": classes.iter().map(|x| x[2]).collect::<Vec<_>>(),
+            })),
+        ),
+    ]);
+    reg.render_template(tab, &vars).unwrap()
+}
+
+// Renders a matrix as heatmap, i.e., a table whose cells are shaded by their value on a linear
+// color scale from min (transparent) to max (opaque), followed by a legend of the scale
+pub fn generate_heatmap(
+    row_labels: &[String],
+    col_labels: &[String],
+    matrix: &[Vec<f64>],
+    (min, max): (f64, f64),
+    alt: &str,
+) -> String {
+    let shade = |x: f64| {
+        if max > min {
+            ((x - min) / (max - min)).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    };
+    let mut rows = format!(
+        "<tr><th></th>{}</tr>\n",
+        col_labels
+            .iter()
+            .map(|l| format!("<th scope=\"col\">{}</th>", handlebars::html_escape(l)))
+            .collect::<String>()
+    );
+    // counts are shown without decimals
+    let precision = if matrix.iter().flatten().all(|x| x.fract() == 0.0) {
+        0
+    } else {
+        2
+    };
+    for (l, row) in row_labels.iter().zip(matrix) {
+        rows.push_str(&format!(
+            "<tr><th scope=\"row\">{}</th>",
+            handlebars::html_escape(l)
+        ));
+        for x in row {
+            rows.push_str(&format!(
+                "<td style=\"background-color: rgba(59, 163, 236, {:.3})\">{:.*}</td>",
+                shade(*x),
+                precision,
+                x
+            ));
+        }
+        rows.push_str("</tr>\n");
+    }
+    format!(
+        r##"    <table class="table table-sm text-center" aria-label="{}">
+{}    </table>
+    <div class="d-flex align-items-center small" aria-hidden="true">
+        <span class="me-2">{:.*}</span>
+        <div style="width: 10em; height: 1em; background: linear-gradient(to right, rgba(59, 163, 236, 0), rgba(59, 163, 236, 1))"></div>
+        <span class="ms-2">{:.*}</span>
+    </div>
+"##,
+        handlebars::html_escape(alt),
+        rows,
+        precision,
+        min,
+        precision,
+        max
+    )
+}
+
+// rows and columns of the heatmap are listed in the given order of groups, e.g., that of the
+// leaves of a tree clustering them
+pub fn generate_similarity_tab(
+    abacus_group: &AbacusByGroup,
+    metric: SimilarityMetric,
+    order: &[usize],
+    fname: &str,
+) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+			<button class="nav-link active" id="nav-similarity-{{count}}-tab" data-bs-toggle="tab" data-bs-target="#nav-similarity-{{count}}" type="button" role="tab" aria-controls="nav-similarity-{{count}}" aria-selected="true">{{count}}</button>
+		</div>
+	</nav>
+	<div class="tab-pane fade show active overflow-auto" id="nav-similarity-{{count}}" role="tabpanel" aria-labelledby="nav-similarity-{{count}}-tab" tabindex="0">
+{{{heatmap}}}
+    <div class="d-flex flex-row-reverse">
+        <a id="btn-download-data-similarity-{{count}}" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{fname}}_similarity_{{count}}.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
+    </div>
+	</div>
+</div>
+"##;
+
+    let similarities = abacus_group.similarities(metric);
+    let alt = format!(
+        "Heatmap of the pairwise {} similarity of {} groups by {}s",
+        metric,
+        abacus_group.groups.len(),
+        abacus_group.count
+    );
+    let labels: Vec<String> = order
+        .iter()
+        .map(|i| abacus_group.groups[*i].clone())
+        .collect();
+    let matrix: Vec<Vec<f64>> = order
+        .iter()
+        .map(|i| order.iter().map(|j| similarities[*i][*j]).collect())
+        .collect();
+    let heatmap = generate_heatmap(&labels, &labels, &matrix, (0.0, 1.0), &alt);
+
+    let vars = HashMap::from([
+        ("count", format!("{}", abacus_group.count)),
+        ("heatmap", heatmap),
+        ("fname", fname.to_string()),
+        (
+            "data",
+            encode_json(&json!({
+                "count": abacus_group.count,
+                "metric": metric.to_string(),
+                "group": abacus_group.groups,
+                "similarity": similarities,
+            })),
+        ),
+    ]);
+    reg.render_template(tab, &vars).unwrap()
+}
+
+pub fn generate_dendrogram_tab(
+    tree: &Tree,
+    method: ClusterMethod,
+    metric: SimilarityMetric,
+    count: CountType,
+    fname: &str,
+) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+			<button class="nav-link active" id="nav-dendrogram-{{count}}-tab" data-bs-toggle="tab" data-bs-target="#nav-dendrogram-{{count}}" type="button" role="tab" aria-controls="nav-dendrogram-{{count}}" aria-selected="true">{{count}}</button>
+		</div>
+	</nav>
+	<div class="tab-pane fade show active overflow-auto" id="nav-dendrogram-{{count}}" role="tabpanel" aria-labelledby="nav-dendrogram-{{count}}-tab" tabindex="0">
+{{{chart}}}
+    <div class="d-flex flex-row-reverse">
+        <a id="btn-download-newick-{{count}}" class="d-flex align-items-center btn m-1" role="button" href="data:text/plain;base64,{{{data}}}" download="{{fname}}_dendrogram_{{count}}.nwk" aria-label="download tree in Newick format">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+        </a>
+    </div>
+	</div>
+</div>
+"##;
+
+    let title = format!(
+        "{} tree of {} distances between groups by {}s",
+        method, metric, count
+    );
+    let vars = HashMap::from([
+        ("count", count.to_string()),
+        ("chart", dendrogram_to_svg(tree, &title)),
+        ("fname", fname.to_string()),
+        (
+            "data",
+            general_purpose::STANDARD.encode(format!("{}\n", tree.to_newick())),
+        ),
+    ]);
+    reg.render_template(tab, &vars).unwrap()
+}
+
+pub fn generate_edge_orientation_tab(hists: &[EdgeOrientationHist], fname: &str) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+    <div class="overflow-auto">
+{{{chart}}}
+    </div>
+    <div class="d-flex flex-row-reverse">
+        <a id="btn-download-data-edge-orientation" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{fname}}_edge_orientation.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
+    </div>
+</div>
+"##;
+
+    let vars = HashMap::from([
+        ("chart", to_svg(&[Chart::from_edge_orientations(hists)])),
+        ("fname", fname.to_string()),
+        ("data", encode_json(&json!({ "edge_orientations": hists }))),
+    ]);
+    reg.render_template(tab, &vars).unwrap()
+}
+
+// sizes of the pangenome (countables covered by any group) and the core (countables covered by
+// all groups) in each window
+fn window_profile(hists: &[Hist]) -> (Vec<usize>, Vec<usize>) {
+    hists
+        .iter()
+        .map(|h| {
+            (
+                h.coverage[1..].iter().sum::<usize>(),
+                *h.coverage
+                    .last()
+                    .filter(|_| h.coverage.len() > 1)
+                    .unwrap_or(&0),
+            )
+        })
+        .unzip()
+}
+
+pub fn generate_windows_tab(
+    hists: &[Hist],
+    windows: &[(usize, usize)],
+    path_name: &str,
+    fname: &str,
+) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+			<button class="nav-link active" id="nav-profile-{{count}}-tab" data-bs-toggle="tab" data-bs-target="#nav-profile-{{count}}" type="button" role="tab" aria-controls="nav-profile-{{count}}" aria-selected="true">{{count}}</button>
+		</div>
+	</nav>
+	<div class="tab-pane fade show active" id="nav-profile-{{count}}" role="tabpanel" aria-labelledby="nav-profile-{{count}}-tab" tabindex="0">
+    <div class="d-flex flex-row-reverse">
+        <div class="form-check form-switch">
+            <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-profile-{{count}}">
+            <label class="form-check-label" for="btn-logscale-plot-profile-{{count}}">log-scale</label>
+        </div>
+    </div>
+    <canvas id="chart-profile-{{count}}" role="img" aria-label="{{alt}}"><p>{{alt}}</p></canvas>
+    <div class="d-flex flex-row-reverse">
+        <button id="btn-download-table-profile-{{count}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
+        </button>
+        <button id="btn-download-plot-profile-{{count}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
+        </button>
+        <a id="btn-download-data-profile-{{count}}" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{fname}}_windows_{{count}}.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
+    </div>
+	</div>
+</div>
+"##;
+
+    let count = hists.first().map(|h| h.count).unwrap_or(CountType::Node);
+    let (pan, core) = window_profile(hists);
+    let vars = HashMap::from([
+        ("count", format!("{}", count)),
+        (
+            "alt",
+            format!(
+                "Line chart of the number of {}s covered by any group (pangenome) and by all groups (core) in {} windows along {}",
+                count,
+                windows.len(),
+                path_name
+            ),
+        ),
+        ("fname", fname.to_string()),
+        (
+            "data",
+            encode_json(&json!({
+                "count": count,
+                "path": path_name,
+                "start": windows.iter().map(|w| w.0).collect::<Vec<_>>(),
+                "end": windows.iter().map(|w| w.1).collect::<Vec<_>>(),
+                "pangenome": pan,
+                "core": core,
+            })),
+        ),
+    ]);
+    reg.render_template(tab, &vars).unwrap()
+}
+
+pub fn generate_growth_tabs(
+    growths: &[(CountType, Vec<Vec<f64>>)],
+    hist_aux: &HistAuxilliary,
+    fname: &str,
+    ordered_names: Option<&Vec<String>>,
+    section: Option<&str>,
+) -> String {
     let reg = Handlebars::new();
 
     let mut tab_content = String::new();
     let mut tab_navigation = String::new();
-    for (i, (count, _)) in growths.iter().enumerate() {
-        let tab = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-growth-{{count}}" role="tabpanel" aria-labelledby="nav-growth-{{count}}">
+    for (i, (count, columns)) in growths.iter().enumerate() {
+        let summary = GrowthSummary::from_columns(columns, hist_aux);
+        let tab = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-growth-{{id}}" role="tabpanel" aria-labelledby="nav-growth-{{id}}-tab" tabindex="0">
     <div class="d-flex flex-row-reverse">
-        <!--this is empty //-->
+        <div class="form-check form-switch">
+            <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-growth-{{id}}">
+            <label class="form-check-label" for="btn-logscale-plot-growth-{{id}}">log-scale</label>
+        </div>
     </div>
-    <canvas id="chart-growth-{{count}}"></canvas>
+    <canvas id="chart-growth-{{id}}" role="img" aria-label="{{alt}}"><p>{{alt}}</p></canvas>
+    <div class="input-group input-group-sm mt-2 d-none" id="form-recompute-growth-{{id}}">
+        <span class="input-group-text">coverage &ge;</span>
+        <input type="number" class="form-control" id="input-coverage-growth-{{id}}" min="1" step="1" value="1" aria-label="coverage threshold">
+        <span class="input-group-text">quorum &ge;</span>
+        <input type="number" class="form-control" id="input-quorum-growth-{{id}}" min="0" max="1" step="0.05" value="0" aria-label="quorum threshold">
+        <button class="btn btn-outline-secondary" type="button" id="btn-recompute-growth-{{id}}">add curve</button>
+    </div>
+    <p class="lead mt-3">{{headline}}</p>
+    <table class="table table-striped table-hover">
+      <thead>
+        <tr>
+          <th scope="col">coverage</th>
+          <th scope="col">quorum</th>
+          <th scope="col">k</th>
+          <th scope="col">&alpha;</th>
+          <th scope="col">groups to saturation</th>
+          <th scope="col">saturation point</th>
+        </tr>
+      </thead>
+      <tbody class="table-group-divider">
+        {{{summary}}}
+      </tbody>
+    </table>
     <div class="d-flex flex-row-reverse">
-        <button id="btn-download-table-growth-{{count}}" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#table"></use></svg>
+        <button id="btn-download-table-growth-{{id}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
         </button>
-        <button id="btn-download-plot-growth-{{count}}" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#card-image"></use></svg>
+        <button id="btn-download-plot-growth-{{id}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
         </button>
+        <a id="btn-download-data-growth-{{id}}" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{fname}}_growth_{{id}}.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
     </div>
 </div>
 "##;
 
-        let nav = r##"<button class="nav-link{{#if is_first}} active{{/if}}" id="nav-growth-{{count}}-tab" data-bs-toggle="tab" data-bs-target="#nav-growth-{{count}}" type="button" role="tab" aria-controls="nav-growth-{{count}}" aria-selected="{{is_first}}">{{count}}</button>
+        let nav = r##"<button class="nav-link{{#if is_first}} active{{/if}}" id="nav-growth-{{id}}-tab" data-bs-toggle="tab" data-bs-target="#nav-growth-{{id}}" type="button" role="tab" aria-controls="nav-growth-{{id}}" aria-selected="{{is_first}}">{{count}}</button>
 "##;
 
-        let mut vars = HashMap::from([("count", format!("{}", count))]);
+        let mut vars = HashMap::from([
+            ("count", format!("{}", count)),
+            ("id", section_id(count, section)),
+            ("alt", growth_alt_text(count, columns, hist_aux)),
+            ("headline", growth_headline(count, &summary)),
+            ("summary", growth_summary_rows(&summary)),
+            ("fname", fname.to_string()),
+            (
+                "data",
+                encode_json(&growth_json(count, columns, hist_aux, ordered_names)),
+            ),
+        ]);
         if i == 0 {
             vars.insert("is_first", String::from("true"));
         }
@@ -186,7 +767,7 @@ pub fn generate_growth_tabs(growths: &[(CountType, Vec<Vec<f64>>)]) -> String {
     reg.render_template(container, &vars).unwrap()
 }
 
-pub fn generate_info_tabs(info: Info) -> String {
+pub fn generate_info_tabs(info: Info, fname: &str) -> String {
     let reg = Handlebars::new();
 
     let mut tab_content = String::new();
@@ -194,9 +775,11 @@ pub fn generate_info_tabs(info: Info) -> String {
     tab_navigation.push_str(r##"<button class="nav-link active" id="nav-info-1-tab" data-bs-toggle="tab" data-bs-target="#nav-info-1" type="button" role="tab" aria-controls="nav-info-1" aria-selected="true">graph</button>"##);
     tab_navigation.push_str(r##"<button class="nav-link" id="nav-info-2-tab" data-bs-toggle="tab" data-bs-target="#nav-info-2" type="button" role="tab" aria-controls="nav-info-2" aria-selected="false">node</button>"##);
     tab_navigation.push_str(r##"<button class="nav-link" id="nav-info-3-tab" data-bs-toggle="tab" data-bs-target="#nav-info-3" type="button" role="tab" aria-controls="nav-info-3" aria-selected="false">path</button>"##);
+    tab_navigation.push_str(r##"<button class="nav-link" id="nav-info-6-tab" data-bs-toggle="tab" data-bs-target="#nav-info-6" type="button" role="tab" aria-controls="nav-info-6" aria-selected="false">samples</button>"##);
     tab_navigation.push_str(r##"<button class="nav-link" id="nav-info-4-tab" data-bs-toggle="tab" data-bs-target="#nav-info-4" type="button" role="tab" aria-controls="nav-info-4" aria-selected="false">groups</button>"##);
+    tab_navigation.push_str(r##"<button class="nav-link" id="nav-info-5-tab" data-bs-toggle="tab" data-bs-target="#nav-info-5" type="button" role="tab" aria-controls="nav-info-5" aria-selected="false">distributions</button>"##);
 
-    let graph_info = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-info-1" role="tabpanel" aria-labelledby="nav-info-1">
+    let graph_info = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-info-1" role="tabpanel" aria-labelledby="nav-info-1-tab" tabindex="0">
         <br/>
 <table class="table table-striped table-hover">
   <thead>
@@ -261,9 +844,9 @@ pub fn generate_info_tabs(info: Info) -> String {
 </table>
 <br/>
     <div class="d-flex flex-row-reverse">
-        <button id="btn-download-table-info-graph" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#table"></use></svg>
+        <button id="btn-download-table-info-graph" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
         </button>
     </div>
 </div>
@@ -310,7 +893,7 @@ pub fn generate_info_tabs(info: Info) -> String {
     ]);
     tab_content.push_str(&reg.render_template(graph_info, &graph_vars).unwrap());
 
-    let node_info = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-info-2" role="tabpanel" aria-labelledby="nav-info-2">
+    let node_info = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-info-2" role="tabpanel" aria-labelledby="nav-info-2-tab" tabindex="0">
     </br>
 <table class="table table-striped table-hover">
   <thead>
@@ -365,9 +948,9 @@ pub fn generate_info_tabs(info: Info) -> String {
 </table>
 <br/>
     <div class="d-flex flex-row-reverse">
-        <button id="btn-download-table-info-node" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#table"></use></svg>
+        <button id="btn-download-table-info-node" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
         </button>
     </div>
 </div>
@@ -405,7 +988,7 @@ pub fn generate_info_tabs(info: Info) -> String {
     ]);
     tab_content.push_str(&reg.render_template(node_info, &node_vars).unwrap());
 
-    let path_info = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-info-3" role="tabpanel" aria-labelledby="nav-info-3">
+    let path_info = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-info-3" role="tabpanel" aria-labelledby="nav-info-3-tab" tabindex="0">
     </br>
 <table class="table table-striped table-hover">
   <thead>
@@ -450,9 +1033,9 @@ pub fn generate_info_tabs(info: Info) -> String {
 </table>
 <br/>
     <div class="d-flex flex-row-reverse">
-        <button id="btn-download-table-info-path" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#table"></use></svg>
+        <button id="btn-download-table-info-path" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
         </button>
     </div>
 </div>
@@ -485,19 +1068,104 @@ pub fn generate_info_tabs(info: Info) -> String {
     ]);
     tab_content.push_str(&reg.render_template(path_info, &path_vars).unwrap());
 
-    let group_info = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-info-4" role="tabpanel" aria-labelledby="nav-info-4">
+    let sample_info = r##"<div class="tab-pane fade" id="nav-info-6" role="tabpanel" aria-labelledby="nav-info-6-tab" tabindex="0">
+    </br>
+<table class="table table-striped table-hover">
+  <thead>
+    <tr>
+      <th scope="col">category</th>
+      <th scope="col">countable</th>
+      <th scope="col">value</th>
+    </tr>
+  </thead>
+  <tbody class="table-group-divider">
+{{#if has_records}}
+    <tr>
+      <td>total</td>
+      <td>P-line</td>
+      <td>{{{path_records}}}</td>
+    </tr>
+    <tr>
+      <td></td>
+      <td>W-line</td>
+      <td>{{{walk_records}}}</td>
+    </tr>
+{{/if}}
+    <tr>
+      <td>{{#unless has_records}}total{{/unless}}</td>
+      <td>sample</td>
+      <td>{{{sample_count}}}</td>
+    </tr>
+    <tr>
+      <td></td>
+      <td>haplotype</td>
+      <td>{{{haplotype_count}}}</td>
+    </tr>
+  </tbody>
+</table>
+<br/>
+<table class="table table-striped table-hover">
+  <thead>
+    <tr>
+      <th scope="col">sample</th>
+      <th scope="col">paths</th>
+      <th scope="col">nodes</th>
+      <th scope="col">bp</th>
+    </tr>
+  </thead>
+  <tbody class="table-group-divider">
+{{#each samples}}
+    <tr>
+      <td>{{this.sample}}</td>
+      <td>{{{this.paths}}}</td>
+      <td>{{{this.node_len}}}</td>
+      <td>{{{this.bp_len}}}</td>
+    </tr>
+{{/each}}
+  </tbody>
+</table>
+<br/>
+    <div class="d-flex flex-row-reverse">
+        <button id="btn-download-table-info-samples" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
+        </button>
+    </div>
+</div>
+"##;
+    let samples = &info.sample_info;
+    let sample_vars = json!({
+        "has_records": samples.path_records.is_some(),
+        "path_records": samples.path_records.unwrap_or(0).separate_with_commas(),
+        "walk_records": samples.walk_records.unwrap_or(0).separate_with_commas(),
+        "sample_count": samples.sample_count.separate_with_commas(),
+        "haplotype_count": samples.haplotype_count.separate_with_commas(),
+        "samples": samples.samples.iter().map(|s| json!({
+            "sample": s.sample,
+            "paths": s.paths.separate_with_commas(),
+            "node_len": s.node_len.separate_with_commas(),
+            "bp_len": s.bp_len.separate_with_commas(),
+        })).collect::<Vec<_>>(),
+    });
+    tab_content.push_str(&reg.render_template(sample_info, &sample_vars).unwrap());
+
+    let group_info = r##"<div class="tab-pane fade{{#if is_first}} show active{{else}} d-none{{/if}}" id="nav-info-4" role="tabpanel" aria-labelledby="nav-info-4-tab" tabindex="0">
     <div class="d-flex flex-row-reverse">
         <div class="form-check form-switch">
             <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-group-node">
             <label class="form-check-label" for="btn-logscale-plot-group-node">log-scale</label>
         </div>
     </div>
-    <canvas id="chart-group-node"></canvas>
+    <canvas id="chart-group-node" role="img" aria-label="{{alt_node.0.text}}"><p>{{alt_node.0.text}}</p></canvas>
     <br/>
     <div class="d-flex flex-row-reverse">
-        <button id="btn-download-plot-group-node" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#card-image"></use></svg>
+        <button id="btn-download-table-group-node" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
+        </button>
+        <button id="btn-download-plot-group-node" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
         </button>
     </div>
 <br/>
@@ -507,49 +1175,132 @@ pub fn generate_info_tabs(info: Info) -> String {
             <label class="form-check-label" for="btn-logscale-plot-group-bp">log-scale</label>
         </div>
     </div>
-    <canvas id="chart-group-bp"></canvas>
+    <canvas id="chart-group-bp" role="img" aria-label="{{alt_bp.0.text}}"><p>{{alt_bp.0.text}}</p></canvas>
 <br/>
     <div class="d-flex flex-row-reverse">
-        <button id="btn-download-table-info-group" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#table"></use></svg>
+        <button id="btn-download-table-info-group" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
+        </button>
+        <button id="btn-download-table-group-bp" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
         </button>
-        <button id="btn-download-plot-group-bp" type="button" class="d-flex align-items-center btn m-1" aria-pressed="false">
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#download"></use></svg>
-            <svg class="bi opacity-50 m-1" width="15" height="15"><use href="#card-image"></use></svg>
+        <button id="btn-download-plot-group-bp" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
         </button>
+        <a id="btn-download-data-group" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data.0.text}}}" download="{{fname.0.text}}_group.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
     </div>
 </div>
 "##;
-    let group_vars = HashMap::from([(
-        "groups",
-        match info.group_info {
-            Some(group_info) => group_info
-                .groups
-                .iter()
-                .map(|(k, v)| {
-                    HashMap::from([
-                        ("name", k.to_string()),
-                        ("node_len", format!("{}", v.0)),
-                        ("bp_len", format!("{}", v.1)),
-                    ])
-                })
-                .collect::<Vec<_>>(),
-            None => Vec::new(),
-        },
-    )]);
+    let (alt_node, alt_bp) = group_alt_texts(&info);
+    let group_vars = HashMap::from([
+        ("alt_node", vec![HashMap::from([("text", alt_node)])]),
+        ("alt_bp", vec![HashMap::from([("text", alt_bp)])]),
+        ("fname", vec![HashMap::from([("text", fname.to_string())])]),
+        (
+            "data",
+            vec![HashMap::from([("text", encode_json(&groups_json(&info)))])],
+        ),
+        (
+            "groups",
+            match info.group_info {
+                Some(group_info) => group_info
+                    .groups
+                    .iter()
+                    .map(|(k, v)| {
+                        HashMap::from([
+                            ("name", k.to_string()),
+                            ("node_len", format!("{}", v.0)),
+                            ("bp_len", format!("{}", v.1)),
+                        ])
+                    })
+                    .collect::<Vec<_>>(),
+                None => Vec::new(),
+            },
+        ),
+    ]);
     tab_content.push_str(&reg.render_template(group_info, &group_vars).unwrap());
 
-    let container = r##"<div class="container p-5">
-	<nav>
-		<div class="nav nav-tabs" id="nav-tab" role="tablist">
-			{{{tab_navigation}}}
-		</div>
-	</nav>
-	{{{tab_content}}}
-</div>
-"##;
-
+    let distribution_info = r##"<div class="tab-pane fade" id="nav-info-5" role="tabpanel" aria-labelledby="nav-info-5-tab" tabindex="0">
+{{#each histograms}}
+    <br/>
+    <div class="d-flex flex-row-reverse">
+        <div class="form-check form-switch">
+            <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-histogram-{{this.id}}">
+            <label class="form-check-label" for="btn-logscale-plot-histogram-{{this.id}}">log-scale</label>
+        </div>
+    </div>
+    <canvas id="chart-histogram-{{this.id}}" role="img" aria-label="{{this.alt}}"><p>{{this.alt}}</p></canvas>
+    <div class="d-flex flex-row-reverse">
+        <button id="btn-download-table-histogram-{{this.id}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
+        </button>
+        <button id="btn-download-plot-histogram-{{this.id}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
+        </button>
+    </div>
+{{/each}}
+</div>
+"##;
+    let distribution_vars = HashMap::from([(
+        "histograms",
+        vec![
+            HashMap::from([
+                ("id", "node-length".to_string()),
+                (
+                    "alt",
+                    distribution_alt_text("node lengths", "nodes", &info.graph_info.node_lens),
+                ),
+            ]),
+            HashMap::from([
+                ("id", "path-length".to_string()),
+                (
+                    "alt",
+                    distribution_alt_text("path lengths", "paths", &info.path_info.bp_lens),
+                ),
+            ]),
+            HashMap::from([
+                ("id", "component-size".to_string()),
+                (
+                    "alt",
+                    distribution_alt_text(
+                        "component sizes",
+                        "components",
+                        &info.graph_info.component_sizes,
+                    ),
+                ),
+            ]),
+            HashMap::from([
+                ("id", "node-degree".to_string()),
+                (
+                    "alt",
+                    distribution_alt_text("node degrees", "nodes", &info.graph_info.degrees),
+                ),
+            ]),
+        ],
+    )]);
+    tab_content.push_str(
+        &reg.render_template(distribution_info, &distribution_vars)
+            .unwrap(),
+    );
+
+    let container = r##"<div class="container p-5">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+			{{{tab_navigation}}}
+		</div>
+	</nav>
+	{{{tab_content}}}
+</div>
+"##;
+
     let vars = HashMap::from([
         ("tab_content", tab_content),
         ("tab_navigation", tab_navigation),
@@ -558,69 +1309,966 @@ pub fn generate_info_tabs(info: Info) -> String {
     reg.render_template(container, &vars).unwrap()
 }
 
+// textual summaries of the plotted data, used as alternative text of charts
+fn hist_alt_text(h: &Hist) -> String {
+    let total: usize = h.coverage.iter().skip(1).sum();
+    let (max_cov, max_val) = h
+        .coverage
+        .iter()
+        .enumerate()
+        .skip(1)
+        .max_by_key(|(_, v)| **v)
+        .map(|(i, v)| (i, *v))
+        .unwrap_or((0, 0));
+    format!(
+        "Bar chart of the coverage histogram of {}s across {} groups: {} {}s are covered by at least one group, the most frequent coverage is {} with {} {}s, and {} {}s are covered by all groups",
+        h.count,
+        h.coverage.len().saturating_sub(1),
+        total.separate_with_commas(),
+        h.count,
+        max_cov,
+        max_val.separate_with_commas(),
+        h.count,
+        h.coverage.last().unwrap_or(&0).separate_with_commas(),
+        h.count
+    )
+}
+
+// trailing arguments of a JS growth object that carry the names of the thresholds, if any
+fn growth_names_js(hist_aux: &HistAuxilliary, section: Option<usize>) -> String {
+    if hist_aux.names.iter().all(|n| n.is_none()) {
+        return String::new();
+    }
+    format!(
+        "{}, {}",
+        if section.is_none() { ", undefined" } else { "" },
+        json!(hist_aux.names)
+    )
+}
+
+fn growth_alt_text(count: &CountType, columns: &[Vec<f64>], hist_aux: &HistAuxilliary) -> String {
+    let curves = columns
+        .iter()
+        .zip(hist_aux.coverage.iter().zip(&hist_aux.quorum))
+        .zip(&hist_aux.names)
+        .map(|((col, (c, q)), name)| {
+            format!(
+                "{}coverage \u{2265} {}, quorum \u{2265} {}: {} {}s at {} groups",
+                name.as_ref()
+                    .map(|n| format!("{}, ", n))
+                    .unwrap_or_default(),
+                c.get_string(),
+                q.get_string(),
+                (col.last().unwrap_or(&0.0).floor() as usize).separate_with_commas(),
+                count,
+                col.len().saturating_sub(1)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("; ");
+    format!(
+        "Line chart of the pangenome growth of {}s; {}",
+        count, curves
+    )
+}
+
+// headline number of the growth tab, i.e., the saturation estimate under the first pair of
+// thresholds
+fn growth_headline(count: &CountType, summary: &[GrowthSummary]) -> String {
+    match summary.first() {
+        Some(s) => match s.genomes_to_saturation {
+            Some(n) => format!(
+                "an estimated {} additional groups are needed until each group adds less than {} {}s (coverage \u{2265} {}, quorum \u{2265} {})",
+                n.separate_with_commas(),
+                s.saturation,
+                count,
+                s.coverage.get_string(),
+                s.quorum.get_string()
+            ),
+            None => format!(
+                "growth of {}s does not slow down enough to estimate the number of groups needed for saturation (coverage \u{2265} {}, quorum \u{2265} {})",
+                count,
+                s.coverage.get_string(),
+                s.quorum.get_string()
+            ),
+        },
+        None => String::new(),
+    }
+}
+
+fn growth_summary_rows(summary: &[GrowthSummary]) -> String {
+    let na = |x: Option<String>| x.unwrap_or_else(|| "n/a".to_string());
+    summary
+        .iter()
+        .map(|s| {
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                s.coverage.get_string(),
+                s.quorum.get_string(),
+                na(s.k.map(|k| format!("{:.4}", k))),
+                na(s.alpha.map(|a| format!("{:.4}", a))),
+                na(s.genomes_to_saturation.map(|n| n.separate_with_commas())),
+                na(s.saturation_point.map(|n| n.separate_with_commas())),
+            )
+        })
+        .collect()
+}
+
+fn group_alt_texts(info: &Info) -> (String, String) {
+    match &info.group_info {
+        Some(group_info) if !group_info.groups.is_empty() => {
+            let n = group_info.groups.len();
+            let nodes = group_info.groups.values().map(|x| x.0);
+            let bps = group_info.groups.values().map(|x| x.1);
+            (
+                format!(
+                    "Bar chart of the number of nodes per group for {} groups, ranging from {} to {}",
+                    n,
+                    nodes.clone().min().unwrap().separate_with_commas(),
+                    nodes.max().unwrap().separate_with_commas()
+                ),
+                format!(
+                    "Bar chart of the number of bps per group for {} groups, ranging from {} to {}",
+                    n,
+                    bps.clone().min().unwrap().separate_with_commas(),
+                    bps.max().unwrap().separate_with_commas()
+                ),
+            )
+        }
+        _ => (
+            String::from("Bar chart of the number of nodes per group (no groups)"),
+            String::from("Bar chart of the number of bps per group (no groups)"),
+        ),
+    }
+}
+
+// underlying data of the report sections, embedded as base64-encoded JSON so that it can be
+// downloaded and re-plotted without access to the original tables
+fn distribution_alt_text<T: Ord + fmt::Display>(what: &str, items: &str, values: &[T]) -> String {
+    match (values.iter().min(), values.iter().max()) {
+        (Some(min), Some(max)) => format!(
+            "Bar chart of the distribution of {} across {} {}, ranging from {} to {}",
+            what,
+            values.len(),
+            items,
+            min,
+            max
+        ),
+        _ => format!("Bar chart of the distribution of {} (no {})", what, items),
+    }
+}
+
+// flat badge in the style of shields.io, text widths are approximated by a fixed width per
+// character since fonts are rendered by the viewer
+pub fn badge_svg(badge: &Badge) -> String {
+    let label = "pangenome";
+    let mut message = format!(
+        "{} groups | {} nodes | {} bp | {:.1}% core",
+        badge.groups,
+        badge.nodes.separate_with_commas(),
+        badge.bp.separate_with_commas(),
+        badge.core_percent
+    );
+    if let Some(gamma) = badge.openness {
+        message.push_str(&format!(" | \u{3b3}={:.2}", gamma));
+    }
+    let label_width = 10 + 7 * label.chars().count();
+    let message_width = 10 + 7 * message.chars().count();
+    let width = label_width + message_width;
+    format!(
+        concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"20\" role=\"img\" aria-label=\"{l}: {m}\">\n",
+            "<title>{l}: {m}</title>\n",
+            "<rect width=\"{lw}\" height=\"20\" fill=\"#555\"/>\n",
+            "<rect x=\"{lw}\" width=\"{mw}\" height=\"20\" fill=\"#4c8eda\"/>\n",
+            "<g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,DejaVu Sans,sans-serif\" font-size=\"11\">\n",
+            "<text x=\"{lx}\" y=\"14\">{l}</text>\n",
+            "<text x=\"{mx}\" y=\"14\">{m}</text>\n",
+            "</g>\n",
+            "</svg>\n"
+        ),
+        w = width,
+        l = label,
+        m = message,
+        lw = label_width,
+        mw = message_width,
+        lx = label_width / 2,
+        mx = label_width + message_width / 2,
+    )
+}
+
+fn encode_json(value: &Value) -> String {
+    general_purpose::STANDARD.encode(value.to_string())
+}
+
+fn hist_json(h: &Hist, ordered_names: Option<&Vec<String>>) -> Value {
+    let index = match ordered_names {
+        Some(names) => json!(names),
+        None => json!((0..h.coverage.len()).collect::<Vec<usize>>()),
+    };
+    json!({
+        "count": h.count.to_string(),
+        "index": index,
+        "coverage": h.coverage,
+    })
+}
+
+fn growth_json(
+    count: &CountType,
+    columns: &[Vec<f64>],
+    hist_aux: &HistAuxilliary,
+    ordered_names: Option<&Vec<String>>,
+) -> Value {
+    let index = match ordered_names {
+        Some(names) => json!(names),
+        None => json!((1..columns.first().map(|c| c.len()).unwrap_or(1)).collect::<Vec<usize>>()),
+    };
+    let growths = columns
+        .iter()
+        .zip(hist_aux.coverage.iter().zip(&hist_aux.quorum))
+        .map(|(col, (c, q))| {
+            json!({
+                "coverage": c.get_string(),
+                "quorum": q.get_string(),
+                "values": col.get(1..).unwrap_or(&[]),
+            })
+        })
+        .collect::<Vec<Value>>();
+    json!({
+        "count": count.to_string(),
+        "index": index,
+        "growths": growths,
+    })
+}
+
+fn groups_json(info: &Info) -> Value {
+    let mut groups: Vec<_> = match &info.group_info {
+        Some(group_info) => group_info.groups.iter().collect(),
+        None => Vec::new(),
+    };
+    groups.sort_by_key(|(k, _)| *k);
+    json!({
+        "group": groups.iter().map(|(k, _)| k).collect::<Vec<_>>(),
+        "node": groups.iter().map(|(_, v)| v.0).collect::<Vec<_>>(),
+        "bp": groups.iter().map(|(_, v)| v.1).collect::<Vec<_>>(),
+    })
+}
+
 pub fn write_html<W: Write>(
     vars: &HashMap<&str, String>,
     out: &mut BufWriter<W>,
 ) -> Result<(), std::io::Error> {
     let reg = Handlebars::new();
-    let html = String::from_utf8_lossy(HTML_TEMPLATE);
-    out.write(reg.render_template(&html, vars).unwrap().as_bytes())
-        .map(|_| ())
+    let html = template_file("report_template.html", HTML_TEMPLATE)?;
+    let mut vars = vars.clone();
+    vars.insert("head_assets", head_assets()?);
+    vars.insert("report_tables", generate_report_tables()?);
+    vars.insert("run_log", generate_run_log());
+    vars.insert(
+        "panacus_logo",
+        general_purpose::STANDARD_NO_PAD.encode(template_file(
+            "panacus-illustration-small.png",
+            PANACUS_LOGO,
+        )?),
+    );
+    vars.insert(
+        "symbols_svg",
+        String::from_utf8_lossy(&template_file("symbols.svg", SYMBOLS_SVG)?).into_owned(),
+    );
+    let report = reg
+        .render_template(&String::from_utf8_lossy(&html), &vars)
+        .map_err(|e| {
+            let msg = format!("unable to render report template: {}", e);
+            log::error!("{}", msg);
+            std::io::Error::new(std::io::ErrorKind::InvalidData, msg)
+        })?;
+    out.write(report.as_bytes()).map(|_| ())
+}
+
+pub fn write_hist_html<W: Write>(
+    hists: &[Hist],
+    edge_orientations: &[EdgeOrientationHist],
+    fname: &str,
+    info: Option<Info>,
+    bins: &Binning,
+    out: &mut BufWriter<W>,
+) -> Result<(), std::io::Error> {
+    let mut vars: HashMap<&str, String> = HashMap::default();
+
+    let content = r##"
+<div class="d-flex align-items-start">
+	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
+        <button class="nav-link text-nowrap active" id="v-pills-info-tab" data-bs-toggle="pill" data-bs-target="#v-pills-info" type="button" role="tab" aria-controls="v-pills-info" aria-selected="true">pangenome info</button>
+    	<button class="nav-link text-nowrap" id="v-pills-hist-tab" data-bs-toggle="pill" data-bs-target="#v-pills-hist" type="button" role="tab" aria-controls="v-pills-hist" aria-selected="false">coverage histogram</button>
+{{#if edge_orientation_content}}
+    	<button class="nav-link text-nowrap" id="v-pills-edge-orientation-tab" data-bs-toggle="pill" data-bs-target="#v-pills-edge-orientation" type="button" role="tab" aria-controls="v-pills-edge-orientation" aria-selected="false">edge orientation</button>
+{{/if}}
+ 	</div>
+  	<div class="tab-content w-100" id="v-pills-tabContent">
+		<div class="tab-pane fade show active" id="v-pills-info" role="tabpanel" aria-labelledby="v-pills-info-tab">
+{{{info_content}}}
+		</div>
+		<div class="tab-pane fade" id="v-pills-hist" role="tabpanel" aria-labelledby="v-pills-hist-tab">
+{{{hist_content}}}
+		</div>
+{{#if edge_orientation_content}}
+		<div class="tab-pane fade" id="v-pills-edge-orientation" role="tabpanel" aria-labelledby="v-pills-edge-orientation-tab">
+{{{edge_orientation_content}}}
+		</div>
+{{/if}}
+  </div>
+</div>
+"##;
+
+    let mut js_objects = String::from("const hists = [\n");
+    for (i, h) in hists.iter().enumerate() {
+        if i > 0 {
+            js_objects.push_str(",\n");
+        }
+        js_objects.push_str(&format!(
+            "new Hist('{}', {:?}, {:?})",
+            h.count,
+            (0..h.coverage.len()).collect::<Vec<usize>>(),
+            h.coverage
+        ));
+    }
+    js_objects.push_str("];\n\nconst growths = [];\n");
+    js_objects.push_str("const fname = '");
+    js_objects.push_str(fname);
+    js_objects.push_str("';\n");
+    js_objects.push_str("const info = `");
+    let info_text = match info {
+        Some(ref s) => s.to_string(),
+        _ => "".to_string(),
+    };
+    js_objects.push_str(info_text.as_str());
+    js_objects.push_str("`;\n");
+
+    if let Some(info_obj) = &info {
+        let info_object = get_info_js_object(info_obj, bins);
+        js_objects.push_str(&info_object[..]);
+    }
+
+    let reg = Handlebars::new();
+    vars.insert("fname", fname.to_string());
+    vars.insert("data_hook", js_objects);
+    vars.insert(
+        "content",
+        reg.render_template(
+            content,
+            &HashMap::from([
+                ("hist_content", generate_hist_tabs(hists, fname, None, None)),
+                ("info_content", generate_info_tabs(info.unwrap(), fname)),
+                (
+                    "edge_orientation_content",
+                    if edge_orientations.is_empty() {
+                        String::new()
+                    } else {
+                        generate_edge_orientation_tab(edge_orientations, fname)
+                    },
+                ),
+            ]),
+        )
+        .unwrap(),
+    );
+
+    populate_constants(&mut vars);
+    write_html(&vars, out)
+}
+
+fn get_info_js_object(info: &Info, bins: &Binning) -> String {
+    let mut js_objects = String::new();
+
+    js_objects.push_str("const groups = [\n");
+
+    let groups = &info.group_info.as_ref().unwrap().groups;
+
+    if groups.len() >= 100 {
+        let nodes = groups.values().map(|x| x.0).collect::<Vec<_>>();
+        let bps = groups.values().map(|x| x.1).collect::<Vec<_>>();
+        let binned_nodes = bins.apply(&nodes);
+        let binned_bps = bins.apply(&bps);
+        js_objects.push_str(&format!(
+            "new Group('node', {:?}, {:?}, true)",
+            binned_nodes.0, binned_nodes.1,
+        ));
+        js_objects.push_str(",\n");
+        js_objects.push_str(&format!(
+            "new Group('bp', {:?}, {:?}, true)",
+            binned_bps.0, binned_bps.1,
+        ));
+    } else {
+        let mut sorted_groups: Vec<_> = groups.clone().into_iter().collect();
+        sorted_groups.sort_by(|(k0, _v0), (k1, _v1)| k0.cmp(k1));
+        let group_names: Vec<_> = sorted_groups.iter().map(|(k, _v)| k).collect();
+        let nodes: Vec<_> = sorted_groups.iter().map(|(_k, v)| v.0).collect();
+        let bps: Vec<_> = sorted_groups.iter().map(|(_k, v)| v.1).collect();
+        js_objects.push_str(&format!(
+            "new Group('node', {:?}, {:?}, false)",
+            group_names, nodes,
+        ));
+        js_objects.push_str(",\n");
+        js_objects.push_str(&format!(
+            "new Group('bp', {:?}, {:?}, false)",
+            group_names, bps,
+        ));
+    }
+    js_objects.push_str("];\n");
+
+    js_objects.push_str("const histograms = [\n");
+    for (id, (labels, counts), x_label, y_label) in [
+        (
+            "node-length",
+            bins.apply(&info.graph_info.node_lens),
+            "node length (bp)",
+            "#nodes",
+        ),
+        (
+            "path-length",
+            bins.apply(&info.path_info.bp_lens),
+            "path length (bp)",
+            "#paths",
+        ),
+        (
+            "component-size",
+            bins.apply(&info.graph_info.component_sizes),
+            "component size (#nodes)",
+            "#components",
+        ),
+        (
+            "node-degree",
+            bins.apply(&info.graph_info.degrees),
+            "node degree",
+            "#nodes",
+        ),
+    ] {
+        js_objects.push_str(&format!(
+            "new Histogram('{}', {:?}, {:?}, '{}', '{}'),\n",
+            id, labels, counts, x_label, y_label
+        ));
+    }
+    js_objects.push_str("];\n");
+    js_objects
+}
+
+pub fn write_info_html<W: Write>(
+    fname: &str,
+    info: Info,
+    bins: &Binning,
+    out: &mut BufWriter<W>,
+) -> Result<(), std::io::Error> {
+    log::info!("Writing info html");
+    let mut vars: HashMap<&str, String> = HashMap::default();
+
+    let content = r##"
+<div class="d-flex align-items-start">
+	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
+        <button class="nav-link text-nowrap active" id="v-pills-info-tab" data-bs-toggle="pill" data-bs-target="#v-pills-info" type="button" role="tab" aria-controls="v-pills-info" aria-selected="true">pangenome info</button>
+ 	</div>
+  	<div class="tab-content w-100" id="v-pills-tabContent">
+		<div class="tab-pane fade show active" id="v-pills-info" role="tabpanel" aria-labelledby="v-pills-info-tab">
+{{{info_content}}}
+		</div>
+  </div>
+</div>
+"##;
+
+    let mut js_objects = String::from("const hists = [");
+    js_objects.push_str("];\n\nconst growths = [];\n");
+    js_objects.push_str("const fname = '");
+    js_objects.push_str(fname);
+    js_objects.push_str("';\n");
+    js_objects.push_str("const info = `");
+    let info_text = info.to_string();
+    js_objects.push_str(info_text.as_str());
+    js_objects.push_str("`;\n");
+
+    let info_object = get_info_js_object(&info, bins);
+    js_objects.push_str(&info_object[..]);
+
+    let reg = Handlebars::new();
+    vars.insert("fname", fname.to_string());
+    vars.insert("data_hook", js_objects);
+    vars.insert(
+        "content",
+        reg.render_template(
+            content,
+            &HashMap::from([("info_content", generate_info_tabs(info, fname))]),
+        )
+        .unwrap(),
+    );
+
+    populate_constants(&mut vars);
+    write_html(&vars, out)
+}
+
+pub fn write_similarity_html<W: Write>(
+    abacus_group: &AbacusByGroup,
+    metric: SimilarityMetric,
+    tree_method: Option<ClusterMethod>,
+    fname: &str,
+    out: &mut BufWriter<W>,
+) -> Result<(), std::io::Error> {
+    log::info!("Writing similarity html");
+    let mut vars: HashMap<&str, String> = HashMap::default();
+
+    let content = r##"
+<div class="d-flex align-items-start">
+	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
+        <button class="nav-link text-nowrap active" id="v-pills-similarity-tab" data-bs-toggle="pill" data-bs-target="#v-pills-similarity" type="button" role="tab" aria-controls="v-pills-similarity" aria-selected="true">{{metric}} similarity</button>
+{{#if dendrogram_content}}
+        <button class="nav-link text-nowrap" id="v-pills-dendrogram-tab" data-bs-toggle="pill" data-bs-target="#v-pills-dendrogram" type="button" role="tab" aria-controls="v-pills-dendrogram" aria-selected="false">dendrogram</button>
+{{/if}}
+ 	</div>
+  	<div class="tab-content w-100" id="v-pills-tabContent">
+		<div class="tab-pane fade show active" id="v-pills-similarity" role="tabpanel" aria-labelledby="v-pills-similarity-tab">
+{{{similarity_content}}}
+		</div>
+{{#if dendrogram_content}}
+		<div class="tab-pane fade" id="v-pills-dendrogram" role="tabpanel" aria-labelledby="v-pills-dendrogram-tab">
+{{{dendrogram_content}}}
+		</div>
+{{/if}}
+  </div>
+</div>
+"##;
+
+    let mut js_objects = String::from("const hists = [];\n\nconst growths = [];\n");
+    js_objects.push_str("const fname = '");
+    js_objects.push_str(fname);
+    js_objects.push_str("';\n");
+
+    // with a tree, the heatmap lists groups in the order of its leaves, so that clusters show up
+    // as blocks along the diagonal
+    let tree = tree_method.map(|method| (method, abacus_group.similarity_tree(metric, method)));
+    let order: Vec<usize> = match &tree {
+        Some((_, t)) => t.leaf_order(),
+        None => (0..abacus_group.groups.len()).collect(),
+    };
+
+    let reg = Handlebars::new();
+    vars.insert("fname", fname.to_string());
+    vars.insert("data_hook", js_objects);
+    vars.insert(
+        "content",
+        reg.render_template(
+            content,
+            &HashMap::from([
+                ("metric", metric.to_string()),
+                (
+                    "similarity_content",
+                    generate_similarity_tab(abacus_group, metric, &order, fname),
+                ),
+                (
+                    "dendrogram_content",
+                    match &tree {
+                        Some((method, t)) => {
+                            generate_dendrogram_tab(t, *method, metric, abacus_group.count, fname)
+                        }
+                        None => String::new(),
+                    },
+                ),
+            ]),
+        )
+        .unwrap(),
+    );
+
+    populate_constants(&mut vars);
+    write_html(&vars, out)
+}
+
+pub fn generate_pairwise_tabs(abaci: &[AbacusByGroup], fname: &str) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+{{#each items}}
+			<button class="nav-link{{#if @first}} active{{/if}}" id="nav-pairwise-{{count}}-tab" data-bs-toggle="tab" data-bs-target="#nav-pairwise-{{count}}" type="button" role="tab" aria-controls="nav-pairwise-{{count}}" aria-selected="{{#if @first}}true{{else}}false{{/if}}">{{count}}</button>
+{{/each}}
+		</div>
+	</nav>
+	<div class="tab-content" id="nav-tabContent">
+{{#each items}}
+	<div class="tab-pane fade{{#if @first}} show active{{/if}} overflow-auto" id="nav-pairwise-{{count}}" role="tabpanel" aria-labelledby="nav-pairwise-{{count}}-tab" tabindex="0">
+{{{heatmap}}}
+    <div class="d-flex flex-row-reverse">
+        <a id="btn-download-data-pairwise-{{count}}" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{../fname}}_pairwise_{{count}}.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
+    </div>
+	</div>
+{{/each}}
+	</div>
+</div>
+"##;
+
+    let items: Vec<_> = abaci
+        .iter()
+        .map(|abacus| {
+            let intersections = abacus.pairwise_intersections();
+            let matrix: Vec<Vec<f64>> = intersections
+                .iter()
+                .map(|row| row.iter().map(|x| *x as f64).collect())
+                .collect();
+            let max = matrix.iter().flatten().fold(0.0, |a: f64, b| a.max(*b));
+            let alt = format!(
+                "Heatmap of the {}s shared by each pair of {} groups",
+                abacus.count,
+                abacus.groups.len()
+            );
+            json!({
+                "count": abacus.count.to_string(),
+                "heatmap": generate_heatmap(&abacus.groups, &abacus.groups, &matrix, (0.0, max), &alt),
+                "data": encode_json(&json!({
+                    "count": abacus.count,
+                    "group": abacus.groups,
+                    "intersection": intersections,
+                })),
+            })
+        })
+        .collect();
+    reg.render_template(tab, &json!({ "items": items, "fname": fname }))
+        .unwrap()
+}
+
+pub fn write_pairwise_html<W: Write>(
+    abaci: &[AbacusByGroup],
+    fname: &str,
+    out: &mut BufWriter<W>,
+) -> Result<(), std::io::Error> {
+    log::info!("Writing pairwise html");
+    let mut vars: HashMap<&str, String> = HashMap::default();
+
+    let content = r##"
+<div class="d-flex align-items-start">
+	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
+        <button class="nav-link text-nowrap active" id="v-pills-pairwise-tab" data-bs-toggle="pill" data-bs-target="#v-pills-pairwise" type="button" role="tab" aria-controls="v-pills-pairwise" aria-selected="true">shared sequence</button>
+ 	</div>
+  	<div class="tab-content w-100" id="v-pills-tabContent">
+		<div class="tab-pane fade show active" id="v-pills-pairwise" role="tabpanel" aria-labelledby="v-pills-pairwise-tab">
+{{{pairwise_content}}}
+		</div>
+  </div>
+</div>
+"##;
+
+    let mut js_objects = String::from("const hists = [];\n\nconst growths = [];\n");
+    js_objects.push_str("const fname = '");
+    js_objects.push_str(fname);
+    js_objects.push_str("';\n");
+
+    let reg = Handlebars::new();
+    vars.insert("fname", fname.to_string());
+    vars.insert("data_hook", js_objects);
+    vars.insert(
+        "content",
+        reg.render_template(
+            content,
+            &HashMap::from([("pairwise_content", generate_pairwise_tabs(abaci, fname))]),
+        )
+        .unwrap(),
+    );
+
+    populate_constants(&mut vars);
+    write_html(&vars, out)
+}
+
+pub fn generate_private_tabs(abaci: &[AbacusByGroup], fname: &str) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+{{#each items}}
+			<button class="nav-link{{#if @first}} active{{/if}}" id="nav-private-{{count}}-tab" data-bs-toggle="tab" data-bs-target="#nav-private-{{count}}" type="button" role="tab" aria-controls="nav-private-{{count}}" aria-selected="{{#if @first}}true{{else}}false{{/if}}">{{count}}</button>
+{{/each}}
+		</div>
+	</nav>
+	<div class="tab-content" id="nav-tabContent">
+{{#each items}}
+	<div class="tab-pane fade{{#if @first}} show active{{/if}} overflow-auto" id="nav-private-{{count}}" role="tabpanel" aria-labelledby="nav-private-{{count}}-tab" tabindex="0">
+{{{chart}}}
+    <div class="d-flex flex-row-reverse">
+        <a id="btn-download-data-private-{{count}}" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{../fname}}_private_{{count}}.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
+    </div>
+	</div>
+{{/each}}
+	</div>
+</div>
+"##;
+
+    let items: Vec<_> = abaci
+        .iter()
+        .map(|abacus| {
+            let (private, total): (Vec<usize>, Vec<usize>) =
+                abacus.private_countables().into_iter().unzip();
+            json!({
+                "count": abacus.count.to_string(),
+                "chart": to_svg(&[Chart::from_private(abacus)]),
+                "data": encode_json(&json!({
+                    "count": abacus.count,
+                    "group": abacus.groups,
+                    "private": private,
+                    "total": total,
+                })),
+            })
+        })
+        .collect();
+    reg.render_template(tab, &json!({ "items": items, "fname": fname }))
+        .unwrap()
+}
+
+pub fn write_private_html<W: Write>(
+    abaci: &[AbacusByGroup],
+    fname: &str,
+    out: &mut BufWriter<W>,
+) -> Result<(), std::io::Error> {
+    log::info!("Writing private countables html");
+    let mut vars: HashMap<&str, String> = HashMap::default();
+
+    let content = r##"
+<div class="d-flex align-items-start">
+	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
+        <button class="nav-link text-nowrap active" id="v-pills-private-tab" data-bs-toggle="pill" data-bs-target="#v-pills-private" type="button" role="tab" aria-controls="v-pills-private" aria-selected="true">private sequence</button>
+ 	</div>
+  	<div class="tab-content w-100" id="v-pills-tabContent">
+		<div class="tab-pane fade show active" id="v-pills-private" role="tabpanel" aria-labelledby="v-pills-private-tab">
+{{{private_content}}}
+		</div>
+  </div>
+</div>
+"##;
+
+    let mut js_objects = String::from("const hists = [];\n\nconst growths = [];\n");
+    js_objects.push_str("const fname = '");
+    js_objects.push_str(fname);
+    js_objects.push_str("';\n");
+
+    let reg = Handlebars::new();
+    vars.insert("fname", fname.to_string());
+    vars.insert("data_hook", js_objects);
+    vars.insert(
+        "content",
+        reg.render_template(
+            content,
+            &HashMap::from([("private_content", generate_private_tabs(abaci, fname))]),
+        )
+        .unwrap(),
+    );
+
+    populate_constants(&mut vars);
+    write_html(&vars, out)
+}
+
+pub fn write_windows_html<W: Write>(
+    hists: &[Hist],
+    windows: &[(usize, usize)],
+    path_name: &str,
+    fname: &str,
+    out: &mut BufWriter<W>,
+) -> Result<(), std::io::Error> {
+    log::info!("Writing windows html");
+    let mut vars: HashMap<&str, String> = HashMap::default();
+
+    let content = r##"
+<div class="d-flex align-items-start">
+	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
+        <button class="nav-link text-nowrap active" id="v-pills-windows-tab" data-bs-toggle="pill" data-bs-target="#v-pills-windows" type="button" role="tab" aria-controls="v-pills-windows" aria-selected="true">pangenome along {{path}}</button>
+ 	</div>
+  	<div class="tab-content w-100" id="v-pills-tabContent">
+		<div class="tab-pane fade show active" id="v-pills-windows" role="tabpanel" aria-labelledby="v-pills-windows-tab">
+{{{windows_content}}}
+		</div>
+  </div>
+</div>
+"##;
+
+    let count = hists.first().map(|h| h.count).unwrap_or(CountType::Node);
+    let (pan, core) = window_profile(hists);
+    let mut js_objects = String::from("const hists = [];\n\nconst growths = [];\n");
+    js_objects.push_str(&format!(
+        "const profiles = [new Profile('{}', {:?}, ['pangenome', 'core'], [{:?}, {:?}], 'position on {}')];\n",
+        count,
+        windows.iter().map(|w| w.0).collect::<Vec<_>>(),
+        pan,
+        core,
+        path_name
+    ));
+    js_objects.push_str("const fname = '");
+    js_objects.push_str(fname);
+    js_objects.push_str("';\n");
+
+    let reg = Handlebars::new();
+    vars.insert("fname", fname.to_string());
+    vars.insert("data_hook", js_objects);
+    vars.insert(
+        "content",
+        reg.render_template(
+            content,
+            &HashMap::from([
+                ("path", path_name.to_string()),
+                (
+                    "windows_content",
+                    generate_windows_tab(hists, windows, path_name, fname),
+                ),
+            ]),
+        )
+        .unwrap(),
+    );
+
+    populate_constants(&mut vars);
+    write_html(&vars, out)
+}
+
+pub fn generate_bubbles_tab(
+    bubbles: &[Bubble],
+    graph_aux: &GraphAuxilliary,
+    fname: &str,
+) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+			<button class="nav-link active" id="nav-bubbles-tab" data-bs-toggle="tab" data-bs-target="#nav-bubbles" type="button" role="tab" aria-controls="nav-bubbles" aria-selected="true">bubbles</button>
+		</div>
+	</nav>
+	<div class="tab-pane fade show active" id="nav-bubbles" role="tabpanel" aria-labelledby="nav-bubbles-tab" tabindex="0">
+    <table class="table table-striped table-hover">
+      <thead>
+        <tr>
+          <th scope="col">type</th>
+          <th scope="col">count</th>
+        </tr>
+      </thead>
+      <tbody>
+        <tr>
+          <td>simple bubble</td>
+          <td>{{{simple}}}</td>
+        </tr>
+        <tr>
+          <td>superbubble</td>
+          <td>{{{super}}}</td>
+        </tr>
+      </tbody>
+    </table>
+{{#each histograms}}
+    <br/>
+    <div class="d-flex flex-row-reverse">
+        <div class="form-check form-switch">
+            <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-histogram-{{this.id}}">
+            <label class="form-check-label" for="btn-logscale-plot-histogram-{{this.id}}">log-scale</label>
+        </div>
+    </div>
+    <canvas id="chart-histogram-{{this.id}}" role="img" aria-label="{{this.alt}}"><p>{{this.alt}}</p></canvas>
+    <div class="d-flex flex-row-reverse">
+        <button id="btn-download-table-histogram-{{this.id}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
+        </button>
+        <button id="btn-download-plot-histogram-{{this.id}}" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
+        </button>
+    </div>
+{{/each}}
+    <div class="d-flex flex-row-reverse">
+        <a id="btn-download-data-bubbles" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{fname}}_bubbles.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
+    </div>
+	</div>
+</div>
+"##;
+
+    let mut id2node: Vec<&[u8]> = vec![&[]; graph_aux.node_count + 1];
+    for (node, id) in graph_aux.node2id.iter() {
+        id2node[id.0 as usize] = &node[..];
+    }
+    let node = |(id, o): (ItemId, Orientation)| {
+        format!("{}{}", o, String::from_utf8_lossy(id2node[id.0 as usize]))
+    };
+    let simple = bubbles.iter().filter(|b| b.simple).count();
+    let sizes: Vec<u64> = bubbles.iter().map(|b| b.nodes as u64).collect();
+    let alleles: Vec<u64> = bubbles.iter().map(|b| b.alleles).collect();
+    let vars = json!({
+        "simple": simple.separate_with_commas(),
+        "super": (bubbles.len() - simple).separate_with_commas(),
+        "histograms": [
+            {
+                "id": "bubble-size",
+                "alt": distribution_alt_text("bubble sizes (#nodes)", "bubbles", &sizes),
+            },
+            {
+                "id": "bubble-alleles",
+                "alt": distribution_alt_text("numbers of alleles", "bubbles", &alleles),
+            },
+        ],
+        "fname": fname,
+        "data": encode_json(&json!({
+            "source": bubbles.iter().map(|b| node(b.source)).collect::<Vec<_>>(),
+            "sink": bubbles.iter().map(|b| node(b.sink)).collect::<Vec<_>>(),
+            "simple": bubbles.iter().map(|b| b.simple).collect::<Vec<_>>(),
+            "nodes": sizes,
+            "bp": bubbles.iter().map(|b| b.bp).collect::<Vec<_>>(),
+            "alleles": alleles,
+        })),
+    });
+    reg.render_template(tab, &vars).unwrap()
 }
 
-pub fn write_hist_html<W: Write>(
-    hists: &[Hist],
+pub fn write_bubbles_html<W: Write>(
+    bubbles: &[Bubble],
+    graph_aux: &GraphAuxilliary,
+    bins: &Binning,
     fname: &str,
-    info: Option<Info>,
     out: &mut BufWriter<W>,
 ) -> Result<(), std::io::Error> {
+    log::info!("Writing bubbles html");
     let mut vars: HashMap<&str, String> = HashMap::default();
 
     let content = r##"
 <div class="d-flex align-items-start">
 	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
-        <button class="nav-link text-nowrap active" id="v-pills-info-tab" data-bs-toggle="pill" data-bs-target="#v-pills-info" type="button" role="tab" aria-controls="v-pills-info" aria-selected="false">pangenome info</button>
-    	<button class="nav-link text-nowrap" id="v-pills-hist-tab" data-bs-toggle="pill" data-bs-target="#v-pills-hist" type="button" role="tab" aria-controls="v-pills-hist" aria-selected="true">coverage histogram</button>
+        <button class="nav-link text-nowrap active" id="v-pills-bubbles-tab" data-bs-toggle="pill" data-bs-target="#v-pills-bubbles" type="button" role="tab" aria-controls="v-pills-bubbles" aria-selected="true">bubbles</button>
  	</div>
   	<div class="tab-content w-100" id="v-pills-tabContent">
-		<div class="tab-pane fade show active" id="v-pills-info" role="tabpanel" aria-labelledby="v-pills-info-tab">
-{{{info_content}}}
-		</div>
-		<div class="tab-pane fade" id="v-pills-hist" role="tabpanel" aria-labelledby="v-pills-hist-tab">
-{{{hist_content}}}
+		<div class="tab-pane fade show active" id="v-pills-bubbles" role="tabpanel" aria-labelledby="v-pills-bubbles-tab">
+{{{bubbles_content}}}
 		</div>
   </div>
 </div>
 "##;
 
-    let mut js_objects = String::from("const hists = [\n");
-    for (i, h) in hists.iter().enumerate() {
-        if i > 0 {
-            js_objects.push_str(",\n");
-        }
+    let mut js_objects = String::from("const hists = [];\n\nconst growths = [];\n");
+    js_objects.push_str("const histograms = [\n");
+    for (id, (labels, counts), x_label) in [
+        (
+            "bubble-size",
+            bins.apply(&bubbles.iter().map(|b| b.nodes as u64).collect::<Vec<_>>()),
+            "bubble size (#nodes)",
+        ),
+        (
+            "bubble-alleles",
+            bins.apply(&bubbles.iter().map(|b| b.alleles).collect::<Vec<_>>()),
+            "#alleles",
+        ),
+    ] {
         js_objects.push_str(&format!(
-            "new Hist('{}', {:?}, {:?})",
-            h.count,
-            (0..h.coverage.len()).collect::<Vec<usize>>(),
-            h.coverage
+            "new Histogram('{}', {:?}, {:?}, '{}', '#bubbles'),\n",
+            id, labels, counts, x_label
         ));
     }
-    js_objects.push_str("];\n\nconst growths = [];\n");
+    js_objects.push_str("];\n");
     js_objects.push_str("const fname = '");
     js_objects.push_str(fname);
     js_objects.push_str("';\n");
-    js_objects.push_str("const info = `");
-    let info_text = match info {
-        Some(ref s) => s.to_string(),
-        _ => "".to_string(),
-    };
-    js_objects.push_str(info_text.as_str());
-    js_objects.push_str("`;\n");
-
-    if let Some(info_obj) = &info {
-        let info_object = get_info_js_object(info_obj);
-        js_objects.push_str(&info_object[..]);
-    }
 
     let reg = Handlebars::new();
     vars.insert("fname", fname.to_string());
@@ -629,10 +2277,10 @@ pub fn write_hist_html<W: Write>(
         "content",
         reg.render_template(
             content,
-            &HashMap::from([
-                ("hist_content", generate_hist_tabs(hists)),
-                ("info_content", generate_info_tabs(info.unwrap())),
-            ]),
+            &HashMap::from([(
+                "bubbles_content",
+                generate_bubbles_tab(bubbles, graph_aux, fname),
+            )]),
         )
         .unwrap(),
     );
@@ -641,123 +2289,314 @@ pub fn write_hist_html<W: Write>(
     write_html(&vars, out)
 }
 
-fn bin_values(list: &Vec<u32>) -> (Vec<String>, Vec<usize>) {
-    log::info!("Binning values");
-    if list.is_empty() {
-        return (Vec::new(), Vec::new());
-    }
-    let n_bins = 50;
-    let max = *list.iter().max().unwrap();
-    let min = *list.iter().min().unwrap();
-    let mut bin_size = ((max - min) as f32 / n_bins as f32).round() as usize;
-    if bin_size < 1 {
-        bin_size = 1;
+// label, values (for 1, 2, ... groups) of all growth curves of the compared files, and the
+// y-axis label shared by all curves
+pub(crate) fn growth_comparison_series(
+    comparison: &[GrowthComparison],
+) -> (Vec<String>, Vec<Vec<f64>>, String) {
+    let mut labels = Vec::new();
+    let mut values = Vec::new();
+    let mut counts = Vec::new();
+    for c in comparison {
+        for g in &c.results.growths {
+            counts.push(g.count);
+            for (i, ((cov, q), v)) in g.coverage.iter().zip(&g.quorum).zip(&g.values).enumerate() {
+                labels.push(match g.name(i) {
+                    Some(name) => format!("{}: {}, {}", c.file, g.count, name),
+                    None => format!(
+                        "{}: {}, coverage {}, quorum {}",
+                        c.file,
+                        g.count,
+                        cov.get_string(),
+                        q.get_string()
+                    ),
+                });
+                values.push(v.clone());
+            }
+        }
     }
-    log::debug!(
-        "Binning with min: {}, max: {}, bin_size: {}",
-        min,
-        max,
-        bin_size
-    );
-    let bins: Vec<_> = (min..max)
-        .step_by(bin_size)
-        .zip((min + (bin_size as u32)..max + 1).step_by(bin_size))
-        .collect();
-    let values = bins
-        .iter()
-        .map(|(s, e)| list.iter().filter(|a| **a >= *s && **a < *e).count())
-        .collect::<Vec<_>>();
-    let bin_names = bins
+    counts.dedup();
+    let y_label = match &counts[..] {
+        [count] => format!("#{}s", count),
+        _ => "#countables".to_string(),
+    };
+    (labels, values, y_label)
+}
+
+fn growth_comparison_js_object(comparison: &[GrowthComparison]) -> String {
+    let (labels, values, y_label) = growth_comparison_series(comparison);
+    let n = values.iter().map(|v| v.len()).max().unwrap_or(0);
+    let mut js_objects = String::from("const hists = [];\n\nconst growths = [];\n");
+    js_objects.push_str(&format!(
+        "const profiles = [new Profile('comparison', {:?}, {:?}, {:?}, '#groups', '{}')];\n",
+        (1..n + 1).collect::<Vec<_>>(),
+        labels,
+        values,
+        y_label
+    ));
+    js_objects
+}
+
+pub fn generate_growth_comparison_tab(comparison: &[GrowthComparison], fname: &str) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+			<button class="nav-link active" id="nav-profile-comparison-tab" data-bs-toggle="tab" data-bs-target="#nav-profile-comparison" type="button" role="tab" aria-controls="nav-profile-comparison" aria-selected="true">growth</button>
+		</div>
+	</nav>
+	<div class="tab-pane fade show active" id="nav-profile-comparison" role="tabpanel" aria-labelledby="nav-profile-comparison-tab" tabindex="0">
+    <div class="d-flex flex-row-reverse">
+        <div class="form-check form-switch">
+            <input class="form-check-input" type="checkbox" role="switch" id="btn-logscale-plot-profile-comparison">
+            <label class="form-check-label" for="btn-logscale-plot-profile-comparison">log-scale</label>
+        </div>
+    </div>
+    <canvas id="chart-profile-comparison" role="img" aria-label="{{alt}}"><p>{{alt}}</p></canvas>
+    <table class="table table-striped table-hover">
+      <thead>
+        <tr>
+          <th scope="col">file</th>
+          <th scope="col">count</th>
+          <th scope="col">coverage</th>
+          <th scope="col">quorum</th>
+          <th scope="col">k</th>
+          <th scope="col">&alpha;</th>
+          <th scope="col">groups to saturation</th>
+          <th scope="col">saturation point</th>
+        </tr>
+      </thead>
+      <tbody class="table-group-divider">
+        {{{summary}}}
+      </tbody>
+    </table>
+    <div class="d-flex flex-row-reverse">
+        <button id="btn-download-table-profile-comparison" type="button" class="d-flex align-items-center btn m-1" aria-label="download table">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#table"></use></svg>
+        </button>
+        <button id="btn-download-plot-profile-comparison" type="button" class="d-flex align-items-center btn m-1" aria-label="download plot">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#card-image"></use></svg>
+        </button>
+        <a id="btn-download-data-profile-comparison" class="d-flex align-items-center btn m-1" role="button" href="data:application/json;base64,{{{data}}}" download="{{fname}}.json" aria-label="download data">
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#download"></use></svg>
+            <svg class="bi opacity-50 m-1" width="15" height="15" aria-hidden="true"><use href="#braces"></use></svg>
+        </a>
+    </div>
+	</div>
+</div>
+"##;
+
+    let (labels, _, y_label) = growth_comparison_series(comparison);
+    let summary: String = comparison
         .iter()
-        .map(|(s, e)| format!("{}-{}", s, e))
-        .collect::<Vec<_>>();
-    (bin_names, values)
+        .flat_map(|c| {
+            c.results.growths.iter().flat_map(move |g| {
+                g.summary.iter().map(move |s| {
+                    growth_summary_rows(std::slice::from_ref(s)).replacen(
+                        "<tr>",
+                        &format!(
+                            "<tr><td>{}</td><td>{}</td>",
+                            handlebars::html_escape(&c.file),
+                            g.count
+                        ),
+                        1,
+                    )
+                })
+            })
+        })
+        .collect();
+    let vars = HashMap::from([
+        (
+            "alt",
+            format!(
+                "Line chart of {} growth curves ({}) of {} histogram files",
+                labels.len(),
+                y_label,
+                comparison.len()
+            ),
+        ),
+        ("summary", summary),
+        ("fname", fname.to_string()),
+        ("data", encode_json(&json!(comparison))),
+    ]);
+    reg.render_template(tab, &vars).unwrap()
 }
 
-fn get_info_js_object(info: &Info) -> String {
-    let mut js_objects = String::new();
+pub fn write_growth_comparison_html<W: Write>(
+    comparison: &[GrowthComparison],
+    out: &mut BufWriter<W>,
+) -> Result<(), std::io::Error> {
+    log::info!("Writing growth comparison html");
+    let mut vars: HashMap<&str, String> = HashMap::default();
+    // downloads are named after the comparison, the report header lists all files
+    let fname = "growth_comparison";
 
-    js_objects.push_str("const groups = [\n");
+    let content = r##"
+<div class="d-flex align-items-start">
+	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
+        <button class="nav-link text-nowrap active" id="v-pills-comparison-tab" data-bs-toggle="pill" data-bs-target="#v-pills-comparison" type="button" role="tab" aria-controls="v-pills-comparison" aria-selected="true">pangenome growth</button>
+ 	</div>
+  	<div class="tab-content w-100" id="v-pills-tabContent">
+		<div class="tab-pane fade show active" id="v-pills-comparison" role="tabpanel" aria-labelledby="v-pills-comparison-tab">
+{{{comparison_content}}}
+		</div>
+  </div>
+</div>
+"##;
 
-    let groups = &info.group_info.as_ref().unwrap().groups;
+    let mut js_objects = growth_comparison_js_object(comparison);
+    js_objects.push_str("const fname = '");
+    js_objects.push_str(fname);
+    js_objects.push_str("';\n");
 
-    if groups.len() >= 100 {
-        let nodes = groups.values().map(|x| x.0).collect::<Vec<_>>();
-        let bps = groups.values().map(|x| x.1).collect::<Vec<_>>();
-        let binned_nodes = bin_values(&nodes);
-        let binned_bps = bin_values(&bps);
-        js_objects.push_str(&format!(
-            "new Group('node', {:?}, {:?}, true)",
-            binned_nodes.0, binned_nodes.1,
-        ));
-        js_objects.push_str(",\n");
-        js_objects.push_str(&format!(
-            "new Group('bp', {:?}, {:?}, true)",
-            binned_bps.0, binned_bps.1,
-        ));
-    } else {
-        let mut sorted_groups: Vec<_> = groups.clone().into_iter().collect();
-        sorted_groups.sort_by(|(k0, _v0), (k1, _v1)| k0.cmp(k1));
-        let group_names: Vec<_> = sorted_groups.iter().map(|(k, _v)| k).collect();
-        let nodes: Vec<_> = sorted_groups.iter().map(|(_k, v)| v.0).collect();
-        let bps: Vec<_> = sorted_groups.iter().map(|(_k, v)| v.1).collect();
-        js_objects.push_str(&format!(
-            "new Group('node', {:?}, {:?}, false)",
-            group_names, nodes,
-        ));
-        js_objects.push_str(",\n");
-        js_objects.push_str(&format!(
-            "new Group('bp', {:?}, {:?}, false)",
-            group_names, bps,
-        ));
+    let reg = Handlebars::new();
+    vars.insert(
+        "fname",
+        comparison
+            .iter()
+            .map(|c| c.file.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    vars.insert("data_hook", js_objects);
+    vars.insert(
+        "content",
+        reg.render_template(
+            content,
+            &HashMap::from([(
+                "comparison_content",
+                generate_growth_comparison_tab(comparison, fname),
+            )]),
+        )
+        .unwrap(),
+    );
+
+    populate_constants(&mut vars);
+    write_html(&vars, out)
+}
+
+pub fn generate_info_comparison_tab(comparison: &[GraphComparison]) -> String {
+    let reg = Handlebars::new();
+
+    let tab = r##"<div class="container">
+	<nav>
+		<div class="nav nav-tabs" id="nav-tab" role="tablist">
+			<button class="nav-link active" id="nav-info-comparison-tab" data-bs-toggle="tab" data-bs-target="#nav-info-comparison" type="button" role="tab" aria-controls="nav-info-comparison" aria-selected="true">info</button>
+		</div>
+	</nav>
+	<div class="tab-pane fade show active overflow-auto" id="nav-info-comparison" role="tabpanel" aria-labelledby="nav-info-comparison-tab" tabindex="0">
+    <table class="table table-striped table-hover" aria-label="{{alt}}">
+      <thead>
+        <tr>
+          <th scope="col">feature</th>
+          <th scope="col">category</th>
+          <th scope="col">countable</th>
+{{#each files}}
+          <th scope="col">{{this}}</th>
+{{/each}}
+{{#each deltas}}
+          <th scope="col">&Delta; {{this}}</th>
+{{/each}}
+        </tr>
+      </thead>
+      <tbody class="table-group-divider">
+        {{{rows}}}
+      </tbody>
+    </table>
+	</div>
+</div>
+"##;
+
+    let files: Vec<&str> = comparison.iter().map(|c| c.growth.file.as_str()).collect();
+    let na = |x: &Option<String>| {
+        handlebars::html_escape(&x.clone().unwrap_or_else(|| "n/a".to_string()))
+    };
+    let mut rows = String::new();
+    for (key, values) in compare_infos(&comparison.iter().map(|c| &c.info).collect::<Vec<_>>()) {
+        rows.push_str("<tr>");
+        for k in key.split('\t') {
+            rows.push_str(&format!("<td>{}</td>", handlebars::html_escape(k)));
+        }
+        for v in &values {
+            rows.push_str(&format!("<td>{}</td>", na(v)));
+        }
+        for v in &values[1..] {
+            let delta = match (&values[0], v) {
+                (Some(a), Some(b)) => info_delta(a, b),
+                _ => None,
+            };
+            rows.push_str(&format!("<td>{}</td>", na(&delta)));
+        }
+        rows.push_str("</tr>\n");
     }
-    js_objects.push_str("];\n");
-    js_objects
+    let vars = json!({
+        "files": files,
+        "deltas": files[1..],
+        "rows": rows,
+        "alt": format!(
+            "Table of graph, path, and group statistics of {} graphs and their differences to {}",
+            files.len(),
+            files[0]
+        ),
+    });
+    reg.render_template(tab, &vars).unwrap()
 }
 
-pub fn write_info_html<W: Write>(
-    fname: &str,
-    info: Info,
+pub fn write_graph_comparison_html<W: Write>(
+    comparison: &[GraphComparison],
     out: &mut BufWriter<W>,
 ) -> Result<(), std::io::Error> {
-    log::info!("Writing info html");
+    log::info!("Writing graph comparison html");
     let mut vars: HashMap<&str, String> = HashMap::default();
+    let fname = "graph_comparison";
 
     let content = r##"
 <div class="d-flex align-items-start">
 	<div class="nav flex-column nav-pills me-3" id="v-pills-tab" role="tablist" aria-orientation="vertical">
         <button class="nav-link text-nowrap active" id="v-pills-info-tab" data-bs-toggle="pill" data-bs-target="#v-pills-info" type="button" role="tab" aria-controls="v-pills-info" aria-selected="true">pangenome info</button>
+        <button class="nav-link text-nowrap" id="v-pills-comparison-tab" data-bs-toggle="pill" data-bs-target="#v-pills-comparison" type="button" role="tab" aria-controls="v-pills-comparison" aria-selected="false">pangenome growth</button>
  	</div>
-  	<div class="tab-connologies to provide
-instantly aggregated statistical or similarity measures, humans otent w-100" id="v-pills-tabContent">
+  	<div class="tab-content w-100" id="v-pills-tabContent">
 		<div class="tab-pane fade show active" id="v-pills-info" role="tabpanel" aria-labelledby="v-pills-info-tab">
 {{{info_content}}}
 		</div>
+		<div class="tab-pane fade" id="v-pills-comparison" role="tabpanel" aria-labelledby="v-pills-comparison-tab">
+{{{comparison_content}}}
+		</div>
   </div>
 </div>
 "##;
 
-    let mut js_objects = String::from("const hists = [");
-    js_objects.push_str("];\n\nconst growths = [];\n");
+    let growths: Vec<GrowthComparison> = comparison.iter().map(|c| c.growth.clone()).collect();
+    let mut js_objects = growth_comparison_js_object(&growths);
     js_objects.push_str("const fname = '");
     js_objects.push_str(fname);
     js_objects.push_str("';\n");
-    js_objects.push_str("const info = `");
-    let info_text = info.to_string();
-    js_objects.push_str(info_text.as_str());
-    js_objects.push_str("`;\n");
-
-    let info_object = get_info_js_object(&info);
-    js_objects.push_str(&info_object[..]);
 
     let reg = Handlebars::new();
-    vars.insert("fname", fname.to_string());
+    vars.insert(
+        "fname",
+        growths
+            .iter()
+            .map(|c| c.file.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
     vars.insert("data_hook", js_objects);
     vars.insert(
         "content",
         reg.render_template(
             content,
-            &HashMap::from([("info_content", generate_info_tabs(info))]),
+            &HashMap::from([
+                ("info_content", generate_info_comparison_tab(comparison)),
+                (
+                    "comparison_content",
+                    generate_growth_comparison_tab(&growths, fname),
+                ),
+            ]),
         )
         .unwrap(),
     );
@@ -766,16 +2605,24 @@ instantly aggregated statistical or similarity measures, humans otent w-100" id=
     write_html(&vars, out)
 }
 
+// name, histograms, and growth curves of a sequence (e.g., chromosome) in per-sequence reports
+pub type SequenceSection = (String, Vec<Hist>, Vec<(CountType, Vec<Vec<f64>>)>);
+
+#[allow(clippy::too_many_arguments)]
 pub fn write_histgrowth_html<W: Write>(
     hists: &Option<Vec<Hist>>,
     growths: &[(CountType, Vec<Vec<f64>>)],
     hist_aux: &HistAuxilliary,
     fname: &str,
-    ordered_names: Option<&Vec<String>>,
+    ordered: Option<&AbacusByGroup>,
     info: Option<Info>,
+    bins: &Binning,
+    bootstraps: &[Option<GrowthBootstrap>],
+    sections: &[SequenceSection],
     out: &mut BufWriter<W>,
 ) -> Result<(), std::io::Error> {
     let mut vars: HashMap<&str, String> = HashMap::default();
+    let ordered_names = ordered.map(|a| &a.groups);
 
     let content = r##"
 <div class="d-flex align-items-start">
@@ -791,19 +2638,51 @@ pub fn write_histgrowth_html<W: Write>(
 		</div>{{/if}}
 		<div class="tab-pane fade{{#unless (or info_content hist_content)}} show active{{/unless}}" id="v-pills-growth" role="tabpanel" aria-labelledby="v-pills-growth-tab">
 {{{growth_content}}}
-		</div>
+		</div>{{#if coverage_class_content}}
+		<div class="tab-pane fade" id="v-pills-coverageclasses" role="tabpanel" aria-labelledby="v-pills-coverageclasses-tab">
+{{{coverage_class_content}}}
+		</div>{{/if}}{{{section_content}}}
   </div>
 </div>
 "##;
 
     let mut nav = String::new();
     if info.is_some() {
-        nav.push_str(r##"<button class="nav-link text-nowrap active" id="v-pills-info-tab" data-bs-toggle="pill" data-bs-target="#v-pills-info" type="button" role="tab" aria-controls="v-pills-info" aria-selected="false">pangenome info</button>"##);
+        nav.push_str(r##"<button class="nav-link text-nowrap active" id="v-pills-info-tab" data-bs-toggle="pill" data-bs-target="#v-pills-info" type="button" role="tab" aria-controls="v-pills-info" aria-selected="true">pangenome info</button>"##);
     }
     if hists.is_some() {
-        nav.push_str(&format!(r##"<button class="nav-link text-nowrap{}" id="v-pills-hist-tab" data-bs-toggle="pill" data-bs-target="#v-pills-hist" type="button" role="tab" aria-controls="v-pills-hist" aria-selected="true">coverage histogram</button>"##, if info.is_some() { "" } else { " active"}));
+        nav.push_str(&format!(r##"<button class="nav-link text-nowrap{}" id="v-pills-hist-tab" data-bs-toggle="pill" data-bs-target="#v-pills-hist" type="button" role="tab" aria-controls="v-pills-hist" aria-selected="{}">coverage histogram</button>"##, if info.is_some() { "" } else { " active"}, info.is_none()));
+    }
+    nav.push_str(&format!(r##"<button class="nav-link text-nowrap{}" id="v-pills-growth-tab" data-bs-toggle="pill" data-bs-target="#v-pills-growth" type="button" role="tab" aria-controls="v-pills-growth" aria-selected="{}">{}pangenome growth</button>"##, if info.is_some() || hists.is_some(){ "" } else { " active"}, info.is_none() && hists.is_none(), if ordered_names.is_some() { "ordered " } else {""} ));
+    if ordered.is_some() {
+        nav.push_str(r##"<button class="nav-link text-nowrap" id="v-pills-coverageclasses-tab" data-bs-toggle="pill" data-bs-target="#v-pills-coverageclasses" type="button" role="tab" aria-controls="v-pills-coverageclasses" aria-selected="false">coverage classes</button>"##);
+    }
+
+    // one section of histograms and growth curves per sequence; element identifiers are
+    // numbered, as sequence names may contain characters that are not admissible in selectors
+    let reg = Handlebars::new();
+    let mut section_content = String::new();
+    for (i, (name, section_hists, section_growths)) in sections.iter().enumerate() {
+        let id = format!("seq{}", i);
+        let vars = HashMap::from([
+            ("id", id.clone()),
+            ("name", name.clone()),
+            (
+                "hist_content",
+                generate_hist_tabs(section_hists, fname, None, Some(&id)),
+            ),
+            (
+                "growth_content",
+                generate_growth_tabs(section_growths, hist_aux, fname, None, Some(&id)),
+            ),
+        ]);
+        nav.push_str(&reg.render_template(r##"<button class="nav-link text-nowrap" id="v-pills-{{id}}-tab" data-bs-toggle="pill" data-bs-target="#v-pills-{{id}}" type="button" role="tab" aria-controls="v-pills-{{id}}" aria-selected="false">{{name}}</button>"##, &vars).unwrap());
+        section_content.push_str(&reg.render_template(r##"
+		<div class="tab-pane fade" id="v-pills-{{id}}" role="tabpanel" aria-labelledby="v-pills-{{id}}-tab">
+{{{hist_content}}}
+{{{growth_content}}}
+		</div>"##, &vars).unwrap());
     }
-    nav.push_str(&format!(r##"<button class="nav-link text-nowrap{}" id="v-pills-growth-tab" data-bs-toggle="pill" data-bs-target="#v-pills-growth" type="button" role="tab" aria-controls="v-pills-growth" aria-selected="true">{}pangenome growth</button>"##, if info.is_some() || hists.is_some(){ "" } else { " active"}, if ordered_names.is_some() { "ordered " } else {""} ));
 
     let mut js_objects = String::from("");
     js_objects.push_str("const hists = [\n");
@@ -826,6 +2705,20 @@ pub fn write_histgrowth_html<W: Write>(
             }
         }
     }
+    for (i, (_, section_hists, _)) in sections.iter().enumerate() {
+        for h in section_hists {
+            if js_objects.ends_with(')') {
+                js_objects.push_str(",\n");
+            }
+            js_objects.push_str(&format!(
+                "new Hist('{}', {:?}, {:?}, 'seq{}')",
+                h.count,
+                (0..h.coverage.len()).collect::<Vec<usize>>(),
+                h.coverage,
+                i
+            ));
+        }
+    }
     js_objects.push_str("];\n\n");
     js_objects.push_str("const growths = [\n");
 
@@ -835,7 +2728,7 @@ pub fn write_histgrowth_html<W: Write>(
         }
         match ordered_names {
             Some(names) => js_objects.push_str(&format!(
-                "new Growth('{}', {:?}, [{}], [{}], {:?})",
+                "new Growth('{}', {:?}, [{}], [{}], {:?}{})",
                 count,
                 names,
                 &hist_aux
@@ -856,10 +2749,11 @@ pub fn write_histgrowth_html<W: Write>(
                         .iter()
                         .map(|x| x.floor() as usize)
                         .collect::<Vec<usize>>())
-                    .collect::<Vec<Vec<usize>>>()
+                    .collect::<Vec<Vec<usize>>>(),
+                growth_names_js(hist_aux, None),
             )),
             None => js_objects.push_str(&format!(
-                "new Growth('{}', {:?}, [{}], [{}], {:?})",
+                "new Growth('{}', {:?}, [{}], [{}], {:?}{})",
                 count,
                 (1..columns[0].len()).collect::<Vec<usize>>(),
                 &hist_aux
@@ -880,11 +2774,100 @@ pub fn write_histgrowth_html<W: Write>(
                         .iter()
                         .map(|x| x.floor() as usize)
                         .collect::<Vec<usize>>())
-                    .collect::<Vec<Vec<usize>>>()
+                    .collect::<Vec<Vec<usize>>>(),
+                growth_names_js(hist_aux, None),
             )),
         }
     }
-    js_objects.push_str("];\n\nconst fname = '");
+    for (i, (_, _, section_growths)) in sections.iter().enumerate() {
+        for (count, columns) in section_growths {
+            if js_objects.ends_with(')') {
+                js_objects.push_str(",\n");
+            }
+            js_objects.push_str(&format!(
+                "new Growth('{}', {:?}, [{}], [{}], {:?}, 'seq{}'{})",
+                count,
+                (1..columns[0].len()).collect::<Vec<usize>>(),
+                &hist_aux
+                    .coverage
+                    .iter()
+                    .map(|x| x.get_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                &hist_aux
+                    .quorum
+                    .iter()
+                    .map(|x| x.get_string())
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                &columns
+                    .iter()
+                    .map(|col| col[1..]
+                        .iter()
+                        .map(|x| x.floor() as usize)
+                        .collect::<Vec<usize>>())
+                    .collect::<Vec<Vec<usize>>>(),
+                i,
+                growth_names_js(hist_aux, Some(i)),
+            ));
+        }
+    }
+    js_objects.push_str("];\n\n");
+    // increments of growth curves are drawn on a secondary axis together with the saturation
+    // threshold
+    if hist_aux.increments {
+        js_objects.push_str(&format!(
+            "const growth_saturation = {};\n\n",
+            hist_aux.saturation
+        ));
+    }
+    // confidence bands of growth curves, spanning the lowest to the highest reported percentile
+    if bootstraps.iter().any(|b| b.is_some()) {
+        js_objects.push_str("const growth_bands = [\n");
+        let bands: Vec<String> = growths
+            .iter()
+            .zip(bootstraps)
+            .filter_map(|((count, _), b)| b.as_ref().map(|b| (count, b)))
+            .map(|(count, b)| {
+                format!(
+                    "new GrowthBand('{}', [{}], [{}], {}, {}, {:?}, {:?})",
+                    count,
+                    hist_aux
+                        .coverage
+                        .iter()
+                        .map(|x| x.get_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    hist_aux
+                        .quorum
+                        .iter()
+                        .map(|x| x.get_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    b.percentiles.first().unwrap_or(&0.0),
+                    b.percentiles.last().unwrap_or(&100.0),
+                    b.values
+                        .iter()
+                        .map(|v| v.first().cloned().unwrap_or_default())
+                        .collect::<Vec<Vec<f64>>>(),
+                    b.values
+                        .iter()
+                        .map(|v| v.last().cloned().unwrap_or_default())
+                        .collect::<Vec<Vec<f64>>>(),
+                )
+            })
+            .collect();
+        js_objects.push_str(&bands.join(",\n"));
+        js_objects.push_str("];\n\n");
+    }
+    // growth curves for further thresholds are recomputed in the browser from the histograms
+    if hists.is_some() {
+        js_objects.push_str(&format!(
+            "const growth_wasm = '{}';\n\n",
+            general_purpose::STANDARD.encode(GROWTH_WASM)
+        ));
+    }
+    js_objects.push_str("const fname = '");
     js_objects.push_str(fname);
     js_objects.push_str("';\n");
     js_objects.push_str("const info = `");
@@ -896,20 +2879,44 @@ pub fn write_histgrowth_html<W: Write>(
     js_objects.push_str("`;\n");
 
     if let Some(info_obj) = &info {
-        let info_object = get_info_js_object(info_obj);
+        let info_object = get_info_js_object(info_obj, bins);
         js_objects.push_str(&info_object[..]);
     }
 
-    let reg = Handlebars::new();
+    if let Some(abacus_group) = ordered {
+        let classes = abacus_group.coverage_classes();
+        js_objects.push_str(&format!(
+            "const coverage_classes = [new CoverageClasses('{}', {:?}, ['private', 'shell', 'core'], [{:?}, {:?}, {:?}])];\n",
+            abacus_group.count,
+            abacus_group.groups,
+            classes.iter().map(|x| x[0]).collect::<Vec<_>>(),
+            classes.iter().map(|x| x[1]).collect::<Vec<_>>(),
+            classes.iter().map(|x| x[2]).collect::<Vec<_>>(),
+        ));
+    }
+
     let mut prevars = HashMap::from([
         ("nav", nav),
-        ("growth_content", generate_growth_tabs(growths)),
+        ("section_content", section_content),
+        (
+            "growth_content",
+            generate_growth_tabs(growths, hist_aux, fname, ordered_names, None),
+        ),
     ]);
     if let Some(hs) = hists {
-        prevars.insert("hist_content", generate_hist_tabs(hs));
+        prevars.insert(
+            "hist_content",
+            generate_hist_tabs(hs, fname, ordered_names, None),
+        );
     }
     if let Some(st) = info {
-        prevars.insert("info_content", generate_info_tabs(st));
+        prevars.insert("info_content", generate_info_tabs(st, fname));
+    }
+    if let Some(abacus_group) = ordered {
+        prevars.insert(
+            "coverage_class_content",
+            generate_coverage_class_tabs(abacus_group, fname),
+        );
     }
 
     vars.insert("fname", fname.to_string());
@@ -919,3 +2926,22 @@ pub fn write_histgrowth_html<W: Write>(
     populate_constants(&mut vars);
     write_html(&vars, out)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_growth_alt_text() {
+        let hist_aux = HistAuxilliary::parse_params("0,1", "1,2", 1.0, false).unwrap();
+        let alt = growth_alt_text(
+            &CountType::Node,
+            &[vec![0.0, 3.0, 5.0], vec![0.0, 1.0, 2.0]],
+            &hist_aux,
+        );
+        // growth curves are drawn as lines, not bars
+        assert!(alt.starts_with("Line chart of the pangenome growth of nodes; "));
+        assert!(alt.contains("5 nodes at 2 groups"));
+        assert!(alt.contains("2 nodes at 2 groups"));
+    }
+}
@@ -1,17 +1,22 @@
 /* standard use */
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::io::{Error, ErrorKind};
 use std::path::Path;
 use std::str::{self, FromStr};
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 
 /* external use */
 use flate2::read::MultiGzDecoder;
 use itertools::Itertools;
+use memmap2::Mmap;
+use once_cell::sync::OnceCell;
 use quick_csv::Csv;
 use rayon::prelude::*;
+use regex::Regex;
+use serde::Serialize;
 use strum_macros::{EnumString, EnumVariantNames};
 
 /* internal use */
@@ -25,27 +30,558 @@ use crate::util::*;
 #[strum(serialize_all = "lowercase")]
 pub enum OutputFormat {
     Table,
+    Csv,
     Html,
+    Json,
+    Plot,
+    Vega,
+    Latex,
+    Arrow,
 }
 
-pub fn bufreader_from_compressed_gfa(gfa_file: &str) -> BufReader<Box<dyn Read>> {
-    log::info!("loading graph from {}", &gfa_file);
-    let f = std::fs::File::open(gfa_file).expect("Error opening file");
-    let reader: Box<dyn Read> = if gfa_file.ends_with(".gz") {
-        log::info!("assuming that {} is gzip compressed..", &gfa_file);
+// Malformed path and walk records, e.g., such that refer to nodes or edges not defined by any
+// S- or L-line
+#[derive(Debug, thiserror::Error)]
+pub enum GraphParseError {
+    #[error("unknown node {0}")]
+    UnknownNode(String),
+    #[error("unknown orientation of segment {0}")]
+    UnknownOrientation(String),
+    #[error("unknown edge {edge}: {reason}")]
+    UnknownEdge { edge: String, reason: String },
+    #[error("segment {0} occurs multiple times in GFA")]
+    DuplicateNode(String),
+    #[error("path sequence is not followed by tab or newline; the record may be truncated")]
+    UnterminatedPath,
+    #[error("line {line}: {source}")]
+    Line {
+        line: usize,
+        source: Box<GraphParseError>,
+    },
+    #[error("corrupt GFA record in line {line}: {reason}")]
+    Corrupt { line: usize, reason: String },
+    #[error("corrupt GFA record at byte {offset} (line {line}): {reason}; last complete record: {last_complete}")]
+    CorruptAt {
+        offset: usize,
+        line: usize,
+        reason: String,
+        last_complete: String,
+    },
+    #[error("line {line}, path {path}: {source}")]
+    Record {
+        line: usize,
+        path: String,
+        source: Box<GraphParseError>,
+    },
+}
+
+impl GraphParseError {
+    // attaches the line number and the name of the path or walk record in which the error occurred
+    pub fn in_record(self, line: usize, path: &PathSegment) -> Self {
+        let err = GraphParseError::Record {
+            line,
+            path: path.to_string(),
+            source: Box::new(self),
+        };
+        log::error!("{}", &err);
+        err
+    }
+
+    // attaches the number of the line of the record in which the error occurred
+    pub fn in_line(self, line: usize) -> Self {
+        let err = GraphParseError::Line {
+            line,
+            source: Box::new(self),
+        };
+        log::error!("{}", &err);
+        err
+    }
+}
+
+impl From<GraphParseError> for Error {
+    fn from(err: GraphParseError) -> Self {
+        Error::new(ErrorKind::InvalidData, err)
+    }
+}
+
+pub fn bufreader_from_compressed_gfa(gfa_file: &str) -> Result<BufReader<Box<dyn Read>>, Error> {
+    bufreader_from_compressed(gfa_file, "graph")
+}
+
+// reads one or more GFA files (e.g., one per chromosome) one after another as a single graph
+pub fn bufreader_from_gfa_files(gfa_files: &[String]) -> Result<BufReader<Box<dyn Read>>, Error> {
+    match gfa_files {
+        [gfa_file] => bufreader_from_compressed_gfa(gfa_file),
+        _ => Ok(BufReader::new(Box::new(NamespacedGfa::new(
+            gfa_files.to_vec(),
+        )))),
+    }
+}
+
+// returns the GFA file followed by the further ones that are analyzed with it as a single graph
+pub fn gfa_file_list(gfa_file: &str, more_gfa_files: &[String]) -> Vec<String> {
+    std::iter::once(gfa_file.to_string())
+        .chain(more_gfa_files.iter().cloned())
+        .collect()
+}
+
+// graph read from standard input (given as "-"), which is spooled to a temporary file once, as
+// the graph is read several times (e.g., segments first, then paths and walks)
+static STDIN_GFA: OnceCell<std::path::PathBuf> = OnceCell::new();
+
+// returns the name of the file from which the given GFA file is read
+pub fn stdin_gfa_file(gfa_file: &str) -> Result<String, Error> {
+    if gfa_file != "-" {
+        return Ok(gfa_file.to_string());
+    }
+    let path = STDIN_GFA.get_or_try_init(|| {
+        let path = std::env::temp_dir().join(format!("panacus_stdin_{}.gfa", std::process::id()));
+        log::info!("spooling graph from standard input to {}", path.display());
+        let mut f = BufWriter::new(std::fs::File::create(&path)?);
+        std::io::copy(&mut std::io::stdin().lock(), &mut f)?;
+        f.flush()?;
+        Ok::<_, Error>(path)
+    })?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+// removes the file to which standard input has been spooled, if any
+pub fn remove_stdin_gfa() {
+    if let Some(path) = STDIN_GFA.get() {
+        if let Err(e) = std::fs::remove_file(path) {
+            log::warn!("unable to remove {}: {}", path.display(), e);
+        }
+    }
+}
+
+// reads several GFA files one after another as a single one, prefixing the node IDs of the i-th
+// file by "i:" in its S, L, P, and W records so that they do not collide across files
+struct NamespacedGfa {
+    files: Vec<String>,
+    // number of files opened so far and reader of the last one
+    opened: usize,
+    current: Option<BufReader<Box<dyn Read>>>,
+    record: Vec<u8>,
+    namespaced: Vec<u8>,
+    pos: usize,
+}
+
+impl NamespacedGfa {
+    fn new(files: Vec<String>) -> Self {
+        Self {
+            files,
+            opened: 0,
+            current: None,
+            record: Vec::new(),
+            namespaced: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    // namespaces the next record of the GFA files, if there is one
+    fn next_record(&mut self) -> Result<bool, Error> {
+        loop {
+            let data = match self.current {
+                Some(ref mut data) => data,
+                None if self.opened == self.files.len() => return Ok(false),
+                None => {
+                    let data = bufreader_from_compressed(&self.files[self.opened], "graph")?;
+                    self.current = Some(data);
+                    self.opened += 1;
+                    continue;
+                }
+            };
+            self.record.clear();
+            if data.read_until(b'\n', &mut self.record)? == 0 {
+                self.current = None;
+                continue;
+            }
+            self.namespaced.clear();
+            self.pos = 0;
+            let prefix = format!("{}:", self.opened);
+            namespace_gfa_record(&self.record, prefix.as_bytes(), &mut self.namespaced);
+            return Ok(true);
+        }
+    }
+}
+
+impl Read for NamespacedGfa {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        if self.pos == self.namespaced.len() && !self.next_record()? {
+            return Ok(0);
+        }
+        let n = usize::min(buf.len(), self.namespaced.len() - self.pos);
+        buf[..n].copy_from_slice(&self.namespaced[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+// writes the record with all node IDs prefixed to out; records end with a newline, so that the
+// last record of one file does not run into the first one of the next file
+fn namespace_gfa_record(record: &[u8], prefix: &[u8], out: &mut Vec<u8>) {
+    let record = record.strip_suffix(b"\n").unwrap_or(record);
+    let record = record.strip_suffix(b"\r").unwrap_or(record);
+    for (i, field) in record.split(|&x| x == b'\t').enumerate() {
+        if i > 0 {
+            out.push(b'\t');
+        }
+        match (record.first(), i) {
+            (Some(b'S'), 1) | (Some(b'L'), 1) | (Some(b'L'), 3) => {
+                out.extend_from_slice(prefix);
+                out.extend_from_slice(field);
+            }
+            (Some(b'P'), 2) => {
+                for (j, node) in field.split(|&x| x == b',').enumerate() {
+                    if j > 0 {
+                        out.push(b',');
+                    }
+                    out.extend_from_slice(prefix);
+                    out.extend_from_slice(node);
+                }
+            }
+            (Some(b'W'), 6) => {
+                for &x in field {
+                    out.push(x);
+                    if x == b'>' || x == b'<' {
+                        out.extend_from_slice(prefix);
+                    }
+                }
+            }
+            _ => out.extend_from_slice(field),
+        }
+    }
+    out.push(b'\n');
+}
+
+pub fn bufreader_from_compressed_gaf(gaf_file: &str) -> Result<BufReader<Box<dyn Read>>, Error> {
+    bufreader_from_compressed(gaf_file, "alignments")
+}
+
+pub fn bufreader_from_compressed_vcf(vcf_file: &str) -> Result<BufReader<Box<dyn Read>>, Error> {
+    bufreader_from_compressed(vcf_file, "variants")
+}
+
+// opens a file that is possibly gzip compressed; kind names its content in log and error messages
+fn bufreader_from_compressed(file: &str, kind: &str) -> Result<BufReader<Box<dyn Read>>, Error> {
+    log::info!("loading {} from {}", kind, &file);
+    let mut f = BufReader::new(std::fs::File::open(file).map_err(|e| {
+        let msg = format!("unable to open {} {}: {}", kind, file, e);
+        log::error!("{}", msg);
+        Error::new(e.kind(), msg)
+    })?);
+    // gzip (and thus also bgzip) compression is recognized by its magic bytes, independent of the
+    // file extension
+    let reader: Box<dyn Read> = if is_gzip(f.fill_buf()?) {
+        log::info!("{} is gzip compressed..", &file);
         Box::new(MultiGzDecoder::new(f))
     } else {
+        if file.ends_with(".gz") {
+            log::warn!("{} ends with .gz, but is not gzip compressed", &file);
+        }
         Box::new(f)
     };
-    BufReader::new(reader)
+    Ok(BufReader::new(reader))
+}
+
+// memory-maps the GFA file so that its records can be parsed in place, unless it is compressed
+// (or cannot be mapped)
+pub fn mmap_uncompressed_gfa(gfa_file: &str) -> Option<Mmap> {
+    let file = std::fs::File::open(gfa_file).ok()?;
+    // the file must not be modified while it is mapped
+    let mmap = unsafe { Mmap::map(&file) }.ok()?;
+    if is_gzip(&mmap[..]) {
+        None
+    } else {
+        log::info!("loading graph from {} (memory-mapped)", &gfa_file);
+        Some(mmap)
+    }
+}
+
+fn is_gzip(head: &[u8]) -> bool {
+    head.starts_with(&[0x1f, 0x8b])
+}
+
+// checks that a S, L, P, or W record is complete and free of corruption; other records are
+// accepted as they are
+pub fn check_gfa_record(buf: &[u8]) -> Result<(), String> {
+    let (min_fields, name_fields): (usize, &[usize]) = match buf.first() {
+        Some(b'S') => (3, &[1]),
+        Some(b'L') => (6, &[1, 3]),
+        Some(b'P') => (3, &[1]),
+        Some(b'W') => (7, &[1, 2, 3, 4, 5]),
+        _ => return Ok(()),
+    };
+    if buf.contains(&0) {
+        return Err("record contains NUL byte(s)".to_string());
+    }
+    let record = buf
+        .strip_suffix(b"\n")
+        .map(|x| x.strip_suffix(b"\r").unwrap_or(x))
+        .unwrap_or(buf);
+    let fields: Vec<&[u8]> = record.splitn(min_fields + 1, |&x| x == b'\t').collect();
+    if fields.len() < min_fields {
+        return Err(format!(
+            "record has {} instead of at least {} tab-separated columns",
+            fields.len(),
+            min_fields
+        ));
+    }
+    for i in name_fields {
+        if str::from_utf8(fields[*i]).is_err() {
+            return Err(format!("column {} is not valid UTF-8", i + 1));
+        }
+    }
+    // the overlaps column follows the sequence of a path, so that a path record that ends without
+    // it (and without newline) has been cut off, e.g., in the middle of its sequence
+    if buf[0] == b'P' && fields.len() == min_fields && buf.last() != Some(&b'\n') {
+        return Err("path record lacks its overlaps column".to_string());
+    }
+    if buf[0] == b'W' {
+        for i in [4, 5] {
+            if fields[i] != b"*" && usize::from_str(str::from_utf8(fields[i]).unwrap()).is_err() {
+                return Err(format!("column {} is neither a number nor '*'", i + 1));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Scans the GFA for truncated or corrupt records, reporting the byte offset of the first
+// problem and the last complete record before it. In best-effort mode, problems are reported
+// as warnings and the number of skipped records is returned.
+pub fn check_gfa<R: Read>(data: &mut BufReader<R>, best_effort: bool) -> Result<usize, Error> {
+    log::info!("checking integrity of GFA");
+    let mut buf = vec![];
+    let mut offset = 0;
+    let mut line = 0;
+    let mut last_complete = LastRecord::default();
+    let mut skipped = 0;
+
+    loop {
+        buf.clear();
+        let n = match data.read_until(b'\n', &mut buf) {
+            Ok(n) => n,
+            Err(e) => {
+                let msg = format!(
+                    "unable to read GFA beyond byte {} (line {}): {}; last complete record: {}",
+                    offset + buf.len(),
+                    line + 1,
+                    e,
+                    last_complete
+                );
+                if best_effort {
+                    log::warn!("{}, continuing with records read so far", msg);
+                    return Ok(skipped);
+                }
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+        };
+        if n == 0 {
+            break;
+        }
+        line += 1;
+
+        match check_gfa_record(&buf) {
+            Err(p) => {
+                let msg = GraphParseError::CorruptAt {
+                    offset,
+                    line,
+                    reason: corrupt_reason(&buf, p),
+                    last_complete: last_complete.to_string(),
+                }
+                .to_string();
+                if !best_effort {
+                    log::error!("{}", &msg);
+                    return Err(Error::new(ErrorKind::InvalidData, msg));
+                }
+                log::warn!("{}, skipping record", msg);
+                skipped += 1;
+            }
+            Ok(()) => last_complete.update(line, &buf),
+        }
+        offset += n;
+    }
+    if skipped > 0 {
+        log::warn!("skipped {} corrupt record(s) in GFA", skipped);
+    }
+    Ok(skipped)
+}
+
+// whether the GFA parsers skip corrupt records instead of failing; like the PanSN delimiter, this
+// is set once for the whole run
+static BEST_EFFORT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_best_effort(best_effort: bool) {
+    BEST_EFFORT.store(best_effort, Ordering::Relaxed);
+}
+
+pub fn best_effort() -> bool {
+    BEST_EFFORT.load(Ordering::Relaxed)
+}
+
+// reads the next line of the GFA (appending it to buf); a read error (e.g., of a truncated
+// compressed file) ends the input in best-effort mode and fails otherwise
+pub fn read_gfa_line<R: BufRead>(
+    data: &mut R,
+    buf: &mut Vec<u8>,
+    line: usize,
+) -> Result<usize, GraphParseError> {
+    data.read_until(b'\n', buf).or_else(|e| {
+        if best_effort() {
+            log::warn!(
+                "unable to read GFA beyond line {}: {}, continuing with records read so far",
+                line,
+                e
+            );
+            return Ok(0);
+        }
+        let err = GraphParseError::Corrupt {
+            line: line + 1,
+            reason: format!("unable to read GFA: {}", e),
+        };
+        log::error!("{}", &err);
+        Err(err)
+    })
+}
+
+// checks a record met by one of the GFA parsers, which thereby detect corrupt records without an
+// extra pass over the file; in best-effort mode, corrupt records have been reported by check_gfa
+// and are skipped (false), otherwise they fail the run
+pub fn check_parsed_record(buf: &[u8], line: usize) -> Result<bool, GraphParseError> {
+    match check_gfa_record(buf) {
+        Ok(()) => Ok(true),
+        Err(_) if best_effort() => Ok(false),
+        Err(p) => {
+            let err = GraphParseError::Corrupt {
+                line,
+                reason: corrupt_reason(buf, p),
+            };
+            log::error!("{}", &err);
+            Err(err)
+        }
+    }
+}
+
+// like check_parsed_record, but reports a corrupt record along with its byte offset and the last
+// complete record before it, as check_gfa does
+pub fn check_parsed_record_at(
+    buf: &[u8],
+    line: usize,
+    offset: usize,
+    last_complete: &LastRecord,
+) -> Result<bool, GraphParseError> {
+    match check_gfa_record(buf) {
+        Ok(()) => Ok(true),
+        Err(_) if best_effort() => Ok(false),
+        Err(p) => {
+            let err = GraphParseError::CorruptAt {
+                offset,
+                line,
+                reason: corrupt_reason(buf, p),
+                last_complete: last_complete.to_string(),
+            };
+            log::error!("{}", &err);
+            Err(err)
+        }
+    }
+}
+
+// a record without newline is the last one of the file, which has been cut off if it is corrupt
+fn corrupt_reason(buf: &[u8], problem: String) -> String {
+    if buf.last() == Some(&b'\n') {
+        problem
+    } else {
+        format!(
+            "unexpected end of file in the middle of the record ({})",
+            problem
+        )
+    }
+}
+
+// the beginning of the last complete (non-comment) record of a GFA file read so far, which is
+// reported along with corrupt records to help locate the damage
+#[derive(Debug, Default)]
+pub struct LastRecord {
+    line: usize,
+    head: Vec<u8>,
+    truncated: bool,
+}
+
+impl LastRecord {
+    pub fn update(&mut self, line: usize, buf: &[u8]) {
+        if buf.first().map_or(true, |&x| x == b'#') {
+            return;
+        }
+        let record = buf.strip_suffix(b"\n").unwrap_or(buf);
+        self.line = line;
+        self.head.clear();
+        self.head.extend_from_slice(&record[..record.len().min(40)]);
+        self.truncated = record.len() > 40;
+    }
+}
+
+impl fmt::Display for LastRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            return write!(f, "none");
+        }
+        write!(
+            f,
+            "line {}: {}{}",
+            self.line,
+            String::from_utf8_lossy(&self.head).replace('\t', " "),
+            if self.truncated { ".." } else { "" }
+        )
+    }
+}
+
+// auxiliary files are often assembled in spreadsheet programs, which leave Windows line endings,
+// trailing tabs, and blank lines behind; these are removed and reported once per file
+#[derive(Debug, Default)]
+struct LineCleanup {
+    stripped: usize,
+    blank: usize,
+}
+
+impl LineCleanup {
+    // returns the line without line ending and trailing whitespace, or None if nothing remains
+    fn clean<'a>(&mut self, line: &'a str) -> Option<&'a str> {
+        let l = line.trim_end_matches(&['\n', '\r'][..]);
+        let trimmed = l.trim_end();
+        if trimmed.is_empty() {
+            self.blank += 1;
+            return None;
+        }
+        if trimmed.len() != line.trim_end_matches('\n').len() {
+            self.stripped += 1;
+        }
+        Some(trimmed)
+    }
+
+    fn report(&self, what: &str) {
+        if self.stripped > 0 {
+            log::warn!(
+                "removed carriage returns and/or trailing whitespace from {} line(s) of {}",
+                self.stripped,
+                what
+            );
+        }
+        if self.blank > 0 {
+            log::warn!("skipped {} blank line(s) of {}", self.blank, what);
+        }
+    }
 }
 
 pub fn parse_bed_to_path_segments<R: Read>(
     data: &mut BufReader<R>,
     use_block_info: bool,
+    pansn_delimiter: char,
 ) -> Vec<PathSegment> {
     // based on https://en.wikipedia.org/wiki/BED_(file_format)
     let mut segments = Vec::new();
+    let mut cleanup = LineCleanup::default();
 
     for (i, line) in data.lines().enumerate() {
         let line = match line {
@@ -54,11 +590,15 @@ pub fn parse_bed_to_path_segments<R: Read>(
                 panic!("error reading line {}: {}", i + 1, e);
             }
         };
+        let line = match cleanup.clean(&line) {
+            Some(l) => l,
+            None => continue,
+        };
 
         let fields = {
             let mut fields: Vec<&str> = line.split('\t').collect();
             if fields.is_empty() {
-                fields = vec![&line];
+                fields = vec![line];
             }
             fields
         };
@@ -72,7 +612,7 @@ pub fn parse_bed_to_path_segments<R: Read>(
         }
 
         if fields.len() == 1 {
-            segments.push(PathSegment::from_str(path_name));
+            segments.push(PathSegment::from_str(path_name, pansn_delimiter));
         } else if fields.len() >= 3 {
             let start = usize::from_str(fields[1]).expect(&format!(
                 "error line {}: `{}` is not an usize",
@@ -104,6 +644,7 @@ pub fn parse_bed_to_path_segments<R: Read>(
                             path_name,
                             block_start,
                             block_end,
+                            pansn_delimiter,
                         ));
                     }
                 } else {
@@ -113,7 +654,12 @@ pub fn parse_bed_to_path_segments<R: Read>(
                     );
                 }
             } else {
-                segments.push(PathSegment::from_str_start_end(path_name, start, end));
+                segments.push(PathSegment::from_str_start_end(
+                    path_name,
+                    start,
+                    end,
+                    pansn_delimiter,
+                ));
             }
         } else {
             panic!(
@@ -122,110 +668,467 @@ pub fn parse_bed_to_path_segments<R: Read>(
             );
         }
     }
+    cleanup.report("coordinate list");
 
     segments
 }
 
-pub fn parse_groups<R: Read>(data: &mut BufReader<R>) -> Result<Vec<(PathSegment, String)>, Error> {
-    let mut res: Vec<(PathSegment, String)> = Vec::new();
+// windows (half-open intervals of path coordinates) along the given path from a BED file;
+// intervals of other paths are ignored and those exceeding the path are clipped
+pub fn parse_bed_windows<R: Read>(
+    data: &mut BufReader<R>,
+    path_name: &str,
+    path_len: usize,
+    pansn_delimiter: char,
+) -> Vec<(usize, usize)> {
+    let segments = parse_bed_to_path_segments(data, false, pansn_delimiter);
+    let n = segments.len();
+    let windows: Vec<(usize, usize)> = segments
+        .into_iter()
+        .filter(|s| {
+            let s = s.clear_coords();
+            s.id() == path_name || s.to_string() == path_name
+        })
+        .map(|s| match s.coords() {
+            Some((start, end)) => (usize::min(start, path_len), usize::min(end, path_len)),
+            None => (0, path_len),
+        })
+        .collect();
+    if windows.len() < n {
+        log::warn!(
+            "ignoring {} intervals that do not lie on path {}",
+            n - windows.len(),
+            path_name
+        );
+    }
+    windows
+}
+
+// path-group assignments, each with the number of the line in which it is given
+pub fn parse_groups<R: Read>(
+    data: &mut BufReader<R>,
+    pansn_delimiter: char,
+) -> Result<Vec<(PathSegment, String, usize)>, Error> {
+    let mut res: Vec<(PathSegment, String, usize)> = Vec::new();
+
+    let mut cleanup = LineCleanup::default();
 
     let mut i = 1;
     let mut buf = vec![];
     while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
-        //Remove new line at the end
-        if let Some(&last_byte) = buf.last() {
-            if last_byte == b'\n' || last_byte == b'\r' {
-                buf.pop();
-            }
-        }
         let line = String::from_utf8(buf.clone())
             .expect(&format!("error in line {}: some character is not UTF-8", i));
-        let columns: Vec<&str> = line.split('\t').collect();
+        if let Some(line) = cleanup.clean(&line) {
+            let columns: Vec<&str> = line.split('\t').collect();
 
-        if columns.len() != 2 {
-            let msg = format!("error in line {}: table must have exactly two columns", i);
-            log::error!("{}", &msg);
-            return Err(Error::new(ErrorKind::InvalidData, msg));
-        }
+            if columns.len() != 2 {
+                let msg = format!("error in line {}: table must have exactly two columns", i);
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+            if columns[0].trim().is_empty() || columns[1].trim().is_empty() {
+                let msg = format!("error in line {}: path or group name is empty", i);
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
 
-        let path_seg = PathSegment::from_str(columns[0]);
-        res.push((path_seg, columns[1].to_string()));
+            let path_seg = PathSegment::from_str(columns[0], pansn_delimiter);
+            res.push((path_seg, columns[1].to_string(), i));
+        }
 
         i += 1;
         buf.clear();
     }
+    cleanup.report("group file");
 
     Ok(res)
 }
 
-pub fn parse_tsv<R: Read>(
+// group assignments from a metadata table with header, whose first column lists paths and whose
+// column of the given name holds the group of each path; paths without value remain unassigned;
+// as for parse_groups, each assignment comes with the number of its line
+pub fn parse_group_metadata<R: Read>(
     data: &mut BufReader<R>,
-) -> Result<(Vec<Vec<u8>>, Vec<Vec<Vec<u8>>>), Error> {
-    let mut comments = Vec::new();
-    let mut table = Vec::new();
-    let reader = Csv::from_reader(data)
-        .delimiter(b'\t')
-        .flexible(true)
-        .has_header(false);
+    column: &str,
+    pansn_delimiter: char,
+) -> Result<Vec<(PathSegment, String, usize)>, Error> {
+    let mut res: Vec<(PathSegment, String, usize)> = Vec::new();
 
-    let mut is_header = true;
-    for (i, row) in reader.enumerate() {
-        let row = row.map_err(|_| {
-            let msg = format!("unable to parse row {}", i);
+    let mut cleanup = LineCleanup::default();
+
+    let mut col: Option<usize> = None;
+    let mut missing = 0;
+    let mut i = 1;
+    let mut buf = vec![];
+    while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+        let line = String::from_utf8(buf.clone()).map_err(|_| {
+            let msg = format!("error in line {}: some character is not UTF-8", i);
             log::error!("{}", &msg);
-            Error::new(ErrorKind::Other, msg)
+            Error::new(ErrorKind::InvalidData, msg)
         })?;
-        let row: Vec<Vec<u8>> = row.bytes_columns().map(|x| x.to_vec()).collect();
-        if row.is_empty() {
-            log::info!("Empty row, skipping");
-            continue;
-        }
-        // Push header
-        if is_header && (b'#' == row[0][0]) {
-            let mut c = row[0].to_vec();
-            for e in &row[1..] {
-                c.push(b'\t');
-                c.extend(e);
-            }
-            comments.push(c);
-        // Skip empty lines (still need to have appropriate amount of tabs)
-        } else if row
-            .iter()
-            .map(|x| x.is_empty())
-            .fold(true, |acc, x| acc && x)
-        {
-            log::debug!("Skipping empty line");
-            continue;
-        // Handle comments
-        } else if b'#' == row[0][0] {
-            log::debug!("Handling comment");
-            let mut c = row[0].to_vec();
-            for e in &row[1..] {
-                c.push(b'\t');
-                c.extend(e);
+        if let Some(line) = cleanup.clean(&line) {
+            let columns: Vec<&str> = line.split('\t').map(|x| x.trim()).collect();
+            match col {
+                None => {
+                    // header, possibly commented out
+                    let header: Vec<&str> = line
+                        .trim_start_matches('#')
+                        .split('\t')
+                        .map(|x| x.trim())
+                        .collect();
+                    col = header
+                        .iter()
+                        .skip(1)
+                        .position(|x| x == &column)
+                        .map(|x| x + 1);
+                    if col.is_none() {
+                        let msg = format!(
+                            "metadata table has no column \"{}\", available columns are: {}",
+                            column,
+                            header[1..].join(", ")
+                        );
+                        log::error!("{}", &msg);
+                        return Err(Error::new(ErrorKind::InvalidData, msg));
+                    }
+                }
+                Some(_) if line.starts_with('#') => (),
+                Some(c) => {
+                    if columns[0].is_empty() {
+                        let msg = format!("error in line {}: path name is empty", i);
+                        log::error!("{}", &msg);
+                        return Err(Error::new(ErrorKind::InvalidData, msg));
+                    }
+                    match columns.get(c).filter(|x| !x.is_empty()) {
+                        Some(group) => res.push((
+                            PathSegment::from_str(columns[0], pansn_delimiter),
+                            group.to_string(),
+                            i,
+                        )),
+                        None => missing += 1,
+                    }
+                }
             }
-            comments.push(c);
-        // Push everything else
-        } else {
-            is_header = false;
-            table.push(row);
         }
+
+        i += 1;
+        buf.clear();
     }
-    Ok((comments, table))
+    cleanup.report("metadata table");
+    if missing > 0 {
+        log::warn!(
+            "{} paths of the metadata table have no value in column \"{}\" and remain ungrouped",
+            missing,
+            column
+        );
+    }
+
+    Ok(res)
 }
 
-fn transpose_table(table: &Vec<Vec<Vec<u8>>>) -> Vec<Vec<&[u8]>> {
-    let n = table.first().unwrap_or(&Vec::new()).len();
+// representative-member pairs, each with the number of the line in which it is given
+pub fn parse_clusters<R: Read>(
+    data: &mut BufReader<R>,
+) -> Result<Vec<(String, String, usize)>, Error> {
+    let mut res: Vec<(String, String, usize)> = Vec::new();
 
-    let mut res = vec![vec![&table[0][0][..]; table.len()]; n];
+    let mut cleanup = LineCleanup::default();
 
-    for j in 0..n {
-        for i in 0..table.len() {
-            res[j][i] = &table[i][j][..];
+    let mut i = 1;
+    let mut buf = vec![];
+    while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+        let line = String::from_utf8(buf.clone()).map_err(|_| {
+            let msg = format!("error in line {}: some character is not UTF-8", i);
+            log::error!("{}", &msg);
+            Error::new(ErrorKind::InvalidData, msg)
+        })?;
+        if let Some(line) = cleanup.clean(&line).filter(|l| !l.starts_with('#')) {
+            let columns: Vec<&str> = line.split('\t').collect();
+
+            if columns.len() != 2 {
+                let msg = format!(
+                    "error in line {}: cluster table must have exactly two columns",
+                    i
+                );
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+            res.push((columns[0].to_string(), columns[1].to_string(), i));
         }
-    }
 
-    res
+        i += 1;
+        buf.clear();
+    }
+    cleanup.report("cluster file");
+
+    Ok(res)
+}
+
+// list of node identifiers, one per line; further columns are ignored
+pub fn parse_node_list<R: Read>(data: &mut BufReader<R>) -> Result<Vec<String>, Error> {
+    let mut res = Vec::new();
+
+    let mut cleanup = LineCleanup::default();
+
+    let mut i = 1;
+    let mut buf = vec![];
+    while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+        let line = String::from_utf8(buf.clone()).map_err(|_| {
+            let msg = format!("error in line {}: some character is not UTF-8", i);
+            log::error!("{}", &msg);
+            Error::new(ErrorKind::InvalidData, msg)
+        })?;
+        if let Some(line) = cleanup.clean(&line).filter(|l| !l.starts_with('#')) {
+            if let Some(node) = line.split('\t').next().map(|x| x.trim()) {
+                if !node.is_empty() {
+                    res.push(node.to_string());
+                }
+            }
+        }
+
+        i += 1;
+        buf.clear();
+    }
+    cleanup.report("node list");
+
+    Ok(res)
+}
+
+// node annotations: maps original node identifiers to (comma-joined) feature labels
+pub type NodeAnnotation = HashMap<Vec<u8>, String>;
+
+pub fn parse_annotation<R: Read>(data: &mut BufReader<R>) -> Result<NodeAnnotation, Error> {
+    let mut res: NodeAnnotation = HashMap::default();
+
+    let mut cleanup = LineCleanup::default();
+
+    let mut i = 1;
+    let mut buf = vec![];
+    while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+        let line = String::from_utf8(buf.clone()).map_err(|_| {
+            let msg = format!("error in line {}: some character is not UTF-8", i);
+            log::error!("{}", &msg);
+            Error::new(ErrorKind::InvalidData, msg)
+        })?;
+        if let Some(line) = cleanup.clean(&line).filter(|l| !l.starts_with('#')) {
+            let columns: Vec<&str> = line.split('\t').map(|x| x.trim()).collect();
+
+            if columns.len() != 2 || columns.iter().any(|x| x.is_empty()) {
+                let msg = format!(
+                    "error in line {}: annotation table must have exactly two non-empty columns",
+                    i
+                );
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidData, msg));
+            }
+            // a node may overlap several features, in which case all labels are reported
+            res.entry(columns[0].as_bytes().to_vec())
+                .and_modify(|l| {
+                    if !l.split(',').any(|x| x == columns[1]) {
+                        l.push(',');
+                        l.push_str(columns[1]);
+                    }
+                })
+                .or_insert_with(|| columns[1].to_string());
+        }
+
+        i += 1;
+        buf.clear();
+    }
+    cleanup.report("annotation file");
+
+    Ok(res)
+}
+
+pub fn load_annotation(file_name: &str) -> Result<Option<NodeAnnotation>, Error> {
+    if file_name.is_empty() {
+        return Ok(None);
+    }
+    log::info!("loading node annotation from {}", file_name);
+    let mut data = BufReader::new(std::fs::File::open(file_name)?);
+    let annotation = parse_annotation(&mut data)?;
+    log::info!("loaded annotation for {} nodes", annotation.len());
+    Ok(Some(annotation))
+}
+
+// renaming rules: exact name mappings and regular expression substitutions
+pub type RenameRules = (HashMap<String, String>, Vec<(Regex, String)>);
+
+pub fn parse_rename_rules<R: Read>(data: &mut BufReader<R>) -> Result<RenameRules, Error> {
+    let mut names: HashMap<String, String> = HashMap::default();
+    let mut patterns: Vec<(Regex, String)> = Vec::new();
+
+    let mut i = 1;
+    let mut buf = vec![];
+    while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
+        //Remove new line at the end
+        while let Some(&last_byte) = buf.last() {
+            if last_byte == b'\n' || last_byte == b'\r' {
+                buf.pop();
+            } else {
+                break;
+            }
+        }
+        if buf.is_empty() || buf[0] == b'#' {
+            i += 1;
+            buf.clear();
+            continue;
+        }
+        let line = String::from_utf8(buf.clone()).map_err(|_| {
+            let msg = format!("error in line {}: some character is not UTF-8", i);
+            log::error!("{}", &msg);
+            Error::new(ErrorKind::InvalidData, msg)
+        })?;
+        let columns: Vec<&str> = line.split('\t').collect();
+
+        if columns.len() != 2 {
+            let msg = format!(
+                "error in line {}: renaming table must have exactly two columns",
+                i
+            );
+            log::error!("{}", &msg);
+            return Err(Error::new(ErrorKind::InvalidData, msg));
+        }
+        let old = columns[0];
+        if old.len() > 1 && old.starts_with('/') && old.ends_with('/') {
+            let re = Regex::new(&old[1..old.len() - 1]).map_err(|e| {
+                let msg = format!("error in line {}: invalid regular expression: {}", i, e);
+                log::error!("{}", &msg);
+                Error::new(ErrorKind::InvalidData, msg)
+            })?;
+            patterns.push((re, columns[1].to_string()));
+        } else if names
+            .insert(old.to_string(), columns[1].to_string())
+            .is_some()
+        {
+            let msg = format!(
+                "error in line {}: group {} is renamed more than once",
+                i, old
+            );
+            log::error!("{}", &msg);
+            return Err(Error::new(ErrorKind::InvalidData, msg));
+        }
+
+        i += 1;
+        buf.clear();
+    }
+
+    Ok((names, patterns))
+}
+
+// Parses a user-provided table, e.g., of external QC results, that is included as-is in HTML
+// reports; blank lines and lines starting with '#' are skipped
+pub fn parse_report_table<R: Read>(
+    data: &mut BufReader<R>,
+    delimiter: u8,
+) -> Result<Vec<Vec<String>>, Error> {
+    let mut table = Vec::new();
+    for (i, line) in data.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let row: Vec<String> = line
+            .split(delimiter as char)
+            .map(|x| x.trim().to_string())
+            .collect();
+        if let Some(header) = table.first().map(|h: &Vec<String>| h.len()) {
+            if row.len() != header {
+                log::warn!(
+                    "row {} of report table has {} columns, but its header has {}",
+                    i + 1,
+                    row.len(),
+                    header
+                );
+            }
+        }
+        table.push(row);
+    }
+    Ok(table)
+}
+
+pub fn load_report_table(file_name: &str) -> Result<Vec<Vec<String>>, Error> {
+    log::info!("loading report table from {}", file_name);
+    let delimiter = if file_name.to_lowercase().ends_with(".csv") {
+        b','
+    } else {
+        b'\t'
+    };
+    let mut data = BufReader::new(std::fs::File::open(file_name).map_err(|e| {
+        let msg = format!("unable to open report table {}: {}", file_name, e);
+        log::error!("{}", msg);
+        Error::new(e.kind(), msg)
+    })?);
+    parse_report_table(&mut data, delimiter)
+}
+
+pub fn parse_tsv<R: Read>(
+    data: &mut BufReader<R>,
+) -> Result<(Vec<Vec<u8>>, Vec<Vec<Vec<u8>>>), Error> {
+    let mut comments = Vec::new();
+    let mut table = Vec::new();
+    let reader = Csv::from_reader(data)
+        .delimiter(b'\t')
+        .flexible(true)
+        .has_header(false);
+
+    let mut is_header = true;
+    for (i, row) in reader.enumerate() {
+        let row = row.map_err(|_| {
+            let msg = format!("unable to parse row {}", i);
+            log::error!("{}", &msg);
+            Error::new(ErrorKind::InvalidData, msg)
+        })?;
+        let row: Vec<Vec<u8>> = row.bytes_columns().map(|x| x.to_vec()).collect();
+        if row.is_empty() {
+            log::info!("Empty row, skipping");
+            continue;
+        }
+        // Push header
+        if is_header && (b'#' == row[0][0]) {
+            let mut c = row[0].to_vec();
+            for e in &row[1..] {
+                c.push(b'\t');
+                c.extend(e);
+            }
+            comments.push(c);
+        // Skip empty lines (still need to have appropriate amount of tabs)
+        } else if row
+            .iter()
+            .map(|x| x.is_empty())
+            .fold(true, |acc, x| acc && x)
+        {
+            log::debug!("Skipping empty line");
+            continue;
+        // Handle comments
+        } else if b'#' == row[0][0] {
+            log::debug!("Handling comment");
+            let mut c = row[0].to_vec();
+            for e in &row[1..] {
+                c.push(b'\t');
+                c.extend(e);
+            }
+            comments.push(c);
+        // Push everything else
+        } else {
+            is_header = false;
+            table.push(row);
+        }
+    }
+    Ok((comments, table))
+}
+
+fn transpose_table(table: &Vec<Vec<Vec<u8>>>) -> Vec<Vec<&[u8]>> {
+    let n = table.first().unwrap_or(&Vec::new()).len();
+
+    let mut res = vec![vec![&table[0][0][..]; table.len()]; n];
+
+    for j in 0..n {
+        for i in 0..table.len() {
+            res[j][i] = &table[i][j][..];
+        }
+    }
+
+    res
 }
 
 fn parse_column(col: &Vec<&[u8]>, offset: usize) -> Result<Vec<usize>, Error> {
@@ -297,6 +1200,48 @@ pub fn parse_hists<R: Read>(
     }
 }
 
+pub fn parse_hist_results<R: Read>(data: &mut BufReader<R>) -> Result<Vec<HistResult>, Error> {
+    let is_json = data
+        .fill_buf()?
+        .iter()
+        .find(|c| !c.is_ascii_whitespace())
+        .map(|c| *c == b'{')
+        .unwrap_or(false);
+
+    if is_json {
+        log::info!("parsing coverage histogram in JSON format");
+        let results: HistGrowthResults = serde_json::from_reader(data).map_err(|e| {
+            let msg = format!("unable to parse JSON: {}", e);
+            log::error!("{}", &msg);
+            Error::new(ErrorKind::InvalidData, msg)
+        })?;
+        if results.hists.is_empty() {
+            let msg = "JSON document does not contain hists";
+            log::error!("{}", msg);
+            return Err(Error::new(ErrorKind::InvalidData, msg));
+        }
+        Ok(results.hists)
+    } else {
+        let (coverages, comments) = parse_hists(data)?;
+        let metadata: Vec<String> = comments
+            .iter()
+            .map(|c| {
+                let c = String::from_utf8_lossy(c);
+                let c = c.strip_prefix('#').unwrap_or(&c);
+                c.strip_prefix(' ').unwrap_or(c).to_string()
+            })
+            .collect();
+        Ok(coverages
+            .into_iter()
+            .map(|(count, coverage)| HistResult {
+                count,
+                coverage,
+                metadata: metadata.clone(),
+            })
+            .collect())
+    }
+}
+
 #[allow(dead_code)]
 pub fn parse_threshold_file<R: Read>(data: &mut BufReader<R>) -> Result<Vec<Threshold>, Error> {
     let mut res = Vec::new();
@@ -309,7 +1254,11 @@ pub fn parse_threshold_file<R: Read>(data: &mut BufReader<R>) -> Result<Vec<Thre
         let row = row.unwrap();
         let mut row_it = row.bytes_columns();
         if let Some(col) = row_it.next() {
-            let threshold_str = str::from_utf8(col).unwrap();
+            let threshold_str = str::from_utf8(col).unwrap().trim();
+            if threshold_str.is_empty() {
+                log::warn!("skipping blank line {} of threshold file", i + 1);
+                continue;
+            }
             if let Ok(t) = usize::from_str(threshold_str) {
                 res.push(Threshold::Absolute(t));
             } else if let Ok(t) = f64::from_str(threshold_str) {
@@ -330,7 +1279,7 @@ pub fn parse_threshold_file<R: Read>(data: &mut BufReader<R>) -> Result<Vec<Thre
     Ok(res)
 }
 
-pub fn parse_walk_identifier(data: &[u8]) -> (PathSegment, &[u8]) {
+pub fn parse_walk_identifier(data: &[u8], pansn_delimiter: char) -> (PathSegment, &[u8]) {
     let mut six_col: Vec<&str> = Vec::with_capacity(6);
 
     let mut it = data.iter();
@@ -357,19 +1306,20 @@ pub fn parse_walk_identifier(data: &[u8]) -> (PathSegment, &[u8]) {
         six_col[3].to_string(),
         seq_start,
         seq_end,
+        pansn_delimiter,
     );
 
     (path_seg, &data[i..])
 }
 
-pub fn parse_path_identifier(data: &[u8]) -> (PathSegment, &[u8]) {
+pub fn parse_path_identifier(data: &[u8], pansn_delimiter: char) -> (PathSegment, &[u8]) {
     let mut iter = data.iter();
 
     let start = iter.position(|&x| x == b'\t').unwrap() + 1;
     let offset = iter.position(|&x| x == b'\t').unwrap();
     let path_name = str::from_utf8(&data[start..start + offset]).unwrap();
     (
-        PathSegment::from_str(path_name),
+        PathSegment::from_str(path_name, pansn_delimiter),
         &data[start + offset + 1..],
     )
 }
@@ -377,10 +1327,21 @@ pub fn parse_path_identifier(data: &[u8]) -> (PathSegment, &[u8]) {
 fn parse_walk_seq_to_item_vec(
     data: &[u8],
     graph_aux: &GraphAuxilliary,
-) -> Vec<(ItemId, Orientation)> {
+) -> Result<Vec<(ItemId, Orientation)>, GraphParseError> {
     // later codes assumes that data is non-empty...
     if data.is_empty() {
-        return Vec::new();
+        return Ok(Vec::new());
+    }
+
+    let end = data
+        .iter()
+        .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
+        .unwrap_or(data.len());
+
+    if data[0] != b'>' && data[0] != b'<' {
+        return Err(GraphParseError::UnknownOrientation(
+            String::from_utf8_lossy(&data[..end]).to_string(),
+        ));
     }
 
     // whatever the orientation of the first node is, will be used to split the sequence first;
@@ -388,70 +1349,36 @@ fn parse_walk_seq_to_item_vec(
     let s1 = Orientation::from_lg(data[0]);
     let s2 = s1.flip();
 
-    let mut it = data.iter();
-    let end = it
-        .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
-        .unwrap_or_else(|| it.len());
-
     log::debug!("parsing walk sequences of size {}..", end);
 
+    let node_id = |x: &[u8]| {
+        graph_aux
+            .node2id
+            .get(x)
+            .copied()
+            .ok_or_else(|| GraphParseError::UnknownNode(String::from_utf8_lossy(x).to_string()))
+    };
+
     // ignore first > | < so that no empty is created for 1st node
-    let sids: Vec<(ItemId, Orientation)> = data[..end]
+    let sids: Vec<Vec<(ItemId, Orientation)>> = data[..end]
         .par_split(|x| &s1 == x)
         .map(|x| {
             if x.is_empty() {
-                // not nice... but Rust expects struct `std::iter::Once<(ItemIdSize, util::Orientation)>`
-                //
-                // this case shouldn't occur too often, so should be fine in terms for runtime
-                vec![]
-            } else {
-                let i = x.iter().position(|z| &s2 == z).unwrap_or(x.len());
-                let sid = (
-                    *graph_aux.node2id.get(&x[..i]).unwrap_or_else(|| {
-                        panic!(
-                            "walk contains unknown node {{{}}}'",
-                            str::from_utf8(&x[..i]).unwrap()
-                        )
-                    }),
-                    s1,
-                );
-                if i < x.len() {
-                    // not nice... but Rust expects struct `std::iter::Once<(ItemIdSize, util::Orientation)>`
-                    //
-                    // this case can happen more frequently... hopefully it doesn't blow up the
-                    // runtime
-                    [sid]
-                        .into_par_iter()
-                        .chain(
-                            x[i + 1..]
-                                .par_split(|y| &s2 == y)
-                                .map(|y| {
-                                    if y.is_empty() {
-                                        vec![]
-                                    } else {
-                                        vec![(
-                                            *graph_aux.node2id.get(y).unwrap_or_else(|| {
-                                                panic!(
-                                                    "walk contains unknown node {{{}}}",
-                                                    str::from_utf8(y).unwrap()
-                                                )
-                                            }),
-                                            s2,
-                                        )]
-                                    }
-                                })
-                                .flatten(),
-                        )
-                        .collect()
-                } else {
-                    vec![sid]
+                return Ok(Vec::new());
+            }
+            // the first node of the split has orientation s1, all following have orientation s2
+            let mut nodes = x.split(|y| &s2 == y);
+            let mut res = vec![(node_id(nodes.next().unwrap())?, s1)];
+            for y in nodes {
+                if !y.is_empty() {
+                    res.push((node_id(y)?, s2));
                 }
             }
+            Ok(res)
         })
-        .flatten()
-        .collect();
+        .collect::<Result<_, GraphParseError>>()?;
     log::debug!("..done");
-    sids
+    Ok(sids.into_iter().flatten().collect())
 }
 
 fn parse_walk_seq_update_tables(
@@ -460,10 +1387,10 @@ fn parse_walk_seq_update_tables(
     item_table: &mut ItemTable,
     exclude_table: Option<&mut ActiveTable>,
     num_path: usize,
-) -> (u32, u32) {
+) -> Result<(u64, u64), GraphParseError> {
     // later codes assumes that data is non-empty...
     if data.is_empty() {
-        return (0, 0);
+        return Ok((0, 0));
     }
 
     let items_ptr = Wrap(&mut item_table.items);
@@ -475,22 +1402,22 @@ fn parse_walk_seq_update_tables(
         .map(|x| Arc::new(Mutex::new(x)))
         .collect();
 
-    let mut it = data.iter();
-    let end = it
+    // the walk sequence is the last column, which the last line of the file may end with
+    let end = data
+        .iter()
         .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
-        .unwrap();
+        .unwrap_or(data.len());
 
     log::debug!("parsing walk sequences of size {}..", end);
 
-    let bp_len = Arc::new(AtomicU32::new(0));
+    let bp_len = Arc::new(AtomicU64::new(0));
     // ignore first > | < so that no empty is created for 1st node
     data[1..end]
         .par_split(|&x| x == b'>' || x == b'<')
-        .for_each(|node| {
-            let sid = *graph_aux
-                .node2id
-                .get(node)
-                .unwrap_or_else(|| panic!("unknown node {}", str::from_utf8(node).unwrap()));
+        .try_for_each(|node| {
+            let sid = *graph_aux.node2id.get(node).ok_or_else(|| {
+                GraphParseError::UnknownNode(String::from_utf8_lossy(node).to_string())
+            })?;
             let idx = (sid.0 as usize) % SIZE_T;
             if let Ok(_) = mutex_vec[idx].lock() {
                 unsafe {
@@ -498,8 +1425,9 @@ fn parse_walk_seq_update_tables(
                     (*id_prefsum_ptr.0)[idx][num_path + 1] += 1;
                 }
             }
-            bp_len.fetch_add(graph_aux.node_len(&sid), Ordering::SeqCst);
-        });
+            bp_len.fetch_add(graph_aux.node_len(&sid) as u64, Ordering::SeqCst);
+            Ok(())
+        })?;
     let bp_len = bp_len.load(Ordering::SeqCst);
 
     // compute prefix sum
@@ -516,46 +1444,55 @@ fn parse_walk_seq_update_tables(
             for j in (item_table.id_prefsum[i][num_path] as usize)
                 ..(item_table.id_prefsum[i][num_path + 1] as usize)
             {
-                ex.items[item_table.items[i][j] as usize] |= true;
+                ex.activate(&ItemId(item_table.items[i][j]));
             }
         }
     }
 
     log::debug!("..done");
-    (num_nodes_path as u32, bp_len)
+    Ok((num_nodes_path, bp_len))
+}
+
+// parses a single oriented segment of a path sequence, e.g., "12+"
+fn parse_path_node(
+    node: &[u8],
+    graph_aux: &GraphAuxilliary,
+) -> Result<(ItemId, Orientation), GraphParseError> {
+    let (o, name) = match node.split_last() {
+        Some((o @ (b'+' | b'-'), name)) => (Orientation::from_pm(*o), name),
+        _ => {
+            return Err(GraphParseError::UnknownOrientation(
+                String::from_utf8_lossy(node).to_string(),
+            ))
+        }
+    };
+    let sid = *graph_aux
+        .node2id
+        .get(name)
+        .ok_or_else(|| GraphParseError::UnknownNode(String::from_utf8_lossy(name).to_string()))?;
+    Ok((sid, o))
 }
 
 fn parse_path_seq_to_item_vec(
     data: &[u8],
     graph_aux: &GraphAuxilliary,
-) -> Vec<(ItemId, Orientation)> {
-    let mut it = data.iter();
-    let end = it
+) -> Result<Vec<(ItemId, Orientation)>, GraphParseError> {
+    // the overlaps column follows the path sequence, whose end is thus always marked
+    let end = data
+        .iter()
         .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
-        .unwrap();
+        .ok_or(GraphParseError::UnterminatedPath)?;
 
     log::debug!("parsing path sequences of size {}..", end);
 
-    let sids: Vec<(ItemId, Orientation)> = data[..end]
+    let sids = data[..end]
         .par_split(|&x| x == b',')
-        .map(|node| {
-            // Parallel
-            let sid = *graph_aux
-                .node2id
-                .get(&node[..node.len() - 1])
-                .unwrap_or_else(|| {
-                    panic!(
-                        "unknown node {}",
-                        str::from_utf8(&node[..node.len() - 1]).unwrap()
-                    )
-                });
-            (sid, Orientation::from_pm(node[node.len() - 1]))
-        })
-        .collect();
+        .map(|node| parse_path_node(node, graph_aux))
+        .collect::<Result<Vec<(ItemId, Orientation)>, GraphParseError>>()?;
 
     log::debug!("..done");
 
-    sids
+    Ok(sids)
 }
 
 fn parse_path_seq_update_tables(
@@ -564,11 +1501,12 @@ fn parse_path_seq_update_tables(
     item_table: &mut ItemTable,
     exclude_table: Option<&mut ActiveTable>,
     num_path: usize,
-) -> (u32, u32) {
-    let mut it = data.iter();
-    let end = it
+) -> Result<(u64, u64), GraphParseError> {
+    // the overlaps column follows the path sequence, whose end is thus always marked
+    let end = data
+        .iter()
         .position(|x| x == &b'\t' || x == &b'\n' || x == &b'\r')
-        .unwrap();
+        .ok_or(GraphParseError::UnterminatedPath)?;
 
     log::debug!("parsing path sequences of size {} bytes..", end);
 
@@ -581,19 +1519,10 @@ fn parse_path_seq_update_tables(
         .map(|x| Arc::new(Mutex::new(x)))
         .collect();
 
-    let bp_len = Arc::new(AtomicU32::new(0));
+    let bp_len = Arc::new(AtomicU64::new(0));
     //let mut plus_strands: Vec<u32> = vec![0; rayon::current_num_threads()];
-    data[..end].par_split(|&x| x == b',').for_each(|node| {
-        let sid = *graph_aux
-            .node2id
-            .get(&node[0..node.len() - 1])
-            .unwrap_or_else(|| panic!("unknown node {}", str::from_utf8(node).unwrap()));
-        let o = node[node.len() - 1];
-        assert!(
-            o == b'-' || o == b'+',
-            "unknown orientation of segment {}",
-            str::from_utf8(node).unwrap()
-        );
+    data[..end].par_split(|&x| x == b',').try_for_each(|node| {
+        let (sid, _) = parse_path_node(node, graph_aux)?;
         //plus_strands[rayon::current_thread_index().unwrap()] += (o == b'+') as u32;
 
         let idx = (sid.0 as usize) % SIZE_T;
@@ -604,8 +1533,9 @@ fn parse_path_seq_update_tables(
                 (*id_prefsum_ptr.0)[idx][num_path + 1] += 1;
             }
         }
-        bp_len.fetch_add(graph_aux.node_len(&sid), Ordering::SeqCst);
-    });
+        bp_len.fetch_add(graph_aux.node_len(&sid) as u64, Ordering::SeqCst);
+        Ok(())
+    })?;
     let bp_len = bp_len.load(Ordering::SeqCst);
 
     // compute prefix sum
@@ -622,19 +1552,20 @@ fn parse_path_seq_update_tables(
             for j in (item_table.id_prefsum[i][num_path] as usize)
                 ..(item_table.id_prefsum[i][num_path + 1] as usize)
             {
-                ex.items[item_table.items[i][j] as usize] |= true;
+                ex.activate(&ItemId(item_table.items[i][j]));
             }
         }
     }
 
     log::debug!("..done");
-    (num_nodes_path as u32, bp_len)
+    Ok((num_nodes_path, bp_len))
 }
 
 #[allow(dead_code)]
 pub fn parse_graph_aux<R: Read>(
     data: &mut BufReader<R>,
     index_edges: bool,
+    pansn_delimiter: char,
 ) -> Result<
     (
         HashMap<Vec<u8>, ItemId>,
@@ -698,10 +1629,10 @@ pub fn parse_graph_aux<R: Read>(
         } else if index_edges && buf[0] == b'L' {
             edges.as_mut().unwrap().push(buf.to_vec());
         } else if buf[0] == b'P' {
-            let (path_seg, _) = parse_path_identifier(&buf);
+            let (path_seg, _) = parse_path_identifier(&buf, pansn_delimiter);
             path_segments.push(path_seg);
         } else if buf[0] == b'W' {
-            let (path_seg, _) = parse_walk_identifier(&buf);
+            let (path_seg, _) = parse_walk_identifier(&buf, pansn_delimiter);
             path_segments.push(path_seg);
         }
 
@@ -765,161 +1696,314 @@ pub fn parse_graph_aux<R: Read>(
 //     item_table
 // }
 
-pub fn parse_gfa_paths_walks<R: Read>(
+// streams path and walk sequences one at a time and hands the oriented nodes of each to the
+// given callback, in the order of the GFA file
+pub fn parse_gfa_paths_walks_oriented<
+    R: Read,
+    F: FnMut(&PathSegment, Vec<(ItemId, Orientation)>) -> Result<(), GraphParseError>,
+>(
     data: &mut BufReader<R>,
-    abacus_aux: &AbacusAuxilliary,
     graph_aux: &GraphAuxilliary,
-    count: &CountType,
-) -> (
-    ItemTable,
-    Option<ActiveTable>,
-    Option<IntervalContainer>,
-    HashMap<PathSegment, (u32, u32)>,
-) {
-    log::info!("parsing path + walk sequences");
-    let mut item_table = ItemTable::new(graph_aux.path_segments.len());
-    let (mut subset_covered_bps, mut exclude_table, include_map, exclude_map) =
-        abacus_aux.load_optional_subsetting(graph_aux, count);
-
-    let mut num_path = 0;
-    let complete: Vec<(usize, usize)> = vec![(0, usize::MAX)];
-    let mut paths_len: HashMap<PathSegment, (u32, u32)> = HashMap::new();
-
+    mut callback: F,
+) -> Result<(), GraphParseError> {
     let mut buf = vec![];
-    while data.read_until(b'\n', &mut buf).unwrap_or(0) > 0 {
-        if buf[0] == b'P' || buf[0] == b'W' {
+    let mut line = 0;
+    while read_gfa_line(data, &mut buf, line)? > 0 {
+        line += 1;
+        if (buf[0] == b'P' || buf[0] == b'W') && check_parsed_record(&buf, line)? {
             let (path_seg, buf_path_seg) = match buf[0] {
-                b'P' => parse_path_identifier(&buf),
-                b'W' => parse_walk_identifier(&buf),
+                b'P' => parse_path_identifier(&buf, graph_aux.pansn_delimiter),
+                b'W' => parse_walk_identifier(&buf, graph_aux.pansn_delimiter),
                 _ => unreachable!(),
             };
-
             log::debug!("processing path {}", &path_seg);
 
-            let include_coords = if abacus_aux.include_coords.is_none() {
-                &complete[..]
-            } else {
-                match include_map.get(&path_seg.id()) {
-                    None => &[],
-                    Some(coords) => {
-                        log::debug!(
-                            "found include coords in interval {}..{} for path segment {}",
-                            &coords.first().unwrap().0,
-                            &coords.last().unwrap().1,
-                            &path_seg.id()
-                        );
-                        &coords[..]
-                    }
-                }
-            };
-            let exclude_coords = if abacus_aux.exclude_coords.is_none() {
-                &[]
-            } else {
-                match exclude_map.get(&path_seg.id()) {
-                    None => &[],
-                    Some(coords) => {
-                        log::debug!(
-                            "found exclude coords in interval {}..{} for path segment {}",
-                            &coords.first().unwrap().0,
-                            &coords.last().unwrap().1,
-                            &path_seg.id()
-                        );
-                        &coords[..]
-                    }
-                }
-            };
-
-            let (start, end) = path_seg.coords().unwrap_or((0, usize::MAX));
-
-            // do not process the path sequence if path is neither part of subset nor exclude
-            if abacus_aux.include_coords.is_some()
-                && !intersects(include_coords, &(start, end))
-                && !intersects(exclude_coords, &(start, end))
-            {
-                log::debug!("path {} does not intersect with subset coordinates in interval {}..{} nor with exclude coordinates {}..{}  and therefore is skipped from processing",
-                    &path_seg, &include_coords.first().unwrap_or(&(0,0)).0, &include_coords.last().unwrap_or(&(0,0)).1, &exclude_coords.first().unwrap_or(&(0,0)).0, &exclude_coords.last().unwrap_or(&(0,0)).1);
-
-                // update prefix sum
-                for i in 0..SIZE_T {
-                    item_table.id_prefsum[i][num_path + 1] += item_table.id_prefsum[i][num_path];
-                }
-
-                num_path += 1;
-                buf.clear();
-                continue;
+            match buf[0] {
+                b'P' => parse_path_seq_to_item_vec(buf_path_seg, graph_aux),
+                b'W' => parse_walk_seq_to_item_vec(buf_path_seg, graph_aux),
+                _ => unreachable!(),
             }
-
-            if count != &CountType::Edge
-                && (abacus_aux.include_coords.is_none()
-                    || is_contained(include_coords, &(start, end)))
-                && (abacus_aux.exclude_coords.is_none()
-                    || is_contained(exclude_coords, &(start, end)))
+            .and_then(|sids| callback(&path_seg, sids))
+            .map_err(|e| e.in_record(line, &path_seg))?;
+        }
+        buf.clear();
+    }
+    Ok(())
+}
+
+// streams path and walk sequences one at a time and hands the countables (nodes or edges) of
+// each to the given callback; unlike parse_gfa_paths_walks, no table holding the countables of
+// all paths is kept in memory
+pub fn parse_gfa_paths_walks_streaming<R: Read, F: FnMut(&PathSegment, &ItemTable)>(
+    data: &mut BufReader<R>,
+    graph_aux: &GraphAuxilliary,
+    count: &CountType,
+    canonicalize_edges: bool,
+    mut callback: F,
+) -> Result<(), GraphParseError> {
+    log::info!("streaming path + walk sequences");
+    let complete: Vec<(usize, usize)> = vec![(0, usize::MAX)];
+    let mut flipped_edges = 0;
+
+    parse_gfa_paths_walks_oriented(data, graph_aux, |path_seg, sids| {
+        let (start, _) = path_seg.coords().unwrap_or((0, usize::MAX));
+
+        // the table holds the countables of the current path only
+        let mut item_table = ItemTable::new(1);
+        match count {
+            CountType::Node | CountType::Bp => {
+                update_tables(
+                    &mut item_table,
+                    &mut None,
+                    &mut None,
+                    0,
+                    graph_aux,
+                    sids,
+                    &complete,
+                    &[],
+                    start,
+                );
+            }
+            CountType::Edge => {
+                flipped_edges += update_tables_edgecount(
+                    &mut item_table,
+                    &mut None,
+                    0,
+                    graph_aux,
+                    sids,
+                    &complete,
+                    &[],
+                    start,
+                    canonicalize_edges,
+                )?
+            }
+            CountType::All => unreachable!("inadmissable count type"),
+        };
+        callback(path_seg, &item_table);
+        Ok(())
+    })?;
+    if flipped_edges > 0 {
+        log::warn!(
+            "{} edge traversals did not match the orientation of any L-line and were canonicalized to the known edge between the same nodes",
+            flipped_edges
+        );
+    }
+    Ok(())
+}
+
+// tables of the countables of all paths and walks of a GFA file for a single count type, i.e.,
+// the item table, the table of excluded countables, the partially covered bps of subset
+// coordinates, and the numbers of nodes and bps of each path
+pub type PathWalkTables = (
+    ItemTable,
+    Option<ActiveTable>,
+    Option<IntervalContainer>,
+    PathsLen,
+);
+
+// numbers of nodes and bps of each path
+pub type PathsLen = HashMap<PathSegment, (u64, u64)>;
+
+pub fn parse_gfa_paths_walks<R: Read>(
+    data: &mut BufReader<R>,
+    abacus_aux: &AbacusAuxilliary,
+    graph_aux: &GraphAuxilliary,
+    count: &CountType,
+) -> Result<PathWalkTables, GraphParseError> {
+    let mut tables = parse_gfa_paths_walks_views(data, graph_aux, &[(abacus_aux, *count)])?;
+    Ok(tables.pop().unwrap())
+}
+
+// fills the tables of several views in a single pass over the paths and walks of the GFA file,
+// which saves reading (and decompressing) the file once per view; each view pairs a count type
+// with its own groups and subset/exclude coordinates, e.g., one per sequence or subset of paths
+pub fn parse_gfa_paths_walks_views<R: Read>(
+    data: &mut BufReader<R>,
+    graph_aux: &GraphAuxilliary,
+    views: &[(&AbacusAuxilliary, CountType)],
+) -> Result<Vec<PathWalkTables>, GraphParseError> {
+    log::info!(
+        "parsing path + walk sequences for count types {}",
+        views
+            .iter()
+            .map(|(_, c)| c.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+    let mut tables: Vec<PathWalkTables> = Vec::new();
+    let mut subset_maps = Vec::new();
+    for (abacus_aux, count) in views {
+        let mut item_table = ItemTable::new(graph_aux.path_segments.len());
+        if abacus_aux.exact_bp && count == &CountType::Bp && abacus_aux.include_coords.is_some() {
+            item_table = item_table.with_covered_bps();
+        }
+        let (subset_covered_bps, exclude_table, include_map, exclude_map) =
+            abacus_aux.load_optional_subsetting(graph_aux, count);
+        tables.push((
+            item_table,
+            exclude_table,
+            subset_covered_bps,
+            HashMap::new(),
+        ));
+        subset_maps.push((include_map, exclude_map));
+    }
+
+    let mut num_path = 0;
+    let complete: Vec<(usize, usize)> = vec![(0, usize::MAX)];
+    let mut flipped_edges = 0;
+
+    let mut buf = vec![];
+    let mut line = 0;
+    while read_gfa_line(data, &mut buf, line)? > 0 {
+        line += 1;
+        if (buf[0] == b'P' || buf[0] == b'W') && check_parsed_record(&buf, line)? {
+            let (path_seg, buf_path_seg) = match buf[0] {
+                b'P' => parse_path_identifier(&buf, graph_aux.pansn_delimiter),
+                b'W' => parse_walk_identifier(&buf, graph_aux.pansn_delimiter),
+                _ => unreachable!(),
+            };
+
+            log::debug!("processing path {}", &path_seg);
+
+            for (((abacus_aux, count), table), (include_map, exclude_map)) in
+                views.iter().zip(tables.iter_mut()).zip(subset_maps.iter())
             {
-                log::debug!("path {} is fully contained within subset coordinates {:?} and is eligible for full parallel processing", path_seg, include_coords);
-                let ex = if exclude_coords.is_empty() {
-                    None
+                let (item_table, exclude_table, subset_covered_bps, paths_len) = table;
+                let include_coords = if abacus_aux.include_coords.is_none() {
+                    &complete[..]
                 } else {
-                    exclude_table.as_mut()
-                };
-
-                let (num_added_nodes, bp_len) = match buf[0] {
-                    b'P' => parse_path_seq_update_tables(
-                        buf_path_seg,
-                        graph_aux,
-                        &mut item_table,
-                        ex,
-                        num_path,
-                    ),
-                    b'W' => parse_walk_seq_update_tables(
-                        buf_path_seg,
-                        graph_aux,
-                        &mut item_table,
-                        ex,
-                        num_path,
-                    ),
-                    _ => unreachable!(),
+                    match include_map.get(&path_seg.id()) {
+                        None => &[],
+                        Some(coords) => {
+                            log::debug!(
+                                "found include coords in interval {}..{} for path segment {}",
+                                &coords.first().unwrap().0,
+                                &coords.last().unwrap().1,
+                                &path_seg.id()
+                            );
+                            &coords[..]
+                        }
+                    }
                 };
-                paths_len.insert(path_seg, (num_added_nodes, bp_len));
-            } else {
-                let sids = match buf[0] {
-                    b'P' => parse_path_seq_to_item_vec(buf_path_seg, graph_aux),
-                    b'W' => parse_walk_seq_to_item_vec(buf_path_seg, graph_aux),
-                    _ => unreachable!(),
+                let exclude_coords = if abacus_aux.exclude_coords.is_none() {
+                    &[]
+                } else {
+                    match exclude_map.get(&path_seg.id()) {
+                        None => &[],
+                        Some(coords) => {
+                            log::debug!(
+                                "found exclude coords in interval {}..{} for path segment {}",
+                                &coords.first().unwrap().0,
+                                &coords.last().unwrap().1,
+                                &path_seg.id()
+                            );
+                            &coords[..]
+                        }
+                    }
                 };
 
-                match count {
-                    CountType::Node | CountType::Bp => {
-                        let (node_len, bp_len) = update_tables(
-                            &mut item_table,
-                            &mut subset_covered_bps.as_mut(),
-                            &mut exclude_table.as_mut(),
+                let (start, end) = path_seg.coords().unwrap_or((0, usize::MAX));
+
+                // do not process the path sequence if path is neither part of subset nor exclude
+                if abacus_aux.include_coords.is_some()
+                    && !intersects(include_coords, &(start, end))
+                    && !intersects(exclude_coords, &(start, end))
+                {
+                    log::debug!("path {} does not intersect with subset coordinates in interval {}..{} nor with exclude coordinates {}..{}  and therefore is skipped from processing",
+                        &path_seg, &include_coords.first().unwrap_or(&(0,0)).0, &include_coords.last().unwrap_or(&(0,0)).1, &exclude_coords.first().unwrap_or(&(0,0)).0, &exclude_coords.last().unwrap_or(&(0,0)).1);
+
+                    // update prefix sum
+                    for i in 0..SIZE_T {
+                        item_table.id_prefsum[i][num_path + 1] +=
+                            item_table.id_prefsum[i][num_path];
+                    }
+                    continue;
+                }
+
+                if count != &CountType::Edge
+                    && (abacus_aux.include_coords.is_none()
+                        || is_contained(include_coords, &(start, end)))
+                    && (abacus_aux.exclude_coords.is_none()
+                        || is_contained(exclude_coords, &(start, end)))
+                {
+                    log::debug!("path {} is fully contained within subset coordinates {:?} and is eligible for full parallel processing", path_seg, include_coords);
+                    let ex = if exclude_coords.is_empty() {
+                        None
+                    } else {
+                        exclude_table.as_mut()
+                    };
+
+                    let (num_added_nodes, bp_len) = match buf[0] {
+                        b'P' => parse_path_seq_update_tables(
+                            buf_path_seg,
+                            graph_aux,
+                            item_table,
+                            ex,
                             num_path,
+                        ),
+                        b'W' => parse_walk_seq_update_tables(
+                            buf_path_seg,
                             graph_aux,
-                            sids,
-                            include_coords,
-                            exclude_coords,
-                            start,
-                        );
-                        paths_len.insert(path_seg, (node_len as u32, bp_len as u32));
+                            item_table,
+                            ex,
+                            num_path,
+                        ),
+                        _ => unreachable!(),
                     }
-                    CountType::Edge => update_tables_edgecount(
-                        &mut item_table,
-                        &mut exclude_table.as_mut(),
-                        num_path,
-                        graph_aux,
-                        sids,
-                        include_coords,
-                        exclude_coords,
-                        start,
-                    ),
-                    CountType::All => unreachable!("inadmissable count type"),
-                };
+                    .map_err(|e| e.in_record(line, &path_seg))?;
+                    paths_len.insert(path_seg.clone(), (num_added_nodes, bp_len));
+                } else {
+                    let sids = match buf[0] {
+                        b'P' => parse_path_seq_to_item_vec(buf_path_seg, graph_aux),
+                        b'W' => parse_walk_seq_to_item_vec(buf_path_seg, graph_aux),
+                        _ => unreachable!(),
+                    }
+                    .map_err(|e| e.in_record(line, &path_seg))?;
+
+                    match count {
+                        CountType::Node | CountType::Bp => {
+                            let (node_len, bp_len) = update_tables(
+                                item_table,
+                                &mut subset_covered_bps.as_mut(),
+                                &mut exclude_table.as_mut(),
+                                num_path,
+                                graph_aux,
+                                sids,
+                                include_coords,
+                                exclude_coords,
+                                start,
+                            );
+                            paths_len.insert(path_seg.clone(), (node_len as u64, bp_len as u64));
+                        }
+                        CountType::Edge => {
+                            flipped_edges += update_tables_edgecount(
+                                item_table,
+                                &mut exclude_table.as_mut(),
+                                num_path,
+                                graph_aux,
+                                sids,
+                                include_coords,
+                                exclude_coords,
+                                start,
+                                abacus_aux.canonicalize_edges,
+                            )
+                            .map_err(|e| e.in_record(line, &path_seg))?
+                        }
+                        CountType::All => unreachable!("inadmissable count type"),
+                    };
+                }
             }
             num_path += 1;
         }
         buf.clear();
     }
-    (item_table, exclude_table, subset_covered_bps, paths_len)
+    if flipped_edges > 0 {
+        log::warn!(
+            "{} edge traversals did not match the orientation of any L-line and were canonicalized to the known edge between the same nodes",
+            flipped_edges
+        );
+    }
+    Ok(tables)
 }
 
 fn update_tables(
@@ -940,6 +2024,12 @@ fn update_tables(
     let mut included = 0;
     let mut included_bp = 0;
     let mut excluded = 0;
+    // covered intervals of this path, if bps are counted exactly and the path is not included as a
+    // whole
+    let mut path_covered_bps = match item_table.covered_bps {
+        Some(_) if include_coords != [(0, usize::MAX)] => Some(IntervalContainer::new()),
+        _ => None,
+    };
 
     log::debug!(
         "checking inclusion/exclusion criteria on {} nodes..",
@@ -974,6 +2064,8 @@ fn update_tables(
         //
         // node count handling: node is only counted if *completely* covered by subset
         //
+        // with --exact-bp, the covered intervals of each path are additionally kept in
+        // item_table.covered_bps, so that bps can be counted exactly per group later on
         //
         // update current pointer in include_coords list
 
@@ -1013,6 +2105,9 @@ fn update_tables(
                         int.add(*sid, a, b);
                     }
                 }
+                if let Some(int) = path_covered_bps.as_mut() {
+                    int.add(*sid, a, b);
+                }
                 included += 1;
                 included_bp += b - a;
             } else {
@@ -1071,6 +2166,19 @@ fn update_tables(
         excluded,
     );
 
+    // only nodes that the path covers partially need to be remembered
+    if let (Some(int), Some(covered)) = (path_covered_bps, item_table.covered_bps.as_mut()) {
+        for sid in int.keys() {
+            let l = graph_aux.node_len(sid) as usize;
+            let v = int.get(sid).unwrap();
+            if v != [(0, l)] {
+                for (a, b) in v {
+                    covered[num_path].add(*sid, *a, *b);
+                }
+            }
+        }
+    }
+
     // Compute prefix sum
     for i in 0..SIZE_T {
         item_table.id_prefsum[i][num_path + 1] += item_table.id_prefsum[i][num_path];
@@ -1088,10 +2196,12 @@ fn update_tables_edgecount(
     include_coords: &[(usize, usize)],
     exclude_coords: &[(usize, usize)],
     offset: usize,
-) {
+    canonicalize_edges: bool,
+) -> Result<usize, GraphParseError> {
     let mut i = 0;
     let mut j = 0;
     let mut p = offset;
+    let mut flipped = 0;
 
     // edges are positioned between nodes, offset by the first node
     if !path.is_empty() {
@@ -1100,6 +2210,11 @@ fn update_tables_edgecount(
 
     log::debug!("checking inclusion/exclusion criteria on {} nodes, inserting successful candidates to corresponding data structures..", path.len());
 
+    let edge2id = graph_aux
+        .edge2id
+        .as_ref()
+        .expect("update_tables_edgecount requires edge2id map in GraphAuxilliary");
+
     for ((sid1, o1), (sid2, o2)) in path.into_iter().tuple_windows() {
         // update current pointer in include_coords list
         while i < include_coords.len() && include_coords[i].1 <= p {
@@ -1114,22 +2229,39 @@ fn update_tables_edgecount(
         let l = graph_aux.node_len(&sid2) as usize;
 
         let e = Edge::canonical(sid1, o1, sid2, o2);
-        let eid = graph_aux
-            .edge2id
-            .as_ref()
-            .expect("update_tables_edgecount requires edge2id map in GraphAuxilliary")
-            .get(&e)
-            .unwrap_or_else(|| {
-                panic!(
-                    "unknown edge {}. Is flipped edge known? {}",
-                    &e,
-                    if graph_aux.edge2id.as_ref().unwrap().contains_key(&e.flip()) {
-                        "Yes"
-                    } else {
-                        "No"
-                    }
-                )
-            });
+        let eid = match edge2id.get(&e) {
+            Some(eid) => eid,
+            None if canonicalize_edges => {
+                flipped += 1;
+                [
+                    Edge::canonical(sid1, o1.flip(), sid2, o2),
+                    Edge::canonical(sid1, o1, sid2, o2.flip()),
+                    Edge::canonical(sid1, o1.flip(), sid2, o2.flip()),
+                ]
+                .iter()
+                .find_map(|f| edge2id.get(f))
+                .ok_or_else(|| GraphParseError::UnknownEdge {
+                    edge: e.to_string(),
+                    reason: format!(
+                        "no L-line connects nodes {} and {} in any orientation",
+                        &sid1, &sid2
+                    ),
+                })?
+            }
+            None => {
+                return Err(GraphParseError::UnknownEdge {
+                    edge: e.to_string(),
+                    reason: format!(
+                        "no L-line connects the nodes in this orientation{}; consider --canonicalize-edges",
+                        if edge2id.contains_key(&e.flip()) {
+                            ", but the flipped edge is known"
+                        } else {
+                            ""
+                        }
+                    ),
+                })
+            }
+        };
         // check if the current position fits within active segment
         if i < include_coords.len() && include_coords[i].0 < p + l {
             let idx = (eid.0 as usize) % SIZE_T;
@@ -1149,6 +2281,478 @@ fn update_tables_edgecount(
         item_table.id_prefsum[i][num_path + 1] += item_table.id_prefsum[i][num_path];
     }
     log::debug!("..done");
+    Ok(flipped)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowCondition {
+    pub column: String,
+    pub cmp: Comparison,
+    pub value: String,
+}
+
+impl Comparison {
+    // parses a leading comparison operator and returns it together with the remainder
+    fn parse_prefix(s: &str) -> Option<(Self, &str)> {
+        if let Some(rest) = s.strip_prefix(">=") {
+            Some((Comparison::Ge, rest))
+        } else if let Some(rest) = s.strip_prefix("<=") {
+            Some((Comparison::Le, rest))
+        } else if let Some(rest) = s.strip_prefix("!=") {
+            Some((Comparison::Ne, rest))
+        } else if let Some(rest) = s.strip_prefix("==") {
+            Some((Comparison::Eq, rest))
+        } else if let Some(rest) = s.strip_prefix('>') {
+            Some((Comparison::Gt, rest))
+        } else {
+            s.strip_prefix('<').map(|rest| (Comparison::Lt, rest))
+        }
+    }
+
+    fn holds(&self, o: Option<std::cmp::Ordering>) -> bool {
+        match o {
+            Some(o) => match self {
+                Comparison::Eq => o == std::cmp::Ordering::Equal,
+                Comparison::Ne => o != std::cmp::Ordering::Equal,
+                Comparison::Lt => o == std::cmp::Ordering::Less,
+                Comparison::Le => o != std::cmp::Ordering::Greater,
+                Comparison::Gt => o == std::cmp::Ordering::Greater,
+                Comparison::Ge => o != std::cmp::Ordering::Less,
+            },
+            None => *self == Comparison::Ne,
+        }
+    }
+}
+
+impl RowCondition {
+    fn holds(&self, x: &str) -> bool {
+        // compare numerically whenever possible, lexicographically otherwise
+        let ord = match (x.parse::<f64>(), self.value.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b),
+            _ => Some(x.cmp(&self.value[..])),
+        };
+        self.cmp.holds(ord)
+    }
+}
+
+pub fn parse_row_filter(expr: &str) -> Result<Vec<RowCondition>, Error> {
+    let mut res = Vec::new();
+    for cond in expr.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+        let (column, cmp, value) = match cond.find(['<', '>', '=', '!']) {
+            Some(i) => match Comparison::parse_prefix(&cond[i..]) {
+                Some((cmp, value)) => (cond[..i].trim(), Some(cmp), value.trim()),
+                None => ("", None, ""),
+            },
+            None => ("", None, ""),
+        };
+        match cmp {
+            Some(cmp) if !column.is_empty() => res.push(RowCondition {
+                column: column.to_string(),
+                cmp,
+                value: value.to_string(),
+            }),
+            _ => {
+                let msg = format!(
+                    "unable to parse filter condition '{}', expected <column><op><value> with <op> one of ==, !=, <, <=, >, >=",
+                    cond
+                );
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidInput, msg));
+            }
+        }
+    }
+    Ok(res)
+}
+
+// Condition on the number (absolute threshold) or fraction (relative threshold) of groups
+// covering a countable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoverageCondition {
+    pub cmp: Comparison,
+    pub threshold: Threshold,
+}
+
+impl CoverageCondition {
+    pub fn holds(&self, coverage: usize, n_groups: usize) -> bool {
+        let ord = match self.threshold {
+            Threshold::Absolute(t) => Some(coverage.cmp(&t)),
+            Threshold::Relative(t) => (coverage as f64 / n_groups.max(1) as f64).partial_cmp(&t),
+        };
+        self.cmp.holds(ord)
+    }
+}
+
+pub fn parse_coverage_query(expr: &str) -> Result<Vec<CoverageCondition>, Error> {
+    let mut res = Vec::new();
+    for cond in expr.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+        let parsed = Comparison::parse_prefix(cond).and_then(|(cmp, value)| {
+            let value = value.trim();
+            let threshold = match usize::from_str(value) {
+                Ok(t) => Some(Threshold::Absolute(t)),
+                Err(_) => f64::from_str(value)
+                    .ok()
+                    .filter(|t| (0.0..=1.0).contains(t))
+                    .map(Threshold::Relative),
+            };
+            threshold.map(|threshold| CoverageCondition { cmp, threshold })
+        });
+        match parsed {
+            Some(c) => res.push(c),
+            None => {
+                let msg = format!(
+                    "unable to parse coverage condition '{}', expected <op><value> with <op> one of ==, !=, <, <=, >, >= and <value> either an integer (number of groups) or a fraction within [0,1]",
+                    cond
+                );
+                log::error!("{}", &msg);
+                return Err(Error::new(ErrorKind::InvalidInput, msg));
+            }
+        }
+    }
+    if res.is_empty() {
+        let msg = "coverage query requires at least one condition, but none is given";
+        log::error!("{}", msg);
+        return Err(Error::new(ErrorKind::InvalidInput, msg));
+    }
+    Ok(res)
+}
+
+// called by a LineWriter with its state, the underlying writer, and a line without its line break
+type LineCallback<S, W> = fn(&mut S, &mut W, &[u8]) -> Result<(), Error>;
+
+// Writer that splits its input into lines and passes each of them to a callback writing to the
+// underlying writer; the table writers below differ only in their callbacks.
+struct LineWriter<W: Write, S> {
+    inner: W,
+    state: S,
+    line: Vec<u8>,
+    process_line: LineCallback<S, W>,
+}
+
+impl<W: Write, S> LineWriter<W, S> {
+    fn new(inner: W, state: S, process_line: LineCallback<S, W>) -> Self {
+        Self {
+            inner,
+            state,
+            line: Vec::new(),
+            process_line,
+        }
+    }
+
+    // processes a trailing line that is not terminated by a newline and returns the underlying
+    // writer along with the state
+    fn finish(mut self) -> Result<(W, S), Error> {
+        if !self.line.is_empty() {
+            (self.process_line)(&mut self.state, &mut self.inner, &self.line)?;
+            self.line.clear();
+        }
+        self.inner.flush()?;
+        Ok((self.inner, self.state))
+    }
+}
+
+impl<W: Write, S> Write for LineWriter<W, S> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        for chunk in buf.split_inclusive(|c| *c == b'\n') {
+            match chunk.strip_suffix(b"\n") {
+                Some(c) => {
+                    self.line.extend_from_slice(c);
+                    (self.process_line)(&mut self.state, &mut self.inner, &self.line)?;
+                    self.line.clear();
+                }
+                None => self.line.extend_from_slice(chunk),
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush()
+    }
+}
+
+struct TableProjection {
+    columns: Vec<String>,
+    conditions: Vec<RowCondition>,
+    // indices of reported columns and of the columns referred to by conditions
+    header: Option<(Vec<usize>, Vec<usize>)>,
+}
+
+impl TableProjection {
+    fn process_line<W: Write>(&mut self, out: &mut W, line: &[u8]) -> Result<(), Error> {
+        let line = str::from_utf8(line).map_err(|_| {
+            Error::new(ErrorKind::InvalidData, "table contains non-UTF8 characters")
+        })?;
+        if line.is_empty() || (self.header.is_none() && line.starts_with('#')) {
+            return writeln!(out, "{}", line);
+        }
+        let fields: Vec<&str> = line.split('\t').collect();
+        match &self.header {
+            None => {
+                let index_of = |c: &String| {
+                    fields.iter().position(|f| f == c).ok_or_else(|| {
+                        let msg = format!(
+                            "column '{}' not found in table header, available columns are: {}",
+                            c,
+                            fields.join(", ")
+                        );
+                        log::error!("{}", &msg);
+                        Error::new(ErrorKind::InvalidInput, msg)
+                    })
+                };
+                let columns = if self.columns.is_empty() {
+                    (0..fields.len()).collect()
+                } else {
+                    self.columns
+                        .iter()
+                        .map(index_of)
+                        .collect::<Result<Vec<usize>, Error>>()?
+                };
+                let conditions = self
+                    .conditions
+                    .iter()
+                    .map(|c| index_of(&c.column))
+                    .collect::<Result<Vec<usize>, Error>>()?;
+                writeln!(out, "{}", columns.iter().map(|i| fields[*i]).join("\t"))?;
+                self.header = Some((columns, conditions));
+            }
+            Some((columns, conditions)) => {
+                if self
+                    .conditions
+                    .iter()
+                    .zip(conditions)
+                    .all(|(c, i)| c.holds(fields.get(*i).unwrap_or(&"")))
+                {
+                    writeln!(
+                        out,
+                        "{}",
+                        columns
+                            .iter()
+                            .map(|i| *fields.get(*i).unwrap_or(&""))
+                            .join("\t")
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Writer that projects a tab-separated table onto a subset of its columns and drops rows that
+// do not satisfy the given conditions. Comment lines preceding the header are passed through.
+pub struct TableFilter<W: Write> {
+    lines: LineWriter<W, TableProjection>,
+    passthrough: bool,
+}
+
+impl<W: Write> TableFilter<W> {
+    pub fn new(inner: W, columns: &str, filter: &str) -> Result<Self, Error> {
+        let projection = TableProjection {
+            columns: columns
+                .split(',')
+                .map(|x| x.trim().to_string())
+                .filter(|x| !x.is_empty())
+                .collect(),
+            conditions: parse_row_filter(filter)?,
+            header: None,
+        };
+        Ok(Self {
+            passthrough: projection.columns.is_empty() && projection.conditions.is_empty(),
+            lines: LineWriter::new(inner, projection, |p, out, line| p.process_line(out, line)),
+        })
+    }
+
+    // processes a trailing line that is not terminated by a newline and returns the underlying
+    // writer
+    pub fn finish(self) -> Result<W, Error> {
+        Ok(self.lines.finish()?.0)
+    }
+}
+
+impl<W: Write> Write for TableFilter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        if self.passthrough {
+            return self.lines.inner.write(buf);
+        }
+        self.lines.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.lines.flush()
+    }
+}
+
+// Writer that converts a tab-separated table into comma-separated values, quoting fields where
+// necessary. Comment lines are passed through unchanged.
+pub struct CsvWriter<W: Write> {
+    lines: LineWriter<W, ()>,
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            lines: LineWriter::new(inner, (), |_, out, line| Self::process_line(out, line)),
+        }
+    }
+
+    fn process_line(out: &mut W, line: &[u8]) -> Result<(), Error> {
+        if line.first() == Some(&b'#') {
+            out.write_all(line)?;
+            return writeln!(out);
+        }
+        for (i, field) in line.split(|c| *c == b'\t').enumerate() {
+            if i > 0 {
+                out.write_all(b",")?;
+            }
+            if field
+                .iter()
+                .any(|c| *c == b',' || *c == b'"' || *c == b'\r')
+            {
+                out.write_all(b"\"")?;
+                for c in field {
+                    if *c == b'"' {
+                        out.write_all(b"\"")?;
+                    }
+                    out.write_all(&[*c])?;
+                }
+                out.write_all(b"\"")?;
+            } else {
+                out.write_all(field)?;
+            }
+        }
+        writeln!(out)
+    }
+
+    // processes a trailing line that is not terminated by a newline and returns the underlying
+    // writer
+    pub fn finish(self) -> Result<W, Error> {
+        Ok(self.lines.finish()?.0)
+    }
+}
+
+impl<W: Write> Write for CsvWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        self.lines.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.lines.flush()
+    }
+}
+
+// Writer that converts tab-separated tables into booktabs-formatted LaTeX tabulars, escaping
+// special characters. Tables are separated by blank lines, and their header spans all rows up to
+// the first one starting with a number (or just the first row if there is none). Comment lines
+// become LaTeX comments.
+pub struct LatexWriter<W: Write> {
+    // its state holds the rows of the table read so far
+    lines: LineWriter<W, Vec<Vec<String>>>,
+}
+
+impl<W: Write> LatexWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            lines: LineWriter::new(inner, Vec::new(), Self::process_line),
+        }
+    }
+
+    fn escape(field: &str) -> String {
+        let mut res = String::with_capacity(field.len());
+        for c in field.chars() {
+            match c {
+                '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                    res.push('\\');
+                    res.push(c);
+                }
+                '~' => res.push_str("\\textasciitilde{}"),
+                '^' => res.push_str("\\textasciicircum{}"),
+                '\\' => res.push_str("\\textbackslash{}"),
+                _ => res.push(c),
+            }
+        }
+        res
+    }
+
+    fn process_line(rows: &mut Vec<Vec<String>>, out: &mut W, line: &[u8]) -> Result<(), Error> {
+        let line = String::from_utf8_lossy(line)
+            .trim_end_matches('\r')
+            .to_string();
+        if let Some(comment) = line.strip_prefix('#') {
+            return writeln!(out, "%{}", comment);
+        }
+        if line.is_empty() {
+            return Self::write_tabular(rows, out);
+        }
+        rows.push(line.split('\t').map(|f| f.to_string()).collect());
+        Ok(())
+    }
+
+    fn write_tabular(rows: &mut Vec<Vec<String>>, out: &mut W) -> Result<(), Error> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let n = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let is_number = |f: &str| f.parse::<f64>().is_ok();
+        let header = match rows.iter().position(|r| is_number(&r[0])) {
+            Some(0) | None => 1,
+            Some(i) => i,
+        };
+        // numeric columns are right-aligned
+        let align: String = (0..n)
+            .map(|j| {
+                let mut body = rows[header..].iter().filter_map(|r| r.get(j));
+                if body.clone().next().is_some()
+                    && body.all(|f| f.is_empty() || f == "NaN" || is_number(f))
+                {
+                    'r'
+                } else {
+                    'l'
+                }
+            })
+            .collect();
+        writeln!(out, "\\begin{{tabular}}{{{}}}", align)?;
+        writeln!(out, "\\toprule")?;
+        for (i, row) in rows.iter().enumerate() {
+            if i == header {
+                writeln!(out, "\\midrule")?;
+            }
+            let mut fields: Vec<String> = row.iter().map(|f| Self::escape(f)).collect();
+            fields.resize(n, String::new());
+            writeln!(out, "{} \\\\", fields.join(" & "))?;
+        }
+        writeln!(out, "\\bottomrule")?;
+        writeln!(out, "\\end{{tabular}}")?;
+        rows.clear();
+        Ok(())
+    }
+
+    // processes a trailing line that is not terminated by a newline, closes the last table, and
+    // returns the underlying writer
+    pub fn finish(self) -> Result<W, Error> {
+        let (mut inner, mut rows) = self.lines.finish()?;
+        Self::write_tabular(&mut rows, &mut inner)?;
+        inner.flush()?;
+        Ok(inner)
+    }
+}
+
+impl<W: Write> Write for LatexWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        self.lines.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.lines.flush()
+    }
 }
 
 pub fn write_table<W: Write>(
@@ -1208,9 +2812,13 @@ pub fn write_ordered_table<W: Write>(
     Ok(())
 }
 
-pub fn write_hist_table<W: Write>(hists: &[Hist], out: &mut BufWriter<W>) -> Result<(), Error> {
+pub fn write_hist_table<W: Write>(
+    hists: &[HistResult],
+    edge_orientations: &[EdgeOrientationHist],
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
     log::info!("reporting hist table");
-    write_metadata_comments(out)?;
+    write_result_metadata(hists.iter().map(|h| &h.metadata), out)?;
 
     let mut header_cols = vec![vec![
         "panacus".to_string(),
@@ -1228,60 +2836,1005 @@ pub fn write_hist_table<W: Write>(hists: &[Hist], out: &mut BufWriter<W>) -> Res
             String::new(),
         ])
     }
-    write_table(&header_cols, &output_columns, out)
+    // columns of edge orientations are not labeled "hist", so that they are not read back as
+    // histograms of their own
+    for h in edge_orientations.iter() {
+        output_columns.push(h.coverage.iter().map(|x| *x as f64).collect());
+        header_cols.push(vec![
+            "orientation".to_string(),
+            h.orientation.clone(),
+            String::new(),
+            String::new(),
+        ])
+    }
+    write_table(&header_cols, &output_columns, out)
+}
+
+// appends the names of thresholds as an additional header row, unless none of them is named
+fn add_name_header(header_cols: &mut [Vec<String>], names: Vec<String>) {
+    if names.iter().skip(1).any(|n| !n.is_empty()) {
+        for (col, name) in header_cols.iter_mut().zip(names) {
+            col.push(name);
+        }
+    }
+}
+
+pub fn write_histgrowth_table<W: Write>(
+    hists: &[HistResult],
+    growths: &[GrowthResult],
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    write_result_metadata(
+        hists
+            .iter()
+            .map(|h| &h.metadata)
+            .chain(growths.iter().map(|g| &g.metadata)),
+        out,
+    )?;
+    if let Some(b) = growths.iter().find_map(|g| g.bootstrap.as_ref()) {
+        writeln!(
+            out,
+            "# growth percentiles are calculated from {} random orders of groups (seed {}), applying coverage thresholds a priori",
+            b.samples, b.seed
+        )?;
+    }
+    if growths.iter().any(|g| g.apriori) {
+        writeln!(
+            out,
+            "# coverage thresholds are applied a priori: only items whose coverage across all groups reaches the threshold are counted, independent of the subset of groups"
+        )?;
+    }
+    write_growth_summary(
+        growths
+            .iter()
+            .flat_map(|g| g.summary.iter().map(move |s| (g.count, s))),
+        out,
+    )?;
+
+    let mut header_cols = vec![vec![
+        "panacus".to_string(),
+        "count".to_string(),
+        "coverage".to_string(),
+        "quorum".to_string(),
+    ]];
+    // names of thresholds, reported in an additional header row if any is named
+    let mut names = vec!["name".to_string()];
+    let mut output_columns: Vec<Vec<f64>> = Vec::new();
+
+    for h in hists.iter() {
+        output_columns.push(h.coverage.iter().map(|x| *x as f64).collect());
+        header_cols.push(vec![
+            "hist".to_string(),
+            h.count.to_string(),
+            String::new(),
+            String::new(),
+        ]);
+        names.push(String::new());
+    }
+
+    for g in growths {
+        output_columns.extend(g.columns());
+        let m = g.coverage.len();
+        header_cols.extend(
+            std::iter::repeat("growth")
+                .take(m)
+                .zip(std::iter::repeat(g.count).take(m))
+                .zip(g.coverage.iter())
+                .zip(&g.quorum)
+                .map(|(((p, t), c), q)| {
+                    vec![p.to_string(), t.to_string(), c.get_string(), q.get_string()]
+                }),
+        );
+        names.extend((0..m).map(|i| g.name(i).cloned().unwrap_or_default()));
+        if let Some(b) = &g.bootstrap {
+            for (i, ((values, c), q)) in b.values.iter().zip(&g.coverage).zip(&g.quorum).enumerate()
+            {
+                for (p, col) in b.percentiles.iter().zip(values) {
+                    output_columns.push(
+                        std::iter::once(f64::NAN)
+                            .chain(col.iter().copied())
+                            .collect(),
+                    );
+                    header_cols.push(vec![
+                        format!("growth-p{}", p),
+                        g.count.to_string(),
+                        c.get_string(),
+                        q.get_string(),
+                    ]);
+                    names.push(g.name(i).cloned().unwrap_or_default());
+                }
+            }
+        }
+        if let Some(increments) = &g.increments {
+            for (i, ((values, c), q)) in increments
+                .iter()
+                .zip(&g.coverage)
+                .zip(&g.quorum)
+                .enumerate()
+            {
+                output_columns.push(
+                    std::iter::once(f64::NAN)
+                        .chain(values.iter().copied())
+                        .collect(),
+                );
+                header_cols.push(vec![
+                    "growth-increment".to_string(),
+                    g.count.to_string(),
+                    c.get_string(),
+                    q.get_string(),
+                ]);
+                names.push(g.name(i).cloned().unwrap_or_default());
+            }
+        }
+    }
+    add_name_header(&mut header_cols, names);
+    write_table(&header_cols, &output_columns, out)
+}
+
+pub fn write_growth_comparison_table<W: Write>(
+    comparison: &[GrowthComparison],
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    write_result_metadata(
+        comparison.iter().flat_map(|c| {
+            c.results
+                .hists
+                .iter()
+                .map(|h| &h.metadata)
+                .chain(c.results.growths.iter().map(|g| &g.metadata))
+        }),
+        out,
+    )?;
+    if comparison
+        .iter()
+        .any(|c| c.results.growths.iter().any(|g| g.apriori))
+    {
+        writeln!(
+            out,
+            "# coverage thresholds are applied a priori: only items whose coverage across all groups reaches the threshold are counted, independent of the subset of groups"
+        )?;
+    }
+    for c in comparison {
+        writeln!(out, "# file {}", c.file)?;
+        write_growth_summary(
+            c.results
+                .growths
+                .iter()
+                .flat_map(|g| g.summary.iter().map(move |s| (g.count, s))),
+            out,
+        )?;
+    }
+
+    let mut header_cols = vec![vec![
+        "panacus".to_string(),
+        "count".to_string(),
+        "coverage".to_string(),
+        "quorum".to_string(),
+        "file".to_string(),
+    ]];
+    let mut names = vec!["name".to_string()];
+    let mut output_columns: Vec<Vec<f64>> = Vec::new();
+    for c in comparison {
+        for h in c.results.hists.iter() {
+            output_columns.push(h.coverage.iter().map(|x| *x as f64).collect());
+            header_cols.push(vec![
+                "hist".to_string(),
+                h.count.to_string(),
+                String::new(),
+                String::new(),
+                c.file.clone(),
+            ]);
+            names.push(String::new());
+        }
+        for g in c.results.growths.iter() {
+            output_columns.extend(g.columns());
+            for (i, (cov, q)) in g.coverage.iter().zip(&g.quorum).enumerate() {
+                header_cols.push(vec![
+                    "growth".to_string(),
+                    g.count.to_string(),
+                    cov.get_string(),
+                    q.get_string(),
+                    c.file.clone(),
+                ]);
+                names.push(g.name(i).cloned().unwrap_or_default());
+            }
+        }
+    }
+    // histograms of different files may comprise different numbers of groups
+    let n = output_columns.iter().map(|c| c.len()).max().unwrap_or(0);
+    for col in output_columns.iter_mut() {
+        col.resize(n, f64::NAN);
+    }
+    add_name_header(&mut header_cols, names);
+    write_table(&header_cols, &output_columns, out)
+}
+
+pub fn write_graph_comparison_table<W: Write>(
+    comparison: &[GraphComparison],
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting graph comparison table");
+    write_metadata_comments(out)?;
+    for c in comparison {
+        writeln!(out, "# file {}", c.growth.file)?;
+        write_growth_summary(
+            c.growth
+                .results
+                .growths
+                .iter()
+                .flat_map(|g| g.summary.iter().map(move |s| (g.count, s))),
+            out,
+        )?;
+    }
+
+    let files: Vec<&str> = comparison.iter().map(|c| c.growth.file.as_str()).collect();
+    write!(out, "feature\tcategory\tcountable\t{}", files.join("\t"))?;
+    for f in &files[1..] {
+        write!(out, "\tdelta {}", f)?;
+    }
+    writeln!(out)?;
+
+    let na = |x: &Option<String>| x.clone().unwrap_or_else(|| "NA".to_string());
+    for (key, values) in compare_infos(&comparison.iter().map(|c| &c.info).collect::<Vec<_>>()) {
+        write!(out, "{}", key)?;
+        for v in &values {
+            write!(out, "\t{}", na(v))?;
+        }
+        for v in &values[1..] {
+            let delta = match (&values[0], v) {
+                (Some(a), Some(b)) => info_delta(a, b),
+                _ => None,
+            };
+            write!(out, "\t{}", na(&delta))?;
+        }
+        writeln!(out)?;
+    }
+
+    // growth curves, reported as additional rows of the form growth <m> <count> <value>...
+    for (i, g) in comparison[0].growth.results.growths.iter().enumerate() {
+        for (j, (cov, q)) in g.coverage.iter().zip(&g.quorum).enumerate() {
+            let n = comparison
+                .iter()
+                .map(|c| c.growth.results.growths[i].values[j].len())
+                .max()
+                .unwrap_or(0);
+            for m in 0..n {
+                let values: Vec<Option<f64>> = comparison
+                    .iter()
+                    .map(|c| c.growth.results.growths[i].values[j].get(m).copied())
+                    .collect();
+                write!(
+                    out,
+                    "growth\t{} groups (coverage {}, quorum {})\t{}",
+                    m + 1,
+                    cov.get_string(),
+                    q.get_string(),
+                    g.count
+                )?;
+                for v in &values {
+                    write!(out, "\t{}", na(&v.map(|v| v.floor().to_string())))?;
+                }
+                for v in &values[1..] {
+                    let delta = match (values[0], v) {
+                        (Some(a), Some(b)) => Some((b.floor() - a.floor()).to_string()),
+                        _ => None,
+                    };
+                    write!(out, "\t{}", na(&delta))?;
+                }
+                writeln!(out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn write_branching_table<W: Write>(
+    branching: &[[(u32, u32, u64); 2]],
+    per_node: bool,
+    graph_aux: &GraphAuxilliary,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting branching table");
+    write_metadata_comments(out)?;
+
+    if per_node {
+        let mut id2node: Vec<&[u8]> = vec![&[]; graph_aux.node_count + 1];
+        for (node, id) in graph_aux.node2id.iter() {
+            id2node[id.0 as usize] = &node[..];
+        }
+        writeln!(
+            out,
+            "node\torientation\tout_degree\tsupported_out_degree\ttraversals"
+        )?;
+        for (id, sides) in branching.iter().enumerate().skip(1) {
+            for (o, (d, s, t)) in ["+", "-"].iter().zip(sides) {
+                writeln!(
+                    out,
+                    "{}\t{}\t{}\t{}\t{}",
+                    str::from_utf8(id2node[id]).unwrap(),
+                    o,
+                    d,
+                    s,
+                    t
+                )?;
+            }
+        }
+    } else {
+        let max_degree = branching
+            .iter()
+            .flat_map(|x| x.iter().map(|y| y.0))
+            .max()
+            .unwrap_or(0) as usize;
+        let mut hist = vec![(0, 0); max_degree + 1];
+        for (d, s, _) in branching.iter().skip(1).flatten() {
+            hist[*d as usize].0 += 1;
+            hist[*s as usize].1 += 1;
+        }
+        writeln!(
+            out,
+            "# number of node sides (i.e., nodes in forward and in reverse orientation) by out-degree"
+        )?;
+        writeln!(out, "out_degree\tstructural\tsupported")?;
+        for (i, (d, s)) in hist.iter().enumerate() {
+            writeln!(out, "{}\t{}\t{}", i, d, s)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_strand_table<W: Write>(
+    strands: &NodeStrands,
+    per_node: bool,
+    graph_aux: &GraphAuxilliary,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting strand table");
+    write_metadata_comments(out)?;
+
+    if per_node {
+        let mut id2node: Vec<&[u8]> = vec![&[]; graph_aux.node_count + 1];
+        for (node, id) in graph_aux.node2id.iter() {
+            id2node[id.0 as usize] = &node[..];
+        }
+        writeln!(out, "node\tforward\treverse\tboth")?;
+        for id in (1..id2node.len()).filter(|&i| strands.is_polymorphic(i)) {
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}",
+                str::from_utf8(id2node[id]).unwrap(),
+                strands.forward[id],
+                strands.reverse[id],
+                strands.both[id]
+            )?;
+        }
+    } else {
+        let [forward, reverse, both] = strands.histograms();
+        writeln!(out, "groups\tforward\treverse\tboth")?;
+        for i in 0..forward.len() {
+            writeln!(out, "{}\t{}\t{}\t{}", i, forward[i], reverse[i], both[i])?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_bubbles_table<W: Write>(
+    bubbles: &[Bubble],
+    summary: bool,
+    graph_aux: &GraphAuxilliary,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting bubbles table");
+    write_metadata_comments(out)?;
+
+    if summary {
+        let simple = bubbles.iter().filter(|b| b.simple).count();
+        writeln!(out, "feature\tcategory\tcountable\tvalue")?;
+        writeln!(out, "bubble\ttotal\tsimple\t{}", simple)?;
+        writeln!(out, "bubble\ttotal\tsuper\t{}", bubbles.len() - simple)?;
+        for (feature, values) in [
+            (
+                "nodes",
+                bubbles.iter().map(|b| b.nodes as u64).collect::<Vec<_>>(),
+            ),
+            ("alleles", bubbles.iter().map(|b| b.alleles).collect()),
+        ] {
+            let mut counts: BTreeMap<u64, usize> = BTreeMap::new();
+            for v in values {
+                *counts.entry(v).or_insert(0) += 1;
+            }
+            for (v, n) in counts {
+                writeln!(out, "{}\t{}\tbubble\t{}", feature, v, n)?;
+            }
+        }
+    } else {
+        let mut id2node: Vec<&[u8]> = vec![&[]; graph_aux.node_count + 1];
+        for (node, id) in graph_aux.node2id.iter() {
+            id2node[id.0 as usize] = &node[..];
+        }
+        writeln!(out, "source\tsink\ttype\tnodes\tbp\talleles")?;
+        for b in bubbles {
+            writeln!(
+                out,
+                "{}{}\t{}{}\t{}\t{}\t{}\t{}",
+                b.source.1,
+                str::from_utf8(id2node[b.source.0 .0 as usize]).unwrap(),
+                b.sink.1,
+                str::from_utf8(id2node[b.sink.0 .0 as usize]).unwrap(),
+                if b.simple { "simple" } else { "super" },
+                b.nodes,
+                b.bp,
+                b.alleles
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn metadata_comments() -> Vec<String> {
+    let version = option_env!("GIT_HASH").unwrap_or(env!("CARGO_PKG_VERSION"));
+    vec![
+        std::env::args().collect::<Vec<String>>().join(" "),
+        format!("version {}", version),
+        // all analyses are deterministic, hence the thread count is all that is needed (besides
+        // command and version) to reproduce a run, including its running time
+        format!("threads {}", rayon::current_num_threads()),
+    ]
+}
+
+pub fn parse_path_nodes<R: Read>(
+    data: &mut BufReader<R>,
+    path_name: &str,
+    graph_aux: &GraphAuxilliary,
+) -> Result<Vec<(ItemId, Orientation)>, Error> {
+    let mut buf = vec![];
+    let mut line = 0;
+    while data.read_until(b'\n', &mut buf)? > 0 {
+        line += 1;
+        if (buf[0] == b'P' || buf[0] == b'W') && check_parsed_record(&buf, line)? {
+            let (path_seg, buf_path_seg) = match buf[0] {
+                b'P' => parse_path_identifier(&buf, graph_aux.pansn_delimiter),
+                _ => parse_walk_identifier(&buf, graph_aux.pansn_delimiter),
+            };
+            if path_seg.id() == path_name || path_seg.to_string() == path_name {
+                let path = match buf[0] {
+                    b'P' => parse_path_seq_to_item_vec(buf_path_seg, graph_aux),
+                    _ => parse_walk_seq_to_item_vec(buf_path_seg, graph_aux),
+                }
+                .map_err(|e| e.in_record(line, &path_seg))?;
+                return Ok(path);
+            }
+        }
+        buf.clear();
+    }
+    let msg = format!("path {} not found in graph", path_name);
+    log::error!("{}", &msg);
+    Err(Error::new(ErrorKind::InvalidInput, msg))
+}
+
+// marks the nodes traversed by the read alignments of a GAF file that have at least the given
+// mapping quality; returns the marks, indexed by node ID, and the number of alignments used
+pub fn parse_gaf_nodes<R: Read>(
+    data: &mut BufReader<R>,
+    graph_aux: &GraphAuxilliary,
+    min_mapq: u8,
+    best_effort: bool,
+) -> Result<(Vec<bool>, usize), Error> {
+    let mut covered = vec![false; graph_aux.node_count + 1];
+    let mut buf = vec![];
+    let mut line = 0;
+    let mut used = 0;
+    let mut skipped = 0;
+    while data.read_until(b'\n', &mut buf)? > 0 {
+        line += 1;
+        match parse_gaf_record(&buf, graph_aux, min_mapq) {
+            Ok(Some(nodes)) => {
+                for (id, _) in nodes {
+                    covered[id.0 as usize] = true;
+                }
+                used += 1;
+            }
+            Ok(None) => (),
+            Err(e) => {
+                let msg = format!("error in line {} of GAF: {}", line, e);
+                if !best_effort {
+                    log::error!("{}", &msg);
+                    return Err(Error::new(ErrorKind::InvalidData, msg));
+                }
+                log::warn!("{}, skipping alignment", msg);
+                skipped += 1;
+            }
+        }
+        buf.clear();
+    }
+    if skipped > 0 {
+        log::warn!("skipped {} malformed alignments", skipped);
+    }
+    Ok((covered, used))
+}
+
+// returns the nodes traversed by a GAF record, or None if the read is unmapped or its mapping
+// quality is below the given threshold
+fn parse_gaf_record(
+    buf: &[u8],
+    graph_aux: &GraphAuxilliary,
+    min_mapq: u8,
+) -> Result<Option<Vec<(ItemId, Orientation)>>, String> {
+    let end = buf
+        .iter()
+        .position(|x| x == &b'\n' || x == &b'\r')
+        .unwrap_or(buf.len());
+    if end == 0 {
+        return Ok(None);
+    }
+    let fields: Vec<&[u8]> = buf[..end].split(|x| x == &b'\t').collect();
+    if fields.len() < 12 {
+        return Err(format!(
+            "expected at least 12 columns, but got {}",
+            fields.len()
+        ));
+    }
+    let path = fields[5];
+    if path == b"*" {
+        return Ok(None);
+    }
+    let mapq = str::from_utf8(fields[11])
+        .ok()
+        .and_then(|x| x.parse::<u8>().ok())
+        .ok_or_else(|| {
+            format!(
+                "invalid mapping quality '{}'",
+                String::from_utf8_lossy(fields[11])
+            )
+        })?;
+    if mapq < min_mapq {
+        return Ok(None);
+    }
+    match path.first() {
+        Some(b'>') | Some(b'<') => parse_walk_seq_to_item_vec(path, graph_aux)
+            .map(Some)
+            .map_err(|e| e.to_string()),
+        // a path without orientations is the name of a single segment, unless it is a stable
+        // sequence identifier, which cannot be resolved without a reference
+        _ => graph_aux
+            .node2id
+            .get(path)
+            .map(|id| Some(vec![(*id, Orientation::Forward)]))
+            .ok_or_else(|| {
+                format!(
+                    "unknown node {} (stable sequence identifiers are not supported)",
+                    String::from_utf8_lossy(path)
+                )
+            }),
+    }
+}
+
+// streams the S-lines of the GFA and reports the nodes whose coverage satisfies all given
+// conditions
+#[allow(clippy::too_many_arguments)]
+pub fn write_query_nodes<R: Read, W: Write>(
+    data: &mut BufReader<R>,
+    abacus: &AbacusByTotal,
+    conditions: &[CoverageCondition],
+    lengths: bool,
+    sequences: bool,
+    annotation: Option<&NodeAnnotation>,
+    graph_aux: &GraphAuxilliary,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting nodes matching coverage query");
+    write_metadata_comments(out)?;
+    write!(out, "node")?;
+    if annotation.is_some() {
+        write!(out, "\tannotation")?;
+    }
+    if lengths {
+        write!(out, "\tlength")?;
+    }
+    if sequences {
+        write!(out, "\tsequence")?;
+    }
+    writeln!(out)?;
+
+    let n_groups = abacus.groups.len();
+    let mut buf = vec![];
+    let mut c = 0;
+    let mut line = 0;
+    while data.read_until(b'\n', &mut buf)? > 0 {
+        line += 1;
+        if buf[0] == b'S' && check_parsed_record(&buf, line)? {
+            let mut fields = buf.split(|x| *x == b'\t' || *x == b'\n' || *x == b'\r');
+            let name = fields.nth(1).unwrap();
+            if let Some(id) = graph_aux.node2id.get(name) {
+                let coverage = abacus.countable[id.0 as usize] as usize;
+                if conditions.iter().all(|x| x.holds(coverage, n_groups)) {
+                    out.write_all(name)?;
+                    if let Some(annotation) = annotation {
+                        write!(
+                            out,
+                            "\t{}",
+                            annotation.get(name).map(|x| &x[..]).unwrap_or(".")
+                        )?;
+                    }
+                    if lengths {
+                        write!(out, "\t{}", graph_aux.node_lens[id.0 as usize])?;
+                    }
+                    if sequences {
+                        write!(out, "\t")?;
+                        out.write_all(fields.next().unwrap_or(b"*"))?;
+                    }
+                    writeln!(out)?;
+                    c += 1;
+                }
+            }
+        }
+        buf.clear();
+    }
+    log::info!("reported {} nodes", c);
+    Ok(())
+}
+
+pub fn write_pairwise_table<W: Write>(
+    abaci: &[AbacusByGroup],
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting pairwise intersection table");
+    write_metadata_comments(out)?;
+    writeln!(out, "group_a\tgroup_b\tcountable\tintersection\tunion")?;
+    for abacus in abaci {
+        let intersections = abacus.pairwise_intersections();
+        for (i, a) in abacus.groups.iter().enumerate() {
+            for (j, b) in abacus.groups.iter().enumerate() {
+                let intersection = intersections[i][j];
+                writeln!(
+                    out,
+                    "{}\t{}\t{}\t{}\t{}",
+                    a,
+                    b,
+                    abacus.count,
+                    intersection,
+                    intersections[i][i] + intersections[j][j] - intersection
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn write_pairwise_matrix<W: Write>(
+    abaci: &[AbacusByGroup],
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting pairwise intersection matrix");
+    write_metadata_comments(out)?;
+    for abacus in abaci {
+        writeln!(out, "# shared {}s of groups", abacus.count)?;
+        writeln!(out, "group\t{}", abacus.groups.join("\t"))?;
+        for (group, row) in abacus.groups.iter().zip(abacus.pairwise_intersections()) {
+            writeln!(out, "{}\t{}", group, row.iter().join("\t"))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_private_table<W: Write>(
+    abaci: &[AbacusByGroup],
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting private countables table");
+    write_metadata_comments(out)?;
+    writeln!(out, "group\tcountable\tprivate\ttotal")?;
+    for abacus in abaci {
+        for (group, (private, total)) in abacus.groups.iter().zip(abacus.private_countables()) {
+            writeln!(out, "{}\t{}\t{}\t{}", group, abacus.count, private, total)?;
+        }
+    }
+    Ok(())
+}
+
+// regions along the path made up of consecutive nodes private to the group of the path
+pub fn write_private_regions<W: Write>(
+    abacus: &AbacusByGroup,
+    path: &[(ItemId, Orientation)],
+    path_name: &str,
+    graph_aux: &GraphAuxilliary,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting private regions along {}", path_name);
+    write_metadata_comments(out)?;
+
+    // (start, end, number of nodes)
+    let mut regions: Vec<(usize, usize, usize)> = Vec::new();
+    let mut pos = 0;
+    for (id, _) in path {
+        let len = graph_aux.node_len(id) as usize;
+        if abacus.is_private(id.0 as usize) {
+            match regions.last_mut() {
+                Some(r) if r.1 == pos => {
+                    r.1 += len;
+                    r.2 += 1;
+                }
+                _ => regions.push((pos, pos + len, 1)),
+            }
+        }
+        pos += len;
+    }
+
+    writeln!(
+        out,
+        "# {} private regions covering {} of {} bp",
+        regions.len(),
+        regions.iter().map(|r| r.1 - r.0).sum::<usize>(),
+        pos
+    )?;
+    writeln!(out, "#path\tstart\tend\tnodes")?;
+    for (start, end, n) in regions {
+        writeln!(out, "{}\t{}\t{}\t{}", path_name, start, end, n)?;
+    }
+    Ok(())
+}
+
+pub fn write_similarity_table<W: Write>(
+    abacus: &AbacusByGroup,
+    metric: SimilarityMetric,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting {} similarity matrix", metric);
+    write_metadata_comments(out)?;
+    writeln!(out, "# {} similarity of groups by {}", metric, abacus.count)?;
+    writeln!(out, "group\t{}", abacus.groups.join("\t"))?;
+    for (group, row) in abacus.groups.iter().zip(abacus.similarities(metric)) {
+        write!(out, "{}", group)?;
+        for x in row {
+            write!(out, "\t{:.4}", x)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+// Newick does not support comments, so no metadata is written
+pub fn write_similarity_tree<W: Write>(
+    abacus: &AbacusByGroup,
+    metric: SimilarityMetric,
+    method: ClusterMethod,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting {} tree of {} distances", method, metric);
+    writeln!(
+        out,
+        "{}",
+        abacus.similarity_tree(metric, method).to_newick()
+    )?;
+    Ok(())
+}
+
+pub fn write_delta_table<W: Write>(
+    abacus_a: &AbacusByTotal,
+    abacus_b: &AbacusByTotal,
+    graph_aux: &GraphAuxilliary,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting coverage delta table");
+    write_metadata_comments(out)?;
+
+    let mut id2node: Vec<&[u8]> = vec![&[]; graph_aux.node_count + 1];
+    for (node, id) in graph_aux.node2id.iter() {
+        id2node[id.0 as usize] = &node[..];
+    }
+    let delta = abacus_a.coverage_delta(abacus_b);
+    writeln!(
+        out,
+        "# delta is the difference between the fractions of groups covering each node in subset a ({} groups) and b ({} groups)",
+        abacus_a.groups.len(),
+        abacus_b.groups.len()
+    )?;
+    writeln!(out, "node\tcoverage_a\tcoverage_b\tdelta")?;
+    for (id, d) in delta.iter().enumerate().skip(1) {
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{:.4}",
+            str::from_utf8(id2node[id]).unwrap(),
+            abacus_a.countable[id],
+            abacus_b.countable[id],
+            d
+        )?;
+    }
+    Ok(())
 }
 
-pub fn write_histgrowth_table<W: Write>(
-    hists: &[Hist],
-    growths: &Vec<(CountType, Vec<Vec<f64>>)>,
-    hist_aux: &HistAuxilliary,
+// reports maximal stretches of consecutive nodes along the reference path whose coverage
+// delta is at least min_delta in absolute value and of the same sign
+pub fn write_delta_regions<W: Write>(
+    delta: &[f64],
+    path: &[(ItemId, Orientation)],
+    path_name: &str,
+    min_delta: f64,
+    graph_aux: &GraphAuxilliary,
     out: &mut BufWriter<W>,
 ) -> Result<(), Error> {
+    log::info!("reporting differential regions along {}", path_name);
     write_metadata_comments(out)?;
 
-    let mut header_cols = vec![vec![
-        "panacus".to_string(),
-        "count".to_string(),
-        "coverage".to_string(),
-        "quorum".to_string(),
-    ]];
-    let mut output_columns: Vec<Vec<f64>> = Vec::new();
+    // (start, end, number of nodes, sum of deltas)
+    let mut regions: Vec<(usize, usize, usize, f64)> = Vec::new();
+    let mut pos = 0;
+    let mut last_sign = 0;
+    for (id, _) in path {
+        let len = graph_aux.node_len(id) as usize;
+        let d = delta[id.0 as usize];
+        // nodes without any difference form regions of their own rather than joining gains
+        // (as f64::signum(0.0) == 1.0 would suggest) or losses
+        let sign = if d > 0.0 {
+            1
+        } else if d < 0.0 {
+            -1
+        } else {
+            0
+        };
+        if d.abs() >= min_delta {
+            match regions.last_mut() {
+                Some(r) if r.1 == pos && sign == last_sign => {
+                    r.1 += len;
+                    r.2 += 1;
+                    r.3 += d;
+                }
+                _ => regions.push((pos, pos + len, 1, d)),
+            }
+            last_sign = sign;
+        }
+        pos += len;
+    }
 
-    for h in hists.iter() {
-        output_columns.push(h.coverage.iter().map(|x| *x as f64).collect());
-        header_cols.push(vec![
-            "hist".to_string(),
-            h.count.to_string(),
-            String::new(),
-            String::new(),
-        ])
+    writeln!(
+        out,
+        "# {} differential regions covering {} of {} bp",
+        regions.len(),
+        regions.iter().map(|r| r.1 - r.0).sum::<usize>(),
+        pos
+    )?;
+    writeln!(out, "#path\tstart\tend\tnodes\tmean_delta")?;
+    for (start, end, n, sum) in regions {
+        writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{:.4}",
+            path_name,
+            start,
+            end,
+            n,
+            sum / n as f64
+        )?;
     }
+    Ok(())
+}
 
-    for (count, g) in growths {
-        output_columns.extend(g.clone());
-        let m = hist_aux.coverage.len();
-        header_cols.extend(
-            std::iter::repeat("growth")
-                .take(m)
-                .zip(std::iter::repeat(count).take(m))
-                .zip(hist_aux.coverage.iter())
-                .zip(&hist_aux.quorum)
-                .map(|(((p, t), c), q)| {
-                    vec![p.to_string(), t.to_string(), c.get_string(), q.get_string()]
-                }),
-        );
+// long-format table of coverage histogram as well as pangenome and core growth in each window
+pub fn write_windows_table<W: Write>(
+    hists: &[Hist],
+    windows: &[(usize, usize)],
+    path_name: &str,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!(
+        "reporting histograms and growth of windows along {}",
+        path_name
+    );
+    write_metadata_comments(out)?;
+    let count = hists.first().map(|h| h.count).unwrap_or(CountType::Node);
+    writeln!(
+        out,
+        "# {} windows along {}; growth counts {}s covered by any and core by all groups",
+        windows.len(),
+        path_name,
+        count
+    )?;
+    writeln!(out, "path\tstart\tend\tcoverage\thist\tgrowth\tcore")?;
+    for (h, (start, end)) in hists.iter().zip(windows) {
+        let growth = h.calc_growth(&Threshold::Absolute(1), &Threshold::Relative(0.0));
+        let core = h.calc_growth(&Threshold::Absolute(1), &Threshold::Relative(1.0));
+        for (k, x) in h.coverage.iter().enumerate() {
+            write!(out, "{}\t{}\t{}\t{}\t{}", path_name, start, end, k, x)?;
+            if k == 0 {
+                writeln!(out, "\tNaN\tNaN")?;
+            } else {
+                writeln!(out, "\t{}\t{}", growth[k - 1], core[k - 1])?;
+            }
+        }
     }
-    write_table(&header_cols, &output_columns, out)
+    Ok(())
 }
 
 fn write_metadata_comments<W: Write>(out: &mut BufWriter<W>) -> Result<(), Error> {
+    for c in metadata_comments() {
+        writeln!(out, "# {}", c)?;
+    }
+    Ok(())
+}
+
+// writes the metadata of all given results, omitting lines that are shared among them
+fn write_result_metadata<'a, W: Write>(
+    metadata: impl Iterator<Item = &'a Vec<String>>,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    let mut written: Vec<&String> = Vec::new();
+    for c in metadata.flatten() {
+        if !written.contains(&c) {
+            writeln!(out, "# {}", c)?;
+            written.push(c);
+        }
+    }
+    Ok(())
+}
+
+fn write_growth_summary<'a, W: Write>(
+    summary: impl Iterator<Item = (CountType, &'a GrowthSummary)>,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    let mut summary = summary.peekable();
+    if summary.peek().is_none() {
+        return Ok(());
+    }
+    writeln!(out, "# growth summary")?;
     writeln!(
         out,
-        "# {}",
-        std::env::args().collect::<Vec<String>>().join(" ")
+        "# count\tcoverage\tquorum\tsaturation\tk\talpha\tgenomes_to_saturation\tsaturation_point"
     )?;
-    let version = option_env!("GIT_HASH").unwrap_or(env!("CARGO_PKG_VERSION"));
-    writeln!(out, "# version {}", version)
+    let na = |x: Option<String>| x.unwrap_or_else(|| "NA".to_string());
+    for (count, s) in summary {
+        writeln!(
+            out,
+            "# {}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            count,
+            s.coverage.get_string(),
+            s.quorum.get_string(),
+            s.saturation,
+            na(s.k.map(|k| format!("{:.4}", k))),
+            na(s.alpha.map(|a| format!("{:.4}", a))),
+            na(s.genomes_to_saturation.map(|n| n.to_string())),
+            na(s.saturation_point.map(|n| n.to_string())),
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_json<W: Write, T: Serialize>(value: &T, out: &mut BufWriter<W>) -> Result<(), Error> {
+    serde_json::to_writer_pretty(&mut *out, value)?;
+    writeln!(out)
+}
+
+pub fn write_badge(badge: &Badge, badge_file: &str) -> Result<(), Error> {
+    log::info!("writing summary badge to {}", badge_file);
+    let f = std::fs::File::create(badge_file).map_err(|e| {
+        let msg = format!("unable to create badge file {}: {}", badge_file, e);
+        log::error!("{}", &msg);
+        Error::new(e.kind(), msg)
+    })?;
+    let mut out = BufWriter::new(f);
+    if badge_file.ends_with(".svg") {
+        write!(out, "{}", badge_svg(badge))?;
+    } else {
+        write_json(badge, &mut out)?;
+    }
+    out.flush()
+}
+
+// numbers of P- and W-lines of the GFA
+pub fn count_path_walk_records<R: Read>(data: &mut BufReader<R>) -> Result<(usize, usize), Error> {
+    let mut path_records = 0;
+    let mut walk_records = 0;
+    let mut buf = vec![];
+    let mut line = 0;
+    while data.read_until(b'\n', &mut buf)? > 0 {
+        line += 1;
+        if check_parsed_record(&buf, line)? {
+            match buf[0] {
+                b'P' => path_records += 1,
+                b'W' => walk_records += 1,
+                _ => (),
+            }
+        }
+        buf.clear();
+    }
+    Ok((path_records, walk_records))
 }
 
 pub fn write_info<W: Write>(info: Info, out: &mut BufWriter<W>) -> Result<(), Error> {
@@ -1293,29 +3846,23 @@ pub fn write_info<W: Write>(info: Info, out: &mut BufWriter<W>) -> Result<(), Er
 pub fn write_ordered_histgrowth_table<W: Write>(
     abacus_group: &AbacusByGroup,
     hist_aux: &HistAuxilliary,
+    anchors: Option<usize>,
     out: &mut BufWriter<W>,
 ) -> Result<(), Error> {
     log::info!("reporting ordered-growth table");
     write_metadata_comments(out)?;
+    if let Some(k) = anchors {
+        writeln!(
+            out,
+            "# growth beyond the first {} groups is averaged over all permutations of the remaining groups",
+            usize::min(k, abacus_group.groups.len())
+        )?;
+    }
 
-    let mut output_columns: Vec<Vec<f64>> = hist_aux
-        .coverage
-        .par_iter()
-        .zip(&hist_aux.quorum)
-        .map(|(c, q)| {
-            log::info!(
-                "calculating ordered growth for coverage >= {} and quorum >= {}",
-                &c,
-                &q
-            );
-            abacus_group.calc_growth(c, q)
-        })
-        .collect();
+    let output_columns = abacus_group.calc_all_growths(hist_aux, anchors);
+    let summary = GrowthSummary::from_columns(&output_columns, hist_aux);
+    write_growth_summary(summary.iter().map(|s| (abacus_group.count, s)), out)?;
 
-    // insert empty row for 0 element
-    for c in &mut output_columns {
-        c.insert(0, f64::NAN);
-    }
     let m = hist_aux.coverage.len();
     let mut header_cols = vec![vec![
         "panacus".to_string(),
@@ -1334,43 +3881,37 @@ pub fn write_ordered_histgrowth_table<W: Write>(
             })
             .collect::<Vec<Vec<String>>>(),
     );
+    add_name_header(
+        &mut header_cols,
+        std::iter::once("name".to_string())
+            .chain(hist_aux.names.iter().map(|n| n.clone().unwrap_or_default()))
+            .collect(),
+    );
     write_ordered_table(&header_cols, &output_columns, &abacus_group.groups, out)
 }
 
 pub fn write_ordered_histgrowth_html<W: Write>(
     abacus_group: &AbacusByGroup,
     hist_aux: &HistAuxilliary,
+    anchors: Option<usize>,
     gfa_file: &str,
-    count: CountType,
     info: Option<Info>,
+    bins: &Binning,
     out: &mut BufWriter<W>,
 ) -> Result<(), Error> {
-    let mut growths: Vec<Vec<f64>> = hist_aux
-        .coverage
-        .par_iter()
-        .zip(&hist_aux.quorum)
-        .map(|(c, q)| {
-            log::info!(
-                "calculating ordered growth for coverage >= {} and quorum >= {}",
-                &c,
-                &q
-            );
-            abacus_group.calc_growth(c, q)
-        })
-        .collect();
-    // insert empty row for 0 element
-    for c in &mut growths {
-        c.insert(0, f64::NAN);
-    }
+    let growths = abacus_group.calc_all_growths(hist_aux, anchors);
     log::info!("reporting (hist-)growth table");
 
     write_histgrowth_html(
         &None,
-        &[(count, growths)],
+        &[(abacus_group.count, growths)],
         hist_aux,
         Path::new(gfa_file).file_name().unwrap().to_str().unwrap(),
-        Some(&abacus_group.groups),
+        Some(abacus_group),
         info,
+        bins,
+        &[],
+        &[],
         out,
     )
 }
@@ -1398,14 +3939,103 @@ mod tests {
             edge_count: 0,
             degree: Some(Vec::new()),
             //extremities: Some(Vec::new())
+            pansn_delimiter: '#',
         }
     }
 
+    #[test]
+    fn test_write_pairwise() {
+        let gfa_file = "test/cdbg.gfa";
+        let graph_aux = GraphAuxilliary::from_gfa(gfa_file, CountType::Node, '#').unwrap();
+        let abacus_aux =
+            AbacusAuxilliary::from_options(&AbacusOptions::default(), &graph_aux).unwrap();
+        let mut data = bufreader_from_compressed_gfa(gfa_file).unwrap();
+        let abacus =
+            AbacusByGroup::from_gfa(&mut data, &abacus_aux, &graph_aux, CountType::Node, false)
+                .unwrap();
+        let abaci = [abacus];
+
+        // a#1#h1 (nodes 1, 2, 3) and d#1#h1 (nodes 1, 4, 5) share node 1 only
+        let mut out = BufWriter::new(Vec::new());
+        write_pairwise_table(&abaci, &mut out).unwrap();
+        let out = String::from_utf8(out.into_inner().unwrap()).unwrap();
+        let rows: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(rows.len(), 1 + 6 * 6);
+        assert!(rows.contains(&"a#1#h1\td#1#h1\tnode\t1\t5"));
+        assert!(rows.contains(&"a#1#h1\tc#1#h2\tnode\t2\t3"));
+        assert!(rows.contains(&"d#1#h1\td#1#h1\tnode\t3\t3"));
+
+        let mut out = BufWriter::new(Vec::new());
+        write_pairwise_matrix(&abaci, &mut out).unwrap();
+        let out = String::from_utf8(out.into_inner().unwrap()).unwrap();
+        let rows: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            rows,
+            vec![
+                "group\ta#1#h1\tb#1#h1\tc#1#h1\tc#1#h2\tc#2#h1\td#1#h1",
+                "a#1#h1\t3\t3\t3\t2\t2\t1",
+                "b#1#h1\t3\t3\t3\t2\t2\t1",
+                "c#1#h1\t3\t3\t3\t2\t2\t1",
+                "c#1#h2\t2\t2\t2\t2\t1\t1",
+                "c#2#h1\t2\t2\t2\t1\t3\t2",
+                "d#1#h1\t1\t1\t1\t1\t2\t3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_write_delta_regions() {
+        let graph_aux = GraphAuxilliary::from_gfa("test/cdbg.gfa", CountType::Node, '#').unwrap();
+        let path: Vec<(ItemId, Orientation)> =
+            (1..6).map(|i| (ItemId(i), Orientation::Forward)).collect();
+        let delta = vec![0.0, 0.5, 0.0, -0.0, -0.5, 0.25];
+        let mut out = BufWriter::new(Vec::new());
+        write_delta_regions(&delta, &path, "a#1#h1", 0.0, &graph_aux, &mut out).unwrap();
+        let out = String::from_utf8(out.into_inner().unwrap()).unwrap();
+        let rows: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+        // zero deltas join neither the preceding gain nor the following loss
+        assert_eq!(
+            rows,
+            vec![
+                "a#1#h1\t0\t5\t1\t0.5000",
+                "a#1#h1\t5\t17\t2\t0.0000",
+                "a#1#h1\t17\t24\t1\t-0.5000",
+                "a#1#h1\t24\t29\t1\t0.2500",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_gaf_nodes() {
+        let graph_aux = mock_graph_auxilliary();
+        let gaf = b"r1\t9\t0\t9\t+\t>node1<node2\t9\t0\t9\t9\t9\t60\n\
+                    r2\t9\t0\t9\t+\t*\t*\t*\t*\t0\t0\t0\n\
+                    r3\t9\t0\t9\t+\tnode3\t9\t0\t9\t9\t9\t5";
+        let (covered, n) =
+            parse_gaf_nodes(&mut BufReader::new(&gaf[..]), &graph_aux, 0, false).unwrap();
+        assert_eq!(covered, vec![false, true, true, true]);
+        assert_eq!(n, 2);
+
+        // alignments of low mapping quality are ignored
+        let (covered, n) =
+            parse_gaf_nodes(&mut BufReader::new(&gaf[..]), &graph_aux, 10, false).unwrap();
+        assert_eq!(covered, vec![false, true, true, false]);
+        assert_eq!(n, 1);
+
+        // unknown nodes are reported, unless malformed alignments are skipped
+        let gaf = b"r1\t9\t0\t9\t+\t>node4\t9\t0\t9\t9\t9\t60\n";
+        assert!(parse_gaf_nodes(&mut BufReader::new(&gaf[..]), &graph_aux, 0, false).is_err());
+        let (covered, n) =
+            parse_gaf_nodes(&mut BufReader::new(&gaf[..]), &graph_aux, 0, true).unwrap();
+        assert_eq!(covered, vec![false; 4]);
+        assert_eq!(n, 0);
+    }
+
     // Test parse_walk_identifier function
     #[test]
     fn test_parse_walk_identifier() {
         let data = b"W\tG01\t0\tU00096.3\t3\t4641652\t>3>4>5>7>8>";
-        let (path_segment, data) = parse_walk_identifier(data);
+        let (path_segment, data) = parse_walk_identifier(data, '#');
         dbg!(&path_segment);
 
         assert_eq!(path_segment.sample, "G01".to_string());
@@ -1420,14 +4050,14 @@ mod tests {
     #[should_panic(expected = "unwrap")]
     fn test_parse_walk_identifier_invalid_utf8() {
         let data = b"W\tG01\t0\tU00096.3\t3\t>3>4>5>7>8>";
-        parse_walk_identifier(data);
+        parse_walk_identifier(data, '#');
     }
 
     // Test parse_path_identifier function
     #[test]
     fn test_parse_path_identifier() {
         let data = b"P\tGCF_000005845.2_ASM584v2_genomic.fna#0#contig1\t1+,2+,3+,4+\t*";
-        let (path_segment, rest) = parse_path_identifier(data);
+        let (path_segment, rest) = parse_path_identifier(data, '#');
 
         assert_eq!(
             path_segment.to_string(),
@@ -1454,12 +4084,66 @@ mod tests {
         let data = b"node1+,node2-\t*";
         let graph_aux = mock_graph_auxilliary();
 
-        let result = parse_path_seq_to_item_vec(data, &graph_aux);
+        let result = parse_path_seq_to_item_vec(data, &graph_aux).unwrap();
         assert_eq!(result.len(), 2);
         assert_eq!(result[0], (ItemId(1), Orientation::Forward));
         assert_eq!(result[1], (ItemId(2), Orientation::Backward));
     }
 
+    #[test]
+    fn test_parse_walk_seq_to_item_vec() {
+        let graph_aux = mock_graph_auxilliary();
+        let result = parse_walk_seq_to_item_vec(b">node1<node2>node1\n", &graph_aux).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                (ItemId(1), Orientation::Forward),
+                (ItemId(2), Orientation::Backward),
+                (ItemId(1), Orientation::Forward)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_seq_to_item_vec_malformed() {
+        let graph_aux = mock_graph_auxilliary();
+        assert!(matches!(
+            parse_path_seq_to_item_vec(b"node1+,node4-\n", &graph_aux),
+            Err(GraphParseError::UnknownNode(node)) if node == "node4"
+        ));
+        assert!(matches!(
+            parse_path_seq_to_item_vec(b"node1+,node2\n", &graph_aux),
+            Err(GraphParseError::UnknownOrientation(_))
+        ));
+        assert!(matches!(
+            parse_walk_seq_to_item_vec(b">node1<node4\n", &graph_aux),
+            Err(GraphParseError::UnknownNode(node)) if node == "node4"
+        ));
+        // a path sequence cut off at the end of the file
+        assert!(matches!(
+            parse_path_seq_to_item_vec(b"node1+,no", &graph_aux),
+            Err(GraphParseError::UnterminatedPath)
+        ));
+        let mut item_table = ItemTable::new(1);
+        assert!(matches!(
+            parse_path_seq_update_tables(b"node1+,no", &graph_aux, &mut item_table, None, 0),
+            Err(GraphParseError::UnterminatedPath)
+        ));
+        // walk sequences, in turn, may end with the file
+        let cdbg_aux = GraphAuxilliary::from_gfa("test/cdbg.gfa", CountType::Node, '#').unwrap();
+        let mut item_table = ItemTable::new(1);
+        assert_eq!(
+            parse_walk_seq_update_tables(b">1<2", &cdbg_aux, &mut item_table, None, 0).unwrap(),
+            (2, 12)
+        );
+
+        // errors are reported with the line and path in which they occur
+        let data = b"H\tVN:Z:1.0\nP\tp1\tnode1+,node4-\t*\n";
+        let err = parse_path_nodes(&mut BufReader::new(&data[..]), "p1", &graph_aux).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "line 2, path p1: unknown node node4");
+    }
+
     //#[test]
     //fn test_parse_cdbg_gfa_paths_walks() {
     //    let data = b"P\tpath1\tnode1+,node2-\n";
@@ -1504,10 +4188,13 @@ mod tests {
     fn test_parse_bed_with_1_column() {
         let bed_data = b"chr1\nchr2";
         let mut reader = BufReader::new(Cursor::new(bed_data));
-        let result = parse_bed_to_path_segments(&mut reader, true);
+        let result = parse_bed_to_path_segments(&mut reader, true, '#');
         assert_eq!(
             result,
-            vec![PathSegment::from_str("chr1"), PathSegment::from_str("chr2"),]
+            vec![
+                PathSegment::from_str("chr1", '#'),
+                PathSegment::from_str("chr2", '#'),
+            ]
         );
     }
 
@@ -1518,7 +4205,7 @@ mod tests {
     fn test_parse_bed_with_2_columns() {
         let bed_data = b"chr1\t1000\n";
         let mut reader = BufReader::new(Cursor::new(bed_data));
-        parse_bed_to_path_segments(&mut reader, false);
+        parse_bed_to_path_segments(&mut reader, false, '#');
     }
 
     #[test]
@@ -1526,25 +4213,25 @@ mod tests {
     fn test_parse_bed_with_2_columns_no_usize() {
         let bed_data = b"chr1\t100.5\tACGT\n";
         let mut reader = BufReader::new(Cursor::new(bed_data));
-        parse_bed_to_path_segments(&mut reader, false);
+        parse_bed_to_path_segments(&mut reader, false, '#');
     }
 
     #[test]
     fn test_parse_bed_with_3_columns() {
         let bed_data = b"chr1\t1000\t2000\nchr2\t1500\t2500";
         let mut reader = BufReader::new(Cursor::new(bed_data));
-        let result = parse_bed_to_path_segments(&mut reader, false);
+        let result = parse_bed_to_path_segments(&mut reader, false, '#');
         assert_eq!(
             result,
             vec![
                 {
-                    let mut tmp = PathSegment::from_str("chr1");
+                    let mut tmp = PathSegment::from_str("chr1", '#');
                     tmp.start = Some(1000);
                     tmp.end = Some(2000);
                     tmp
                 },
                 {
-                    let mut tmp = PathSegment::from_str("chr2");
+                    let mut tmp = PathSegment::from_str("chr2", '#');
                     tmp.start = Some(1500);
                     tmp.end = Some(2500);
                     tmp
@@ -1557,11 +4244,11 @@ mod tests {
     fn test_parse_bed_with_12_columns_no_block() {
         let bed_data = b"chr1\t1000\t2000\tname\t0\t+\t1000\t2000\t0\t2\t100,100\t0,900\n";
         let mut reader = BufReader::new(Cursor::new(bed_data));
-        let result = parse_bed_to_path_segments(&mut reader, false);
+        let result = parse_bed_to_path_segments(&mut reader, false, '#');
         assert_eq!(
             result,
             vec![{
-                let mut tmp = PathSegment::from_str("chr1");
+                let mut tmp = PathSegment::from_str("chr1", '#');
                 tmp.start = Some(1000);
                 tmp.end = Some(2000);
                 tmp
@@ -1573,18 +4260,18 @@ mod tests {
     fn test_parse_bed_with_12_columns_with_block() {
         let bed_data = b"chr1\t1000\t2000\tname\t0\t+\t1000\t2000\t0\t2\t100,100\t0,900\n";
         let mut reader = BufReader::new(Cursor::new(bed_data));
-        let result = parse_bed_to_path_segments(&mut reader, true);
+        let result = parse_bed_to_path_segments(&mut reader, true, '#');
         assert_eq!(
             result,
             vec![
                 {
-                    let mut tmp = PathSegment::from_str("chr1");
+                    let mut tmp = PathSegment::from_str("chr1", '#');
                     tmp.start = Some(1000);
                     tmp.end = Some(1100);
                     tmp
                 },
                 {
-                    let mut tmp = PathSegment::from_str("chr1");
+                    let mut tmp = PathSegment::from_str("chr1", '#');
                     tmp.start = Some(1900);
                     tmp.end = Some(2000);
                     tmp
@@ -1597,18 +4284,18 @@ mod tests {
     fn test_parse_bed_with_header() {
         let bed_data = b"browser position chr1:1-1000\nbrowser position chr7:127471196-127495720\nbrowser hide all\ntrack name='ItemRGBDemo' description='Item RGB demonstration' visibility=2 itemRgb='On'\nchr1\t1000\t2000\nchr2\t1500\t2500\n";
         let mut reader = BufReader::new(Cursor::new(bed_data));
-        let result = parse_bed_to_path_segments(&mut reader, false);
+        let result = parse_bed_to_path_segments(&mut reader, false, '#');
         assert_eq!(
             result,
             vec![
                 {
-                    let mut tmp = PathSegment::from_str("chr1");
+                    let mut tmp = PathSegment::from_str("chr1", '#');
                     tmp.start = Some(1000);
                     tmp.end = Some(2000);
                     tmp
                 },
                 {
-                    let mut tmp = PathSegment::from_str("chr2");
+                    let mut tmp = PathSegment::from_str("chr2", '#');
                     tmp.start = Some(1500);
                     tmp.end = Some(2500);
                     tmp
@@ -1617,21 +4304,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_bed_with_crlf_and_blank_lines() {
+        let bed_data = b"chr1\t1000\t2000\t\r\n\r\nchr2 \r\n\n";
+        let mut reader = BufReader::new(Cursor::new(bed_data));
+        let result = parse_bed_to_path_segments(&mut reader, false, '#');
+        assert_eq!(
+            result,
+            vec![
+                PathSegment::from_str_start_end("chr1", 1000, 2000, '#'),
+                PathSegment::from_str("chr2", '#'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_groups_with_crlf_and_blank_lines() {
+        let data = b"a#0\tG1\r\nb#0\tG2\t\r\n\r\n";
+        let result = parse_groups(&mut BufReader::new(Cursor::new(data)), '#').unwrap();
+        assert_eq!(
+            result,
+            vec![
+                (PathSegment::from_str("a#0", '#'), "G1".to_string(), 1),
+                (PathSegment::from_str("b#0", '#'), "G2".to_string(), 2),
+            ]
+        );
+
+        // a missing group name must not silently create an empty-named group
+        let data = b"a#0\tG1\nb#0\t \tG2\n";
+        assert!(parse_groups(&mut BufReader::new(Cursor::new(data)), '#').is_err());
+        let data = b"a#0\t \n";
+        assert!(parse_groups(&mut BufReader::new(Cursor::new(data)), '#').is_err());
+    }
+
+    #[test]
+    fn test_parse_clusters_line_numbers() {
+        let data = b"# representative\tmember\n\na\ta\n# comment\na\tb\n";
+        let result = parse_clusters(&mut BufReader::new(Cursor::new(data))).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                ("a".to_string(), "a".to_string(), 3),
+                ("a".to_string(), "b".to_string(), 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_annotation() {
+        let data = b"# node\tlabel\n1\tgeneA\r\n2\tgeneA\n2\tgeneB\n\n2\tgeneB\n";
+        let result = parse_annotation(&mut BufReader::new(Cursor::new(data))).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[&b"1".to_vec()], "geneA");
+        assert_eq!(result[&b"2".to_vec()], "geneA,geneB");
+
+        let data = b"1\tgeneA\tgeneB\n";
+        assert!(parse_annotation(&mut BufReader::new(Cursor::new(data))).is_err());
+        let data = b"1\t \n";
+        assert!(parse_annotation(&mut BufReader::new(Cursor::new(data))).is_err());
+    }
+
+    #[test]
+    fn test_parse_report_table() {
+        let data = b"# QC\nsample,reads,coverage\r\n\nHG002, 1000 ,30.5\nHG003,900\n";
+        let result = parse_report_table(&mut BufReader::new(Cursor::new(data)), b',').unwrap();
+        assert_eq!(
+            result,
+            vec![
+                vec!["sample", "reads", "coverage"],
+                vec!["HG002", "1000", "30.5"],
+                vec!["HG003", "900"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_node_list() {
+        let data = b"# repeats\n1\r\n2\tLINE\n\n s3 \n";
+        let result = parse_node_list(&mut BufReader::new(Cursor::new(data))).unwrap();
+        assert_eq!(result, vec!["1", "2", "s3"]);
+    }
+
+    #[test]
+    fn test_parse_group_metadata() {
+        let data = b"#path\tsample\tpopulation\na#0\ta\tEUR\r\nb#0\tb\tAFR\n\nc#0\tc\t\n";
+        let result =
+            parse_group_metadata(&mut BufReader::new(Cursor::new(data)), "population", '#')
+                .unwrap();
+        assert_eq!(
+            result,
+            vec![
+                (PathSegment::from_str("a#0", '#'), "EUR".to_string(), 2),
+                (PathSegment::from_str("b#0", '#'), "AFR".to_string(), 3),
+            ]
+        );
+        let result =
+            parse_group_metadata(&mut BufReader::new(Cursor::new(data)), "sample", '#').unwrap();
+        assert_eq!(result.len(), 3);
+
+        // the first column lists paths and cannot be selected
+        assert!(parse_group_metadata(&mut BufReader::new(Cursor::new(data)), "path", '#').is_err());
+        assert!(
+            parse_group_metadata(&mut BufReader::new(Cursor::new(data)), "clade", '#').is_err()
+        );
+    }
+
     #[test]
     fn test_parse_groups_with_valid_input() {
         //let (graph_aux, _, _) = setup_test_data();
         let file_name = "test/test_groups.txt";
         let test_path_segments = vec![
-            PathSegment::from_str("a#0"),
-            PathSegment::from_str("b#0"),
-            PathSegment::from_str("c#0"),
-            PathSegment::from_str("c#1"),
-            PathSegment::from_str("d#0"),
+            PathSegment::from_str("a#0", '#'),
+            PathSegment::from_str("b#0", '#'),
+            PathSegment::from_str("c#0", '#'),
+            PathSegment::from_str("c#1", '#'),
+            PathSegment::from_str("d#0", '#'),
         ];
         let test_groups = vec!["G1", "G1", "G2", "G2", "G2"];
 
         let mut data = BufReader::new(std::fs::File::open(file_name).unwrap());
-        let result = parse_groups(&mut data);
+        let result = parse_groups(&mut data, '#');
         assert!(result.is_ok(), "Expected successful group loading");
         let path_segments_group = result.unwrap();
         assert!(
@@ -1639,9 +4431,258 @@ mod tests {
             "Expected non-empty group assignments"
         );
         assert_eq!(path_segments_group.len(), 5); // number of paths == groups
-        for (i, (path_seg, group)) in path_segments_group.into_iter().enumerate() {
+        for (i, (path_seg, group, _)) in path_segments_group.into_iter().enumerate() {
             assert_eq!(path_seg, test_path_segments[i]);
             assert_eq!(group, test_groups[i]);
         }
     }
+
+    #[test]
+    fn test_parse_hist_results_round_trip() {
+        let results = HistGrowthResults {
+            hists: vec![
+                HistResult {
+                    count: CountType::Node,
+                    coverage: vec![0, 5, 3, 2],
+                    metadata: vec!["panacus hist test.gfa".to_string()],
+                },
+                HistResult {
+                    count: CountType::Bp,
+                    coverage: vec![0, 50, 30, 20],
+                    metadata: vec!["panacus hist test.gfa".to_string()],
+                },
+            ],
+            growths: Vec::new(),
+            edge_orientations: vec![EdgeOrientationHist {
+                orientation: "><".to_string(),
+                coverage: vec![0, 1, 0, 1],
+            }],
+        };
+
+        let mut buf = BufWriter::new(Vec::new());
+        write_json(&results, &mut buf).unwrap();
+        let json = buf.into_inner().unwrap();
+        let parsed = parse_hist_results(&mut BufReader::new(Cursor::new(json))).unwrap();
+        assert_eq!(parsed, results.hists);
+
+        let mut buf = BufWriter::new(Vec::new());
+        write_hist_table(&results.hists, &results.edge_orientations, &mut buf).unwrap();
+        let tsv = buf.into_inner().unwrap();
+        let parsed = parse_hist_results(&mut BufReader::new(Cursor::new(tsv))).unwrap();
+        assert_eq!(parsed, results.hists);
+    }
+
+    #[test]
+    fn test_write_growth_comparison_table() {
+        let hist_aux = HistAuxilliary::parse_params("0", "1", 1.0, false).unwrap();
+        let comparison: Vec<_> = [("a.tsv", vec![0, 5, 3, 2]), ("b.tsv", vec![0, 4, 1])]
+            .iter()
+            .map(|(file, coverage)| {
+                let hist = HistResult {
+                    count: CountType::Node,
+                    coverage: coverage.clone(),
+                    metadata: Vec::new(),
+                }
+                .to_hist();
+                GrowthComparison {
+                    file: file.to_string(),
+                    results: HistGrowthResults {
+                        hists: Vec::new(),
+                        growths: vec![GrowthResult::new(
+                            CountType::Node,
+                            &hist.calc_all_growths(&hist_aux),
+                            &hist_aux,
+                            Vec::new(),
+                        )],
+                        edge_orientations: Vec::new(),
+                    },
+                }
+            })
+            .collect();
+
+        let mut buf = BufWriter::new(Vec::new());
+        write_growth_comparison_table(&comparison, &mut buf).unwrap();
+        let tsv = String::from_utf8(buf.into_inner().unwrap()).unwrap();
+        let rows: Vec<_> = tsv.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(rows[4], "file\ta.tsv\tb.tsv");
+        // growth of b.tsv is padded, as it comprises fewer groups
+        assert_eq!(rows[8], "3\t10\tNaN");
+    }
+
+    #[test]
+    fn test_table_filter() {
+        assert!(parse_row_filter("total=2").is_err());
+        assert!(parse_row_filter(">=2").is_err());
+        assert_eq!(
+            parse_row_filter(" total >= 2, node!=x ").unwrap(),
+            vec![
+                RowCondition {
+                    column: "total".to_string(),
+                    cmp: Comparison::Ge,
+                    value: "2".to_string(),
+                },
+                RowCondition {
+                    column: "node".to_string(),
+                    cmp: Comparison::Ne,
+                    value: "x".to_string(),
+                },
+            ]
+        );
+
+        let mut filter = TableFilter::new(Vec::new(), "b,node", "a>=2,node!=s3").unwrap();
+        filter
+            .write_all(b"# comment\nnode\ta\tb\ns1\t1\t0\ns2\t10\t1\ns3\t3\t1\ns4\t2\t1")
+            .unwrap();
+        let out = filter.finish().unwrap();
+        assert_eq!(
+            from_utf8(&out).unwrap(),
+            "# comment\nb\tnode\n1\ts2\n1\ts4\n"
+        );
+
+        let mut filter = TableFilter::new(Vec::new(), "c", "").unwrap();
+        assert!(filter.write_all(b"node\ta\tb\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_coverage_query() {
+        assert!(parse_coverage_query("").is_err());
+        assert!(parse_coverage_query("0.5").is_err());
+        assert!(parse_coverage_query(">=1.5").is_err());
+        let conditions = parse_coverage_query(">=0.5, <4").unwrap();
+        assert_eq!(
+            conditions,
+            vec![
+                CoverageCondition {
+                    cmp: Comparison::Ge,
+                    threshold: Threshold::Relative(0.5),
+                },
+                CoverageCondition {
+                    cmp: Comparison::Lt,
+                    threshold: Threshold::Absolute(4),
+                },
+            ]
+        );
+        let matches: Vec<usize> = (0..=6)
+            .filter(|c| conditions.iter().all(|x| x.holds(*c, 6)))
+            .collect();
+        assert_eq!(matches, vec![3]);
+    }
+
+    #[test]
+    fn test_bufreader_from_compressed_gfa() {
+        let content = "H\tVN:Z:1.0\nS\t1\tACGT\n";
+        let dir = std::env::temp_dir();
+        let plain = dir.join(format!("panacus_test_{}.gfa.gz", std::process::id()));
+        let compressed = dir.join(format!("panacus_test_{}.gfa", std::process::id()));
+        std::fs::write(&plain, content).unwrap();
+        let mut encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(&compressed).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(content.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        // compression is detected from the content, not from the file extension
+        for file in [&plain, &compressed] {
+            let mut s = String::new();
+            bufreader_from_compressed_gfa(file.to_str().unwrap())
+                .unwrap()
+                .read_to_string(&mut s)
+                .unwrap();
+            assert_eq!(s, content);
+            std::fs::remove_file(file).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_csv_writer() {
+        let mut csv = CsvWriter::new(Vec::new());
+        csv.write_all(b"# a\tcomment\npanacus\thist\ncount\tnode\n")
+            .unwrap();
+        csv.write_all(b"0\t\"x\"\n1\t2,3\n2\t5").unwrap();
+        let out = csv.finish().unwrap();
+        assert_eq!(
+            from_utf8(&out).unwrap(),
+            "# a\tcomment\npanacus,hist\ncount,node\n0,\"\"\"x\"\"\"\n1,\"2,3\"\n2,5\n"
+        );
+    }
+
+    #[test]
+    fn test_latex_writer() {
+        let mut latex = LatexWriter::new(Vec::new());
+        latex
+            .write_all(b"# a comment\npanacus\thist\ncount\tnode\n0\t1\n1\t2\n\n")
+            .unwrap();
+        latex.write_all(b"feature\tvalue\nnode_len\t10%").unwrap();
+        let out = latex.finish().unwrap();
+        assert_eq!(
+            from_utf8(&out).unwrap(),
+            "% a comment\n\\begin{tabular}{rr}\n\\toprule\npanacus & hist \\\\\ncount & node \\\\\n\\midrule\n0 & 1 \\\\\n1 & 2 \\\\\n\\bottomrule\n\\end{tabular}\n\\begin{tabular}{ll}\n\\toprule\nfeature & value \\\\\n\\midrule\nnode\\_len & 10\\% \\\\\n\\bottomrule\n\\end{tabular}\n"
+        );
+    }
+
+    #[test]
+    fn test_count_path_walk_records() {
+        let data = b"S\t1\tA\nP\tx#1#c\t1+\t*\nW\ty\t1\tc\t0\t1\t>1\nW\tz\t1\tc\t*\t*\t>1\n";
+        let counts = count_path_walk_records(&mut BufReader::new(&data[..])).unwrap();
+        assert_eq!(counts, (1, 2));
+    }
+
+    #[test]
+    fn test_namespace_gfa_record() {
+        let mut out = Vec::new();
+        for record in [
+            &b"S\t1\tACGT\n"[..],
+            b"L\t1\t+\t2\t-\t*\n",
+            b"P\tp\t1+,2-\t*\n",
+            b"W\ts\t0\tc\t0\t4\t>1<2\n",
+            b"\n",
+            b"H\tVN:Z:1.0",
+        ] {
+            namespace_gfa_record(record, b"2:", &mut out);
+        }
+        assert_eq!(
+            str::from_utf8(&out).unwrap(),
+            "S\t2:1\tACGT\nL\t2:1\t+\t2:2\t-\t*\nP\tp\t2:1+,2:2-\t*\nW\ts\t0\tc\t0\t4\t>2:1<2:2\n\nH\tVN:Z:1.0\n"
+        );
+    }
+
+    #[test]
+    fn test_bufreader_from_gfa_files() {
+        let gfa_files = gfa_file_list("test/cdbg.gfa", &["test/chrM_test.gfa".to_string()]);
+        assert_eq!(gfa_files, vec!["test/cdbg.gfa", "test/chrM_test.gfa"]);
+        let records: Vec<String> = bufreader_from_gfa_files(&gfa_files)
+            .unwrap()
+            .lines()
+            .map(|l| l.unwrap())
+            .collect();
+        assert_eq!(records.len(), 25 + 364);
+        assert!(records.contains(&"P\ta#1#h1\t1:1+,1:2+,1:3+\t*".to_string()));
+        assert!(records.iter().any(|r| r.starts_with("S\t2:1\t")));
+
+        // nodes of the same ID in different graphs are kept apart
+        let graph_aux = GraphAuxilliary::from_gfa_files(&gfa_files, CountType::Edge, '#').unwrap();
+        assert_eq!(graph_aux.node_count, 5 + 154);
+    }
+
+    #[test]
+    fn test_check_gfa() {
+        let gfa = b"H\tVN:Z:1.0\nS\t1\tACGT\nS\t2\tAC\nL\t1\t+\t2\t+\t0M\nP\tx\t1+,2+\t*\n";
+        assert_eq!(check_gfa(&mut BufReader::new(&gfa[..]), false).unwrap(), 0);
+
+        // truncated in the middle of the last record
+        let truncated = &gfa[..gfa.len() - 10];
+        let err = check_gfa(&mut BufReader::new(truncated), false).unwrap_err();
+        assert!(err.to_string().contains("unexpected end of file"));
+        assert!(err.to_string().contains("byte 40"));
+        assert!(err.to_string().contains("last complete record: line 4"));
+        assert_eq!(check_gfa(&mut BufReader::new(truncated), true).unwrap(), 1);
+
+        let corrupt = b"S\t1\tAC\0GT\nS\t\xff\tAC\nW\ts\t0\tc\tx\t*\t>1\n";
+        assert!(check_gfa(&mut BufReader::new(&corrupt[..]), false).is_err());
+        assert_eq!(
+            check_gfa(&mut BufReader::new(&corrupt[..]), true).unwrap(),
+            3
+        );
+    }
 }
@@ -0,0 +1,17 @@
+/* public use */
+pub mod abacus;
+pub mod api;
+pub mod cli;
+pub mod cluster;
+pub mod graph;
+pub mod growth;
+pub mod hist;
+pub mod html;
+pub mod io;
+pub mod plot;
+pub mod util;
+pub mod vcf;
+
+pub use api::{Grouping, PanacusBuilder};
+pub use hist::{GrowthResult, Hist, HistGrowthResults, HistResult};
+pub use util::CountType;
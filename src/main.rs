@@ -1,34 +1,79 @@
 /* standard use */
+use std::fs::File;
 use std::io::Write;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::time::Instant;
 
 /* private use */
-mod abacus;
-mod cli;
-mod graph;
-mod hist;
-mod html;
-mod io;
-mod util;
+use panacus::cli;
+use panacus::util::{write_error_json, ErrorCategory};
 
-fn main() -> Result<(), std::io::Error> {
-    env_logger::init();
+fn main() {
+    panacus::util::init_logger();
     let timer = Instant::now();
 
-    // print output to stdout
-    let mut out = std::io::BufWriter::new(std::io::stdout());
-
     // read parameters and store them in memory
-    let params = cli::read_params();
-    cli::set_number_of_threads(&params);
+    let (params, pansn, cache_dir, output_file, error_json) = cli::read_params();
+
+    // panics are reported like any other error, as internal error
+    let res = match catch_unwind(AssertUnwindSafe(|| {
+        run(params, pansn, &cache_dir, &output_file)
+    })) {
+        Ok(Ok(())) => {
+            let duration = timer.elapsed();
+            log::info!("done; time elapsed: {:?} ", duration);
+            return;
+        }
+        Ok(Err(e)) => (
+            ErrorCategory::from_kind(e.kind()),
+            e.kind().to_string(),
+            e.to_string(),
+        ),
+        Err(p) => (
+            ErrorCategory::Internal,
+            "panic".to_string(),
+            p.downcast_ref::<String>()
+                .cloned()
+                .or_else(|| p.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default(),
+        ),
+    };
+    let (category, kind, msg) = res;
+    if !error_json.is_empty() {
+        if let Err(e) = write_error_json(&error_json, category, &kind, &msg) {
+            log::error!("unable to write error report to {}: {}", error_json, e);
+        }
+    }
+    eprintln!("Error: {}", msg);
+    std::process::exit(category.exit_code());
+}
+
+fn run(
+    params: cli::Params,
+    pansn: char,
+    cache_dir: &str,
+    output_file: &str,
+) -> Result<(), std::io::Error> {
+    // print output to stdout, unless an output file is given
+    let out: Box<dyn Write> = if output_file.is_empty() {
+        Box::new(std::io::stdout())
+    } else {
+        Box::new(File::create(output_file).map_err(|e| {
+            let msg = format!("unable to create output file {}: {}", output_file, e);
+            log::error!("{}", msg);
+            std::io::Error::new(e.kind(), msg)
+        })?)
+    };
+    let mut out = std::io::BufWriter::new(out);
 
     // ride on!
-    cli::run(params, &mut out)?;
+    if cache_dir.is_empty() {
+        cli::run(params, pansn, &mut out)?;
+    } else {
+        cli::run_cached(params, pansn, cache_dir, &mut out)?;
+    }
 
     // clean up & close down
     out.flush()?;
-    let duration = timer.elapsed();
-    log::info!("done; time elapsed: {:?} ", duration);
-
     Ok(())
 }
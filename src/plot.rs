@@ -0,0 +1,575 @@
+/* standard use */
+use std::io::{BufWriter, Error, Write};
+
+/* external use */
+use serde_json::{json, Value};
+
+/* private use */
+use crate::abacus::AbacusByGroup;
+use crate::cluster::Tree;
+use crate::hist::{EdgeOrientationHist, GrowthComparison, GrowthResult, HistResult};
+use crate::html::growth_comparison_series;
+use crate::io::OutputFormat;
+
+// same palette as the charts of the HTML report
+const PCOLORS: [&str; 6] = [
+    "#f77189", "#bb9832", "#50b131", "#36ada4", "#3ba3ec", "#e866f4",
+];
+
+const WIDTH: f64 = 640.0;
+const HEIGHT: f64 = 400.0;
+const MARGIN_LEFT: f64 = 80.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 50.0;
+
+// Static chart of one or more data series over a common x-axis, rendered as bars (histograms)
+// or lines (growth curves)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chart {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    pub x_ticks: Vec<String>,
+    pub series: Vec<(String, Vec<f64>)>,
+    pub bars: bool,
+}
+
+impl Chart {
+    pub fn from_hist(hist: &HistResult) -> Self {
+        Self {
+            title: format!("coverage histogram of {}s", hist.count),
+            x_label: "taxa".to_string(),
+            y_label: format!("#{}s", hist.count),
+            x_ticks: (0..hist.coverage.len()).map(|i| i.to_string()).collect(),
+            series: vec![(
+                hist.count.to_string(),
+                hist.coverage.iter().map(|c| *c as f64).collect(),
+            )],
+            bars: true,
+        }
+    }
+
+    pub fn from_edge_orientations(hists: &[EdgeOrientationHist]) -> Self {
+        let n = hists.iter().map(|h| h.coverage.len()).max().unwrap_or(0);
+        Self {
+            title: "coverage histogram of edges by orientation".to_string(),
+            x_label: "taxa".to_string(),
+            y_label: "#edges".to_string(),
+            x_ticks: (0..n).map(|i| i.to_string()).collect(),
+            series: hists
+                .iter()
+                .map(|h| {
+                    (
+                        h.orientation.clone(),
+                        h.coverage.iter().map(|c| *c as f64).collect(),
+                    )
+                })
+                .collect(),
+            bars: true,
+        }
+    }
+
+    pub fn from_private(abacus: &AbacusByGroup) -> Self {
+        Self {
+            title: format!("private {}s per group", abacus.count),
+            x_label: "group".to_string(),
+            y_label: format!("#{}s", abacus.count),
+            x_ticks: abacus.groups.clone(),
+            series: vec![(
+                abacus.count.to_string(),
+                abacus
+                    .private_countables()
+                    .iter()
+                    .map(|(p, _)| *p as f64)
+                    .collect(),
+            )],
+            bars: true,
+        }
+    }
+
+    pub fn from_growth(growth: &GrowthResult) -> Self {
+        let n = growth.values.iter().map(|v| v.len()).max().unwrap_or(0);
+        Self {
+            title: format!("pangenome growth of {}s", growth.count),
+            x_label: "#groups".to_string(),
+            y_label: format!("#{}s", growth.count),
+            x_ticks: (1..n + 1).map(|i| i.to_string()).collect(),
+            series: growth
+                .coverage
+                .iter()
+                .zip(&growth.quorum)
+                .zip(&growth.values)
+                .enumerate()
+                .map(|(i, ((c, q), v))| {
+                    (
+                        growth.name(i).cloned().unwrap_or_else(|| {
+                            format!("coverage {}, quorum {}", c.get_string(), q.get_string())
+                        }),
+                        v.clone(),
+                    )
+                })
+                .collect(),
+            bars: false,
+        }
+    }
+
+    pub fn from_growth_comparison(comparison: &[GrowthComparison]) -> Self {
+        let (labels, values, y_label) = growth_comparison_series(comparison);
+        let n = values.iter().map(|v| v.len()).max().unwrap_or(0);
+        Self {
+            title: "pangenome growth".to_string(),
+            x_label: "#groups".to_string(),
+            y_label,
+            x_ticks: (1..n + 1).map(|i| i.to_string()).collect(),
+            series: labels.into_iter().zip(values).collect(),
+            bars: false,
+        }
+    }
+
+    // data in long format, i.e., one record per value, omitting non-finite values that JSON
+    // cannot represent
+    fn to_vega_lite(&self) -> Value {
+        let mut values = Vec::new();
+        for (label, series) in &self.series {
+            for (tick, v) in self.x_ticks.iter().zip(series) {
+                if v.is_finite() {
+                    values.push(json!({ "x": tick, "series": label, "value": v }));
+                }
+            }
+        }
+        let mut spec = json!({
+            "title": self.title,
+            "width": WIDTH - MARGIN_LEFT - MARGIN_RIGHT,
+            "height": HEIGHT - MARGIN_TOP - MARGIN_BOTTOM,
+            "data": { "values": values },
+            "mark": if self.bars { json!("bar") } else { json!({ "type": "line", "point": true }) },
+            "encoding": {
+                // keep the order of ticks, which are numbers given as strings
+                "x": { "field": "x", "type": "ordinal", "sort": null, "title": self.x_label },
+                "y": { "field": "value", "type": "quantitative", "title": self.y_label },
+                "color": {
+                    "field": "series",
+                    "type": "nominal",
+                    "title": null,
+                    "scale": { "range": PCOLORS },
+                },
+                "tooltip": [
+                    { "field": "series", "type": "nominal" },
+                    { "field": "x", "type": "ordinal", "title": self.x_label },
+                    { "field": "value", "type": "quantitative", "title": self.y_label },
+                ],
+            },
+        });
+        if self.bars && self.series.len() > 1 {
+            spec["encoding"]["xOffset"] = json!({ "field": "series", "sort": null });
+        }
+        spec
+    }
+
+    fn render(&self, svg: &mut String) {
+        let plot_w = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+        let plot_h = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+        let n = self.x_ticks.len();
+        let max = self
+            .series
+            .iter()
+            .flat_map(|(_, v)| v.iter())
+            .filter(|x| x.is_finite())
+            .fold(0.0, |m: f64, x| m.max(*x));
+        let step = tick_step(max);
+        let y_max = (max / step).ceil().max(1.0) * step;
+        let y = |v: f64| MARGIN_TOP + plot_h * (1.0 - v / y_max);
+        // bars are centered in equally sized slots, lines span the full plot width
+        let slot = if self.bars || n < 2 {
+            plot_w / n.max(1) as f64
+        } else {
+            plot_w / (n - 1) as f64
+        };
+        let x = |i: usize| {
+            if self.bars || n < 2 {
+                MARGIN_LEFT + slot * (i as f64 + 0.5)
+            } else {
+                MARGIN_LEFT + slot * i as f64
+            }
+        };
+
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"16\">{}</text>\n",
+            WIDTH / 2.0,
+            MARGIN_TOP / 2.0 + 5.0,
+            handlebars::html_escape(&self.title)
+        ));
+
+        // y-axis with horizontal grid lines
+        let decimals = if step >= 1.0 {
+            0
+        } else {
+            -step.log10().floor() as usize
+        };
+        let mut k = 0;
+        while k as f64 * step <= y_max + step / 2.0 {
+            let v = k as f64 * step;
+            svg.push_str(&format!(
+                "<line x1=\"{}\" y1=\"{:.1}\" x2=\"{}\" y2=\"{:.1}\" stroke=\"#dddddd\"/>\n",
+                MARGIN_LEFT,
+                y(v),
+                WIDTH - MARGIN_RIGHT,
+                y(v)
+            ));
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{:.1}\" text-anchor=\"end\">{:.*}</text>\n",
+                MARGIN_LEFT - 6.0,
+                y(v) + 4.0,
+                decimals,
+                v
+            ));
+            k += 1;
+        }
+
+        // x-axis, labelling at most about 20 ticks
+        let every = (n + 19) / 20;
+        for (i, tick) in self.x_ticks.iter().enumerate() {
+            if i % every.max(1) == 0 {
+                svg.push_str(&format!(
+                    "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                    x(i),
+                    HEIGHT - MARGIN_BOTTOM + 16.0,
+                    handlebars::html_escape(tick)
+                ));
+            }
+        }
+        svg.push_str(&format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>\n",
+            MARGIN_LEFT,
+            HEIGHT - MARGIN_BOTTOM,
+            WIDTH - MARGIN_RIGHT,
+            HEIGHT - MARGIN_BOTTOM
+        ));
+        svg.push_str(&format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"black\"/>\n",
+            MARGIN_LEFT,
+            MARGIN_TOP,
+            MARGIN_LEFT,
+            HEIGHT - MARGIN_BOTTOM
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+            MARGIN_LEFT + plot_w / 2.0,
+            HEIGHT - 12.0,
+            handlebars::html_escape(&self.x_label)
+        ));
+        svg.push_str(&format!(
+            "<text transform=\"translate(16,{}) rotate(-90)\" text-anchor=\"middle\">{}</text>\n",
+            MARGIN_TOP + plot_h / 2.0,
+            handlebars::html_escape(&self.y_label)
+        ));
+
+        // data series, where non-finite values interrupt lines
+        for (j, (_, values)) in self.series.iter().enumerate() {
+            let color = PCOLORS[j % PCOLORS.len()];
+            if self.bars {
+                let w = slot * 0.8 / self.series.len() as f64;
+                for (i, v) in values.iter().enumerate().filter(|(_, v)| v.is_finite()) {
+                    svg.push_str(&format!(
+                        "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\"/>\n",
+                        x(i) - slot * 0.4 + w * j as f64,
+                        y(*v),
+                        w,
+                        y(0.0) - y(*v),
+                        color
+                    ));
+                }
+            } else {
+                let mut d = String::new();
+                let mut gap = true;
+                for (i, v) in values.iter().enumerate() {
+                    if v.is_finite() {
+                        d.push_str(&format!(
+                            "{}{:.1} {:.1} ",
+                            if gap { "M" } else { "L" },
+                            x(i),
+                            y(*v)
+                        ));
+                        gap = false;
+                    } else {
+                        gap = true;
+                    }
+                }
+                svg.push_str(&format!(
+                    "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+                    d.trim_end(),
+                    color
+                ));
+            }
+        }
+
+        // legend in the upper left corner, which growth curves and histograms rarely cover
+        if self.series.len() > 1 || !self.bars {
+            for (j, (label, _)) in self.series.iter().enumerate() {
+                let ly = MARGIN_TOP + 14.0 + 16.0 * j as f64;
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"12\" height=\"12\" fill=\"{}\"/>\n",
+                    MARGIN_LEFT + 10.0,
+                    ly - 10.0,
+                    PCOLORS[j % PCOLORS.len()]
+                ));
+                svg.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\">{}</text>\n",
+                    MARGIN_LEFT + 28.0,
+                    ly,
+                    handlebars::html_escape(label)
+                ));
+            }
+        }
+    }
+}
+
+// distance between y-axis ticks such that about five ticks cover [0, max]
+fn tick_step(max: f64) -> f64 {
+    if max <= 0.0 {
+        return 1.0;
+    }
+    let raw = max / 5.0;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|s| s * magnitude)
+        .find(|s| *s >= raw)
+        .unwrap();
+    // counts are integral, hence fractional ticks only add noise
+    if max >= 1.0 {
+        step.max(1.0)
+    } else {
+        step
+    }
+}
+
+// Renders the charts as a single SVG document, stacked on top of each other
+pub fn to_svg(charts: &[Chart]) -> String {
+    let height = HEIGHT * charts.len() as f64;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"white\"/>\n",
+        w = WIDTH,
+        h = height
+    );
+    for (i, chart) in charts.iter().enumerate() {
+        svg.push_str(&format!(
+            "<g transform=\"translate(0,{})\">\n",
+            HEIGHT * i as f64
+        ));
+        chart.render(&mut svg);
+        svg.push_str("</g>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+pub fn write_svg<W: Write>(charts: &[Chart], out: &mut BufWriter<W>) -> Result<(), Error> {
+    out.write_all(to_svg(charts).as_bytes())
+}
+
+// Renders a tree as dendrogram, with leaves listed top to bottom and branch lengths drawn to
+// scale along the x-axis
+pub fn dendrogram_to_svg(tree: &Tree, title: &str) -> String {
+    const ROW: f64 = 20.0;
+    const LABEL_WIDTH: f64 = 200.0;
+    let leaves = tree.leaf_order();
+    let height = MARGIN_TOP + ROW * leaves.len().max(1) as f64 + MARGIN_BOTTOM;
+
+    // distance of each node from the root
+    let mut depth = vec![0.0; tree.nodes.len()];
+    let mut stack = vec![tree.root];
+    while let Some(v) = stack.pop() {
+        for (c, l) in tree.nodes[v].children.iter() {
+            depth[*c] = depth[v] + l;
+            stack.push(*c);
+        }
+    }
+    let max_depth = depth.iter().cloned().fold(0.0, f64::max);
+    let width = WIDTH - MARGIN_RIGHT - LABEL_WIDTH - MARGIN_RIGHT;
+    let x = |v: usize| {
+        if max_depth > 0.0 {
+            MARGIN_RIGHT + width * depth[v] / max_depth
+        } else {
+            MARGIN_RIGHT
+        }
+    };
+
+    // leaves are placed in rows, inner nodes centered between their outermost children
+    let mut y = vec![0.0; tree.nodes.len()];
+    for (k, v) in leaves.iter().enumerate() {
+        y[*v] = MARGIN_TOP + ROW * (k as f64 + 0.5);
+    }
+    fn place(tree: &Tree, v: usize, y: &mut Vec<f64>) {
+        let children = &tree.nodes[v].children;
+        if children.is_empty() {
+            return;
+        }
+        for (c, _) in children.iter() {
+            place(tree, *c, y);
+        }
+        y[v] = (y[children[0].0] + y[children[children.len() - 1].0]) / 2.0;
+    }
+    place(tree, tree.root, &mut y);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\" font-size=\"12\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"white\"/>\n",
+        w = WIDTH,
+        h = height
+    );
+    svg.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" font-size=\"14\">{}</text>\n",
+        WIDTH / 2.0,
+        MARGIN_TOP / 2.0,
+        handlebars::html_escape(title)
+    ));
+    for (v, node) in tree.nodes.iter().enumerate() {
+        if let Some((first, last)) = node.children.first().zip(node.children.last()) {
+            svg.push_str(&format!(
+                "<line x1=\"{x:.1}\" y1=\"{:.1}\" x2=\"{x:.1}\" y2=\"{:.1}\" stroke=\"black\"/>\n",
+                y[first.0],
+                y[last.0],
+                x = x(v)
+            ));
+            for (c, _) in node.children.iter() {
+                svg.push_str(&format!(
+                    "<line x1=\"{:.1}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"black\"/>\n",
+                    x(v),
+                    x(*c),
+                    y = y[*c]
+                ));
+            }
+        }
+    }
+    for v in leaves.iter() {
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" dominant-baseline=\"middle\">{}</text>\n",
+            x(*v) + 4.0,
+            y[*v],
+            handlebars::html_escape(tree.nodes[*v].label.as_deref().unwrap_or(""))
+        ));
+    }
+    // scale bar spanning the distance from root to the farthest leaf
+    let bottom = height - MARGIN_BOTTOM / 2.0;
+    svg.push_str(&format!(
+        "<line x1=\"{:.1}\" y1=\"{b:.1}\" x2=\"{:.1}\" y2=\"{b:.1}\" stroke=\"black\"/>\n<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{:.4}</text>\n",
+        MARGIN_RIGHT,
+        MARGIN_RIGHT + width,
+        MARGIN_RIGHT + width / 2.0,
+        bottom + 16.0,
+        max_depth,
+        b = bottom
+    ));
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Writes the charts as a single Vega-Lite specification, concatenated vertically, for styling
+// them further or embedding them in other dashboards
+pub fn write_vega_lite<W: Write>(charts: &[Chart], out: &mut BufWriter<W>) -> Result<(), Error> {
+    let spec = json!({
+        "$schema": "https://vega.github.io/schema/vega-lite/v5.json",
+        "vconcat": charts.iter().map(|c| c.to_vega_lite()).collect::<Vec<Value>>(),
+    });
+    serde_json::to_writer_pretty(&mut *out, &spec)?;
+    writeln!(out)
+}
+
+fn write_charts<W: Write>(
+    charts: &[Chart],
+    output_format: OutputFormat,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    match output_format {
+        OutputFormat::Plot => write_svg(charts, out),
+        OutputFormat::Vega => write_vega_lite(charts, out),
+        _ => unreachable!("charts are written only as plot or vega output"),
+    }
+}
+
+pub fn write_histgrowth_chart<W: Write>(
+    hists: &[HistResult],
+    growths: &[GrowthResult],
+    edge_orientations: &[EdgeOrientationHist],
+    output_format: OutputFormat,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    let mut charts: Vec<Chart> = hists
+        .iter()
+        .map(Chart::from_hist)
+        .chain(growths.iter().map(Chart::from_growth))
+        .collect();
+    if !edge_orientations.is_empty() {
+        charts.push(Chart::from_edge_orientations(edge_orientations));
+    }
+    write_charts(&charts, output_format, out)
+}
+
+pub fn write_private_chart<W: Write>(
+    abaci: &[AbacusByGroup],
+    output_format: OutputFormat,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    let charts: Vec<Chart> = abaci.iter().map(Chart::from_private).collect();
+    write_charts(&charts, output_format, out)
+}
+
+pub fn write_growth_comparison_chart<W: Write>(
+    comparison: &[GrowthComparison],
+    output_format: OutputFormat,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    write_charts(
+        &[Chart::from_growth_comparison(comparison)],
+        output_format,
+        out,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::CountType;
+
+    #[test]
+    fn test_tick_step() {
+        assert_eq!(tick_step(0.0), 1.0);
+        assert_eq!(tick_step(3.0), 1.0);
+        assert_eq!(tick_step(42.0), 10.0);
+        assert_eq!(tick_step(1234.0), 500.0);
+        assert!((tick_step(0.5) - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_write_histgrowth_chart() {
+        let hist = HistResult {
+            count: CountType::Node,
+            coverage: vec![0, 3, 1, 2],
+            metadata: Vec::new(),
+        };
+        let mut out = BufWriter::new(Vec::new());
+        write_histgrowth_chart(
+            std::slice::from_ref(&hist),
+            &[],
+            &[],
+            OutputFormat::Plot,
+            &mut out,
+        )
+        .unwrap();
+        let svg = String::from_utf8(out.into_inner().unwrap()).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        // one bar per coverage value plus the background
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert!(svg.contains("coverage histogram of nodes"));
+
+        let mut out = BufWriter::new(Vec::new());
+        write_histgrowth_chart(&[hist], &[], &[], OutputFormat::Vega, &mut out).unwrap();
+        let spec: Value = serde_json::from_slice(&out.into_inner().unwrap()).unwrap();
+        let chart = &spec["vconcat"][0];
+        assert_eq!(chart["mark"], "bar");
+        assert_eq!(chart["data"]["values"].as_array().unwrap().len(), 4);
+        assert_eq!(chart["data"]["values"][1]["value"], 3.0);
+    }
+}
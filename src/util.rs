@@ -2,8 +2,14 @@
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt;
+use std::io::{Error, ErrorKind, Write};
+use std::str::FromStr;
+use std::sync::Mutex;
 
 /* external use */
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use strum_macros::{EnumIter, EnumString, EnumVariantNames};
 
 /* internal use */
@@ -26,8 +32,11 @@ unsafe impl Sync for Wrap<[Vec<u64>; SIZE_T]> {}
 unsafe impl Sync for Wrap<Vec<Vec<u64>>> {}
 // unsafe impl Sync for Wrap<[HashMap<u64, InfixEqStorage>; SIZE_T]> {}
 
-#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumVariantNames, EnumIter)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, EnumString, EnumVariantNames, EnumIter, Serialize, Deserialize,
+)]
 #[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum CountType {
     Node,
     Bp,
@@ -50,9 +59,182 @@ impl fmt::Display for CountType {
     }
 }
 
+// handling of segments of length 0 (i.e., sequence "*" without LN tag or empty sequence)
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "lowercase")]
+pub enum ZeroLengthNodes {
+    Count,
+    Skip,
+    Error,
+}
+
+impl fmt::Display for ZeroLengthNodes {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}",
+            match self {
+                ZeroLengthNodes::Count => "count",
+                ZeroLengthNodes::Skip => "skip",
+                ZeroLengthNodes::Error => "error",
+            }
+        )
+    }
+}
+
+// similarity of two groups, computed from the countables (or bps) they share
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "lowercase")]
+pub enum SimilarityMetric {
+    Jaccard,
+    Cosine,
+    Containment,
+}
+
+impl SimilarityMetric {
+    // similarity of groups a and b, given the size of their intersection and their own sizes;
+    // containment is the fraction of a that is contained in b and thus not symmetric
+    pub fn apply(&self, intersection: usize, a: usize, b: usize) -> f64 {
+        let denominator = match self {
+            SimilarityMetric::Jaccard => (a + b - intersection) as f64,
+            SimilarityMetric::Cosine => ((a as f64) * (b as f64)).sqrt(),
+            SimilarityMetric::Containment => a as f64,
+        };
+        if denominator > 0.0 {
+            intersection as f64 / denominator
+        } else {
+            0.0
+        }
+    }
+}
+
+impl fmt::Display for SimilarityMetric {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}",
+            match self {
+                SimilarityMetric::Jaccard => "jaccard",
+                SimilarityMetric::Cosine => "cosine",
+                SimilarityMetric::Containment => "containment",
+            }
+        )
+    }
+}
+
+// arithmetic operation for combining coverage histograms of several tables
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "lowercase")]
+pub enum HistOperation {
+    Add,
+    Subtract,
+}
+
+impl fmt::Display for HistOperation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}",
+            match self {
+                HistOperation::Add => "add",
+                HistOperation::Subtract => "subtract",
+            }
+        )
+    }
+}
+
+// method for hierarchical clustering of groups by their pairwise distances
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "lowercase")]
+pub enum ClusterMethod {
+    Upgma,
+    Nj,
+}
+
+impl fmt::Display for ClusterMethod {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "{}",
+            match self {
+                ClusterMethod::Upgma => "UPGMA",
+                ClusterMethod::Nj => "neighbor joining",
+            }
+        )
+    }
+}
+
+// binning of value distributions in HTML reports, given either as number of bins or as bin
+// width (prefixed by "w", e.g., "w1000")
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Binning {
+    Count(usize),
+    Width(u32),
+}
+
+impl FromStr for Binning {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let res = match s.strip_prefix('w') {
+            Some(w) => u32::from_str(w).ok().filter(|x| *x > 0).map(Binning::Width),
+            None => usize::from_str(s)
+                .ok()
+                .filter(|x| *x > 0)
+                .map(Binning::Count),
+        };
+        res.ok_or_else(|| {
+            format!(
+                "invalid binning '{}', expected positive number of bins (e.g., 50) or bin width prefixed by 'w' (e.g., w1000)",
+                s
+            )
+        })
+    }
+}
+
+impl Default for Binning {
+    fn default() -> Self {
+        Binning::Count(50)
+    }
+}
+
+impl Binning {
+    // returns the bin labels and the number of values in each bin
+    pub fn apply<T: Copy + Into<u64>>(&self, values: &[T]) -> (Vec<String>, Vec<usize>) {
+        if values.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+        let min = values.iter().map(|x| (*x).into()).min().unwrap();
+        let max = values.iter().map(|x| (*x).into()).max().unwrap();
+        let width = match self {
+            Binning::Count(n) => u64::max(1, (max - min + *n as u64) / *n as u64),
+            Binning::Width(w) => *w as u64,
+        };
+        let n_bins = ((max - min) / width + 1) as usize;
+        let mut counts = vec![0; n_bins];
+        for v in values {
+            counts[(((*v).into() - min) / width) as usize] += 1;
+        }
+        let labels = (0..n_bins as u64)
+            .map(|i| {
+                let start = min + i * width;
+                if width == 1 {
+                    format!("{}", start)
+                } else {
+                    format!("{}-{}", start, start + width - 1)
+                }
+            })
+            .collect();
+        (labels, counts)
+    }
+}
+
 pub struct ItemTable {
     pub items: [Vec<ItemIdSize>; SIZE_T],
     pub id_prefsum: [Vec<ItemIdSize>; SIZE_T],
+    // for each path, the covered intervals of items that the path covers only partially; only
+    // kept if bps are counted exactly
+    pub covered_bps: Option<Vec<IntervalContainer>>,
 }
 
 impl ItemTable {
@@ -60,8 +242,23 @@ impl ItemTable {
         Self {
             items: [(); SIZE_T].map(|_| vec![]),
             id_prefsum: [(); SIZE_T].map(|_| vec![0; num_walks_paths + 1]),
+            covered_bps: None,
         }
     }
+
+    pub fn with_covered_bps(mut self) -> Self {
+        self.covered_bps = Some(vec![IntervalContainer::new(); self.id_prefsum[0].len() - 1]);
+        self
+    }
+
+    // items of the given path, in no particular order
+    pub fn path_items(&self, path_id: usize) -> impl Iterator<Item = ItemIdSize> + '_ {
+        (0..SIZE_T).flat_map(move |i| {
+            let start = self.id_prefsum[i][path_id] as usize;
+            let end = self.id_prefsum[i][path_id + 1] as usize;
+            self.items[i][start..end].iter().cloned()
+        })
+    }
 }
 
 // pub struct InfixEqStorage {
@@ -86,8 +283,30 @@ impl ItemTable {
 //     }
 // }
 
+// packed vector of bits, one per item, that are all unset initially
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new(size: usize) -> Self {
+        Self {
+            words: vec![0; (size + 63) / 64],
+        }
+    }
+
+    pub fn set(&mut self, i: usize) {
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+}
+
 pub struct ActiveTable {
-    pub items: Vec<bool>,
+    items: BitSet,
     // intervall container + item len vector
     annotation: Option<IntervalContainer>,
 }
@@ -96,7 +315,7 @@ impl ActiveTable {
     // if you provide item_length, then it an active table with annotation
     pub fn new(size: usize, with_annotation: bool) -> Self {
         Self {
-            items: vec![false; size],
+            items: BitSet::new(size),
             annotation: if with_annotation {
                 Some(IntervalContainer::new())
             } else {
@@ -106,12 +325,11 @@ impl ActiveTable {
     }
 
     pub fn activate(&mut self, id: &ItemId) {
-        self.items[id.0 as usize] |= true;
+        self.items.set(id.0 as usize);
     }
 
-    #[allow(dead_code)]
     pub fn is_active(&self, id: &ItemId) -> bool {
-        self.items[id.0 as usize]
+        self.items.get(id.0 as usize)
     }
 
     pub fn activate_n_annotate(
@@ -126,7 +344,7 @@ impl ActiveTable {
             Some(m) => {
                 // if interval completely covers item, remove it from map
                 if end - start == item_len {
-                    self.items[id.0 as usize] |= true;
+                    self.items.set(id.0 as usize);
                     m.remove(&id);
                 } else {
                     if start > end {
@@ -141,7 +359,7 @@ impl ActiveTable {
                     }
                     if m.get(&id).unwrap()[0] == (0, item_len) {
                         m.remove(&id);
-                        self.items[id.0 as usize] |= true;
+                        self.items.set(id.0 as usize);
                     }
                 }
                 Ok(())
@@ -150,7 +368,7 @@ impl ActiveTable {
     }
 
     pub fn get_active_intervals(&self, id: &ItemId, item_len: usize) -> Vec<(usize, usize)> {
-        if self.items[id.0 as usize] {
+        if self.items.get(id.0 as usize) {
             vec![(0, item_len)]
         } else if let Some(container) = &self.annotation {
             match container.get(id) {
@@ -167,7 +385,7 @@ impl ActiveTable {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct IntervalContainer {
     map: HashMap<ItemId, Vec<(usize, usize)>>,
 }
@@ -295,7 +513,8 @@ impl fmt::Display for ActiveTableError {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Threshold {
     Relative(f64),
     Absolute(usize),
@@ -372,9 +591,9 @@ pub fn averageu32(v: &[u32]) -> f32 {
     (v.iter().map(|x| *x as u64).sum::<u64>() as f64 / v.len() as f64) as f32
 }
 
-//pub fn averageu64 (v: &[u64]) -> f64 {
-//    v.iter().sum::<u64>() as f64 / v.len() as f64
-//}
+pub fn averageu64(v: &[u64]) -> f32 {
+    (v.iter().sum::<u64>() as f64 / v.len() as f64) as f32
+}
 
 pub fn median_already_sorted(v: &[u32]) -> f64 {
     //v.sort(); this has been done before
@@ -389,11 +608,12 @@ pub fn median_already_sorted(v: &[u32]) -> f64 {
 
 pub fn n50_already_sorted(v: &[u32]) -> Option<u32> {
     //v.sort(); this has been done before
-    let total_length: u32 = v.iter().sum();
+    // lengths of individual items fit into u32, but not necessarily their sum
+    let total_length: u64 = v.iter().map(|x| *x as u64).sum();
 
     let mut running_sum = 0;
     for &len in v.iter() {
-        running_sum += len;
+        running_sum += len as u64;
         if running_sum * 2 >= total_length {
             return Some(len);
         }
@@ -513,12 +733,125 @@ pub fn canonical(kmer_bits: u64, k: usize) -> u64 {
 //    b + (1.0 + (a - b).exp2()).log2()
 //}
 
+// log messages (info and above) of panacus itself emitted during this run, e.g., for inclusion
+// in HTML reports
+static RUN_LOG: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+// Logger that records the messages of panacus for the run log in addition to passing all
+// messages on to env_logger, whose filter (RUST_LOG) applies only to the latter
+struct RunLogger {
+    inner: env_logger::Logger,
+}
+
+impl Log for RunLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Info || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() <= Level::Info && record.target().starts_with("panacus") {
+            RUN_LOG
+                .lock()
+                .unwrap()
+                .push(format!("{:<5} {}", record.level(), record.args()));
+        }
+        if self.inner.matches(record) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+// Sets up logging to stderr as configured by RUST_LOG, while recording the run log
+pub fn init_logger() {
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = std::cmp::max(inner.filter(), LevelFilter::Info);
+    if log::set_boxed_logger(Box::new(RunLogger { inner })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+pub fn run_log() -> Vec<String> {
+    RUN_LOG.lock().unwrap().clone()
+}
+
+// Category of the error that terminated a run, each with its own exit code so that workflow
+// managers can tell, e.g., invalid parameters from corrupt input files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorCategory {
+    Config,
+    Parse,
+    Io,
+    Internal,
+}
+
+impl ErrorCategory {
+    pub fn from_kind(kind: ErrorKind) -> Self {
+        match kind {
+            ErrorKind::InvalidInput | ErrorKind::Unsupported => ErrorCategory::Config,
+            ErrorKind::InvalidData | ErrorKind::UnexpectedEof => ErrorCategory::Parse,
+            ErrorKind::Other => ErrorCategory::Internal,
+            _ => ErrorCategory::Io,
+        }
+    }
+
+    // config errors share their exit code with the command line errors reported by clap
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorCategory::Config => 2,
+            ErrorCategory::Parse => 3,
+            ErrorCategory::Io => 4,
+            ErrorCategory::Internal => 5,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorReport<'a> {
+    category: ErrorCategory,
+    exit_code: i32,
+    kind: String,
+    message: &'a str,
+}
+
+// Writes the error that terminated the run as JSON object to the given file
+pub fn write_error_json(
+    file: &str,
+    category: ErrorCategory,
+    kind: &str,
+    msg: &str,
+) -> Result<(), Error> {
+    let report = ErrorReport {
+        category,
+        exit_code: category.exit_code(),
+        kind: kind.to_string(),
+        message: msg,
+    };
+    let mut out = std::fs::File::create(file)?;
+    serde_json::to_writer_pretty(&mut out, &report)?;
+    writeln!(out)
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
     use crate::graph::ItemId;
 
+    #[test]
+    fn test_active_table() {
+        let mut table = ActiveTable::new(130, false);
+        for i in [0, 63, 64, 129] {
+            table.activate(&ItemId(i));
+        }
+        let active: Vec<u64> = (0..130).filter(|&i| table.is_active(&ItemId(i))).collect();
+        assert_eq!(active, vec![0, 63, 64, 129]);
+    }
+
     #[test]
     fn test_interval_container() {
         let mut ic = IntervalContainer::new();
@@ -541,4 +874,100 @@ mod tests {
         ic.add(ItemId(0), 14, 17);
         assert_eq!(ic.map.get(&ItemId(0)), Some(&vec![(0, 12), (13, 20)]));
     }
+
+    #[test]
+    fn test_similarity_metric() {
+        assert_eq!(SimilarityMetric::Jaccard.apply(2, 4, 6), 0.25);
+        assert_eq!(SimilarityMetric::Cosine.apply(2, 4, 4), 0.5);
+        assert_eq!(SimilarityMetric::Containment.apply(2, 4, 6), 0.5);
+        assert_eq!(SimilarityMetric::Containment.apply(2, 6, 4), 2.0 / 6.0);
+        assert_eq!(SimilarityMetric::Jaccard.apply(0, 0, 0), 0.0);
+        assert_eq!(
+            SimilarityMetric::from_str("cosine"),
+            Ok(SimilarityMetric::Cosine)
+        );
+    }
+
+    #[test]
+    fn test_n50_already_sorted() {
+        assert_eq!(n50_already_sorted(&[5, 3, 2, 1]), Some(3));
+        // the total length exceeds the range of u32
+        assert_eq!(
+            n50_already_sorted(&[u32::MAX, u32::MAX - 1, 1]),
+            Some(u32::MAX)
+        );
+        assert_eq!(n50_already_sorted(&[]), None);
+    }
+
+    #[test]
+    fn test_binning() {
+        assert_eq!(Binning::from_str("50"), Ok(Binning::Count(50)));
+        assert_eq!(Binning::from_str("w1000"), Ok(Binning::Width(1000)));
+        assert!(Binning::from_str("0").is_err());
+        assert!(Binning::from_str("w0").is_err());
+        assert!(Binning::from_str("wide").is_err());
+
+        let values: Vec<u32> = vec![1, 2, 3, 4, 10];
+        assert_eq!(
+            Binning::Count(2).apply(&values),
+            (vec!["1-5".to_string(), "6-10".to_string()], vec![4, 1])
+        );
+        assert_eq!(
+            Binning::Width(4).apply(&values),
+            (
+                vec!["1-4".to_string(), "5-8".to_string(), "9-12".to_string()],
+                vec![4, 0, 1]
+            )
+        );
+        assert_eq!(
+            Binning::Count(50).apply(&[7u32, 7]),
+            (vec!["7".to_string()], vec![2])
+        );
+        assert_eq!(
+            Binning::Count(50).apply::<u32>(&[]),
+            (Vec::new(), Vec::new())
+        );
+        // path lengths may exceed the range of u32
+        assert_eq!(
+            Binning::Count(1).apply(&[5_000_000_000u64, 5_000_000_001]),
+            (vec!["5000000000-5000000001".to_string()], vec![2])
+        );
+    }
+
+    #[test]
+    fn test_error_category() {
+        let codes: Vec<i32> = [
+            ErrorKind::InvalidInput,
+            ErrorKind::InvalidData,
+            ErrorKind::NotFound,
+            ErrorKind::Other,
+        ]
+        .iter()
+        .map(|&k| ErrorCategory::from_kind(k).exit_code())
+        .collect();
+        assert_eq!(codes, vec![2, 3, 4, 5]);
+
+        // a graph cut off in the middle of a path is reported as parse error
+        let gfa_file = std::env::temp_dir().join("panacus_test_error_category.gfa");
+        std::fs::write(&gfa_file, "S\t1\tACGT\nP\tx\t1+").unwrap();
+        let mut params = crate::cli::Params::test_default_histgrowth();
+        if let crate::cli::Params::Histgrowth {
+            gfa_file: ref mut f,
+            ..
+        } = params
+        {
+            *f = gfa_file.to_string_lossy().to_string();
+        }
+        // the analysis needs more stack in debug builds than test threads have by default
+        let err = std::thread::Builder::new()
+            .stack_size(64 << 20)
+            .spawn(move || crate::cli::run(params, '#', &mut std::io::BufWriter::new(Vec::new())))
+            .unwrap()
+            .join()
+            .unwrap()
+            .unwrap_err();
+        std::fs::remove_file(&gfa_file).unwrap();
+        assert_eq!(ErrorCategory::from_kind(err.kind()), ErrorCategory::Parse);
+        assert_eq!(ErrorCategory::from_kind(err.kind()).exit_code(), 3);
+    }
 }
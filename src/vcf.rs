@@ -0,0 +1,495 @@
+// Synthesis of haplotype walks from VCF genotypes for graphs that have been constructed from a
+// reference and a VCF (e.g., with vg construct), but do not embed the haplotypes as paths. Each
+// variant allele is placed in the graph by following, from the reference node that precedes the
+// variant, the nodes whose sequences spell out the allele until the reference path is joined
+// again.
+
+/* standard use */
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{Error, ErrorKind};
+use std::str::{self, FromStr};
+
+/* private use */
+use crate::graph::{Edge, GraphAuxilliary, ItemId, Orientation};
+use crate::io::best_effort;
+
+// a variant allele placed on the reference path: the reference nodes with indices in
+// [start, end) are replaced by the given nodes
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedAllele {
+    pub start: usize,
+    pub end: usize,
+    pub nodes: Vec<ItemId>,
+}
+
+pub struct ReferenceGraph {
+    // nodes of the reference path and the offsets at which they start; the last offset is the
+    // length of the reference
+    nodes: Vec<ItemId>,
+    offsets: Vec<usize>,
+    offset2idx: HashMap<usize, usize>,
+    // sequences, names, and successors (in forward orientation) of nodes, indexed by node ID
+    seqs: Vec<Vec<u8>>,
+    names: Vec<Vec<u8>>,
+    succ: Vec<Vec<ItemId>>,
+}
+
+impl ReferenceGraph {
+    // reads the sequences and links of all nodes from the GFA
+    pub fn from_gfa<R: Read>(
+        data: &mut BufReader<R>,
+        reference: &[(ItemId, Orientation)],
+        graph_aux: &GraphAuxilliary,
+    ) -> Result<Self, Error> {
+        log::info!("loading node sequences and links");
+        let mut seqs = vec![Vec::new(); graph_aux.node_count + 1];
+        let mut names = vec![Vec::new(); graph_aux.node_count + 1];
+        let mut succ = vec![Vec::new(); graph_aux.node_count + 1];
+        let mut buf = vec![];
+        let mut line_no = 0;
+        while data.read_until(b'\n', &mut buf)? > 0 {
+            line_no += 1;
+            let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            if line.first() == Some(&b'S') {
+                let mut fields = line.split(|x| x == &b'\t').skip(1);
+                let name = fields.next().unwrap_or_default();
+                // nodes skipped while indexing the graph (e.g., of zero length) are ignored
+                if let Some(id) = graph_aux.node2id.get(name) {
+                    let seq = fields.next().unwrap_or_default();
+                    if seq == b"*" {
+                        let msg = format!(
+                            "sequence of node {} is not given, but needed to place variants",
+                            String::from_utf8_lossy(name)
+                        );
+                        log::error!("{}", &msg);
+                        return Err(Error::new(ErrorKind::InvalidData, msg));
+                    }
+                    seqs[id.0 as usize] = seq.to_ascii_uppercase();
+                    names[id.0 as usize] = name.to_vec();
+                }
+            } else if line.first() == Some(&b'L') {
+                // alleles are spelled out in forward orientation only, as in graphs constructed
+                // from a VCF
+                let edge = match Edge::from_link(line, &graph_aux.node2id, false) {
+                    Ok(edge) => edge,
+                    // links of corrupt records skipped in best-effort mode are ignored
+                    Err(_) if best_effort() => {
+                        buf.clear();
+                        continue;
+                    }
+                    Err(e) => return Err(e.in_line(line_no).into()),
+                };
+                match edge {
+                    Edge(u, Orientation::Forward, v, Orientation::Forward) => {
+                        succ[u.0 as usize].push(v)
+                    }
+                    Edge(u, Orientation::Backward, v, Orientation::Backward) => {
+                        succ[v.0 as usize].push(u)
+                    }
+                    _ => (),
+                }
+            }
+            buf.clear();
+        }
+        Self::new(reference, seqs, names, succ)
+    }
+
+    pub fn new(
+        reference: &[(ItemId, Orientation)],
+        seqs: Vec<Vec<u8>>,
+        names: Vec<Vec<u8>>,
+        succ: Vec<Vec<ItemId>>,
+    ) -> Result<Self, Error> {
+        if reference.iter().any(|(_, o)| o == &Orientation::Backward) {
+            let msg = "reference path must traverse all its nodes in forward orientation";
+            log::error!("{}", msg);
+            return Err(Error::new(ErrorKind::InvalidData, msg));
+        }
+        let nodes: Vec<ItemId> = reference.iter().map(|(v, _)| *v).collect();
+        let mut offsets = vec![0];
+        for v in nodes.iter() {
+            offsets.push(offsets.last().unwrap() + seqs[v.0 as usize].len());
+        }
+        let offset2idx = offsets.iter().enumerate().map(|(i, o)| (*o, i)).collect();
+        Ok(Self {
+            nodes,
+            offsets,
+            offset2idx,
+            seqs,
+            names,
+            succ,
+        })
+    }
+
+    // places an alternative allele of the variant at the given (0-based) position of the
+    // reference path
+    pub fn place(&self, pos: usize, ref_allele: &[u8], alt: &[u8]) -> Result<PlacedAllele, String> {
+        if pos + ref_allele.len() > self.len() {
+            return Err(format!(
+                "variant lies beyond the end of the reference path of length {}",
+                self.len()
+            ));
+        }
+        let ref_allele = ref_allele.to_ascii_uppercase();
+        let alt = alt.to_ascii_uppercase();
+        // alleles of indels share a padding base, which is part of the reference node(s)
+        let prefix = ref_allele
+            .iter()
+            .zip(&alt)
+            .take_while(|(a, b)| a == b)
+            .count();
+        let suffix = ref_allele[prefix..]
+            .iter()
+            .rev()
+            .zip(alt[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let ref_allele = &ref_allele[prefix..ref_allele.len() - suffix];
+        let alt = &alt[prefix..alt.len() - suffix];
+        let start = pos + prefix;
+
+        let (start, end) = match (
+            self.offset2idx.get(&start),
+            self.offset2idx.get(&(start + ref_allele.len())),
+        ) {
+            (Some(&start), Some(&end)) if start > 0 && end < self.nodes.len() => (start, end),
+            (Some(_), Some(_)) => {
+                return Err("variant at either end of the reference path".to_string())
+            }
+            _ => return Err("variant does not start or end at a node boundary".to_string()),
+        };
+        let seq: Vec<u8> = self.nodes[start..end]
+            .iter()
+            .flat_map(|v| self.seqs[v.0 as usize].iter().copied())
+            .collect();
+        if seq != ref_allele {
+            return Err("reference allele does not match sequence of reference path".to_string());
+        }
+        let mut nodes = Vec::new();
+        if self.spell(self.nodes[start - 1], alt, self.nodes[end], &mut nodes) {
+            Ok(PlacedAllele { start, end, nodes })
+        } else {
+            Err("allele is not spelled out by any walk in the graph".to_string())
+        }
+    }
+
+    // searches for a walk of nodes from u to v (both excluded) that spells out the given sequence
+    fn spell(&self, u: ItemId, seq: &[u8], v: ItemId, walk: &mut Vec<ItemId>) -> bool {
+        if seq.is_empty() {
+            return self.succ[u.0 as usize].contains(&v);
+        }
+        for w in self.succ[u.0 as usize].iter() {
+            let s = &self.seqs[w.0 as usize];
+            if !s.is_empty() && seq.starts_with(s) {
+                walk.push(*w);
+                if self.spell(*w, &seq[s.len()..], v, walk) {
+                    return true;
+                }
+                walk.pop();
+            }
+        }
+        false
+    }
+
+    // tells whether the next allele can follow the previous one on the same haplotype, i.e., the
+    // alleles do not overlap, and if they are adjacent, the graph links them
+    pub fn joins(&self, prev: &PlacedAllele, next: &PlacedAllele) -> bool {
+        if prev.end != next.start {
+            return prev.end < next.start;
+        }
+        let u = prev
+            .nodes
+            .last()
+            .copied()
+            .unwrap_or(self.nodes[prev.start - 1]);
+        let v = next.nodes.first().copied().unwrap_or(self.nodes[next.end]);
+        self.succ[u.0 as usize].contains(&v)
+    }
+
+    // nodes of the haplotype that carries the given (sorted and non-overlapping) alleles
+    pub fn haplotype(&self, alleles: &[PlacedAllele]) -> Vec<ItemId> {
+        let mut res = Vec::with_capacity(self.nodes.len());
+        let mut i = 0;
+        for a in alleles {
+            res.extend_from_slice(&self.nodes[i..a.start]);
+            res.extend_from_slice(&a.nodes);
+            i = a.end;
+        }
+        res.extend_from_slice(&self.nodes[i..]);
+        res
+    }
+
+    pub fn len(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// alleles of each haplotype of each sample, as read from the GT fields of a VCF
+#[derive(Debug, Default)]
+pub struct VcfHaplotypes {
+    pub samples: Vec<String>,
+    pub alleles: Vec<Vec<Vec<PlacedAllele>>>,
+}
+
+// reads the genotypes of all samples of the VCF for variants of the given chromosome and places
+// their alleles on the reference path, which starts at the given position of the chromosome;
+// alleles that cannot be placed or joined with the previous allele of the same haplotype are
+// skipped with a warning, and unphased genotypes are read as if phased, also with a warning
+pub fn parse_vcf_haplotypes<R: Read>(
+    data: &mut BufReader<R>,
+    graph: &ReferenceGraph,
+    chrom: &str,
+    offset: usize,
+) -> Result<VcfHaplotypes, Error> {
+    log::info!("placing variants of {} on reference path", chrom);
+    let mut res = VcfHaplotypes::default();
+    let mut buf = vec![];
+    let mut line = 0;
+    let (mut variants, mut unplaced, mut overlapping, mut unphased) = (0, 0, 0, 0);
+    let invalid = |line: usize, what: &str| {
+        let msg = format!("error in line {} of VCF: {}", line, what);
+        log::error!("{}", &msg);
+        Error::new(ErrorKind::InvalidData, msg)
+    };
+    while data.read_until(b'\n', &mut buf)? > 0 {
+        line += 1;
+        let record = str::from_utf8(&buf)
+            .map_err(|_| invalid(line, "invalid UTF-8"))?
+            .trim_end_matches(&['\n', '\r'][..]);
+        if record.starts_with("#CHROM") {
+            res.samples = record.split('\t').skip(9).map(|s| s.to_string()).collect();
+            res.alleles = vec![Vec::new(); res.samples.len()];
+        } else if !record.is_empty() && !record.starts_with('#') {
+            let fields: Vec<&str> = record.split('\t').collect();
+            if fields.len() < 9 + res.samples.len() {
+                return Err(invalid(
+                    line,
+                    &format!(
+                        "expected {} columns, but got {}",
+                        9 + res.samples.len(),
+                        fields.len()
+                    ),
+                ));
+            }
+            if fields[0] != chrom {
+                buf.clear();
+                continue;
+            }
+            variants += 1;
+            let pos = usize::from_str(fields[1])
+                .ok()
+                .filter(|p| *p > offset)
+                .ok_or_else(|| invalid(line, &format!("invalid position {}", fields[1])))?;
+            let gt = fields[8]
+                .split(':')
+                .position(|f| f == "GT")
+                .ok_or_else(|| invalid(line, "missing GT field"))?;
+            let alts: Vec<&str> = fields[4].split(',').collect();
+            // alleles are placed only once they are observed in some genotype
+            let mut placed: Vec<Option<Result<PlacedAllele, String>>> = vec![None; alts.len()];
+            for (s, alleles) in res.alleles.iter_mut().enumerate() {
+                let genotype = fields[9 + s].split(':').nth(gt).unwrap_or(".");
+                if genotype.contains('/') {
+                    unphased += 1;
+                }
+                for (h, allele) in genotype.split(&['|', '/'][..]).enumerate() {
+                    if alleles.len() <= h {
+                        alleles.push(Vec::new());
+                    }
+                    // missing alleles are taken to be those of the reference
+                    let k = match usize::from_str(allele) {
+                        Ok(0) | Err(_) => continue,
+                        Ok(k) if k > alts.len() => {
+                            return Err(invalid(line, &format!("unknown allele {}", k)))
+                        }
+                        Ok(k) => k - 1,
+                    };
+                    let p = placed[k].get_or_insert_with(|| {
+                        graph
+                            .place(pos - 1 - offset, fields[3].as_bytes(), alts[k].as_bytes())
+                            .map_err(|e| {
+                                log::warn!(
+                                    "unable to place allele {} of variant at {}:{}: {}",
+                                    alts[k],
+                                    chrom,
+                                    pos,
+                                    e
+                                );
+                                unplaced += 1;
+                                e
+                            })
+                    });
+                    if let Ok(p) = p {
+                        if !alleles[h].last().map(|a| graph.joins(a, p)).unwrap_or(true) {
+                            overlapping += 1;
+                        } else {
+                            alleles[h].push(p.clone());
+                        }
+                    }
+                }
+            }
+        }
+        buf.clear();
+    }
+    log::info!(
+        "read {} variants of {} samples, {} alleles could not be placed",
+        variants,
+        res.samples.len(),
+        unplaced
+    );
+    if unphased > 0 {
+        log::warn!(
+            "{} genotypes are unphased; their alleles are assigned to haplotypes in the order given, which may not reflect the actual haplotypes",
+            unphased
+        );
+    }
+    if overlapping > 0 {
+        log::warn!(
+            "skipped {} alleles that overlap or cannot be joined with previous alleles of the same haplotype",
+            overlapping
+        );
+    }
+    Ok(res)
+}
+
+// writes the haplotypes as W-lines, numbering haplotypes of each sample from 1
+pub fn write_vcf_walks<W: Write>(
+    haplotypes: &VcfHaplotypes,
+    graph: &ReferenceGraph,
+    chrom: &str,
+    out: &mut BufWriter<W>,
+) -> Result<(), Error> {
+    log::info!("reporting haplotype walks");
+    for (sample, alleles) in haplotypes.samples.iter().zip(&haplotypes.alleles) {
+        for (h, a) in alleles.iter().enumerate() {
+            let walk = graph.haplotype(a);
+            let len: usize = walk.iter().map(|v| graph.seqs[v.0 as usize].len()).sum();
+            write!(out, "W\t{}\t{}\t{}\t0\t{}\t", sample, h + 1, chrom, len)?;
+            for v in walk {
+                out.write_all(b">")?;
+                out.write_all(&graph.names[v.0 as usize])?;
+            }
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reference 1 (ACG) 2 (T) 3 (CA) 4 (GG) with SNP 2 -> 5 (C), deletion of 3, and insertion of
+    // 6 (TT) between 3 and 4
+    fn mock_reference_graph() -> ReferenceGraph {
+        let seqs: Vec<Vec<u8>> = vec!["", "ACG", "T", "CA", "GG", "C", "TT"]
+            .into_iter()
+            .map(|s| s.as_bytes().to_vec())
+            .collect();
+        let names = (0..seqs.len())
+            .map(|i| i.to_string().into_bytes())
+            .collect();
+        let mut succ = vec![Vec::new(); seqs.len()];
+        for (u, v) in [
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (1, 5),
+            (5, 3),
+            (2, 4),
+            (3, 6),
+            (6, 4),
+        ] {
+            succ[u as usize].push(ItemId(v));
+        }
+        let reference: Vec<(ItemId, Orientation)> =
+            (1..5).map(|i| (ItemId(i), Orientation::Forward)).collect();
+        ReferenceGraph::new(&reference, seqs, names, succ).unwrap()
+    }
+
+    #[test]
+    fn test_place_alleles() {
+        let graph = mock_reference_graph();
+        assert_eq!(graph.len(), 8);
+        // SNP
+        assert_eq!(
+            graph.place(3, b"T", b"c"),
+            Ok(PlacedAllele {
+                start: 1,
+                end: 2,
+                nodes: vec![ItemId(5)]
+            })
+        );
+        // deletion and insertion with padding base
+        assert_eq!(
+            graph.place(3, b"TCA", b"T"),
+            Ok(PlacedAllele {
+                start: 2,
+                end: 3,
+                nodes: vec![]
+            })
+        );
+        assert_eq!(
+            graph.place(5, b"A", b"ATT"),
+            Ok(PlacedAllele {
+                start: 3,
+                end: 3,
+                nodes: vec![ItemId(6)]
+            })
+        );
+        assert!(graph.place(3, b"T", b"G").is_err());
+        assert!(graph.place(3, b"A", b"C").is_err());
+        assert!(graph.place(4, b"C", b"G").is_err());
+        assert!(graph.place(0, b"ACG", b"A").is_err());
+        // beyond the end of the reference
+        assert_eq!(
+            graph.place(7, b"GT", b"G"),
+            Err("variant lies beyond the end of the reference path of length 8".to_string())
+        );
+        assert!(graph
+            .place(8, b"A", b"C")
+            .unwrap_err()
+            .contains("beyond the end"));
+    }
+
+    #[test]
+    fn test_vcf_haplotypes() {
+        let graph = mock_reference_graph();
+        let vcf = b"##fileformat=VCFv4.2\n\
+                    #CHROM\tPOS\tID\tREF\tALT\tQUAL\tFILTER\tINFO\tFORMAT\ts1\ts2\n\
+                    chr1\t4\t.\tT\tC,G\t.\t.\t.\tGT\t1|0\t0|2\n\
+                    chr1\t4\t.\tTCA\tT\t.\t.\t.\tGT\t1|0\t.|1\n\
+                    chr1\t6\t.\tA\tATT\t.\t.\t.\tGT:DP\t0|1:3\t1/1:5\n\
+                    chr2\t6\t.\tA\tATT\t.\t.\t.\tGT\t1|1\t1|1\n";
+        let haplotypes =
+            parse_vcf_haplotypes(&mut BufReader::new(&vcf[..]), &graph, "chr1", 0).unwrap();
+        assert_eq!(haplotypes.samples, vec!["s1", "s2"]);
+        let walks: Vec<Vec<Vec<ItemId>>> = haplotypes
+            .alleles
+            .iter()
+            .map(|s| s.iter().map(|a| graph.haplotype(a)).collect())
+            .collect();
+        let ids = |v: &[u64]| v.iter().map(|i| ItemId(*i)).collect::<Vec<_>>();
+        // G is not in the graph, and the deletion can be joined neither with the preceding SNP
+        // (first haplotype of s1) nor with the following insertion (second haplotype of s2)
+        assert_eq!(
+            walks,
+            vec![
+                vec![ids(&[1, 5, 3, 4]), ids(&[1, 2, 3, 6, 4])],
+                vec![ids(&[1, 2, 3, 6, 4]), ids(&[1, 2, 4])]
+            ]
+        );
+
+        let mut out = BufWriter::new(Vec::new());
+        write_vcf_walks(&haplotypes, &graph, "chr1", &mut out).unwrap();
+        let out = String::from_utf8(out.into_inner().unwrap()).unwrap();
+        assert_eq!(out.lines().next(), Some("W\ts1\t1\tchr1\t0\t8\t>1>5>3>4"));
+        assert_eq!(out.lines().count(), 4);
+    }
+}